@@ -9,10 +9,24 @@
 //! 2. **Serialization Contracts**: Tests verify identical JSON output
 //! 3. **Handler Contracts**: Trait enforces identical behavior
 //! 4. **Snapshot Tests**: Catch any serialization changes
+//!
+//! # Casing
+//!
+//! camelCase is the canonical casing for every JSON payload crossing the Tauri
+//! IPC or HTTP boundary (`deadFunctions`, never `dead_functions`), matching
+//! `src/types/bindings.ts`. Both transports serialize the same types with
+//! serde_json, so their output is byte-identical; [`non_camel_case_keys`]
+//! catches types that forget `#[serde(rename_all = "camelCase")]`.
+//!
+//! The CLI's `--format json` analysis report (`AnalysisResult`) is a file
+//! format, not part of this contract, and keeps its snake_case keys. The
+//! transports send it as a [`TreeNode`] hierarchy instead.
 
 use crate::models::TreeNode;
-use code_viz_dead_code::DeadCodeResult;
+use code_viz_core::models::{AnalysisResult, AnalysisWarning, FileMetrics};
+use code_viz_dead_code::{DeadCodeResult, DeadCodeSummary, FileDeadCode};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Request/Response types that BOTH Tauri and Web must use
 ///
@@ -47,6 +61,129 @@ pub struct DeadCodeResponse {
     pub result: DeadCodeResult,
 }
 
+/// Page of dead code files to return, as query parameters of `/api/dead-code`
+///
+/// Without either parameter the full `DeadCodeResult` is returned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadCodePageQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// One page of the files of a dead code result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadCodePage {
+    /// Summary of the whole analysis, not only this page
+    pub summary: DeadCodeSummary,
+    /// Files with dead code, starting at `offset`
+    pub files: Vec<FileDeadCode>,
+    /// Files with dead code across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl DeadCodePage {
+    /// Keep at most `limit` files of `result` starting at `offset`, or all
+    /// remaining files if no limit is given
+    pub fn new(result: DeadCodeResult, query: &DeadCodePageQuery) -> Self {
+        let total = result.files.len();
+        let offset = query.offset.unwrap_or(0);
+        let limit = query.limit.unwrap_or(total);
+        let files = result.files.into_iter().skip(offset).take(limit).collect();
+
+        Self {
+            summary: result.summary,
+            files,
+            total,
+            offset,
+            limit,
+        }
+    }
+}
+
+/// `file` event of a streamed analysis, sent as soon as a file is processed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetricsEvent {
+    pub path: PathBuf,
+    pub language: String,
+    pub loc: usize,
+    pub size_bytes: u64,
+    pub function_count: usize,
+    pub max_nesting_depth: usize,
+    pub export_count: usize,
+}
+
+impl From<&FileMetrics> for FileMetricsEvent {
+    fn from(file: &FileMetrics) -> Self {
+        Self {
+            path: file.path.clone(),
+            language: file.language.clone(),
+            loc: file.loc,
+            size_bytes: file.size_bytes,
+            function_count: file.function_count,
+            max_nesting_depth: file.max_nesting_depth,
+            export_count: file.export_count,
+        }
+    }
+}
+
+/// Final `summary` event of a streamed analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisSummaryEvent {
+    pub total_files: usize,
+    pub total_loc: usize,
+    pub total_functions: usize,
+    pub skipped_files: usize,
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+impl From<&AnalysisResult> for AnalysisSummaryEvent {
+    fn from(result: &AnalysisResult) -> Self {
+        Self {
+            total_files: result.summary.total_files,
+            total_loc: result.summary.total_loc,
+            total_functions: result.summary.total_functions,
+            skipped_files: result.skipped_files,
+            warnings: result.warnings.clone(),
+        }
+    }
+}
+
+/// Keys of a JSON payload that are not camelCase, as `/`-separated paths
+///
+/// An empty result means the payload follows the canonical casing.
+pub fn non_camel_case_keys(value: &serde_json::Value) -> Vec<String> {
+    fn walk(value: &serde_json::Value, path: &str, found: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, child) in map {
+                    let child_path = format!("{}/{}", path, key);
+                    if key.contains('_') || key.starts_with(char::is_uppercase) {
+                        found.push(child_path.clone());
+                    }
+                    walk(child, &child_path, found);
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    walk(item, &format!("{}/{}", path, i), found);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut found = Vec::new();
+    walk(value, "", &mut found);
+    found.sort();
+    found
+}
+
 /// Contract validation - ensures both implementations produce identical JSON
 #[cfg(test)]
 pub mod test_utils {
@@ -102,10 +239,15 @@ pub mod test_utils {
             path: PathBuf::from("test.rs"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: None,
+            warnings: vec![],
         }
     }
 
@@ -160,6 +302,7 @@ pub mod test_utils {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::SystemTime;
     use test_utils::*;
 
     #[test]
@@ -220,6 +363,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_non_camel_case_keys() {
+        let tree = serde_json::to_value(create_test_tree()).unwrap();
+        assert!(non_camel_case_keys(&tree).is_empty());
+
+        let drifted = serde_json::json!({
+            "summary": { "deadFunctions": 1, "dead_classes": 0 },
+            "files": [{ "Path": "a.ts" }],
+        });
+        assert_eq!(
+            non_camel_case_keys(&drifted),
+            vec!["/files/0/Path", "/summary/dead_classes"]
+        );
+    }
+
+    #[test]
+    fn test_stream_events_are_camel_case() {
+        let file = FileMetrics {
+            path: PathBuf::from("src/main.ts"),
+            language: "typescript".to_string(),
+            loc: 10,
+            size_bytes: 200,
+            function_count: 2,
+            max_nesting_depth: 1,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
+            functions: vec![],
+            last_modified: SystemTime::UNIX_EPOCH,
+            dead_function_count: None,
+            dead_code_loc: None,
+            dead_code_ratio: None,
+        };
+        let event = serde_json::to_value(FileMetricsEvent::from(&file)).unwrap();
+        assert!(non_camel_case_keys(&event).is_empty());
+        assert_eq!(event["functionCount"], 2);
+
+        let result = AnalysisResult {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: code_viz_core::calculate_summary(std::slice::from_ref(&file)),
+            files: vec![file],
+            timestamp: SystemTime::UNIX_EPOCH,
+            skipped_files: 1,
+            warnings: vec![],
+            commit_sha: None,
+        };
+        let summary = serde_json::to_value(AnalysisSummaryEvent::from(&result)).unwrap();
+        assert!(non_camel_case_keys(&summary).is_empty());
+        assert_eq!(summary["totalLoc"], 10);
+        assert_eq!(summary["skippedFiles"], 1);
+    }
+
     #[test]
     fn test_tree_node_roundtrip() {
         let original = create_test_tree();
@@ -0,0 +1,253 @@
+//! Metric deltas between two trees
+//!
+//! Before/after visualizations render a single merged tree in which every
+//! node carries how its metrics changed. Nodes are aligned by `id` at each
+//! level, so a file keeps its identity as long as its path is unchanged.
+
+use crate::models::TreeNode;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// How a node changed between the old and the new tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffStatus {
+    /// Only present in the new tree
+    Added,
+    /// Only present in the old tree
+    Removed,
+    /// Present in both, with different metrics or changed children
+    Changed,
+    /// Present in both with identical metrics
+    Unchanged,
+}
+
+/// A node of the merged tree produced by [`TreeNode::diff`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNodeDiff {
+    /// Identifier shared by the aligned nodes
+    pub id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Full path from repository root
+    pub path: PathBuf,
+
+    /// Node type: "file" or "directory"
+    #[serde(rename = "type")]
+    pub node_type: String,
+
+    pub status: DiffStatus,
+
+    /// Lines of code in the new tree (in the old tree for removed nodes)
+    pub loc: usize,
+
+    /// Change in lines of code, negative when the node shrank or was removed
+    pub loc_delta: i64,
+
+    /// Change in complexity score
+    pub complexity_delta: i64,
+
+    /// Merged children: those of the new tree in order, then removed ones
+    #[serde(default)]
+    pub children: Vec<TreeNodeDiff>,
+}
+
+impl TreeNode {
+    /// Merge `old` and `new` into one tree annotated with metric deltas
+    ///
+    /// The two roots are always compared with each other; below them, children
+    /// are aligned by `id`.
+    pub fn diff(old: &TreeNode, new: &TreeNode) -> TreeNodeDiff {
+        let old_children: HashMap<&str, &TreeNode> = old
+            .children
+            .iter()
+            .map(|child| (child.id.as_str(), child))
+            .collect();
+
+        let mut children: Vec<TreeNodeDiff> = new
+            .children
+            .iter()
+            .map(|child| match old_children.get(child.id.as_str()) {
+                Some(old_child) => TreeNode::diff(old_child, child),
+                None => one_sided(child, DiffStatus::Added),
+            })
+            .collect();
+
+        let new_ids: HashSet<&str> = new.children.iter().map(|child| child.id.as_str()).collect();
+        children.extend(
+            old.children
+                .iter()
+                .filter(|child| !new_ids.contains(&child.id.as_str()))
+                .map(|child| one_sided(child, DiffStatus::Removed)),
+        );
+
+        let loc_delta = new.loc as i64 - old.loc as i64;
+        let complexity_delta = new.complexity as i64 - old.complexity as i64;
+        let changed = loc_delta != 0
+            || complexity_delta != 0
+            || children
+                .iter()
+                .any(|child| child.status != DiffStatus::Unchanged);
+
+        TreeNodeDiff {
+            id: new.id.clone(),
+            name: new.name.clone(),
+            path: new.path.clone(),
+            node_type: new.node_type.clone(),
+            status: if changed {
+                DiffStatus::Changed
+            } else {
+                DiffStatus::Unchanged
+            },
+            loc: new.loc,
+            loc_delta,
+            complexity_delta,
+            children,
+        }
+    }
+}
+
+/// Diff of a subtree that exists on one side only
+fn one_sided(node: &TreeNode, status: DiffStatus) -> TreeNodeDiff {
+    let sign = if status == DiffStatus::Removed { -1 } else { 1 };
+
+    TreeNodeDiff {
+        id: node.id.clone(),
+        name: node.name.clone(),
+        path: node.path.clone(),
+        node_type: node.node_type.clone(),
+        status,
+        loc: node.loc,
+        loc_delta: sign * node.loc as i64,
+        complexity_delta: sign * node.complexity as i64,
+        children: node
+            .children
+            .iter()
+            .map(|child| one_sided(child, status))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn file(path: &str, loc: usize) -> TreeNode {
+        TreeNode {
+            id: path.to_string(),
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: PathBuf::from(path),
+            loc,
+            complexity: (loc / 10) as u32,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
+            node_type: "file".to_string(),
+            children: vec![],
+            last_modified: UNIX_EPOCH,
+            dead_code_ratio: None,
+            warnings: vec![],
+        }
+    }
+
+    fn directory(path: &str, children: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            loc: children.iter().map(|c| c.loc).sum(),
+            complexity: children.iter().map(|c| c.complexity).max().unwrap_or(0),
+            node_type: "directory".to_string(),
+            children,
+            ..file(path, 0)
+        }
+    }
+
+    fn child<'a>(diff: &'a TreeNodeDiff, id: &str) -> &'a TreeNodeDiff {
+        diff.children.iter().find(|c| c.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_grown_files() {
+        let old = directory(
+            "",
+            vec![directory(
+                "src",
+                vec![
+                    file("src/main.ts", 100),
+                    file("src/old.ts", 40),
+                    file("src/same.ts", 10),
+                ],
+            )],
+        );
+        let new = directory(
+            "",
+            vec![directory(
+                "src",
+                vec![
+                    file("src/main.ts", 150),
+                    file("src/new.ts", 30),
+                    file("src/same.ts", 10),
+                ],
+            )],
+        );
+
+        let diff = TreeNode::diff(&old, &new);
+        assert_eq!(diff.status, DiffStatus::Changed);
+        assert_eq!(diff.loc, 190);
+        assert_eq!(diff.loc_delta, 40);
+
+        let src = child(&diff, "src");
+        assert_eq!(src.children.len(), 4);
+
+        let grown = child(src, "src/main.ts");
+        assert_eq!(grown.status, DiffStatus::Changed);
+        assert_eq!(grown.loc_delta, 50);
+        assert_eq!(grown.complexity_delta, 5);
+
+        let added = child(src, "src/new.ts");
+        assert_eq!(added.status, DiffStatus::Added);
+        assert_eq!(added.loc_delta, 30);
+
+        let removed = child(src, "src/old.ts");
+        assert_eq!(removed.status, DiffStatus::Removed);
+        assert_eq!(removed.loc, 40);
+        assert_eq!(removed.loc_delta, -40);
+        assert_eq!(removed.complexity_delta, -4);
+
+        assert_eq!(child(src, "src/same.ts").status, DiffStatus::Unchanged);
+    }
+
+    #[test]
+    fn test_diff_marks_whole_subtrees() {
+        let old = directory("", vec![directory("lib", vec![file("lib/a.ts", 20)])]);
+        let new = directory("", vec![directory("app", vec![file("app/b.ts", 20)])]);
+
+        let diff = TreeNode::diff(&old, &new);
+        // Same total size, but the contents moved
+        assert_eq!(diff.loc_delta, 0);
+        assert_eq!(diff.status, DiffStatus::Changed);
+
+        assert_eq!(child(&diff, "app").status, DiffStatus::Added);
+        assert_eq!(
+            child(child(&diff, "app"), "app/b.ts").status,
+            DiffStatus::Added
+        );
+        assert_eq!(child(&diff, "lib").status, DiffStatus::Removed);
+        assert_eq!(child(child(&diff, "lib"), "lib/a.ts").loc_delta, -20);
+    }
+
+    #[test]
+    fn test_diff_serialization_format() {
+        let diff = TreeNode::diff(&file("a.ts", 10), &file("a.ts", 25));
+        let json = serde_json::to_value(&diff).unwrap();
+
+        assert_eq!(json["type"], "file");
+        assert_eq!(json["status"], "changed");
+        assert_eq!(json["locDelta"], 15);
+        assert_eq!(json["complexityDelta"], 1);
+    }
+}
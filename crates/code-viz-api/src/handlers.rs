@@ -3,11 +3,12 @@
 //! This module defines the handler trait and implementations that BOTH
 //! Tauri and Web must use. This ensures compile-time consistency.
 
+use crate::contracts::{AnalysisSummaryEvent, FileMetricsEvent};
 use crate::error::ApiError;
 use crate::models::TreeNode;
 use crate::transform::flat_to_hierarchy;
 use code_viz_core::traits::{AppContext, FileSystem, GitProvider};
-use code_viz_dead_code::DeadCodeResult;
+use code_viz_dead_code::{AnalysisSession, DeadCodeResult};
 use std::path::PathBuf;
 
 /// SSOT Handler Trait - Both Tauri and Web MUST implement this
@@ -85,7 +86,8 @@ where
         .map_err(|e| ApiError::AnalysisFailed(e.to_string()))?;
 
         // Transform flat metrics to hierarchical tree (presentation layer)
-        let tree = flat_to_hierarchy(analysis_result.files);
+        let mut tree = flat_to_hierarchy(analysis_result.files);
+        tree.warnings = analysis_result.warnings;
 
         Ok(tree)
     }
@@ -135,11 +137,40 @@ where
         .await
         .map_err(|e| ApiError::AnalysisFailed(e.to_string()))?;
 
-    let tree = flat_to_hierarchy(analysis_result.files);
+    let mut tree = flat_to_hierarchy(analysis_result.files);
+    tree.warnings = analysis_result.warnings;
 
     Ok(tree)
 }
 
+/// Streaming variant of [`analyze_repository_handler`]
+///
+/// Calls `on_file` with each file's metrics as soon as it is analyzed and
+/// returns the totals once the whole repository is done.
+pub async fn analyze_repository_stream_handler<C, F>(
+    ctx: C,
+    fs: F,
+    path: String,
+    mut on_file: impl FnMut(FileMetricsEvent) + Send,
+) -> Result<AnalysisSummaryEvent, ApiError>
+where
+    C: AppContext,
+    F: FileSystem,
+{
+    let repo_path = PathBuf::from(&path);
+
+    let analysis_result = code_viz_commands::analyze_repository_with_callback(
+        &repo_path,
+        ctx,
+        fs,
+        |file| on_file(FileMetricsEvent::from(file)),
+    )
+    .await
+    .map_err(|e| ApiError::AnalysisFailed(e.to_string()))?;
+
+    Ok(AnalysisSummaryEvent::from(&analysis_result))
+}
+
 pub async fn analyze_dead_code_handler<C, F, G>(
     ctx: C,
     fs: F,
@@ -164,6 +195,30 @@ where
     Ok(filtered_result)
 }
 
+/// [`analyze_dead_code_handler`] through a session held for the server's lifetime
+///
+/// The session's settings apply, and a repository that did not change since
+/// the last request reuses its symbol graph instead of being parsed again.
+pub async fn analyze_dead_code_session_handler<C>(
+    session: &AnalysisSession,
+    ctx: C,
+    path: String,
+    min_confidence: u8,
+    _request_id: Option<String>,
+) -> Result<DeadCodeResult, ApiError>
+where
+    C: AppContext,
+{
+    let repo_path = PathBuf::from(&path);
+
+    let analysis_result =
+        code_viz_commands::calculate_dead_code_in_session(&repo_path, session, ctx)
+            .await
+            .map_err(|e| ApiError::DeadCodeFailed(e.to_string()))?;
+
+    Ok(analysis_result.filter_by_confidence(min_confidence))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
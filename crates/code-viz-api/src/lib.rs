@@ -22,12 +22,20 @@
 //!   └───────────────┘       └────────────────┘
 //! ```
 
+// The handlers scan and analyze repositories on disk, so browser (wasm32)
+// builds only get the models, transforms and contracts
 pub mod models;
+pub mod diff;
 pub mod transform;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod handlers;
 pub mod error;
 pub mod contracts;
 
 pub use models::*;
+pub use diff::{DiffStatus, TreeNodeDiff};
+#[cfg(not(target_arch = "wasm32"))]
 pub use handlers::*;
 pub use error::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use code_viz_dead_code::AnalysisSession;
@@ -3,6 +3,7 @@
 //! This module defines the TreeNode structure used for hierarchical
 //! visualization of code metrics in the frontend.
 
+use code_viz_core::models::AnalysisWarning;
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
 use std::path::PathBuf;
 use std::time::SystemTime;
@@ -52,6 +53,23 @@ pub struct TreeNode {
     /// This is a placeholder metric; future versions may use cyclomatic complexity
     pub complexity: u32,
 
+    /// Deepest control-flow nesting of any function (max of children for directories)
+    #[serde(default)]
+    pub max_nesting_depth: usize,
+
+    /// Lines that are only comments (sum of children for directories)
+    #[serde(default)]
+    pub comment_lines: usize,
+
+    /// Share of lines that are only comments (0.0 to 1.0), from the summed
+    /// comment and code lines for directories
+    #[serde(default)]
+    pub comment_ratio: f64,
+
+    /// Number of exported symbols (sum of children for directories)
+    #[serde(default)]
+    pub export_count: usize,
+
     /// Node type: "file" or "directory"
     #[serde(rename = "type")]
     pub node_type: String,
@@ -67,6 +85,11 @@ pub struct TreeNode {
     /// Dead code ratio (0.0 to 1.0), only present when dead code analysis is enabled
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dead_code_ratio: Option<f64>,
+
+    /// Signs that the analysis is incomplete, such as skipped files; only set
+    /// on the root node
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AnalysisWarning>,
 }
 
 #[cfg(test)]
@@ -83,10 +106,15 @@ mod tests {
             path: PathBuf::from("test.ts"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: None,
+            warnings: vec![],
         };
 
         // Debug: Print the actual JSON to see PathBuf serialization
@@ -131,10 +159,15 @@ mod tests {
             path: PathBuf::from("src/child.ts"),
             loc: 50,
             complexity: 5,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: Some(0.15),
+            warnings: vec![],
         };
 
         let parent = TreeNode {
@@ -143,10 +176,15 @@ mod tests {
             path: PathBuf::from("src"),
             loc: 50,
             complexity: 5,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "directory".to_string(),
             children: vec![child],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: Some(0.15),
+            warnings: vec![],
         };
 
         let json = serde_json::to_value(&parent).expect("Failed to serialize");
@@ -170,10 +208,15 @@ mod tests {
             path: PathBuf::from("test.ts"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: Some(0.25),
+            warnings: vec![],
         };
 
         // Serialize
@@ -208,10 +251,15 @@ mod tests {
             path: PathBuf::from("test.ts"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: SystemTime::now(),
             dead_code_ratio: None,
+            warnings: vec![],
         };
 
         let json = serde_json::to_value(&without_dead_code).unwrap();
@@ -3,12 +3,23 @@
 //! This module provides functions to transform the flat Vec<FileMetrics> output
 //! from code-viz-core into hierarchical TreeNode structures for visualization.
 
-use code_viz_core::models::FileMetrics;
+use code_viz_core::models::{comment_ratio, FileMetrics};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use crate::models::TreeNode;
 
+/// Timestamp for directories, until their files' latest one is known
+///
+/// wasm32 has no clock to ask, so browser builds use the epoch instead.
+fn now() -> SystemTime {
+    #[cfg(target_arch = "wasm32")]
+    return SystemTime::UNIX_EPOCH;
+    #[cfg(not(target_arch = "wasm32"))]
+    SystemTime::now()
+}
+
 /// Finds the common root directory from a list of file paths
 ///
 /// This function identifies the deepest common directory that contains all files.
@@ -114,6 +125,11 @@ fn strip_prefix(path: &Path, prefix: &Path) -> PathBuf {
 ///         loc: 100,
 ///         size_bytes: 2048,
 ///         function_count: 5,
+///         max_nesting_depth: 0,
+///         comment_lines: 0,
+///         comment_ratio: 0.0,
+///         export_count: 0,
+///         functions: vec![],
 ///         last_modified: SystemTime::now(),
 ///         dead_function_count: None,
 ///         dead_code_loc: None,
@@ -134,10 +150,15 @@ pub fn flat_to_hierarchy(files: Vec<FileMetrics>) -> TreeNode {
             path: PathBuf::from("/"),
             loc: 0,
             complexity: 0,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "directory".to_string(),
             children: vec![],
-            last_modified: std::time::SystemTime::now(),
+            last_modified: now(),
             dead_code_ratio: None,
+            warnings: vec![],
         };
     }
 
@@ -174,10 +195,15 @@ pub fn flat_to_hierarchy(files: Vec<FileMetrics>) -> TreeNode {
         path: root_node_path.clone(),
         loc: 0,
         complexity: 0,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "directory".to_string(),
         children: vec![],
-        last_modified: std::time::SystemTime::now(),
+        last_modified: now(),
         dead_code_ratio: None,
+        warnings: vec![],
     };
     dir_map.insert(root_node_path.clone(), root_node);
 
@@ -206,10 +232,15 @@ pub fn flat_to_hierarchy(files: Vec<FileMetrics>) -> TreeNode {
             path: file_path.clone(),
             loc: file_loc,
             complexity: file_complexity,
+            max_nesting_depth: file.max_nesting_depth,
+            comment_lines: file.comment_lines,
+            comment_ratio: file.comment_ratio,
+            export_count: file.export_count,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: file.last_modified,
-            dead_code_ratio: None,
+            dead_code_ratio: file.dead_code_ratio,
+            warnings: vec![],
         };
         file_nodes.push((file_path.clone(), file_node));
 
@@ -260,10 +291,15 @@ fn ensure_parent_directories(
                 path: parent_buf.clone(),
                 loc: 0,
                 complexity: 0,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
                 node_type: "directory".to_string(),
                 children: vec![],
-                last_modified: std::time::SystemTime::now(),
+                last_modified: now(),
                 dead_code_ratio: None,
+                warnings: vec![],
             };
             dir_map.insert(parent_buf.clone(), dir_node);
 
@@ -301,7 +337,7 @@ fn get_parent_path(path: &Path, root_path: &Path) -> PathBuf {
         .unwrap_or_else(|| root_path.to_path_buf())
 }
 
-/// Aggregates metrics (LOC, complexity) from children up to parent directories
+/// Aggregates metrics (LOC, complexity, nesting depth, comment ratio, export count) from children up to parent directories
 fn aggregate_directory_metrics(
     dir_map: &mut HashMap<PathBuf, TreeNode>,
     root_path: &Path,
@@ -323,12 +359,20 @@ fn aggregate_directory_metrics(
         // Calculate this directory's metrics from its children
         if let Some(dir_node) = dir_map.get(&path) {
             let total_loc: usize = dir_node.children.iter().map(|c| c.loc).sum();
+            let export_count: usize = dir_node.children.iter().map(|c| c.export_count).sum();
+            let max_nesting_depth = dir_node
+                .children
+                .iter()
+                .map(|c| c.max_nesting_depth)
+                .max()
+                .unwrap_or(0);
+            let comment_lines: usize = dir_node.children.iter().map(|c| c.comment_lines).sum();
             let max_modified = dir_node
                 .children
                 .iter()
                 .map(|c| c.last_modified)
                 .max()
-                .unwrap_or(std::time::SystemTime::now());
+                .unwrap_or_else(now);
 
             // Store calculated values
             let complexity = calculate_complexity(total_loc);
@@ -337,6 +381,10 @@ fn aggregate_directory_metrics(
             if let Some(dir_node_mut) = dir_map.get_mut(&path) {
                 dir_node_mut.loc = total_loc;
                 dir_node_mut.complexity = complexity;
+                dir_node_mut.max_nesting_depth = max_nesting_depth;
+                dir_node_mut.comment_lines = comment_lines;
+                dir_node_mut.comment_ratio = comment_ratio(comment_lines, total_loc);
+                dir_node_mut.export_count = export_count;
                 dir_node_mut.last_modified = max_modified;
             }
 
@@ -364,10 +412,19 @@ fn aggregate_directory_metrics(
             .iter()
             .map(|c| c.last_modified)
             .max()
-            .unwrap_or(std::time::SystemTime::now());
+            .unwrap_or_else(now);
 
         root.loc = total_loc;
         root.complexity = calculate_complexity(total_loc);
+        root.max_nesting_depth = root
+            .children
+            .iter()
+            .map(|c| c.max_nesting_depth)
+            .max()
+            .unwrap_or(0);
+        root.comment_lines = root.children.iter().map(|c| c.comment_lines).sum();
+        root.comment_ratio = comment_ratio(root.comment_lines, total_loc);
+        root.export_count = root.children.iter().map(|c| c.export_count).sum();
         root.last_modified = max_modified;
     }
 }
@@ -377,3 +434,44 @@ fn calculate_complexity(loc: usize) -> u32 {
     ((loc / 10) as u32).min(100)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, loc: usize, comment_lines: usize) -> FileMetrics {
+        FileMetrics {
+            path: PathBuf::from(path),
+            language: "typescript".to_string(),
+            loc,
+            size_bytes: 0,
+            function_count: 0,
+            max_nesting_depth: 0,
+            comment_lines,
+            comment_ratio: comment_ratio(comment_lines, loc),
+            export_count: 0,
+            functions: vec![],
+            last_modified: SystemTime::UNIX_EPOCH,
+            dead_function_count: None,
+            dead_code_loc: None,
+            dead_code_ratio: None,
+        }
+    }
+
+    #[test]
+    fn test_comment_ratio_sums_comment_and_code_lines() {
+        let files = vec![
+            file("src/documented.ts", 10, 90),
+            file("src/bare.ts", 90, 10),
+            file("tests/empty.ts", 0, 0),
+        ];
+        let tree = flat_to_hierarchy(files);
+
+        let src_dir = tree.children.iter().find(|c| c.name == "src").unwrap();
+        assert_eq!(src_dir.comment_lines, 100);
+        assert!((src_dir.comment_ratio - 0.5).abs() < 1e-9);
+        let tests_dir = tree.children.iter().find(|c| c.name == "tests").unwrap();
+        assert_eq!(tests_dir.comment_ratio, 0.0);
+        assert_eq!(tree.comment_lines, 100);
+        assert!((tree.comment_ratio - 0.5).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,12 @@
+use crate::config_loader::ConfigFile;
+use crate::exit_code::ExitCode;
 use crate::output::{self, MetricsFormatter};
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process;
+use crate::remote::{self, RemoteCheckout};
+use crate::threshold::ThresholdExpr;
+use code_viz_core::report::VersionedReport;
+use code_viz_core::scanner::{FileSelection, ScanOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -15,79 +20,176 @@ pub enum AnalyzeError {
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
 
-    #[error("Invalid threshold format: {0}")]
-    InvalidThreshold(String),
+    #[error("{0}")]
+    InvalidThreshold(#[from] crate::threshold::ThresholdError),
 
     #[error("Config error: {0}")]
     ConfigError(#[from] crate::config_loader::ConfigError),
 
     #[error("Dead code analysis failed: {0}")]
     DeadCodeFailed(String),
+
+    #[error("Report error: {0}")]
+    ReportError(#[from] code_viz_core::report::ReportError),
+
+    #[error(transparent)]
+    RemoteError(#[from] remote::RemoteError),
+
+    #[error("Unsupported --language: {0}")]
+    UnsupportedLanguage(String),
 }
 
+impl AnalyzeError {
+    /// Whether the error comes from invalid arguments rather than the analysis
+    pub fn is_usage_error(&self) -> bool {
+        matches!(self, AnalyzeError::InvalidThreshold(_) | AnalyzeError::UnsupportedLanguage(_))
+    }
+}
+
+/// Synthetic path reported for source read with `--stdin`
+const STDIN_PATH: &str = "<stdin>";
+
 pub struct AnalyzeConfig {
     pub path: PathBuf,
-    pub format: String,
-    #[allow(dead_code)]
+    /// Directories under `path` analyzed together (all of `path` if empty)
+    pub roots: Vec<PathBuf>,
+    pub format: Option<String>,
     pub exclude: Vec<String>,
-    pub verbose: bool,
-    pub threshold: Option<String>,
+    /// Only scan this many directory levels deep (unlimited if `None`); the
+    /// file system passed to `run` must apply the same limit
+    pub max_depth: Option<usize>,
+    pub threshold: Vec<String>,
     pub output: Option<PathBuf>,
     pub baseline: Option<PathBuf>,
     pub dead_code: bool,
+    pub tree: bool,
+    pub exit_zero: bool,
+    pub stdin: bool,
+    pub language: Option<String>,
+    /// Strip the analyzed directory from every reported path
+    pub relative_paths: bool,
+    /// Output only the dead code summary numbers as compact JSON (implies
+    /// `dead_code`)
+    pub summary_json: bool,
 }
 
 use code_viz_core::traits::{AppContext, FileSystem, GitProvider};
 
+/// Analyze the configured path, writing the report to `out` unless an output
+/// file is configured
 pub fn run(
+    mut config: AnalyzeConfig,
+    ctx: impl AppContext + Clone,
+    fs: impl FileSystem + Clone,
+    git: impl GitProvider,
+    out: &mut dyn Write,
+) -> Result<ExitCode, AnalyzeError> {
+    // Git URLs are cloned into a temp dir that is removed once `checkout` drops
+    let checkout = match config.path.to_str().filter(|p| remote::is_git_url(p)) {
+        Some(url) => Some(RemoteCheckout::clone(url)?),
+        None => None,
+    };
+    let commit_sha = checkout.as_ref().map(|c| c.commit_sha.clone());
+    if let Some(checkout) = &checkout {
+        config.path = checkout.path().to_path_buf();
+    }
+
+    let exit_zero = config.exit_zero;
+    let passed = analyze(config, commit_sha, ctx, fs, git, out)?;
+    drop(checkout);
+
+    Ok(ExitCode::for_checks(passed, exit_zero))
+}
+
+/// Run the analysis pipeline on a local directory and write the report,
+/// returning false if a baseline or threshold check failed
+fn analyze(
     config: AnalyzeConfig,
+    commit_sha: Option<String>,
     ctx: impl AppContext + Clone,
     fs: impl FileSystem + Clone,
     git: impl GitProvider,
-) -> Result<(), AnalyzeError> {
+    out: &mut dyn Write,
+) -> Result<bool, AnalyzeError> {
     let AnalyzeConfig {
         path,
+        roots,
         format,
-        exclude: _,
-        verbose,
+        exclude,
+        max_depth,
         threshold,
         output,
         baseline,
         dead_code,
+        tree,
+        exit_zero: _,
+        stdin,
+        language,
+        relative_paths,
+        summary_json,
     } = config;
-    // Setup logging
-    let mut builder = env_logger::Builder::from_default_env();
-    if verbose {
-        builder.filter_level(log::LevelFilter::Debug);
-    } else {
-        builder.filter_level(log::LevelFilter::Info);
+
+    let thresholds = ThresholdExpr::parse_all(&threshold, &["loc", "dead_code_ratio", "export_count"])?;
+
+    // CLI flags take precedence over .code-viz.toml in the analyzed directory
+    let file_config = crate::config_loader::load_config(&path)?;
+    let format = format
+        .or_else(|| file_config.output_format().map(str::to_string))
+        .unwrap_or_else(|| "text".to_string());
+    let output = output.map(|template| output::expand_output_path(&template, &path, &format));
+    if format == "json" || summary_json {
+        output::progress::suppress();
     }
-    let _ = builder.try_init();
-
-    // Use code-viz-commands to run analysis
-    let mut result = tokio::runtime::Runtime::new()
-        .unwrap()
-        .block_on(code_viz_commands::analyze_repository(&path, ctx.clone(), fs.clone()))
-        .map_err(|e| AnalyzeError::DeadCodeFailed(e.to_string()))?;
-
-    // Perform dead code analysis if enabled
-    if dead_code {
-        log::info!("Running dead code analysis");
-        let dead_code_result = tokio::runtime::Runtime::new()
-            .unwrap()
-            .block_on(code_viz_commands::calculate_dead_code(&path, ctx, fs.clone(), git))
+    // The summary consists of dead code numbers only
+    let dead_code = dead_code || summary_json;
+
+    let (mut result, dead_summary) = if stdin {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source)?;
+        let dead_code_config = dead_code.then(|| file_config.dead_code_config(&exclude));
+        analyze_source(&source, language.as_deref().unwrap_or_default(), dead_code_config)?
+    } else {
+        // Use code-viz-commands to run analysis, with per-file dead code metrics if enabled
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let selection = file_selection(&path, &file_config, &exclude)?;
+        let mut result = runtime
+            .block_on(analyze_roots(&path, &roots, &selection, ctx.clone(), fs.clone()))
             .map_err(|e| AnalyzeError::DeadCodeFailed(e.to_string()))?;
+        let dead_summary = if dead_code {
+            tracing::info!("Running dead code analysis");
+            let mut dead_code_config = file_config.dead_code_config(&exclude);
+            dead_code_config.roots = roots;
+            dead_code_config.max_depth = max_depth;
+            let dead = runtime
+                .block_on(code_viz_commands::calculate_dead_code_with_config(
+                    &path,
+                    dead_code_config,
+                    ctx,
+                    fs.clone(),
+                    git,
+                ))
+                .map_err(|e| AnalyzeError::DeadCodeFailed(e.to_string()))?;
+            code_viz_commands::merge_dead_code(&mut result.files, &dead);
+            code_viz_commands::merge_dead_code_warnings(&mut result.warnings, &dead);
+            Some(dead.summary)
+        } else {
+            None
+        };
 
-        // Merge dead code info into result files
-        merge_dead_code_results(&mut result.files, dead_code_result);
+        (result, dead_summary)
+    };
+    result.commit_sha = commit_sha;
+    if relative_paths {
+        result.strip_root(&path);
     }
 
     // Handle baseline comparison
+    let mut passed = true;
     if let Some(baseline_path) = baseline {
         let baseline_content = fs.read_to_string(&baseline_path)
             .map_err(|e| AnalyzeError::IoError(std::io::Error::other(e)))?;
-        let baseline: code_viz_core::AnalysisResult = serde_json::from_str(&baseline_content)
-            .map_err(|e| AnalyzeError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        let baseline: code_viz_core::AnalysisResult =
+            code_viz_core::report::load_report(&baseline_content)?;
 
         let current_loc = result.summary.total_loc;
         let baseline_loc = baseline.summary.total_loc;
@@ -99,125 +201,198 @@ pub fn run(
             0.0
         };
 
-        println!(
+        writeln!(
+            out,
             "Baseline comparison: {} -> {} ({:+.1}%)",
             baseline_loc, current_loc, delta_percent
-        );
+        )?;
 
         if delta_percent > 10.0 {
             eprintln!("Error: Total LOC increased by {:.1}% (limit: 10%)", delta_percent);
-            process::exit(3);
+            passed = false;
         }
     }
 
-    // Handle threshold
-    if let Some(threshold_str) = threshold {
-        check_threshold(&threshold_str, &result.files)?;
-    }
+    // Handle thresholds
+    passed &= check_thresholds(&thresholds, &result.files);
 
     // Format output
     // CLI format arg takes precedence
     let format_str = format.as_str();
     let formatter: Box<dyn MetricsFormatter> = match format_str {
+        "json" if tree => Box::new(output::tree_json::TreeJsonFormatter),
         "json" => Box::new(output::json::JsonFormatter),
-        "csv" => Box::new(output::csv::CsvFormatter),
+        "csv" => Box::new(output::csv::CsvFormatter::default()),
+        "csv-excel" => Box::new(output::csv::CsvFormatter::excel()),
         "text" => Box::new(output::text::TextFormatter),
         _ => Box::new(output::text::TextFormatter),
     };
 
-    let formatted_output = formatter.format(&result)?;
+    // Reports written to disk as JSON carry a schema version so they can be
+    // loaded back as baselines after the model changes
+    let formatted_output = if let Some(summary) = dead_summary.as_ref().filter(|_| summary_json) {
+        output::summary_json::format_summary_json(summary)?
+    } else if output.is_some() && format_str == "json" && !tree {
+        VersionedReport::new(&result).to_json_pretty()?
+    } else {
+        formatter.format(&result)?
+    };
 
     // Write output
     if let Some(output_path) = output {
         fs.write(&output_path, &formatted_output)
             .map_err(|e| AnalyzeError::IoError(std::io::Error::other(e)))?;
     } else {
-        println!("{}", formatted_output);
+        writeln!(out, "{}", formatted_output)?;
     }
 
-    Ok(())
+    Ok(passed)
 }
 
-fn check_threshold(threshold_str: &str, files: &[code_viz_core::FileMetrics]) -> Result<(), AnalyzeError> {
-    let parts: Vec<&str> = threshold_str.split('=').collect();
-    if parts.len() != 2 {
-        return Err(AnalyzeError::InvalidThreshold(threshold_str.to_string()));
-    }
-
-    let key = parts[0];
+/// Analyze each of `roots` (all of `path` if there are none) into one result
+///
+/// Metrics are per file, so each root is scanned on its own rather than
+/// everything under their common ancestor.
+async fn analyze_roots(
+    path: &Path,
+    roots: &[PathBuf],
+    selection: &FileSelection,
+    ctx: impl AppContext + Clone,
+    fs: impl FileSystem + Clone,
+) -> anyhow::Result<code_viz_core::AnalysisResult> {
+    let Some((first, rest)) = roots.split_first() else {
+        return code_viz_commands::analyze_repository_with_selection(path, selection, ctx, fs).await;
+    };
 
-    match key {
-        "loc" => {
-            let value = parts[1].parse::<usize>().map_err(|_| AnalyzeError::InvalidThreshold(threshold_str.to_string()))?;
-            let violating_files: Vec<_> = files.iter()
-                .filter(|f| f.loc > value)
-                .collect();
+    let mut result = code_viz_commands::analyze_repository_with_selection(first, selection, ctx.clone(), fs.clone()).await?;
+    for root in rest {
+        let root_result = code_viz_commands::analyze_repository_with_selection(root, selection, ctx.clone(), fs.clone()).await?;
+        result.files.extend(root_result.files);
+        result.skipped_files += root_result.skipped_files;
+        result.warnings.extend(root_result.warnings);
+    }
+    // The summary of the first root only covers its own files
+    result.summary = code_viz_core::calculate_summary(&result.files);
+    Ok(result)
+}
 
-            if !violating_files.is_empty() {
-                eprintln!("Error: The following files exceed the LOC threshold of {}:", value);
-                for file in violating_files {
-                    eprintln!("  {} ({} LOC)", file.path.display(), file.loc);
-                }
-                process::exit(3);
-            }
-        }
-        "dead_code_ratio" => {
-            let value = parts[1].parse::<f64>().map_err(|_| AnalyzeError::InvalidThreshold(threshold_str.to_string()))?;
-            let violating_files: Vec<_> = files.iter()
-                .filter(|f| f.dead_code_ratio.unwrap_or(0.0) > value)
-                .collect();
-
-            if !violating_files.is_empty() {
-                eprintln!("Error: The following files exceed the dead code ratio threshold of {:.2}:", value);
-                for file in violating_files {
-                    eprintln!("  {} ({:.2}% dead code)", file.path.display(), file.dead_code_ratio.unwrap_or(0.0) * 100.0);
-                }
-                process::exit(3);
+/// Analyze one file's source as `<stdin>`, with best-effort single-file dead
+/// code metrics and summary when `dead_code_config` is given (TypeScript and
+/// JavaScript only)
+fn analyze_source(
+    source: &str,
+    language: &str,
+    dead_code_config: Option<code_viz_dead_code::AnalysisConfig>,
+) -> Result<(code_viz_core::AnalysisResult, Option<code_viz_dead_code::DeadCodeSummary>), AnalyzeError> {
+    let parser = code_viz_core::parser::get_parser(language)
+        .map_err(|_| AnalyzeError::UnsupportedLanguage(language.to_string()))?;
+    let path = PathBuf::from(STDIN_PATH);
+    let mut file = code_viz_core::metrics::calculate_metrics(&path, source, parser.as_ref(), None)
+        .map_err(code_viz_core::analyzer::AnalysisError::from)?;
+
+    let mut dead_summary = None;
+    if let Some(config) = dead_code_config {
+        // The dead code parser is picked by extension
+        let extension = match parser.language() {
+            "typescript" => "ts",
+            "tsx" => "tsx",
+            "javascript" => "js",
+            other => {
+                return Err(AnalyzeError::DeadCodeFailed(format!(
+                    "dead code analysis is not supported for {}",
+                    other
+                )))
             }
+        };
+        let mut dead = code_viz_dead_code::analyze_source(&path.with_extension(extension), source, Some(config))
+            .map_err(|e| AnalyzeError::DeadCodeFailed(e.to_string()))?;
+        for dead_file in &mut dead.files {
+            dead_file.path = path.clone();
         }
-        _ => return Err(AnalyzeError::InvalidThreshold(format!("Unknown metric '{}'", key))),
+        code_viz_commands::merge_dead_code(std::slice::from_mut(&mut file), &dead);
+        dead_summary = Some(dead.summary);
     }
 
-    Ok(())
+    let files = vec![file];
+    let result = code_viz_core::AnalysisResult {
+        schema_version: code_viz_core::report::current_schema_version(),
+        summary: code_viz_core::calculate_summary(&files),
+        files,
+        timestamp: std::time::SystemTime::now(),
+        skipped_files: 0,
+        warnings: vec![],
+        commit_sha: None,
+    };
+    Ok((result, dead_summary))
 }
 
-fn merge_dead_code_results(
-    file_metrics: &mut [code_viz_core::FileMetrics],
-    dead_code_result: code_viz_dead_code::DeadCodeResult,
-) {
-    // Create a map of file -> dead code info for efficient lookup
-    let mut dead_code_by_file: HashMap<PathBuf, &code_viz_dead_code::FileDeadCode> =
-        HashMap::new();
-
-    for file_dead_code in &dead_code_result.files {
-        dead_code_by_file.insert(file_dead_code.path.clone(), file_dead_code);
-    }
-
-    // Update file metrics with dead code info
-    for file_metric in file_metrics.iter_mut() {
-        if let Some(dead_code_info) = dead_code_by_file.get(&file_metric.path) {
-            let dead_function_count = dead_code_info.dead_code.iter()
-                .filter(|s| matches!(
-                    s.kind,
-                    code_viz_dead_code::models::SymbolKind::Function
-                        | code_viz_dead_code::models::SymbolKind::ArrowFunction
-                        | code_viz_dead_code::models::SymbolKind::Method
-                ))
-                .count();
-
-            let dead_code_loc: usize = dead_code_info.dead_code.iter()
-                .map(|s| s.loc)
-                .sum();
-
-            let dead_code_ratio = if file_metric.loc > 0 {
-                dead_code_loc as f64 / file_metric.loc as f64
-            } else {
-                0.0
-            };
+/// Files under `root` selected by the exclude, include and language settings,
+/// with CLI exclude patterns taking precedence
+fn file_selection(
+    root: &Path,
+    file_config: &ConfigFile,
+    cli_exclude: &[String],
+) -> Result<FileSelection, AnalyzeError> {
+    let analysis = file_config.analysis.as_ref();
+    let options = ScanOptions {
+        exclude_patterns: file_config.exclude_patterns(cli_exclude).unwrap_or_default(),
+        include_patterns: analysis.and_then(|a| a.include.clone()).unwrap_or_default(),
+        languages: analysis.and_then(|a| a.languages.clone()).unwrap_or_default(),
+        extension_overrides: analysis.and_then(|a| a.extension_overrides.clone()).unwrap_or_default(),
+        ..Default::default()
+    };
+    Ok(FileSelection::new(root, &options).map_err(code_viz_core::analyzer::AnalysisError::from)?)
+}
 
-            file_metric.dead_function_count = Some(dead_function_count);
-            file_metric.dead_code_loc = Some(dead_code_loc);
-            file_metric.dead_code_ratio = Some(dead_code_ratio);
+/// Print every violated threshold, returning false if any was violated
+fn check_thresholds(thresholds: &[ThresholdExpr], files: &[code_viz_core::FileMetrics]) -> bool {
+    let mut passed = true;
+
+    for threshold in thresholds {
+        match threshold.metric.as_str() {
+            "loc" => {
+                let violating_files: Vec<_> = files.iter()
+                    .filter(|f| threshold.is_violated_by(f.loc as f64))
+                    .collect();
+
+                if !violating_files.is_empty() {
+                    eprintln!("Error: The following files exceed the LOC threshold ({}):", threshold);
+                    for file in violating_files {
+                        eprintln!("  {} ({} LOC)", file.path.display(), file.loc);
+                    }
+                    passed = false;
+                }
+            }
+            "dead_code_ratio" => {
+                let violating_files: Vec<_> = files.iter()
+                    .filter(|f| threshold.is_violated_by(f.dead_code_ratio.unwrap_or(0.0)))
+                    .collect();
+
+                if !violating_files.is_empty() {
+                    eprintln!("Error: The following files exceed the dead code ratio threshold ({}):", threshold);
+                    for file in violating_files {
+                        eprintln!("  {} ({:.2}% dead code)", file.path.display(), file.dead_code_ratio.unwrap_or(0.0) * 100.0);
+                    }
+                    passed = false;
+                }
+            }
+            "export_count" => {
+                let violating_files: Vec<_> = files.iter()
+                    .filter(|f| threshold.is_violated_by(f.export_count as f64))
+                    .collect();
+
+                if !violating_files.is_empty() {
+                    eprintln!("Error: The following files exceed the export count threshold ({}):", threshold);
+                    for file in violating_files {
+                        eprintln!("  {} ({} exports)", file.path.display(), file.export_count);
+                    }
+                    passed = false;
+                }
+            }
+            // Metrics are validated by ThresholdExpr::parse_all
+            _ => unreachable!("unvalidated threshold metric '{}'", threshold.metric),
         }
     }
+
+    passed
 }
@@ -0,0 +1,147 @@
+use code_viz_core::traits::FileSystem;
+use code_viz_dead_code::PublicApiReport;
+use colored::Colorize;
+use std::io::Write;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ApiSurfaceError {
+    #[error("Public API analysis failed: {0}")]
+    AnalysisFailed(#[from] code_viz_dead_code::AnalysisError),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config_loader::ConfigError),
+}
+
+pub struct ApiSurfaceConfig {
+    pub path: PathBuf,
+    pub format: Option<String>,
+    pub exclude: Vec<String>,
+    pub output: Option<PathBuf>,
+    /// Strip the analyzed directory from every reported path
+    pub relative_paths: bool,
+}
+
+/// Write the public API report to `out` unless an output file is configured
+pub fn run(
+    config: ApiSurfaceConfig,
+    fs: impl FileSystem,
+    out: &mut dyn Write,
+) -> Result<(), ApiSurfaceError> {
+    let ApiSurfaceConfig {
+        path,
+        format,
+        exclude,
+        output,
+        relative_paths,
+    } = config;
+
+    // Same settings as dead-code, so entry points agree between the two
+    let file_config = crate::config_loader::load_config(&path)?;
+    let format = format
+        .or_else(|| file_config.output_format().map(str::to_string))
+        .unwrap_or_else(|| "text".to_string());
+    let output = output
+        .map(|template| crate::output::expand_output_path(&template, &path, &format));
+    let analysis_config = file_config.dead_code_config(&exclude);
+
+    let mut report = code_viz_dead_code::analyze_public_api(&path, Some(analysis_config))?;
+    if relative_paths {
+        report.strip_root(&path);
+    }
+
+    let formatted_output = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&report)
+            .map_err(|e| ApiSurfaceError::IoError(std::io::Error::other(e)))?,
+        _ => format_text(&report), // Default to text
+    };
+
+    if let Some(output_path) = output {
+        fs.write(&output_path, &formatted_output)
+            .map_err(|e| ApiSurfaceError::IoError(std::io::Error::other(e)))?;
+    } else {
+        writeln!(out, "{}", formatted_output)?;
+    }
+
+    Ok(())
+}
+
+fn format_text(report: &PublicApiReport) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+
+    writeln!(&mut output, "Public API Surface").unwrap();
+    writeln!(&mut output, "==================").unwrap();
+    writeln!(&mut output).unwrap();
+
+    let used = report.exports.iter().filter(|e| e.used_internally).count();
+    writeln!(
+        &mut output,
+        "Exports: {} ({} used internally, {} public only)",
+        report.exports.len(),
+        used,
+        report.exports.len() - used
+    ).unwrap();
+
+    // Exports are sorted by path, so each file's exports are adjacent
+    let mut current_file = None;
+    for export in &report.exports {
+        if current_file != Some(&export.path) {
+            writeln!(&mut output).unwrap();
+            writeln!(&mut output, "{}", export.path.display().to_string().bold()).unwrap();
+            current_file = Some(&export.path);
+        }
+
+        let usage = if export.used_internally {
+            "used internally".normal()
+        } else {
+            "public only".yellow()
+        };
+        let entry = if export.is_entry { "  [entry point]" } else { "" };
+        writeln!(
+            &mut output,
+            "  {} ({:?}, line {})  {}{}",
+            export.symbol, export.kind, export.line_start, usage, entry
+        ).unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_viz_dead_code::models::SymbolKind;
+    use code_viz_dead_code::PublicExport;
+
+    #[test]
+    fn test_format_text_groups_exports_by_file() {
+        let export = |path: &str, symbol: &str, used_internally: bool| PublicExport {
+            id: format!("{}:1:{}", path, symbol),
+            symbol: symbol.to_string(),
+            kind: SymbolKind::Function,
+            path: PathBuf::from(path),
+            line_start: 1,
+            used_internally,
+            is_entry: false,
+        };
+        let report = PublicApiReport {
+            exports: vec![
+                export("src/a.ts", "parse", true),
+                export("src/a.ts", "render", false),
+                export("src/b.ts", "run", false),
+            ],
+        };
+
+        let text = format_text(&report);
+        assert!(text.contains("Exports: 3 (1 used internally, 2 public only)"));
+        assert_eq!(text.matches("src/a.ts").count(), 1);
+        assert!(text.contains("  render (Function, line 1)  "));
+        assert!(text.contains("public only"));
+    }
+}
@@ -12,10 +12,15 @@ pub enum ConfigError {
 }
 
 const TEMPLATE: &str = r#"# CodeViz Configuration
+# Command-line flags take precedence over values in this file.
 
 [analysis]
 # Glob patterns to exclude from analysis
 # exclude = ["node_modules/**", "target/**", "dist/**", ".git/**"]
+# Only analyze files matching these glob patterns
+# include = ["src/**"]
+# Languages to analyze
+# languages = ["typescript", "javascript"]
 
 [output]
 # Default output format (text, json, csv)
@@ -24,6 +29,26 @@ const TEMPLATE: &str = r#"# CodeViz Configuration
 [cache]
 # Enable caching to speed up re-analysis
 # enabled = true
+
+[dead_code]
+# Minimum confidence score (0-100) for reported dead code
+# min_confidence = 80
+# Dead symbols shorter than this many lines are not reported
+# min_loc = 0
+# Warn that entry points were probably missed when more code than this is dead
+# suspicious_dead_ratio = 0.8
+# Glob patterns of files whose symbols are always treated as used
+# entry_points = ["src/routes/**"]
+# Regexes of symbol names invoked reflectively; matches are never reported
+# keep_patterns = ["^route_", "Command$"]
+
+[dead_code.confidence_weights]
+# Confidence penalty applied by each heuristic
+# exported = 30
+# recently_modified = 20
+# dynamic_pattern = 25
+# test_coverage = 15
+# string_reference = 20
 "#;
 
 pub fn run_init(fs: impl FileSystem) -> Result<(), ConfigError> {
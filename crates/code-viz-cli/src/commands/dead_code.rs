@@ -1,5 +1,9 @@
-use std::path::PathBuf;
-use std::process;
+use crate::exit_code::ExitCode;
+use crate::threshold::ThresholdExpr;
+use code_viz_core::report::VersionedReport;
+use colored::Colorize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -13,39 +17,228 @@ pub enum DeadCodeError {
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
 
-    #[error("Invalid threshold format: {0}")]
-    InvalidThreshold(String),
+    #[error("{0}")]
+    InvalidThreshold(#[from] crate::threshold::ThresholdError),
+
+    #[error("Report error: {0}")]
+    ReportError(#[from] code_viz_core::report::ReportError),
+
+    #[error("Invalid --why target '{0}', expected <file>::<symbol>")]
+    InvalidWhyTarget(String),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config_loader::ConfigError),
+}
+
+impl DeadCodeError {
+    /// Whether the error comes from invalid arguments rather than the analysis
+    pub fn is_usage_error(&self) -> bool {
+        matches!(
+            self,
+            DeadCodeError::InvalidThreshold(_) | DeadCodeError::InvalidWhyTarget(_)
+        )
+    }
+}
+
+pub struct DeadCodeConfig {
+    pub path: PathBuf,
+    /// Directories under `path` analyzed together (all of `path` if empty)
+    pub roots: Vec<PathBuf>,
+    pub format: Option<String>,
+    pub min_confidence: Option<u8>,
+    pub min_loc: Option<usize>,
+    pub exclude: Vec<String>,
+    /// Only scan this many directory levels deep (unlimited if `None`)
+    pub max_depth: Option<usize>,
+    /// Fail if the scan selects more source files than this (unlimited if `None`)
+    pub max_files: Option<usize>,
+    pub threshold: Vec<String>,
+    pub output: Option<PathBuf>,
+    pub by_dir: bool,
+    pub explain: bool,
+    pub why: Option<String>,
+    pub split_output: Option<PathBuf>,
+    pub exit_zero: bool,
+    /// Strip the analyzed directory from every reported path
+    pub relative_paths: bool,
+    /// Time parsing per file and print the slowest files to stderr
+    pub profile: bool,
+    /// Output raw symbol IDs instead of the dead code report
+    pub emit_symbols: bool,
+    /// Ordering of the detailed text listing
+    pub sort: DeadCodeSort,
+    /// Output only the headline summary numbers as compact JSON
+    pub summary_json: bool,
+    /// Only report dead exported symbols (unused public API)
+    pub exports_only: bool,
+    /// Follow calls between functions of the same file
+    pub call_graph: bool,
+    /// Package directories to report separately, relative to `path`
+    pub report_roots: Vec<PathBuf>,
+    /// Prior dead code report whose symbols are accepted and not reported again
+    pub baseline_suppress: Option<PathBuf>,
+}
+
+/// Ordering of the symbols listed by the text report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DeadCodeSort {
+    /// Confidence tiers, each in file order
+    #[default]
+    File,
+    /// One list, most confident first
+    Confidence,
+    /// One list, largest first
+    Loc,
+    /// One list, longest unchanged first; symbols without git history last
+    Age,
 }
 
+/// Confidence tiers written by `--split-output`, matching the text report sections
+const CONFIDENCE_TIERS: [(&str, u8, u8); 3] = [
+    ("high", 80, u8::MAX),
+    ("medium", 60, 79),
+    ("low", 0, 59),
+];
+
+/// Number of slowest files reported by `--profile`
+const PROFILE_FILES: usize = 10;
+
 use code_viz_core::traits::{AppContext, FileSystem, GitProvider};
 
+/// Run dead code analysis, writing the report to `out` unless an output file
+/// is configured
 pub fn run(
-    path: PathBuf,
-    format: String,
-    min_confidence: u8,
-    _exclude: Vec<String>,
-    verbose: bool,
-    threshold: Option<String>,
-    output: Option<PathBuf>,
+    config: DeadCodeConfig,
     ctx: impl AppContext,
     fs: impl FileSystem + Clone,
     git: impl GitProvider,
-) -> Result<(), DeadCodeError> {
-    // Setup logging
-    let mut builder = env_logger::Builder::from_default_env();
-    if verbose {
-        builder.filter_level(log::LevelFilter::Debug);
-    } else {
-        builder.filter_level(log::LevelFilter::Info);
+    out: &mut dyn Write,
+) -> Result<ExitCode, DeadCodeError> {
+    let DeadCodeConfig {
+        path,
+        roots,
+        format,
+        min_confidence,
+        min_loc,
+        exclude,
+        max_depth,
+        max_files,
+        threshold,
+        output,
+        by_dir,
+        explain,
+        why,
+        split_output,
+        exit_zero,
+        relative_paths,
+        profile,
+        emit_symbols,
+        sort,
+        summary_json,
+        exports_only,
+        call_graph,
+        report_roots,
+        baseline_suppress,
+    } = config;
+
+    // CLI flags take precedence over .code-viz.toml, which overrides defaults
+    let file_config = crate::config_loader::load_config(&path)?;
+    let format = format
+        .or_else(|| file_config.output_format().map(str::to_string))
+        .unwrap_or_else(|| "text".to_string());
+    let output = output
+        .map(|template| crate::output::expand_output_path(&template, &path, &format));
+    if format == "json" || format == "junit" || summary_json {
+        crate::output::progress::suppress();
+    }
+    let min_confidence = min_confidence.or(file_config.min_confidence()).unwrap_or(80);
+    let mut analysis_config = file_config.dead_code_config(&exclude);
+    if let Some(min_loc) = min_loc {
+        analysis_config.min_loc = min_loc;
+    }
+    analysis_config.roots = roots;
+    analysis_config.max_depth = max_depth;
+    analysis_config.max_files = max_files;
+    analysis_config.exports_only = exports_only;
+    analysis_config.call_graph = call_graph;
+    // Blaming every file with dead code is only worth it to sort by age
+    analysis_config.symbol_ages = sort == DeadCodeSort::Age;
+    if profile {
+        analysis_config.profile = Some(PROFILE_FILES);
     }
-    let _ = builder.try_init();
+
+    if let Some(target) = why {
+        writeln!(out, "{}", explain_why(&path, analysis_config, &target)?)?;
+        return Ok(ExitCode::Success);
+    }
+
+    if emit_symbols {
+        let mut ids = code_viz_dead_code::analyze_dead_code_raw(&path, Some(analysis_config))?
+            .symbol_ids();
+        if relative_paths {
+            ids.strip_root(&path);
+        }
+        let json = format_json(&ids)?;
+        match output {
+            Some(output_path) => fs
+                .write(&output_path, &json)
+                .map_err(|e| DeadCodeError::IoError(std::io::Error::other(e)))?,
+            None => writeln!(out, "{}", json)?,
+        }
+        return Ok(ExitCode::Success);
+    }
+
+    let thresholds = ThresholdExpr::parse_all(&threshold, &["dead_code_ratio", "dead_functions"])?;
 
     // Use code-viz-commands to run dead code analysis
     let result = tokio::runtime::Runtime::new()
         .unwrap()
-        .block_on(code_viz_commands::calculate_dead_code(&path, ctx, fs.clone(), git))
+        .block_on(code_viz_commands::calculate_dead_code_with_config(
+            &path,
+            analysis_config,
+            ctx,
+            fs.clone(),
+            git,
+        ))
         .map_err(|e| DeadCodeError::IoError(std::io::Error::other(e)))?;
 
+    let mut result = result;
+    if relative_paths {
+        result.strip_root(&path);
+    }
+
+    // Dead code accepted in the baseline is neither reported nor gated
+    if let Some(baseline_path) = baseline_suppress {
+        let baseline_content = fs
+            .read_to_string(&baseline_path)
+            .map_err(|e| DeadCodeError::IoError(std::io::Error::other(e)))?;
+        let baseline: code_viz_dead_code::DeadCodeResult =
+            code_viz_core::report::load_report(&baseline_content)?;
+        result = result.suppress_baseline(&baseline, &path);
+    }
+
+    // On stderr so the report itself stays machine-readable
+    if let Some(timings) = &result.profile {
+        eprintln!("Slowest files to parse:");
+        for timing in timings {
+            eprintln!("  {:>8.1}ms  {}", timing.ms, timing.path.display());
+        }
+    }
+
+    // Confidence penalties are only reported with --explain
+    if !explain {
+        for file in &mut result.files {
+            for symbol in &mut file.dead_code {
+                symbol.confidence_penalties = None;
+            }
+        }
+    }
+
+    // Tier reports cover every finding, regardless of --min-confidence
+    if let Some(dir) = split_output {
+        write_split_output(&dir, &result, &fs)?;
+    }
+
     // Filter by minimum confidence
     let filtered_result = if min_confidence > 0 {
         result.filter_by_confidence(min_confidence)
@@ -53,15 +246,33 @@ pub fn run(
         result
     };
 
-    // Handle threshold
-    if let Some(threshold_str) = threshold {
-        check_threshold(&threshold_str, &filtered_result)?;
-    }
+    // Handle thresholds
+    let passed = check_thresholds(&thresholds, &filtered_result);
+
+    // Roots are given relative to the analyzed directory, like reported paths
+    let report_roots: Vec<PathBuf> = if relative_paths {
+        report_roots
+    } else {
+        report_roots.iter().map(|root| path.join(root)).collect()
+    };
 
     // Format output
-    let formatted_output = match format.as_str() {
-        "json" => format_json(&filtered_result)?,
-        _ => format_text(&filtered_result), // Default to text
+    let formatted_output = match (format.as_str(), by_dir) {
+        _ if !report_roots.is_empty() => {
+            format_root_reports(&filtered_result.partition_by_roots(&report_roots), &format, sort)?
+        }
+        _ if summary_json => {
+            crate::output::summary_json::format_summary_json(&filtered_result.summary)?
+        }
+        ("json", true) => format_json(&filtered_result.by_directory)?,
+        // Reports written to disk carry a schema version
+        ("json", false) if output.is_some() => {
+            VersionedReport::new(&filtered_result).to_json_pretty()?
+        }
+        ("json", false) => format_json(&filtered_result)?,
+        ("junit", _) => crate::output::junit::format_junit(&filtered_result),
+        (_, true) => format_by_dir_text(&filtered_result),
+        _ => format_text(&filtered_result, sort), // Default to text
     };
 
     // Write output
@@ -69,73 +280,163 @@ pub fn run(
         fs.write(&output_path, &formatted_output)
             .map_err(|e| DeadCodeError::IoError(std::io::Error::other(e)))?;
     } else {
-        println!("{}", formatted_output);
+        writeln!(out, "{}", formatted_output)?;
     }
 
-    Ok(())
+    Ok(ExitCode::for_checks(passed, exit_zero))
+}
+
+/// One report per root: a JSON array, or a text report under a heading per root
+fn format_root_reports(
+    reports: &[code_viz_dead_code::RootReport],
+    format: &str,
+    sort: DeadCodeSort,
+) -> Result<String, DeadCodeError> {
+    if format == "json" {
+        return format_json(reports);
+    }
+
+    let mut output = String::new();
+    for report in reports {
+        let root = match &report.root {
+            Some(root) => root.display().to_string(),
+            None => "(outside report roots)".to_string(),
+        };
+        output.push_str(&format!("== {} ==\n\n", root));
+        output.push_str(&format_text(&report.result, sort));
+        output.push('\n');
+    }
+    Ok(output)
 }
 
-fn check_threshold(
-    threshold_str: &str,
+/// Write one `DeadCodeResult` per confidence tier into `dir`
+fn write_split_output(
+    dir: &Path,
     result: &code_viz_dead_code::DeadCodeResult,
+    fs: &impl FileSystem,
 ) -> Result<(), DeadCodeError> {
-    let parts: Vec<&str> = threshold_str.split('=').collect();
-    if parts.len() != 2 {
-        return Err(DeadCodeError::InvalidThreshold(threshold_str.to_string()));
-    }
-
-    let key = parts[0];
-    let value_str = parts[1];
-
-    match key {
-        "dead_code_ratio" => {
-            let threshold: f64 = value_str
-                .parse()
-                .map_err(|_| DeadCodeError::InvalidThreshold(threshold_str.to_string()))?;
-
-            if result.summary.dead_code_ratio > threshold {
-                eprintln!(
-                    "Error: Dead code ratio {:.2}% exceeds threshold {:.2}%",
-                    result.summary.dead_code_ratio * 100.0,
-                    threshold * 100.0
-                );
-                process::exit(3);
+    for (tier, min, max) in CONFIDENCE_TIERS {
+        let tier_result = result.filter_by_confidence_range(min..=max);
+        let path = dir.join(format!("{}.json", tier));
+        fs.write(&path, &format_json(&tier_result)?)
+            .map_err(|e| DeadCodeError::IoError(std::io::Error::other(e)))?;
+
+        let symbols: usize = tier_result.files.iter().map(|f| f.dead_code.len()).sum();
+        eprintln!("Wrote {} ({} symbols)", path.display(), symbols);
+    }
+
+    Ok(())
+}
+
+/// Print every violated threshold, returning false if any was violated
+fn check_thresholds(
+    thresholds: &[ThresholdExpr],
+    result: &code_viz_dead_code::DeadCodeResult,
+) -> bool {
+    let mut passed = true;
+
+    for threshold in thresholds {
+        match threshold.metric.as_str() {
+            "dead_code_ratio" => {
+                if threshold.is_violated_by(result.summary.dead_code_ratio) {
+                    eprintln!(
+                        "Error: Dead code ratio {:.2}% violates threshold {}",
+                        result.summary.dead_code_ratio * 100.0,
+                        threshold
+                    );
+                    passed = false;
+                }
             }
-        }
-        "dead_functions" => {
-            let threshold: usize = value_str
-                .parse()
-                .map_err(|_| DeadCodeError::InvalidThreshold(threshold_str.to_string()))?;
-
-            if result.summary.dead_functions > threshold {
-                eprintln!(
-                    "Error: Dead functions {} exceeds threshold {}",
-                    result.summary.dead_functions, threshold
-                );
-                process::exit(3);
+            "dead_functions" => {
+                if threshold.is_violated_by(result.summary.dead_functions as f64) {
+                    eprintln!(
+                        "Error: Dead functions {} violates threshold {}",
+                        result.summary.dead_functions, threshold
+                    );
+                    passed = false;
+                }
             }
-        }
-        _ => {
-            return Err(DeadCodeError::InvalidThreshold(format!(
-                "Unknown metric '{}'",
-                key
-            )))
+            // Metrics are validated by ThresholdExpr::parse_all
+            _ => unreachable!("unvalidated threshold metric '{}'", threshold.metric),
         }
     }
 
-    Ok(())
+    passed
+}
+
+/// Describe the shortest entry-point path keeping `target` (`file::symbol`) alive
+fn explain_why(
+    path: &Path,
+    config: code_viz_dead_code::AnalysisConfig,
+    target: &str,
+) -> Result<String, DeadCodeError> {
+    let (file, symbol) = super::explain::split_target(target)
+        .ok_or_else(|| DeadCodeError::InvalidWhyTarget(target.to_string()))?;
+
+    let chain = code_viz_dead_code::reachability_path(path, Some(config), Path::new(file), symbol)?;
+
+    Ok(match chain {
+        Some(chain) => super::explain::format_chain(target, &chain),
+        None => format!("{} is not reachable from any entry point\n", target),
+    })
 }
 
-fn format_json(result: &code_viz_dead_code::DeadCodeResult) -> Result<String, DeadCodeError> {
-    serde_json::to_string_pretty(result)
+fn format_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, DeadCodeError> {
+    serde_json::to_string_pretty(value)
         .map_err(|e| DeadCodeError::IoError(std::io::Error::other(e)))
 }
 
-fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
+fn format_by_dir_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+
+    writeln!(&mut output, "Dead Code by Directory").unwrap();
+    writeln!(&mut output, "======================").unwrap();
+    writeln!(&mut output).unwrap();
+
+    if result.by_directory.is_empty() {
+        writeln!(&mut output, "No dead code found!").unwrap();
+        return output;
+    }
+
+    writeln!(
+        &mut output,
+        "{:>9}  {:>7}  {:>7}  Directory",
+        "Dead LOC", "Symbols", "Ratio"
+    ).unwrap();
+    // by_directory is already sorted by dead LOC (descending)
+    for dir in &result.by_directory {
+        writeln!(
+            &mut output,
+            "{:>9}  {:>7}  {:>6.2}%  {}",
+            dir.dead_loc,
+            dir.dead_symbols,
+            dir.dead_ratio * 100.0,
+            dir.dir.display()
+        ).unwrap();
+    }
+
+    output
+}
+
+fn format_text(result: &code_viz_dead_code::DeadCodeResult, sort: DeadCodeSort) -> String {
     use std::fmt::Write;
 
     let mut output = String::new();
 
+    // Warnings come first so a suspicious result is not mistaken for a real one
+    for warning in &result.warnings {
+        writeln!(
+            &mut output,
+            "{}",
+            format!("Warning: {}", warning.message).yellow().bold()
+        ).unwrap();
+    }
+    if !result.warnings.is_empty() {
+        writeln!(&mut output).unwrap();
+    }
+
     // Summary section
     writeln!(&mut output, "Dead Code Analysis Summary").unwrap();
     writeln!(&mut output, "===========================").unwrap();
@@ -160,6 +461,11 @@ fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
         "Dead classes:               {}",
         result.summary.dead_classes
     ).unwrap();
+    writeln!(
+        &mut output,
+        "Other dead symbols:         {}",
+        result.summary.dead_other
+    ).unwrap();
     writeln!(
         &mut output,
         "Total dead LOC:             {}",
@@ -170,6 +476,34 @@ fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
         "Dead code ratio:            {:.2}%",
         result.summary.dead_code_ratio * 100.0
     ).unwrap();
+    if result.summary.kept_by_pattern > 0 {
+        writeln!(
+            &mut output,
+            "Kept by pattern:            {}",
+            result.summary.kept_by_pattern
+        ).unwrap();
+    }
+    if result.summary.filtered_small > 0 {
+        writeln!(
+            &mut output,
+            "Filtered as too small:      {}",
+            result.summary.filtered_small
+        ).unwrap();
+    }
+    if result.summary.skipped_generated > 0 {
+        writeln!(
+            &mut output,
+            "Skipped as generated:       {}",
+            result.summary.skipped_generated
+        ).unwrap();
+    }
+    if result.summary.skipped_files > 0 {
+        writeln!(
+            &mut output,
+            "Skipped files:              {} (too large or unreadable)",
+            result.summary.skipped_files
+        ).unwrap();
+    }
     writeln!(&mut output).unwrap();
 
     if result.files.is_empty() {
@@ -177,20 +511,51 @@ fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
         return output;
     }
 
+    let symbols = result
+        .files
+        .iter()
+        .flat_map(|file| file.dead_code.iter().map(move |symbol| (file, symbol)));
+
+    // A single list puts the safest or largest deletions at the top
+    let flat_title = match sort {
+        DeadCodeSort::File => None,
+        DeadCodeSort::Confidence => Some("Dead Symbols by Confidence"),
+        DeadCodeSort::Loc => Some("Dead Symbols by Size"),
+        DeadCodeSort::Age => Some("Dead Symbols by Age"),
+    };
+    if let Some(title) = flat_title {
+        let mut symbols: Vec<_> = symbols.collect();
+        // Stable sort, so ties stay in file order
+        match sort {
+            DeadCodeSort::Confidence => {
+                symbols.sort_by_key(|(_, symbol)| std::cmp::Reverse(symbol.confidence))
+            }
+            // Unknown ages (None) sort below every known one
+            DeadCodeSort::Age => symbols.sort_by_key(|(_, symbol)| std::cmp::Reverse(symbol.age_days)),
+            _ => symbols.sort_by_key(|(_, symbol)| std::cmp::Reverse(symbol.loc)),
+        }
+
+        writeln!(&mut output, "{}", title).unwrap();
+        writeln!(&mut output, "{}", "-".repeat(title.len())).unwrap();
+        for (file, symbol) in symbols {
+            write_symbol(&mut output, file, symbol);
+        }
+        writeln!(&mut output).unwrap();
+        return output;
+    }
+
     // Group by confidence tiers
     let mut high_confidence = Vec::new();
     let mut medium_confidence = Vec::new();
     let mut low_confidence = Vec::new();
 
-    for file in &result.files {
-        for symbol in &file.dead_code {
-            if symbol.confidence >= 80 {
-                high_confidence.push((file, symbol));
-            } else if symbol.confidence >= 60 {
-                medium_confidence.push((file, symbol));
-            } else {
-                low_confidence.push((file, symbol));
-            }
+    for (file, symbol) in symbols {
+        if symbol.confidence >= 80 {
+            high_confidence.push((file, symbol));
+        } else if symbol.confidence >= 60 {
+            medium_confidence.push((file, symbol));
+        } else {
+            low_confidence.push((file, symbol));
         }
     }
 
@@ -199,18 +564,7 @@ fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
         writeln!(&mut output, "High Confidence Deletions (>= 80%)").unwrap();
         writeln!(&mut output, "-----------------------------------").unwrap();
         for (file, symbol) in &high_confidence {
-            writeln!(
-                &mut output,
-                "  {} ({}:{})",
-                symbol.symbol,
-                file.path.display(),
-                symbol.line_start
-            ).unwrap();
-            writeln!(
-                &mut output,
-                "    Kind: {:?}, Lines: {}-{}, Confidence: {}%",
-                symbol.kind, symbol.line_start, symbol.line_end, symbol.confidence
-            ).unwrap();
+            write_symbol(&mut output, file, symbol);
         }
         writeln!(&mut output).unwrap();
     }
@@ -220,18 +574,7 @@ fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
         writeln!(&mut output, "Medium Confidence (60-79%)").unwrap();
         writeln!(&mut output, "--------------------------").unwrap();
         for (file, symbol) in &medium_confidence {
-            writeln!(
-                &mut output,
-                "  {} ({}:{})",
-                symbol.symbol,
-                file.path.display(),
-                symbol.line_start
-            ).unwrap();
-            writeln!(
-                &mut output,
-                "    Kind: {:?}, Lines: {}-{}, Confidence: {}%",
-                symbol.kind, symbol.line_start, symbol.line_end, symbol.confidence
-            ).unwrap();
+            write_symbol(&mut output, file, symbol);
         }
         writeln!(&mut output).unwrap();
     }
@@ -241,21 +584,162 @@ fn format_text(result: &code_viz_dead_code::DeadCodeResult) -> String {
         writeln!(&mut output, "Low Confidence (< 60%)").unwrap();
         writeln!(&mut output, "----------------------").unwrap();
         for (file, symbol) in &low_confidence {
-            writeln!(
-                &mut output,
-                "  {} ({}:{})",
-                symbol.symbol,
-                file.path.display(),
-                symbol.line_start
-            ).unwrap();
-            writeln!(
-                &mut output,
-                "    Kind: {:?}, Lines: {}-{}, Confidence: {}%",
-                symbol.kind, symbol.line_start, symbol.line_end, symbol.confidence
-            ).unwrap();
+            write_symbol(&mut output, file, symbol);
         }
         writeln!(&mut output).unwrap();
     }
 
     output
 }
+
+/// Write one symbol of the detailed text listing
+fn write_symbol(
+    output: &mut String,
+    file: &code_viz_dead_code::FileDeadCode,
+    symbol: &code_viz_dead_code::DeadSymbol,
+) {
+    use std::fmt::Write;
+
+    writeln!(
+        output,
+        "  {} ({}:{})",
+        symbol.symbol,
+        file.path.display(),
+        symbol.line_start
+    ).unwrap();
+    writeln!(
+        output,
+        "    Kind: {:?}, Lines: {}-{}, Confidence: {}%",
+        symbol.kind, symbol.line_start, symbol.line_end, symbol.confidence
+    ).unwrap();
+    if let Some(age_days) = symbol.age_days {
+        writeln!(output, "    Unchanged for {} days", age_days).unwrap();
+    }
+    if symbol.confidence_penalties.is_some() {
+        writeln!(output, "    Explain: {}", symbol.explain_confidence()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_viz_dead_code::fixtures::{dead_code_result, dead_symbol};
+    use code_viz_dead_code::DeadCodeResult;
+
+    fn sample_result() -> DeadCodeResult {
+        dead_code_result(vec![
+            (
+                "src/a.ts",
+                vec![
+                    dead_symbol("aLow", 1, 40, 50),
+                    dead_symbol("aHigh", 1, 5, 85),
+                ],
+            ),
+            (
+                "src/b.ts",
+                vec![
+                    dead_symbol("bTop", 1, 10, 95),
+                    dead_symbol("bMid", 1, 45, 70),
+                ],
+            ),
+        ])
+    }
+
+    /// Symbol names in the order they are listed
+    fn listed(text: &str) -> Vec<&str> {
+        text.lines()
+            .filter(|line| line.starts_with("  ") && !line.starts_with("    "))
+            .map(|line| line.trim().split(' ').next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_format_text_sort_confidence_is_one_global_list() {
+        let text = format_text(&sample_result(), DeadCodeSort::Confidence);
+        assert!(text.contains("Dead Symbols by Confidence"));
+        assert!(!text.contains("High Confidence Deletions"));
+        assert_eq!(listed(&text), vec!["bTop", "aHigh", "bMid", "aLow"]);
+    }
+
+    #[test]
+    fn test_format_text_sort_file_and_loc() {
+        let text = format_text(&sample_result(), DeadCodeSort::File);
+        assert!(text.contains("High Confidence Deletions"));
+        assert_eq!(listed(&text), vec!["aHigh", "bTop", "bMid", "aLow"]);
+
+        let text = format_text(&sample_result(), DeadCodeSort::Loc);
+        assert_eq!(listed(&text), vec!["bMid", "aLow", "bTop", "aHigh"]);
+    }
+
+    #[test]
+    fn test_format_text_sort_age_lists_oldest_first() {
+        let mut result = sample_result();
+        for (symbol, age) in result
+            .files
+            .iter_mut()
+            .flat_map(|file| file.dead_code.iter_mut())
+            .zip([Some(3), None, Some(400), Some(40)])
+        {
+            symbol.age_days = age;
+        }
+
+        let text = format_text(&result, DeadCodeSort::Age);
+        assert!(text.contains("Dead Symbols by Age"));
+        assert!(text.contains("Unchanged for 400 days"));
+        assert_eq!(listed(&text), vec!["bTop", "bMid", "aLow", "aHigh"]);
+    }
+
+    #[test]
+    fn test_run_writes_report_to_sink() {
+        use crate::context::CliContext;
+        use code_viz_core::context::{RealFileSystem, RealGit};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("main.ts"),
+            "function main() {}\nfunction unused() {\n    return 1;\n}\nmain();\n",
+        )
+        .unwrap();
+
+        let mut sink: Vec<u8> = Vec::new();
+        let config = DeadCodeConfig {
+            path: temp_dir.path().to_path_buf(),
+            roots: Vec::new(),
+            format: Some("json".to_string()),
+            min_confidence: Some(0),
+            min_loc: Some(1),
+            exclude: vec![],
+            max_depth: None,
+            max_files: None,
+            threshold: vec![],
+            output: None,
+            by_dir: false,
+            explain: false,
+            why: None,
+            split_output: None,
+            exit_zero: false,
+            relative_paths: true,
+            profile: false,
+            emit_symbols: false,
+            sort: DeadCodeSort::File,
+            summary_json: false,
+            exports_only: false,
+            call_graph: false,
+            report_roots: Vec::new(),
+            baseline_suppress: None,
+        };
+        let code = run(
+            config,
+            CliContext::new(false),
+            RealFileSystem::new(),
+            RealGit::new(),
+            &mut sink,
+        )
+        .unwrap();
+
+        assert_eq!(code, ExitCode::Success);
+        let report: serde_json::Value = serde_json::from_slice(&sink).unwrap();
+        assert_eq!(report["files"][0]["path"], "main.ts");
+        assert_eq!(report["files"][0]["deadCode"][0]["symbol"], "unused");
+    }
+}
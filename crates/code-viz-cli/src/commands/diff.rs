@@ -1,7 +1,10 @@
+use code_viz_core::report::load_report;
 use code_viz_core::AnalysisResult;
 use code_viz_core::traits::FileSystem;
+use code_viz_dead_code::{DeadCodeDiff, DeadCodeResult};
 use colored::Colorize;
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -12,16 +15,38 @@ pub enum DiffError {
 
     #[error("Parse error: {0}")]
     ParseError(#[from] serde_json::Error),
+
+    #[error("Report error: {0}")]
+    InvalidReport(#[from] code_viz_core::report::ReportError),
 }
 
-pub fn run(old_path: PathBuf, new_path: PathBuf, fs: impl FileSystem) -> Result<(), DiffError> {
+/// Write the changes between two reports to `out`
+pub fn run(
+    old_path: PathBuf,
+    new_path: PathBuf,
+    fs: impl FileSystem,
+    out: &mut dyn Write,
+) -> Result<(), DiffError> {
     let old_json = fs.read_to_string(&old_path)
         .map_err(|e| DiffError::IoError(std::io::Error::other(e)))?;
     let new_json = fs.read_to_string(&new_path)
         .map_err(|e| DiffError::IoError(std::io::Error::other(e)))?;
 
-    let old_result: AnalysisResult = serde_json::from_str(&old_json)?;
-    let new_result: AnalysisResult = serde_json::from_str(&new_json)?;
+    let old_result: AnalysisResult = match load_report(&old_json) {
+        Ok(result) => result,
+        // Dead code reports are compared symbol by symbol instead
+        Err(e) => match (
+            load_report::<DeadCodeResult>(&old_json),
+            load_report::<DeadCodeResult>(&new_json),
+        ) {
+            (Ok(old), Ok(new)) => {
+                print_dead_code_diff(&DeadCodeResult::diff(&old, &new), out)?;
+                return Ok(());
+            }
+            _ => return Err(e.into()),
+        },
+    };
+    let new_result: AnalysisResult = load_report(&new_json)?;
 
     let old_files: HashMap<_, _> = old_result.files.iter().map(|f| (f.path.clone(), f)).collect();
     let new_files: HashMap<_, _> = new_result.files.iter().map(|f| (f.path.clone(), f)).collect();
@@ -63,26 +88,57 @@ pub fn run(old_path: PathBuf, new_path: PathBuf, fs: impl FileSystem) -> Result<
     let delta_loc = new_loc as isize - old_loc as isize;
     let delta_sign = if delta_loc >= 0 { "+" } else { "" };
 
-    println!("{} files added", files_added.to_string().green());
-    println!("{} files deleted", files_deleted.to_string().red());
-    println!("{} files modified (LOC changed)", files_modified.to_string().yellow());
+    writeln!(out, "{} files added", files_added.to_string().green())?;
+    writeln!(out, "{} files deleted", files_deleted.to_string().red())?;
+    writeln!(out, "{} files modified (LOC changed)", files_modified.to_string().yellow())?;
     
-    print!("Total LOC: {} -> {} (", old_loc, new_loc);
+    write!(out, "Total LOC: {} -> {} (", old_loc, new_loc)?;
     if delta_loc > 0 {
-        print!("{}", format!("{}{}", delta_sign, delta_loc).green());
+        write!(out, "{}", format!("{}{}", delta_sign, delta_loc).green())?;
     } else if delta_loc < 0 {
-        print!("{}", format!("{}{}", delta_sign, delta_loc).red());
+        write!(out, "{}", format!("{}{}", delta_sign, delta_loc).red())?;
     } else {
-        print!("0");
+        write!(out, "0")?;
     }
-    println!(")");
+    writeln!(out, ")")?;
 
     if let Some(path) = largest_growth_file {
-        println!(
+        writeln!(
+            out,
             "Largest growth: {} (+{} LOC)",
             path.display().to_string().cyan(),
             largest_growth_delta
-        );
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Print the dead symbols added and removed between two dead code reports
+///
+/// Symbols that only moved are matched by their fingerprint and not listed.
+fn print_dead_code_diff(diff: &DeadCodeDiff, out: &mut dyn Write) -> std::io::Result<()> {
+    let count = |files: &[code_viz_dead_code::FileDeadCode]| -> usize {
+        files.iter().map(|f| f.dead_code.len()).sum()
+    };
+
+    writeln!(out, "{} dead symbols added", count(&diff.added).to_string().red())?;
+    writeln!(out, "{} dead symbols removed", count(&diff.removed).to_string().green())?;
+    writeln!(out, "{} dead symbols unchanged", diff.unchanged)?;
+
+    for (sign, files) in [("+", &diff.added), ("-", &diff.removed)] {
+        for file in files.iter() {
+            for symbol in &file.dead_code {
+                writeln!(
+                    out,
+                    "  {} {}:{} {}",
+                    sign,
+                    file.path.display(),
+                    symbol.line_start,
+                    symbol.symbol.cyan()
+                )?;
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,147 @@
+use code_viz_core::traits::FileSystem;
+use code_viz_dead_code::DuplicateGroup;
+use colored::Colorize;
+use std::io::Write;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DuplicatesError {
+    #[error("Duplicate analysis failed: {0}")]
+    AnalysisFailed(#[from] code_viz_dead_code::AnalysisError),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config_loader::ConfigError),
+}
+
+pub struct DuplicatesConfig {
+    pub path: PathBuf,
+    pub format: Option<String>,
+    pub exclude: Vec<String>,
+    /// Skip symbols shorter than this many lines
+    pub min_loc: usize,
+    pub output: Option<PathBuf>,
+    /// Strip the analyzed directory from every reported path
+    pub relative_paths: bool,
+}
+
+/// Write the duplicate groups to `out` unless an output file is configured
+pub fn run(
+    config: DuplicatesConfig,
+    fs: impl FileSystem,
+    out: &mut dyn Write,
+) -> Result<(), DuplicatesError> {
+    let DuplicatesConfig {
+        path,
+        format,
+        exclude,
+        min_loc,
+        output,
+        relative_paths,
+    } = config;
+
+    // Same file selection as dead-code
+    let file_config = crate::config_loader::load_config(&path)?;
+    let format = format
+        .or_else(|| file_config.output_format().map(str::to_string))
+        .unwrap_or_else(|| "text".to_string());
+    let output = output
+        .map(|template| crate::output::expand_output_path(&template, &path, &format));
+    let analysis_config = file_config.dead_code_config(&exclude);
+
+    let mut groups = code_viz_dead_code::analyze_duplicates(&path, Some(analysis_config), min_loc)?;
+    if relative_paths {
+        for group in &mut groups {
+            group.strip_root(&path);
+        }
+    }
+
+    let formatted_output = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&groups)
+            .map_err(|e| DuplicatesError::IoError(std::io::Error::other(e)))?,
+        _ => format_text(&groups), // Default to text
+    };
+
+    if let Some(output_path) = output {
+        fs.write(&output_path, &formatted_output)
+            .map_err(|e| DuplicatesError::IoError(std::io::Error::other(e)))?;
+    } else {
+        writeln!(out, "{}", formatted_output)?;
+    }
+
+    Ok(())
+}
+
+fn format_text(groups: &[DuplicateGroup]) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+
+    writeln!(&mut output, "Duplicate Symbols").unwrap();
+    writeln!(&mut output, "=================").unwrap();
+    writeln!(&mut output).unwrap();
+
+    let copies: usize = groups.iter().map(|g| g.symbols.len()).sum();
+    let duplicated_loc: usize = groups.iter().map(|g| g.loc * (g.symbols.len() - 1)).sum();
+    writeln!(
+        &mut output,
+        "Groups: {} ({} copies, {} duplicated lines)",
+        groups.len(),
+        copies,
+        duplicated_loc
+    ).unwrap();
+
+    for group in groups {
+        writeln!(&mut output).unwrap();
+        writeln!(
+            &mut output,
+            "{}",
+            format!("{} copies, {} lines each", group.symbols.len(), group.loc).bold()
+        ).unwrap();
+        for symbol in &group.symbols {
+            writeln!(
+                &mut output,
+                "  {} ({:?}) {}:{}-{}",
+                symbol.symbol,
+                symbol.kind,
+                symbol.path.display(),
+                symbol.line_start,
+                symbol.line_end
+            ).unwrap();
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_viz_dead_code::models::SymbolKind;
+    use code_viz_dead_code::DuplicateSymbol;
+
+    #[test]
+    fn test_format_text_lists_each_copy() {
+        let copy = |path: &str| DuplicateSymbol {
+            id: format!("{}:3:formatPrice", path),
+            symbol: "formatPrice".to_string(),
+            kind: SymbolKind::Function,
+            path: PathBuf::from(path),
+            line_start: 3,
+            line_end: 8,
+        };
+        let groups = vec![DuplicateGroup {
+            fingerprint: "0123456789abcdef".to_string(),
+            loc: 6,
+            symbols: vec![copy("src/cart.ts"), copy("src/invoice.ts")],
+        }];
+
+        let text = format_text(&groups);
+        assert!(text.contains("Groups: 1 (2 copies, 6 duplicated lines)"));
+        assert!(text.contains("2 copies, 6 lines each"));
+        assert!(text.contains("  formatPrice (Function) src/invoice.ts:3-8"));
+    }
+}
@@ -0,0 +1,125 @@
+use code_viz_dead_code::models::Symbol;
+use code_viz_dead_code::SymbolLiveness;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExplainError {
+    #[error("Dead code analysis failed: {0}")]
+    AnalysisFailed(#[from] code_viz_dead_code::AnalysisError),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config_loader::ConfigError),
+
+    #[error("Invalid symbol '{0}', expected <file>::<symbol>")]
+    InvalidTarget(String),
+}
+
+impl ExplainError {
+    /// Whether the error comes from invalid arguments rather than the analysis
+    pub fn is_usage_error(&self) -> bool {
+        matches!(self, ExplainError::InvalidTarget(_))
+    }
+}
+
+pub struct ExplainConfig {
+    pub path: PathBuf,
+    /// Symbol to explain, as `file::symbol`
+    pub target: String,
+    pub exclude: Vec<String>,
+}
+
+/// Write whether the target symbol is dead, with its confidence penalties,
+/// or the entry-point path keeping it alive
+pub fn run(config: ExplainConfig, out: &mut dyn Write) -> Result<(), ExplainError> {
+    let ExplainConfig {
+        path,
+        target,
+        exclude,
+    } = config;
+
+    let (file, symbol) =
+        split_target(&target).ok_or_else(|| ExplainError::InvalidTarget(target.clone()))?;
+
+    // Same settings as dead-code, so the verdict matches its report
+    let file_config = crate::config_loader::load_config(&path)?;
+    let analysis_config = file_config.dead_code_config(&exclude);
+    let liveness =
+        code_viz_dead_code::explain_symbol(&path, Some(analysis_config), Path::new(file), symbol)?;
+
+    write!(out, "{}", format_liveness(&target, &liveness))?;
+    Ok(())
+}
+
+/// Split a `file::symbol` target into its file and symbol name
+pub(crate) fn split_target(target: &str) -> Option<(&str, &str)> {
+    target
+        .rsplit_once("::")
+        .filter(|(file, symbol)| !file.is_empty() && !symbol.is_empty())
+}
+
+/// Describe the entry-point chain keeping `target` alive
+pub(crate) fn format_chain(target: &str, chain: &[Symbol]) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    writeln!(&mut output, "{} is reachable via:", target).unwrap();
+    for (i, step) in chain.iter().enumerate() {
+        let marker = if i == 0 { "(entry point)" } else { "->" };
+        writeln!(
+            &mut output,
+            "  {} {} ({}:{})",
+            marker,
+            step.name,
+            step.path.display(),
+            step.line_start
+        ).unwrap();
+    }
+    output
+}
+
+fn format_liveness(target: &str, liveness: &SymbolLiveness) -> String {
+    use std::fmt::Write;
+
+    let dead = match liveness {
+        SymbolLiveness::Reachable(chain) => return format_chain(target, chain),
+        SymbolLiveness::Unreported => {
+            return format!(
+                "{} is not reachable from any entry point, but is left out of the report \
+                 (keep pattern, minimum size or generated file)\n",
+                target
+            );
+        }
+        SymbolLiveness::Dead(dead) => dead,
+    };
+
+    let mut output = String::new();
+    writeln!(&mut output, "{} is dead: {}", target, dead.reason).unwrap();
+    writeln!(
+        &mut output,
+        "  {:?} at lines {}-{} ({} LOC)",
+        dead.kind, dead.line_start, dead.line_end, dead.loc
+    ).unwrap();
+    writeln!(&mut output, "  Confidence: {}", dead.confidence).unwrap();
+    writeln!(&mut output, "    base 100").unwrap();
+    for penalty in dead.confidence_penalties.iter().flatten() {
+        writeln!(&mut output, "    {} {:+}", penalty.name, -penalty.amount).unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_target() {
+        assert_eq!(split_target("src/utils.ts::helper"), Some(("src/utils.ts", "helper")));
+        assert_eq!(split_target("helper"), None);
+        assert_eq!(split_target("src/utils.ts::"), None);
+    }
+}
@@ -0,0 +1,49 @@
+use code_viz_core::traits::FileSystem;
+use std::io::Write;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GraphError {
+    #[error("Symbol graph construction failed: {0}")]
+    AnalysisFailed(#[from] code_viz_dead_code::AnalysisError),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config_loader::ConfigError),
+}
+
+pub struct GraphConfig {
+    pub path: PathBuf,
+    pub exclude: Vec<String>,
+    pub output: Option<PathBuf>,
+}
+
+/// Write the symbol graph as JSON to `out` unless an output file is configured
+pub fn run(config: GraphConfig, fs: impl FileSystem, out: &mut dyn Write) -> Result<(), GraphError> {
+    let GraphConfig {
+        path,
+        exclude,
+        output,
+    } = config;
+
+    // Same settings as dead-code, so the graph is the one its report is built on
+    let file_config = crate::config_loader::load_config(&path)?;
+    let output = output.map(|template| crate::output::expand_output_path(&template, &path, "json"));
+    let analysis_config = file_config.dead_code_config(&exclude);
+
+    let graph = code_viz_dead_code::export_symbol_graph(&path, Some(analysis_config))?;
+    let formatted_output = serde_json::to_string_pretty(&graph)
+        .map_err(|e| GraphError::IoError(std::io::Error::other(e)))?;
+
+    if let Some(output_path) = output {
+        fs.write(&output_path, &formatted_output)
+            .map_err(|e| GraphError::IoError(std::io::Error::other(e)))?;
+    } else {
+        writeln!(out, "{}", formatted_output)?;
+    }
+
+    Ok(())
+}
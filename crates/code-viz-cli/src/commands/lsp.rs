@@ -0,0 +1,371 @@
+//! Stdio JSON-RPC server for editor integrations.
+//!
+//! Messages use the LSP base protocol framing: a `Content-Length` header, a
+//! blank line, then a JSON-RPC 2.0 body. Supported methods:
+//!
+//! - `initialize`: returns the server name and version
+//! - `analyze` `{ "path": "...", "minConfidence": 60 }`: returns
+//!   `{ "files": [{ "uri", "diagnostics": [...] }] }` with one LSP diagnostic
+//!   per dead symbol
+//! - `shutdown`, then the `exit` notification to stop the server
+//!
+//! Each analyzed directory keeps its symbol graph in memory, so repeated
+//! `analyze` requests only rebuild it after source files change.
+
+use code_viz_dead_code::{AnalysisSession, DeadCodeResult};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LspError {
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Invalid message header: {0}")]
+    InvalidHeader(String),
+}
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+// LSP DiagnosticSeverity values
+const SEVERITY_WARNING: u8 = 2;
+const SEVERITY_INFORMATION: u8 = 3;
+const SEVERITY_HINT: u8 = 4;
+
+pub fn run() -> Result<(), LspError> {
+    // stdout carries the protocol
+    crate::output::progress::suppress();
+
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    serve(stdin.lock(), stdout.lock())
+}
+
+/// Answer requests from `reader` on `writer` until `exit` or end of input
+pub fn serve(mut reader: impl BufRead, mut writer: impl Write) -> Result<(), LspError> {
+    let mut server = Server::default();
+
+    while let Some(body) = read_message(&mut reader)? {
+        if let Some(response) = server.handle(&body) {
+            write_message(&mut writer, &response)?;
+        }
+        if server.exited {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read one framed message body, or `None` at end of input
+fn read_message(reader: &mut impl BufRead) -> Result<Option<String>, LspError> {
+    let mut content_length = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return match content_length {
+                None => Ok(None),
+                Some(_) => Err(LspError::InvalidHeader(
+                    "unexpected end of input".to_string(),
+                )),
+            };
+        }
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                let length = value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| LspError::InvalidHeader(header.to_string()))?;
+                content_length = Some(length);
+            }
+        }
+    }
+
+    let length = content_length
+        .ok_or_else(|| LspError::InvalidHeader("missing Content-Length".to_string()))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|_| LspError::InvalidHeader("body is not valid UTF-8".to_string()))
+}
+
+fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), LspError> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Server {
+    /// Analysis sessions by canonical root directory
+    sessions: HashMap<PathBuf, AnalysisSession>,
+    exited: bool,
+}
+
+impl Server {
+    /// Handle one message, returning the response for requests
+    fn handle(&mut self, body: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(body) {
+            Ok(message) => message,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(PARSE_ERROR, e.to_string()),
+                ))
+            }
+        };
+
+        let method = message["method"].as_str().unwrap_or_default();
+        let result = match method {
+            "initialize" => Ok(json!({
+                "capabilities": {},
+                "serverInfo": { "name": "code-viz", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "analyze" => self.analyze(&message["params"]),
+            "shutdown" => Ok(Value::Null),
+            "exit" => {
+                self.exited = true;
+                return None;
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            )),
+        };
+
+        // Notifications carry no id and get no response
+        let id = message.get("id")?.clone();
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    fn analyze(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let path = params["path"]
+            .as_str()
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing 'path' parameter"))?;
+        let root = Path::new(path).canonicalize().map_err(|e| {
+            RpcError::new(INVALID_PARAMS, format!("Invalid path '{}': {}", path, e))
+        })?;
+
+        let file_config = crate::config_loader::load_config(&root)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+        let min_confidence = params["minConfidence"]
+            .as_u64()
+            .map(|c| c.min(100) as u8)
+            .or(file_config.min_confidence())
+            .unwrap_or(0);
+
+        let session = self
+            .sessions
+            .entry(root.clone())
+            .or_insert_with(|| AnalysisSession::new(file_config.dead_code_config(&[])));
+        let result = session
+            .analyze(&root)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+        Ok(diagnostics(
+            &root,
+            &result.filter_by_confidence(min_confidence),
+        ))
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// LSP severity for a confidence score, matching the text report tiers
+fn severity(confidence: u8) -> u8 {
+    match confidence {
+        80.. => SEVERITY_WARNING,
+        60..=79 => SEVERITY_INFORMATION,
+        _ => SEVERITY_HINT,
+    }
+}
+
+/// Convert dead code findings under `root` into per-file LSP diagnostics
+fn diagnostics(root: &Path, result: &DeadCodeResult) -> Value {
+    let files: Vec<Value> = result
+        .files
+        .iter()
+        .map(|file| {
+            let diagnostics: Vec<Value> = file
+                .dead_code
+                .iter()
+                .map(|symbol| {
+                    // LSP lines are 0-based; the range covers whole lines
+                    json!({
+                        "range": {
+                            "start": { "line": symbol.line_start.saturating_sub(1), "character": 0 },
+                            "end": { "line": symbol.line_end, "character": 0 },
+                        },
+                        "severity": severity(symbol.confidence),
+                        "source": "code-viz",
+                        "code": "dead-code",
+                        "message": format!(
+                            "'{}' is unused: {} ({}% confidence)",
+                            symbol.symbol, symbol.reason, symbol.confidence
+                        ),
+                    })
+                })
+                .collect();
+
+            // `root` is canonical, so the joined path is absolute
+            let uri = url::Url::from_file_path(root.join(&file.path))
+                .map(String::from)
+                .unwrap_or_default();
+            json!({
+                "uri": uri,
+                "diagnostics": diagnostics,
+            })
+        })
+        .collect();
+
+    json!({ "files": files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn frame(message: Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    #[test]
+    fn test_analyze_request_returns_diagnostics() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path().join("my project");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(
+            project.join("main.ts"),
+            "function main() {\n    console.log('hi');\n}\n\nfunction unusedHelper() {\n    return 1;\n}\n\nmain();\n",
+        )
+        .unwrap();
+
+        let analyze = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "analyze",
+            "params": { "path": project, "minConfidence": 0 },
+        });
+        let input = [
+            frame(analyze),
+            frame(json!({ "jsonrpc": "2.0", "id": 2, "method": "bogus" })),
+            frame(json!({ "jsonrpc": "2.0", "method": "exit" })),
+        ]
+        .concat();
+
+        let mut output = Vec::new();
+        serve(Cursor::new(input), &mut output).unwrap();
+
+        let mut reader = Cursor::new(output);
+        let first: Value =
+            serde_json::from_str(&read_message(&mut reader).unwrap().unwrap()).unwrap();
+        assert_eq!(first["id"], 1);
+        let files = first["result"]["files"].as_array().unwrap();
+        assert_eq!(files.len(), 1);
+        let main = project.canonicalize().unwrap().join("main.ts");
+        assert_eq!(
+            files[0]["uri"],
+            url::Url::from_file_path(&main).unwrap().as_str()
+        );
+        assert!(files[0]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("/my%20project/main.ts"));
+
+        let diagnostic = &files[0]["diagnostics"][0];
+        assert!(diagnostic["message"]
+            .as_str()
+            .unwrap()
+            .contains("unusedHelper"));
+        assert_eq!(diagnostic["range"]["start"]["line"], 4);
+        assert!(diagnostic["severity"].as_u64().is_some());
+
+        let unknown: Value =
+            serde_json::from_str(&read_message(&mut reader).unwrap().unwrap()).unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        // `exit` is a notification, so nothing else is written
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_repeated_analyze_reuses_symbol_graph() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let main = temp_dir.path().join("main.ts");
+        std::fs::write(
+            &main,
+            "function main() {}\n\nfunction unused() {}\n\nmain();\n",
+        )
+        .unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+
+        let analyze = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "analyze",
+            "params": { "path": temp_dir.path(), "minConfidence": 0 },
+        })
+        .to_string();
+        let mut server = Server::default();
+
+        let first = server.handle(&analyze).unwrap();
+        let second = server.handle(&analyze).unwrap();
+        assert_eq!(second["result"], first["result"]);
+        assert_eq!(server.sessions[&root].rebuilds(), 1);
+
+        // Editing a file invalidates the cached graph
+        std::fs::write(&main, "function main() {}\n\nmain();\n").unwrap();
+        let third = server.handle(&analyze).unwrap();
+        assert_eq!(server.sessions[&root].rebuilds(), 2);
+        assert_eq!(third["result"]["files"], json!([]));
+    }
+
+    #[test]
+    fn test_severity_follows_confidence_tiers() {
+        assert_eq!(severity(95), SEVERITY_WARNING);
+        assert_eq!(severity(70), SEVERITY_INFORMATION);
+        assert_eq!(severity(30), SEVERITY_HINT);
+    }
+}
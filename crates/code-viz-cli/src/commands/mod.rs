@@ -1,5 +1,10 @@
 pub mod analyze;
+pub mod api_surface;
 pub mod watch;
 pub mod diff;
 pub mod config;
 pub mod dead_code;
+pub mod duplicates;
+pub mod explain;
+pub mod graph;
+pub mod lsp;
@@ -30,20 +30,16 @@ pub enum WatchError {
     FormattingFailed(#[from] crate::output::FormatterError),
 }
 
-pub async fn run(path: PathBuf, format: String, verbose: bool, ctx: impl AppContext + Clone, fs: impl FileSystem + Clone) -> Result<(), WatchError> {
-    // Setup logging
-    let mut builder = env_logger::Builder::from_default_env();
-    if verbose {
-        builder.filter_level(log::LevelFilter::Debug);
-    } else {
-        builder.filter_level(log::LevelFilter::Info);
-    }
-    let _ = builder.try_init();
-
-    // Load config
+pub async fn run(path: PathBuf, format: Option<String>, ctx: impl AppContext + Clone, fs: impl FileSystem + Clone) -> Result<(), WatchError> {
+    // Load config from the watched directory; CLI flags take precedence
     let mut config = AnalysisConfig::default();
-    let current_dir = std::env::current_dir()?;
-    let file_config = config_loader::load_config(&current_dir)?;
+    let file_config = config_loader::load_config(&path)?;
+    let format = format
+        .or_else(|| file_config.output_format().map(str::to_string))
+        .unwrap_or_else(|| "text".to_string());
+    if format == "json" {
+        output::progress::suppress();
+    }
     if let Some(analysis) = file_config.analysis {
         if let Some(file_excludes) = analysis.exclude {
             config.exclude_patterns = file_excludes;
@@ -133,7 +129,7 @@ fn add_paths_from_event(paths: &mut HashSet<PathBuf>, event: notify::Event) {
         // Filter by extension
         if let Some(ext) = path.extension() {
             match ext.to_string_lossy().as_ref() {
-                "ts" | "tsx" | "js" | "jsx" | "rs" | "py" => {
+                "ts" | "tsx" | "js" | "jsx" | "vue" | "svelte" | "rs" | "py" => {
                     paths.insert(path);
                 }
                 _ => {}
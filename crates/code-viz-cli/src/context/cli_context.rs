@@ -31,8 +31,8 @@ impl AppContext for CliContext {
     }
 
     async fn report_progress(&self, percentage: f32, message: &str) -> Result<()> {
-        // Report progress to stderr to keep stdout clean for JSON output or other data.
-        eprintln!("{:.0}% - {}", percentage * 100.0, message);
+        // Drawn on stderr to keep stdout clean for JSON output or other data.
+        crate::output::progress::report(percentage, message);
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,78 @@
+use crate::commands::analyze::AnalyzeError;
+use crate::commands::dead_code::DeadCodeError;
+use crate::commands::explain::ExplainError;
+
+/// Process exit codes shared by every subcommand
+///
+/// - `0`: success
+/// - `1`: the analysis failed (unreadable path, parse or I/O error, ...)
+/// - `2`: invalid arguments, such as a malformed `--threshold`
+/// - `3`: a `--threshold` or `--baseline` check failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    AnalysisError = 1,
+    UsageError = 2,
+    ThresholdViolation = 3,
+}
+
+impl ExitCode {
+    /// Exit code after the gating checks ran; `--exit-zero` reports failures but exits 0
+    pub fn for_checks(passed: bool, exit_zero: bool) -> Self {
+        if passed || exit_zero {
+            ExitCode::Success
+        } else {
+            ExitCode::ThresholdViolation
+        }
+    }
+
+    /// Exit code for a command that returned an error
+    pub fn for_error(error: &anyhow::Error) -> Self {
+        let is_usage_error = error
+            .downcast_ref::<AnalyzeError>()
+            .is_some_and(AnalyzeError::is_usage_error)
+            || error
+                .downcast_ref::<DeadCodeError>()
+                .is_some_and(DeadCodeError::is_usage_error)
+            || error
+                .downcast_ref::<ExplainError>()
+                .is_some_and(ExplainError::is_usage_error);
+
+        if is_usage_error {
+            ExitCode::UsageError
+        } else {
+            ExitCode::AnalysisError
+        }
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        std::process::ExitCode::from(code as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_checks() {
+        assert_eq!(ExitCode::for_checks(true, false), ExitCode::Success);
+        assert_eq!(ExitCode::for_checks(false, false), ExitCode::ThresholdViolation);
+        assert_eq!(ExitCode::for_checks(false, true), ExitCode::Success);
+    }
+
+    #[test]
+    fn test_for_error() {
+        let invalid_threshold = crate::threshold::ThresholdError::InvalidFormat("loc".to_string());
+        let usage = anyhow::Error::from(AnalyzeError::from(invalid_threshold));
+        assert_eq!(ExitCode::for_error(&usage), ExitCode::UsageError);
+
+        let why = anyhow::Error::from(DeadCodeError::InvalidWhyTarget("helper".to_string()));
+        assert_eq!(ExitCode::for_error(&why), ExitCode::UsageError);
+
+        let io = anyhow::Error::from(AnalyzeError::from(std::io::Error::other("disk")));
+        assert_eq!(ExitCode::for_error(&io), ExitCode::AnalysisError);
+    }
+}
@@ -0,0 +1,63 @@
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+/// Log output of `--log-format`, independent of the report `--format`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log pipelines
+    Json,
+}
+
+/// Without flags or `RUST_LOG`, only the CLI's own info messages and
+/// library warnings are shown, keeping stderr quiet around reports
+const DEFAULT_FILTER: &str = "warn,code_viz_cli=info";
+
+/// Install the global tracing subscriber writing to stderr
+///
+/// The level comes from `--log-level`, then `--verbose` (debug), then
+/// `RUST_LOG`, then [`DEFAULT_FILTER`]. Stdout stays reserved for reports.
+pub fn init(format: LogFormat, level: Option<LevelFilter>, verbose: bool, no_color: bool) {
+    let filter = filter(level, verbose, std::env::var("RUST_LOG").ok());
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+
+    // Another subscriber may already be installed (e.g. in tests)
+    let _ = match format {
+        LogFormat::Text => builder
+            .with_ansi(!no_color && std::io::stderr().is_terminal())
+            .try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    };
+}
+
+fn filter(level: Option<LevelFilter>, verbose: bool, rust_log: Option<String>) -> EnvFilter {
+    match (level, verbose, rust_log) {
+        (Some(level), _, _) => EnvFilter::default().add_directive(level.into()),
+        (None, true, _) => EnvFilter::default().add_directive(LevelFilter::DEBUG.into()),
+        (None, false, Some(directives)) => {
+            EnvFilter::try_new(directives).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+        }
+        (None, false, None) => EnvFilter::new(DEFAULT_FILTER),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_precedence() {
+        let rust_log = || Some("code_viz_core=trace".to_string());
+
+        assert_eq!(filter(Some(LevelFilter::ERROR), true, rust_log()).to_string(), "error");
+        assert_eq!(filter(None, true, rust_log()).to_string(), "debug");
+        assert_eq!(filter(None, false, rust_log()).to_string(), "code_viz_core=trace");
+        assert_eq!(filter(None, false, None).to_string(), "code_viz_cli=info,warn");
+    }
+}
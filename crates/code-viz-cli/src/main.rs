@@ -1,45 +1,76 @@
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use crate::context::CliContext;
+use crate::exit_code::ExitCode;
 use code_viz_core::context::{RealFileSystem, RealGit};
 
 mod commands;
 mod config_loader;
 mod context;
+mod exit_code;
+mod logging;
 mod output;
+mod remote;
+mod roots;
+mod threshold;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Disable colored output (also respects NO_COLOR)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Hide the progress bar shown on a terminal
+    #[arg(long, short, global = true)]
+    quiet: bool,
+
+    /// Log format on stderr, separate from the report --format
+    #[arg(long, value_enum, default_value_t, global = true)]
+    log_format: logging::LogFormat,
+
+    /// Log level (trace, debug, info, warn, error, off); overrides --verbose and RUST_LOG
+    #[arg(long, global = true)]
+    log_level: Option<tracing_subscriber::filter::LevelFilter>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Analyze a directory and generate metrics
     Analyze {
-        /// Path to the directory to analyze
+        /// Path to the directory to analyze, or a git URL to clone (requires the
+        /// git-integration feature). Several directories are analyzed together,
+        /// with paths relative to their common ancestor
         #[arg(default_value = ".")]
-        path: PathBuf,
+        paths: Vec<PathBuf>,
 
-        /// Output format (json, csv, text)
-        #[arg(long, short, default_value = "text")]
-        format: String,
+        /// Output format (json, csv, csv-excel, text) [default: text, or output.format in .code-viz.toml]
+        #[arg(long, short)]
+        format: Option<String>,
 
         /// Glob patterns to exclude
         #[arg(long, short)]
         exclude: Vec<String>,
 
+        /// Only scan this many directory levels deep; files directly in the
+        /// analyzed directory are at depth 1 [default: unlimited]
+        #[arg(long)]
+        max_depth: Option<usize>,
+
         /// Enable verbose logging
         #[arg(long, short)]
         verbose: bool,
 
-        /// Fail if metrics exceed threshold (e.g., "loc=500")
+        /// Fail if a metric condition holds (e.g., "loc>500"); repeatable.
+        /// Supports >, >=, < and <=; "loc=500" fails above 500
         #[arg(long)]
-        threshold: Option<String>,
+        threshold: Vec<String>,
 
-        /// Write output to file instead of stdout
+        /// Write output to file instead of stdout; {date}, {commit} and {format}
+        /// in the path are expanded (e.g., "reports/{date}-{commit}.{format}")
         #[arg(long, short)]
         output: Option<PathBuf>,
 
@@ -50,6 +81,36 @@ enum Commands {
         /// Enable dead code analysis
         #[arg(long)]
         dead_code: bool,
+
+        /// Embed the hierarchical directory tree in JSON output
+        #[arg(long)]
+        tree: bool,
+
+        /// Report threshold and baseline failures but exit 0
+        #[arg(long)]
+        exit_zero: bool,
+
+        /// Analyze a single file's source read from stdin, reported as "<stdin>"
+        #[arg(long, requires = "language")]
+        stdin: bool,
+
+        /// Language of the source read with --stdin (e.g. ts, tsx, js, rust, python, go, cpp)
+        #[arg(long, requires = "stdin")]
+        language: Option<String>,
+
+        /// Report absolute paths instead of paths relative to the analyzed directory
+        #[arg(long)]
+        absolute_paths: bool,
+
+        /// Print only {dead_functions, dead_classes, total_dead_loc, dead_code_ratio}
+        /// as one-line JSON (implies --dead-code)
+        #[arg(long, conflicts_with_all = ["format", "tree"])]
+        summary_json: bool,
+
+        /// Extension of files counted as plain text, contributing LOC but no
+        /// functions; repeatable, replaces the defaults [default: sql, yaml, yml]
+        #[arg(long, value_name = "EXT")]
+        plain_text: Vec<String>,
     },
     /// Watch a directory for changes and re-analyze
     Watch {
@@ -57,9 +118,9 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
-        /// Output format (json, text)
-        #[arg(long, short, default_value = "text")]
-        format: String,
+        /// Output format (json, text) [default: text, or output.format in .code-viz.toml]
+        #[arg(long, short)]
+        format: Option<String>,
 
         /// Enable verbose logging
         #[arg(long, short)]
@@ -78,19 +139,153 @@ enum Commands {
         #[command(subcommand)]
         subcommand: ConfigSubcommand,
     },
+    /// Serve dead code diagnostics to editors over stdio (JSON-RPC)
+    Lsp,
     /// Analyze dead code in a directory
     DeadCode {
+        /// Path to the directory to analyze. Several directories are analyzed
+        /// as one graph, so imports between them keep symbols alive, with paths
+        /// relative to their common ancestor
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// Output format (json, junit, text) [default: text, or output.format in .code-viz.toml]
+        #[arg(long, short)]
+        format: Option<String>,
+
+        /// Minimum confidence score (0-100) [default: 80, or dead_code.min_confidence in .code-viz.toml]
+        #[arg(long)]
+        min_confidence: Option<u8>,
+
+        /// Skip dead symbols shorter than this many lines [default: 0, or dead_code.min_loc in .code-viz.toml]
+        #[arg(long)]
+        min_loc: Option<usize>,
+
+        /// Glob patterns to exclude
+        #[arg(long, short)]
+        exclude: Vec<String>,
+
+        /// Only scan this many directory levels deep; files directly in the
+        /// analyzed directory are at depth 1 [default: unlimited]
+        #[arg(long)]
+        max_depth: Option<usize>,
+
+        /// Fail instead of analyzing more than this many source files, e.g. when
+        /// pointed at a home directory by mistake [default: unlimited]
+        #[arg(long)]
+        max_files: Option<usize>,
+
+        /// Enable verbose logging
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Fail if a metric condition holds (e.g., "dead_code_ratio>0.05"); repeatable.
+        /// Supports >, >=, < and <=; "dead_code_ratio=0.05" fails above 0.05
+        #[arg(long)]
+        threshold: Vec<String>,
+
+        /// Write output to file instead of stdout; {date}, {commit} and {format}
+        /// in the path are expanded (e.g., "reports/{date}-{commit}.{format}")
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Show dead code totals per directory, sorted by dead LOC
+        #[arg(long)]
+        by_dir: bool,
+
+        /// Explain each confidence score with the penalties applied
+        #[arg(long)]
+        explain: bool,
+
+        /// Show the entry-point path keeping a symbol alive (e.g. "src/utils.ts::helper")
+        #[arg(long)]
+        why: Option<String>,
+
+        /// Write high.json, medium.json and low.json (one per confidence tier) into a directory
+        #[arg(long, value_name = "DIR")]
+        split_output: Option<PathBuf>,
+
+        /// Report threshold failures but exit 0
+        #[arg(long)]
+        exit_zero: bool,
+
+        /// Report absolute paths instead of paths relative to the analyzed directory
+        #[arg(long)]
+        absolute_paths: bool,
+
+        /// Time parsing per file and print the slowest files (bypasses the cache)
+        #[arg(long)]
+        profile: bool,
+
+        /// Output the raw reachable, dead and entry point symbol IDs as JSON
+        #[arg(long)]
+        emit_symbols: bool,
+
+        /// Order of the text listing: confidence tiers in file order, or one
+        /// list by confidence, by size or by age (oldest first)
+        #[arg(long, value_enum, default_value_t)]
+        sort: commands::dead_code::DeadCodeSort,
+
+        /// Print only {dead_functions, dead_classes, total_dead_loc, dead_code_ratio}
+        /// as one-line JSON
+        #[arg(long, conflicts_with_all = ["format", "by_dir", "emit_symbols", "why"])]
+        summary_json: bool,
+
+        /// Only report dead exported symbols (unused public API); totals cover them alone
+        #[arg(long)]
+        exports_only: bool,
+
+        /// Keep same-file functions alive when live code calls them, including
+        /// through optional chaining like `maybeFn?.()`
+        #[arg(long)]
+        call_graph: bool,
+
+        /// Report each package separately, with paths relative to it; files
+        /// outside every root get a report of their own (repeatable)
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["by_dir", "summary_json", "emit_symbols", "why"])]
+        report_root: Vec<PathBuf>,
+
+        /// Hide dead symbols already listed in this earlier JSON report
+        /// (matched by file and fingerprint), so only new dead code is
+        /// reported and checked against thresholds
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["emit_symbols", "why"])]
+        baseline_suppress: Option<PathBuf>,
+    },
+    /// List exported symbols and whether the analyzed code uses each one
+    ApiSurface {
         /// Path to the directory to analyze
         #[arg(default_value = ".")]
         path: PathBuf,
 
-        /// Output format (json, text)
-        #[arg(long, short, default_value = "text")]
-        format: String,
+        /// Output format (json, text) [default: text, or output.format in .code-viz.toml]
+        #[arg(long, short)]
+        format: Option<String>,
+
+        /// Glob patterns to exclude
+        #[arg(long, short)]
+        exclude: Vec<String>,
+
+        /// Enable verbose logging
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Write output to file instead of stdout; {date}, {commit} and {format}
+        /// in the path are expanded (e.g., "reports/{date}-{commit}.{format}")
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Report absolute paths instead of paths relative to the analyzed directory
+        #[arg(long)]
+        absolute_paths: bool,
+    },
+    /// Explain whether one symbol is dead: its confidence penalties if so,
+    /// otherwise the entry-point path keeping it alive
+    Explain {
+        /// Path to the directory to analyze
+        path: PathBuf,
 
-        /// Minimum confidence score (0-100)
-        #[arg(long, default_value = "80")]
-        min_confidence: u8,
+        /// Symbol to explain (e.g. "src/utils.ts::helper")
+        target: String,
 
         /// Glob patterns to exclude
         #[arg(long, short)]
@@ -99,12 +294,59 @@ enum Commands {
         /// Enable verbose logging
         #[arg(long, short)]
         verbose: bool,
+    },
+    /// Find functions, methods and classes copied across the codebase
+    Duplicates {
+        /// Path to the directory to analyze
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output format (json, text) [default: text, or output.format in .code-viz.toml]
+        #[arg(long, short)]
+        format: Option<String>,
+
+        /// Glob patterns to exclude
+        #[arg(long, short)]
+        exclude: Vec<String>,
+
+        /// Skip symbols shorter than this many lines, such as one-line getters
+        #[arg(long, default_value_t = 5)]
+        min_loc: usize,
 
-        /// Fail if metrics exceed threshold (e.g., "dead_code_ratio=0.05")
+        /// Enable verbose logging
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Write output to file instead of stdout; {date}, {commit} and {format}
+        /// in the path are expanded (e.g., "reports/{date}-{commit}.{format}")
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Report absolute paths instead of paths relative to the analyzed directory
         #[arg(long)]
-        threshold: Option<String>,
+        absolute_paths: bool,
+    },
+    /// Output the symbol graph (symbols, imports and exports) for external
+    /// tools, with symbol IDs relative to the analyzed directory
+    Graph {
+        /// Path to the directory to analyze
+        #[arg(default_value = ".")]
+        path: PathBuf,
 
-        /// Write output to file instead of stdout
+        /// Output format
+        #[arg(long, short, default_value = "json", value_parser = ["json"])]
+        format: String,
+
+        /// Glob patterns to exclude
+        #[arg(long, short)]
+        exclude: Vec<String>,
+
+        /// Enable verbose logging
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Write output to file instead of stdout; {date}, {commit} and {format}
+        /// in the path are expanded (e.g., "reports/{date}-{commit}.{format}")
         #[arg(long, short)]
         output: Option<PathBuf>,
     },
@@ -116,34 +358,84 @@ enum ConfigSubcommand {
     Init,
 }
 
-fn main() -> anyhow::Result<()> {
+impl Commands {
+    /// `--verbose` of the subcommands that have it
+    fn verbose(&self) -> bool {
+        match self {
+            Commands::Analyze { verbose, .. }
+            | Commands::Watch { verbose, .. }
+            | Commands::DeadCode { verbose, .. }
+            | Commands::ApiSurface { verbose, .. }
+            | Commands::Explain { verbose, .. }
+            | Commands::Duplicates { verbose, .. }
+            | Commands::Graph { verbose, .. } => *verbose,
+            _ => false,
+        }
+    }
+}
+
+fn main() -> std::process::ExitCode {
+    // Invalid arguments exit with ExitCode::UsageError (2) from clap
     let cli = Cli::parse();
+    output::configure_color(cli.no_color);
+    output::progress::configure(cli.quiet);
+    logging::init(cli.log_format, cli.log_level, cli.command.verbose(), cli.no_color);
 
-    match cli.command {
+    match run(cli) {
+        Ok(code) => code.into(),
+        Err(e) => {
+            output::progress::finish();
+            eprintln!("Error: {:?}", e);
+            ExitCode::for_error(&e).into()
+        }
+    }
+}
+
+fn run(cli: Cli) -> anyhow::Result<ExitCode> {
+    let code = match cli.command {
         Commands::Analyze {
-            path,
+            paths,
             format,
             exclude,
+            max_depth,
             verbose,
             threshold,
             output,
             baseline,
             dead_code,
+            tree,
+            exit_zero,
+            stdin,
+            language,
+            absolute_paths,
+            summary_json,
+            plain_text,
         } => {
+            let (path, roots) = roots::resolve(paths)?;
             let ctx = CliContext::new(verbose);
-            let fs = RealFileSystem::new();
+            let mut fs = RealFileSystem::new().with_max_depth(max_depth);
+            if !plain_text.is_empty() {
+                fs = fs.with_plain_text_extensions(plain_text);
+            }
             let git = RealGit::new();
             
             commands::analyze::run(commands::analyze::AnalyzeConfig {
                 path,
+                roots,
                 format,
                 exclude,
-                verbose,
+                max_depth,
                 threshold,
                 output,
                 baseline,
                 dead_code,
-            }, ctx, fs, git)?;
+                tree,
+                exit_zero,
+                stdin,
+                language,
+                relative_paths: !absolute_paths,
+                summary_json,
+            }, ctx, fs, git, &mut std::io::stdout())?
         }
         Commands::Watch {
             path,
@@ -153,34 +445,150 @@ fn main() -> anyhow::Result<()> {
             let ctx = CliContext::new(verbose);
             let fs = RealFileSystem::new();
             let rt = tokio::runtime::Runtime::new()?;
-            rt.block_on(commands::watch::run(path, format, verbose, ctx, fs))?;
+            rt.block_on(commands::watch::run(path, format, ctx, fs))?;
+            ExitCode::Success
         }
         Commands::Diff { old, new } => {
             let fs = RealFileSystem::new();
-            commands::diff::run(old, new, fs)?;
+            commands::diff::run(old, new, fs, &mut std::io::stdout())?;
+            ExitCode::Success
         }
         Commands::Config { subcommand } => match subcommand {
             ConfigSubcommand::Init => {
                 let fs = RealFileSystem::new();
                 commands::config::run_init(fs)?;
+                ExitCode::Success
             }
         },
+        Commands::Lsp => {
+            commands::lsp::run()?;
+            ExitCode::Success
+        }
         Commands::DeadCode {
-            path,
+            paths,
             format,
             min_confidence,
+            min_loc,
             exclude,
+            max_depth,
+            max_files,
             verbose,
             threshold,
             output,
+            by_dir,
+            explain,
+            why,
+            split_output,
+            exit_zero,
+            absolute_paths,
+            profile,
+            emit_symbols,
+            sort,
+            summary_json,
+            exports_only,
+            call_graph,
+            report_root,
+            baseline_suppress,
         } => {
+            let (path, roots) = roots::resolve(paths)?;
             let ctx = CliContext::new(verbose);
             let fs = RealFileSystem::new();
             let git = RealGit::new();
 
-            commands::dead_code::run(path, format, min_confidence, exclude, verbose, threshold, output, ctx, fs, git)?;
+            commands::dead_code::run(commands::dead_code::DeadCodeConfig {
+                path,
+                roots,
+                format,
+                min_confidence,
+                min_loc,
+                exclude,
+                max_depth,
+                max_files,
+                threshold,
+                output,
+                by_dir,
+                explain,
+                why,
+                split_output,
+                exit_zero,
+                relative_paths: !absolute_paths,
+                profile,
+                emit_symbols,
+                sort,
+                summary_json,
+                exports_only,
+                call_graph,
+                report_roots: report_root,
+                baseline_suppress,
+            }, ctx, fs, git, &mut std::io::stdout())?
         }
-    }
+        Commands::ApiSurface {
+            path,
+            format,
+            exclude,
+            verbose: _,
+            output,
+            absolute_paths,
+        } => {
+            let fs = RealFileSystem::new();
+            commands::api_surface::run(commands::api_surface::ApiSurfaceConfig {
+                path,
+                format,
+                exclude,
+                output,
+                relative_paths: !absolute_paths,
+            }, fs, &mut std::io::stdout())?;
+            ExitCode::Success
+        }
+        Commands::Explain {
+            path,
+            target,
+            exclude,
+            verbose: _,
+        } => {
+            commands::explain::run(commands::explain::ExplainConfig {
+                path,
+                target,
+                exclude,
+            }, &mut std::io::stdout())?;
+            ExitCode::Success
+        }
+        Commands::Duplicates {
+            path,
+            format,
+            exclude,
+            min_loc,
+            verbose: _,
+            output,
+            absolute_paths,
+        } => {
+            let fs = RealFileSystem::new();
+            commands::duplicates::run(commands::duplicates::DuplicatesConfig {
+                path,
+                format,
+                exclude,
+                min_loc,
+                output,
+                relative_paths: !absolute_paths,
+            }, fs, &mut std::io::stdout())?;
+            ExitCode::Success
+        }
+        Commands::Graph {
+            path,
+            format: _,
+            exclude,
+            verbose: _,
+            output,
+        } => {
+            let fs = RealFileSystem::new();
+            commands::graph::run(commands::graph::GraphConfig {
+                path,
+                exclude,
+                output,
+            }, fs, &mut std::io::stdout())?;
+            ExitCode::Success
+        }
+    };
 
-    Ok(())
+    Ok(code)
 }
\ No newline at end of file
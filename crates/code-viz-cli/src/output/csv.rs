@@ -1,12 +1,29 @@
 use super::{FormatterError, MetricsFormatter};
 use code_viz_core::AnalysisResult;
-use csv::WriterBuilder;
+use csv::{Terminator, WriterBuilder};
 
-pub struct CsvFormatter;
+/// UTF-8 byte order mark, which Excel needs to detect the encoding
+const UTF8_BOM: &str = "\u{feff}";
+
+/// CSV output; `excel` prepends a UTF-8 BOM and uses CRLF line endings
+#[derive(Default)]
+pub struct CsvFormatter {
+    pub excel: bool,
+}
+
+impl CsvFormatter {
+    pub fn excel() -> Self {
+        Self { excel: true }
+    }
+}
 
 impl MetricsFormatter for CsvFormatter {
     fn format(&self, result: &AnalysisResult) -> Result<String, FormatterError> {
-        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+        let mut builder = WriterBuilder::new();
+        if self.excel {
+            builder.terminator(Terminator::CRLF);
+        }
+        let mut wtr = builder.from_writer(vec![]);
 
         // Check if any file has dead code metrics
         let has_dead_code = result.files.iter().any(|f| f.dead_code_ratio.is_some());
@@ -40,6 +57,12 @@ impl MetricsFormatter for CsvFormatter {
         }
 
         let data = wtr.into_inner().map_err(|_| FormatterError::FormattingFailed)?;
-        String::from_utf8(data).map_err(|_| FormatterError::FormattingFailed)
+        let csv = String::from_utf8(data).map_err(|_| FormatterError::FormattingFailed)?;
+
+        if self.excel {
+            Ok(format!("{}{}", UTF8_BOM, csv))
+        } else {
+            Ok(csv)
+        }
     }
 }
@@ -41,6 +41,12 @@ pub fn format_text(result: &DeadCodeResult) -> Result<String, DeadCodeFormatterE
     writeln!(output, "{}", "=".repeat(50))
         .map_err(|_| DeadCodeFormatterError::TextFormattingFailed)?;
 
+    // Warnings that the whole result may be wrong
+    for warning in &result.warnings {
+        writeln!(output, "{}", format!("Warning: {}", warning.message).yellow().bold())
+            .map_err(|_| DeadCodeFormatterError::TextFormattingFailed)?;
+    }
+
     // Summary statistics
     writeln!(
         output,
@@ -69,6 +75,13 @@ pub fn format_text(result: &DeadCodeResult) -> Result<String, DeadCodeFormatterE
     )
     .map_err(|_| DeadCodeFormatterError::TextFormattingFailed)?;
 
+    writeln!(
+        output,
+        "Dead complexity:          {:.1}%",
+        summary.dead_complexity_ratio * 100.0
+    )
+    .map_err(|_| DeadCodeFormatterError::TextFormattingFailed)?;
+
     writeln!(
         output,
         "Dead functions:           {}",
@@ -83,6 +96,13 @@ pub fn format_text(result: &DeadCodeResult) -> Result<String, DeadCodeFormatterE
     )
     .map_err(|_| DeadCodeFormatterError::TextFormattingFailed)?;
 
+    writeln!(
+        output,
+        "Other dead symbols:       {}",
+        summary.dead_other
+    )
+    .map_err(|_| DeadCodeFormatterError::TextFormattingFailed)?;
+
     // High-confidence deletions
     let high_confidence_count = result
         .files
@@ -105,14 +125,10 @@ pub fn format_text(result: &DeadCodeResult) -> Result<String, DeadCodeFormatterE
 
         // Sort files by total dead LOC
         let mut files_sorted: Vec<_> = result.files.iter().collect();
-        files_sorted.sort_by(|a, b| {
-            let a_loc: usize = a.dead_code.iter().map(|s| s.loc).sum();
-            let b_loc: usize = b.dead_code.iter().map(|s| s.loc).sum();
-            b_loc.cmp(&a_loc)
-        });
+        files_sorted.sort_by_key(|file| std::cmp::Reverse(file.dead_loc()));
 
         for (i, file) in files_sorted.iter().take(10).enumerate() {
-            let total_dead_loc: usize = file.dead_code.iter().map(|s| s.loc).sum();
+            let total_dead_loc = file.dead_loc();
             let symbol_count = file.dead_code.len();
             writeln!(
                 output,
@@ -230,91 +246,41 @@ fn format_symbol_kind(kind: SymbolKind) -> &'static str {
         SymbolKind::Class => "class",
         SymbolKind::Method => "method",
         SymbolKind::Variable => "var",
+        SymbolKind::ReExport => "re-export",
+        SymbolKind::Member => "member",
+        SymbolKind::Namespace => "namespace",
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use code_viz_dead_code::{DeadCodeSummary, DeadSymbol, FileDeadCode};
-    use std::path::PathBuf;
-
-    fn create_sample_result() -> DeadCodeResult {
-        DeadCodeResult {
-            summary: DeadCodeSummary {
-                total_files: 10,
-                files_with_dead_code: 2,
-                dead_functions: 5,
-                dead_classes: 1,
-                total_dead_loc: 150,
-                dead_code_ratio: 0.15,
-            },
-            files: vec![
-                FileDeadCode {
-                    path: PathBuf::from("src/utils.ts"),
-                    dead_code: vec![
-                        DeadSymbol {
-                            symbol: "unusedFunction".to_string(),
-                            kind: SymbolKind::Function,
-                            line_start: 10,
-                            line_end: 20,
-                            loc: 10,
-                            confidence: 95,
-                            reason: "Not imported or called anywhere".to_string(),
-                            last_modified: None,
-                        },
-                        DeadSymbol {
-                            symbol: "oldHelper".to_string(),
-                            kind: SymbolKind::ArrowFunction,
-                            line_start: 25,
-                            line_end: 30,
-                            loc: 5,
-                            confidence: 85,
-                            reason: "Exported but never used".to_string(),
-                            last_modified: None,
-                        },
-                    ],
-                },
-                FileDeadCode {
-                    path: PathBuf::from("src/legacy.ts"),
-                    dead_code: vec![DeadSymbol {
-                        symbol: "LegacyClass".to_string(),
-                        kind: SymbolKind::Class,
-                        line_start: 1,
-                        line_end: 100,
-                        loc: 100,
-                        confidence: 65,
-                        reason: "Exported and recently modified".to_string(),
-                        last_modified: None,
-                    }],
-                },
-            ],
-        }
-    }
+    use code_viz_dead_code::fixtures::sample_result;
+    use code_viz_dead_code::DeadCodeSummary;
 
     #[test]
     fn test_format_json() {
-        let result = create_sample_result();
+        let result = sample_result();
         let json = format_json(&result).unwrap();
 
         // Verify it's valid JSON
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["summary"]["totalFiles"], 10);
-        assert_eq!(parsed["summary"]["deadFunctions"], 5);
+        assert_eq!(parsed["summary"]["deadFunctions"], 2);
         assert_eq!(parsed["files"].as_array().unwrap().len(), 2);
     }
 
     #[test]
     fn test_format_text() {
-        let result = create_sample_result();
+        let result = sample_result();
         let text = format_text(&result).unwrap();
 
         // Verify key elements are present
         assert!(text.contains("Dead Code Analysis Summary"));
         assert!(text.contains("Total files analyzed:     10"));
         assert!(text.contains("Files with dead code:     2"));
-        assert!(text.contains("Total dead code:          150 LOC"));
-        assert!(text.contains("Dead functions:           5"));
+        assert!(text.contains("Total dead code:          115 LOC"));
+        assert!(text.contains("Dead functions:           2"));
         assert!(text.contains("Dead classes:             1"));
         assert!(text.contains("unusedFunction"));
         assert!(text.contains("LegacyClass"));
@@ -327,13 +293,9 @@ mod tests {
         let result = DeadCodeResult {
             summary: DeadCodeSummary {
                 total_files: 5,
-                files_with_dead_code: 0,
-                dead_functions: 0,
-                dead_classes: 0,
-                total_dead_loc: 0,
-                dead_code_ratio: 0.0,
+                ..Default::default()
             },
-            files: vec![],
+            ..Default::default()
         };
 
         let text = format_text(&result).unwrap();
@@ -361,5 +323,6 @@ mod tests {
         assert_eq!(format_symbol_kind(SymbolKind::Class), "class");
         assert_eq!(format_symbol_kind(SymbolKind::Method), "method");
         assert_eq!(format_symbol_kind(SymbolKind::Variable), "var");
+        assert_eq!(format_symbol_kind(SymbolKind::ReExport), "re-export");
     }
 }
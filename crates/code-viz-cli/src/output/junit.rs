@@ -0,0 +1,153 @@
+use code_viz_dead_code::{DeadCodeResult, FileDeadCode};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Format dead code as a JUnit XML report for CI test tabs
+///
+/// Every analyzed file (from `file_totals`) becomes a `<testcase>`, in path
+/// order: files with dead code hold one `<failure>` per dead symbol and the
+/// others pass. The `<testsuite>` carries the totals, with `failures`
+/// counting dead symbols.
+pub fn format_junit(result: &DeadCodeResult) -> String {
+    let mut testcases: BTreeMap<&Path, Option<&FileDeadCode>> = result
+        .file_totals
+        .iter()
+        .map(|totals| (totals.path.as_path(), None))
+        .collect();
+    for file in &result.files {
+        testcases.insert(file.path.as_path(), Some(file));
+    }
+
+    let failures: usize = result.files.iter().map(|f| f.dead_code.len()).sum();
+    let mut output = String::new();
+
+    writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        output,
+        r#"<testsuite name="dead-code" tests="{}" failures="{}" errors="0" skipped="0">"#,
+        testcases.len(),
+        failures
+    )
+    .unwrap();
+    writeln!(output, "  <properties>").unwrap();
+    for (name, value) in [
+        ("totalFiles", result.summary.total_files.to_string()),
+        ("totalDeadLoc", result.summary.total_dead_loc.to_string()),
+        ("deadCodeRatio", format!("{:.4}", result.summary.dead_code_ratio)),
+    ] {
+        writeln!(output, r#"    <property name="{}" value="{}"/>"#, name, value).unwrap();
+    }
+    writeln!(output, "  </properties>").unwrap();
+
+    for (path, file) in testcases {
+        let path = escape(&path.display().to_string());
+        let Some(file) = file else {
+            writeln!(output, r#"  <testcase classname="dead-code" name="{}" file="{}"/>"#, path, path)
+                .unwrap();
+            continue;
+        };
+        writeln!(output, r#"  <testcase classname="dead-code" name="{}" file="{}">"#, path, path)
+            .unwrap();
+        for symbol in &file.dead_code {
+            writeln!(
+                output,
+                r#"    <failure message="{}" type="{:?}">{}</failure>"#,
+                escape(&format!(
+                    "{} (lines {}-{}, confidence {}%)",
+                    symbol.symbol, symbol.line_start, symbol.line_end, symbol.confidence
+                )),
+                symbol.kind,
+                escape(&symbol.reason)
+            )
+            .unwrap();
+        }
+        writeln!(output, "  </testcase>").unwrap();
+    }
+
+    writeln!(output, "</testsuite>").unwrap();
+    output
+}
+
+/// Escape text for XML attributes and element content
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_viz_dead_code::fixtures::{dead_code_result, dead_symbol};
+    use code_viz_dead_code::FileTotals;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+    use std::path::PathBuf;
+
+    fn sample_result() -> DeadCodeResult {
+        let mut result = dead_code_result(vec![
+            (
+                "src/a&b.ts",
+                vec![dead_symbol("unused", 1, 5, 95), dead_symbol("<helper>", 10, 5, 70)],
+            ),
+            ("src/c.ts", vec![dead_symbol("orphan", 3, 5, 100)]),
+        ]);
+        result.file_totals = ["src/a&b.ts", "src/c.ts", "src/clean.ts"]
+            .into_iter()
+            .map(|path| FileTotals {
+                path: PathBuf::from(path),
+                loc: 20,
+                complexity: 0,
+                export_count: 0,
+            })
+            .collect();
+        result
+    }
+
+    #[test]
+    fn test_format_junit_is_well_formed() {
+        let xml = format_junit(&sample_result());
+
+        let mut reader = Reader::from_str(&xml);
+        reader.config_mut().check_end_names = true;
+        let (mut testcases, mut passing, mut failures) = (0, 0, 0);
+        loop {
+            match reader.read_event().expect("well-formed XML") {
+                Event::Start(e) if e.name().as_ref() == b"testcase" => testcases += 1,
+                Event::Empty(e) if e.name().as_ref() == b"testcase" => passing += 1,
+                Event::Start(e) if e.name().as_ref() == b"failure" => failures += 1,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        assert_eq!(testcases, 2);
+        assert_eq!(passing, 1);
+        assert_eq!(failures, 3);
+        assert!(xml.contains(r#"tests="3" failures="3""#));
+        assert!(xml.contains(r#"<testcase classname="dead-code" name="src/clean.ts" file="src/clean.ts"/>"#));
+        assert!(xml.contains(r#"name="src/a&amp;b.ts""#));
+        assert!(xml.contains(r#"message="&lt;helper&gt; (lines 10-14, confidence 70%)""#));
+    }
+
+    #[test]
+    fn test_format_junit_without_dead_code() {
+        let mut result = sample_result();
+        result.files.clear();
+
+        let xml = format_junit(&result);
+        assert!(xml.contains(r#"tests="3" failures="0""#));
+        assert_eq!(xml.matches("<testcase").count(), 3);
+        assert!(!xml.contains("<failure"));
+    }
+}
@@ -1,10 +1,17 @@
 use code_viz_core::AnalysisResult;
+use std::ffi::OsString;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 pub mod csv;
 pub mod dead_code;
 pub mod json;
+pub mod junit;
+pub mod progress;
+pub mod summary_json;
 pub mod text;
+pub mod tree_json;
 
 #[derive(Error, Debug)]
 pub enum FormatterError {
@@ -16,10 +23,56 @@ pub trait MetricsFormatter {
     fn format(&self, result: &AnalysisResult) -> Result<String, FormatterError>;
 }
 
+/// Enable or disable ANSI colors for all formatters
+///
+/// Colors are off with `--no-color`, when `NO_COLOR` is set to a non-empty
+/// value, or when stdout is not a terminal (pipes, files, CI logs).
+pub fn configure_color(no_color: bool) {
+    let enabled = should_color(
+        no_color,
+        std::env::var_os("NO_COLOR"),
+        std::io::stdout().is_terminal(),
+    );
+    colored::control::set_override(enabled);
+}
+
+fn should_color(no_color: bool, no_color_env: Option<OsString>, is_terminal: bool) -> bool {
+    let env_disabled = no_color_env.is_some_and(|v| !v.is_empty());
+    !no_color && !env_disabled && is_terminal
+}
+
+/// Expand the placeholders of an `--output` path
+///
+/// `{date}` is today's local date (`2024-05-31`), `{commit}` the short HEAD
+/// commit of the repository containing `repo_dir` (`nogit` outside of one)
+/// and `{format}` the output format. Missing parent directories are created
+/// when the report is written.
+pub fn expand_output_path(template: &Path, repo_dir: &Path, format: &str) -> PathBuf {
+    let Some(template) = template.to_str().filter(|t| t.contains('{')) else {
+        return template.to_path_buf();
+    };
+    let mut expanded = template
+        .replace("{date}", &chrono::Local::now().format("%Y-%m-%d").to_string())
+        .replace("{format}", format);
+    if expanded.contains("{commit}") {
+        let commit = head_commit(repo_dir).unwrap_or_else(|| "nogit".to_string());
+        expanded = expanded.replace("{commit}", &commit);
+    }
+    PathBuf::from(expanded)
+}
+
+/// Abbreviated HEAD commit of the repository containing `dir`
+fn head_commit(dir: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(dir).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    let id = commit.id().to_string();
+    Some(id[..7].to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use code_viz_core::models::{Summary, FileMetrics};
+    use code_viz_core::models::{Summary, FileMetrics, FunctionMetrics};
     use std::path::PathBuf;
     use std::time::SystemTime;
 
@@ -31,6 +84,11 @@ mod tests {
                 loc: 100,
                 size_bytes: 1024,
                 function_count: 5,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
+                functions: vec![],
                 last_modified: SystemTime::now(),
                 dead_function_count: None,
                 dead_code_loc: None,
@@ -42,6 +100,11 @@ mod tests {
                 loc: 50,
                 size_bytes: 512,
                 function_count: 2,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
+                functions: vec![],
                 last_modified: SystemTime::now(),
                 dead_function_count: None,
                 dead_code_loc: None,
@@ -54,15 +117,35 @@ mod tests {
             total_loc: 150,
             total_functions: 7,
             largest_files: vec![PathBuf::from("src/main.rs"), PathBuf::from("src/lib.rs")],
+            largest_functions: vec![FunctionMetrics {
+                path: PathBuf::from("src/main.rs"),
+                name: "run".to_string(),
+                loc: 40,
+                complexity: 6,
+            }],
         };
 
         AnalysisResult {
+            schema_version: code_viz_core::report::current_schema_version(),
             summary,
             files,
             timestamp: SystemTime::now(),
+            skipped_files: 0,
+            warnings: vec![],
+            commit_sha: None,
         }
     }
 
+    #[test]
+    fn test_should_color() {
+        assert!(should_color(false, None, true));
+        assert!(!should_color(true, None, true));
+        assert!(!should_color(false, Some(OsString::from("1")), true));
+        assert!(!should_color(false, None, false));
+        // An empty NO_COLOR does not disable color
+        assert!(should_color(false, Some(OsString::new()), true));
+    }
+
     #[test]
     fn test_json_formatter() {
         let result = create_sample_result();
@@ -76,10 +159,26 @@ mod tests {
         assert!(parsed["files"].as_array().unwrap().len() == 2);
     }
 
+    #[test]
+    fn test_tree_json_formatter() {
+        let result = create_sample_result();
+        let formatter = tree_json::TreeJsonFormatter;
+        let output = formatter.format(&result).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        // Flat data is preserved
+        assert_eq!(parsed["summary"]["total_files"], 2);
+        assert_eq!(parsed["files"].as_array().unwrap().len(), 2);
+        // Tree root aggregates all files
+        assert!(parsed["tree"].is_object());
+        assert_eq!(parsed["tree"]["loc"], parsed["summary"]["total_loc"]);
+        assert_eq!(parsed["tree"]["type"], "directory");
+    }
+
     #[test]
     fn test_csv_formatter() {
         let result = create_sample_result();
-        let formatter = csv::CsvFormatter;
+        let formatter = csv::CsvFormatter::default();
         let output = formatter.format(&result).unwrap();
         
         let lines: Vec<&str> = output.lines().collect();
@@ -87,6 +186,44 @@ mod tests {
         assert_eq!(lines[0], "path,language,loc,functions,size_bytes");
         assert!(lines[1].contains("src/main.rs"));
         assert!(lines[1].contains("100"));
+        assert!(!output.starts_with('\u{feff}'));
+        assert!(!output.contains("\r\n"));
+    }
+
+    #[test]
+    fn test_csv_formatter_excel() {
+        let mut result = create_sample_result();
+        result.files[0].path = PathBuf::from("src/\"ä, ö\"\nmain.rs");
+
+        let output = csv::CsvFormatter::excel().format(&result).unwrap();
+
+        assert_eq!(&output.as_bytes()[..3], &[0xEF, 0xBB, 0xBF]);
+        assert!(output.starts_with("\u{feff}path,language,loc,functions,size_bytes\r\n"));
+
+        // Paths with commas, quotes and newlines survive a round trip
+        let mut reader = ::csv::Reader::from_reader(output.trim_start_matches('\u{feff}').as_bytes());
+        let first = reader.records().next().unwrap().unwrap();
+        assert_eq!(&first[0], "src/\"ä, ö\"\nmain.rs");
+    }
+
+    #[test]
+    fn test_expand_output_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+        let path = expand_output_path(Path::new("out/report-{date}-{commit}.{format}"), temp.path(), "json");
+        assert_eq!(path, PathBuf::from(format!("out/report-{}-nogit.json", today)));
+
+        // Paths without placeholders are kept as they are
+        let path = expand_output_path(Path::new("out/report.json"), temp.path(), "json");
+        assert_eq!(path, PathBuf::from("out/report.json"));
+
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let id = repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[]).unwrap();
+        let path = expand_output_path(Path::new("{commit}.txt"), temp.path(), "text");
+        assert_eq!(path, PathBuf::from(format!("{}.txt", &id.to_string()[..7])));
     }
 
     #[test]
@@ -99,5 +236,7 @@ mod tests {
         assert!(output.contains("Total LOC:   150"));
         assert!(output.contains("Largest Files:"));
         assert!(output.contains("src/main.rs (100 LOC)"));
+        assert!(output.contains("Largest Functions:"));
+        assert!(output.contains("src/main.rs: run (40 LOC, complexity 6)"));
     }
 }
@@ -0,0 +1,98 @@
+//! Progress bar for long-running commands.
+//!
+//! The bar is drawn on stderr so stdout stays clean for piping. It is only
+//! shown when stderr is a terminal, and is off with `--quiet` or for JSON
+//! output.
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Bar template: a 30 character bar, the percentage and the current step
+const TEMPLATE: &str = "[{bar:30}] {percent:>3}% {msg}";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Bar of the running analysis, created by the first report
+static BAR: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Enable or disable the progress bar for this process
+pub fn configure(quiet: bool) {
+    ENABLED.store(
+        should_show(quiet, std::io::stderr().is_terminal()),
+        Ordering::Relaxed,
+    );
+}
+
+/// Turn the progress bar off, e.g. when the output format is JSON
+pub fn suppress() {
+    finish();
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Move the bar to `fraction` (0.0 to 1.0); a complete bar is cleared
+pub fn report(fraction: f32, message: &str) {
+    if fraction >= 1.0 {
+        finish();
+        return;
+    }
+
+    let mut bar = BAR.lock().unwrap();
+    let bar = bar.get_or_insert_with(|| new_bar(ENABLED.load(Ordering::Relaxed)));
+    update(bar, fraction, message);
+}
+
+/// Clear the bar if it is on screen
+pub fn finish() {
+    if let Some(bar) = BAR.lock().unwrap().take() {
+        bar.finish_and_clear();
+    }
+}
+
+fn should_show(quiet: bool, is_terminal: bool) -> bool {
+    !quiet && is_terminal
+}
+
+/// Percentage bar on stderr, or a hidden one that draws nothing
+fn new_bar(visible: bool) -> ProgressBar {
+    let target = if visible {
+        ProgressDrawTarget::stderr()
+    } else {
+        ProgressDrawTarget::hidden()
+    };
+    let style = ProgressStyle::with_template(TEMPLATE)
+        .expect("valid progress template")
+        .progress_chars("#-");
+    ProgressBar::with_draw_target(Some(100), target).with_style(style)
+}
+
+fn update(bar: &ProgressBar, fraction: f32, message: &str) {
+    bar.set_position((fraction.clamp(0.0, 1.0) * 100.0).round() as u64);
+    bar.set_message(message.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_show() {
+        assert!(should_show(false, true));
+        assert!(!should_show(true, true));
+        assert!(!should_show(false, false));
+    }
+
+    #[test]
+    fn test_hidden_bar_tracks_progress() {
+        let bar = new_bar(false);
+        assert!(bar.is_hidden());
+
+        update(&bar, 0.5, "Scanning directory...");
+        assert_eq!(bar.position(), 50);
+        assert_eq!(bar.message(), "Scanning directory...");
+
+        update(&bar, 2.0, "done");
+        assert_eq!(bar.position(), 100);
+    }
+}
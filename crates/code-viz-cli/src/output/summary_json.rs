@@ -0,0 +1,60 @@
+use super::FormatterError;
+use code_viz_dead_code::DeadCodeSummary;
+use serde::Serialize;
+
+/// Headline dead code numbers printed by `--summary-json`
+#[derive(Debug, Serialize)]
+struct SummaryJson {
+    dead_functions: usize,
+    dead_classes: usize,
+    total_dead_loc: usize,
+    dead_code_ratio: f64,
+}
+
+/// Format the dead code summary as one flat, single-line JSON object
+///
+/// Unlike the full JSON report there is no `files` array, so the output stays
+/// small enough for `jq` one-liners in shell scripts.
+pub fn format_summary_json(summary: &DeadCodeSummary) -> Result<String, FormatterError> {
+    let compact = SummaryJson {
+        dead_functions: summary.dead_functions,
+        dead_classes: summary.dead_classes,
+        total_dead_loc: summary.total_dead_loc,
+        dead_code_ratio: summary.dead_code_ratio,
+    };
+    serde_json::to_string(&compact).map_err(|_| FormatterError::FormattingFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_summary_json_has_only_headline_keys() {
+        let summary = DeadCodeSummary {
+            total_files: 4,
+            files_with_dead_code: 2,
+            dead_functions: 3,
+            dead_classes: 1,
+            dead_other: 0,
+            total_dead_loc: 42,
+            total_loc: 168,
+            total_complexity: 50,
+            dead_code_ratio: 0.25,
+            dead_complexity_ratio: 0.2,
+            kept_by_pattern: 0,
+            filtered_small: 0,
+            skipped_generated: 0,
+            skipped_files: 0,
+        };
+
+        let output = format_summary_json(&summary).unwrap();
+        assert!(!output.contains('\n'));
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["dead_classes", "dead_code_ratio", "dead_functions", "total_dead_loc"]);
+        assert_eq!(json["dead_functions"], 3);
+        assert_eq!(json["total_dead_loc"], 42);
+    }
+}
@@ -1,5 +1,5 @@
 use super::{FormatterError, MetricsFormatter};
-use code_viz_core::AnalysisResult;
+use code_viz_core::{AnalysisResult, AnalysisWarningKind};
 use std::fmt::Write;
 
 pub struct TextFormatter;
@@ -14,6 +14,16 @@ impl MetricsFormatter for TextFormatter {
         writeln!(output, "Total Files: {}", summary.total_files).map_err(|_| FormatterError::FormattingFailed)?;
         writeln!(output, "Total LOC:   {}", summary.total_loc).map_err(|_| FormatterError::FormattingFailed)?;
         writeln!(output, "Functions:   {}", summary.total_functions).map_err(|_| FormatterError::FormattingFailed)?;
+        if let Some(sha) = &result.commit_sha {
+            writeln!(output, "Commit:      {}", sha).map_err(|_| FormatterError::FormattingFailed)?;
+        }
+        if result.skipped_files > 0 {
+            writeln!(output, "Skipped:     {} (too large or unreadable)", result.skipped_files).map_err(|_| FormatterError::FormattingFailed)?;
+        }
+        // The skipped file count is already shown above
+        for warning in result.warnings.iter().filter(|w| w.kind != AnalysisWarningKind::SkippedFiles) {
+            writeln!(output, "Warning: {}", warning.message).map_err(|_| FormatterError::FormattingFailed)?;
+        }
         writeln!(output).map_err(|_| FormatterError::FormattingFailed)?;
 
         writeln!(output, "Largest Files:").map_err(|_| FormatterError::FormattingFailed)?;
@@ -32,6 +42,22 @@ impl MetricsFormatter for TextFormatter {
             ).map_err(|_| FormatterError::FormattingFailed)?;
         }
 
+        if !summary.largest_functions.is_empty() {
+            writeln!(output).map_err(|_| FormatterError::FormattingFailed)?;
+            writeln!(output, "Largest Functions:").map_err(|_| FormatterError::FormattingFailed)?;
+            for (i, function) in summary.largest_functions.iter().enumerate() {
+                writeln!(
+                    output,
+                    "  {}. {}: {} ({} LOC, complexity {})",
+                    i + 1,
+                    function.path.display(),
+                    function.name,
+                    function.loc,
+                    function.complexity
+                ).map_err(|_| FormatterError::FormattingFailed)?;
+            }
+        }
+
         Ok(output)
     }
 }
@@ -0,0 +1,25 @@
+use super::{FormatterError, MetricsFormatter};
+use code_viz_api::transform::flat_to_hierarchy;
+use code_viz_api::TreeNode;
+use code_viz_core::AnalysisResult;
+use serde::Serialize;
+
+/// JSON output that embeds the hierarchical tree alongside the flat data
+pub struct TreeJsonFormatter;
+
+#[derive(Serialize)]
+struct TreeJsonOutput<'a> {
+    #[serde(flatten)]
+    result: &'a AnalysisResult,
+    tree: TreeNode,
+}
+
+impl MetricsFormatter for TreeJsonFormatter {
+    fn format(&self, result: &AnalysisResult) -> Result<String, FormatterError> {
+        let output = TreeJsonOutput {
+            result,
+            tree: flat_to_hierarchy(result.files.clone()),
+        };
+        serde_json::to_string_pretty(&output).map_err(|_| FormatterError::FormattingFailed)
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    #[cfg(not(feature = "git-integration"))]
+    #[error("Analyzing a git URL requires the git-integration feature: {0}")]
+    Unsupported(String),
+
+    #[cfg(feature = "git-integration")]
+    #[error("Failed to clone {url}: {message}")]
+    CloneFailed { url: String, message: String },
+}
+
+/// Whether an `analyze` path argument names a git repository to clone
+///
+/// Matches http(s), ssh, git and file URLs, scp-style `git@host:repo`, and
+/// anything ending in `.git` that is not an existing local path.
+pub fn is_git_url(path: &str) -> bool {
+    const SCHEMES: &[&str] = &["http://", "https://", "ssh://", "git://", "file://", "git@"];
+
+    SCHEMES.iter().any(|scheme| path.starts_with(scheme))
+        || (path.ends_with(".git") && !Path::new(path).exists())
+}
+
+/// Shallow clone of a remote repository in a temp dir, removed on drop
+pub struct RemoteCheckout {
+    /// SHA of the checked-out commit
+    pub commit_sha: String,
+    #[cfg(feature = "git-integration")]
+    dir: tempfile::TempDir,
+}
+
+impl RemoteCheckout {
+    #[cfg(feature = "git-integration")]
+    pub fn clone(url: &str) -> Result<Self, RemoteError> {
+        let clone_failed = |message: String| RemoteError::CloneFailed {
+            url: url.to_string(),
+            message,
+        };
+
+        let dir = tempfile::Builder::new()
+            .prefix("code-viz-")
+            .tempdir()
+            .map_err(|e| clone_failed(e.to_string()))?;
+        let commit_sha = code_viz_core::context::RealGit::new()
+            .clone_shallow(url, dir.path())
+            .map_err(|e| clone_failed(e.root_cause().to_string()))?;
+
+        Ok(Self { commit_sha, dir })
+    }
+
+    #[cfg(not(feature = "git-integration"))]
+    pub fn clone(url: &str) -> Result<Self, RemoteError> {
+        Err(RemoteError::Unsupported(url.to_string()))
+    }
+
+    /// Root of the checkout
+    #[cfg(feature = "git-integration")]
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    #[cfg(not(feature = "git-integration"))]
+    pub fn path(&self) -> &Path {
+        unreachable!("RemoteCheckout cannot be created without git-integration")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_git_url() {
+        assert!(is_git_url("https://github.com/org/repo"));
+        assert!(is_git_url("ssh://git@github.com/org/repo"));
+        assert!(is_git_url("git@github.com:org/repo.git"));
+        assert!(is_git_url("file:///tmp/repo"));
+        assert!(is_git_url("does-not-exist/repo.git"));
+
+        assert!(!is_git_url("."));
+        assert!(!is_git_url("./src"));
+        assert!(!is_git_url("/home/user/project"));
+    }
+}
@@ -0,0 +1,68 @@
+use std::io;
+use std::path::PathBuf;
+
+/// Directory to analyze for the path arguments of a command, along with the
+/// roots under it the analysis is limited to
+///
+/// A single path is analyzed as given, without roots. Several paths are
+/// reported relative to their deepest common ancestor; only the roots
+/// themselves are scanned, into one shared symbol graph.
+pub fn resolve(paths: Vec<PathBuf>) -> io::Result<(PathBuf, Vec<PathBuf>)> {
+    if paths.len() <= 1 {
+        let path = paths
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| PathBuf::from("."));
+        return Ok((path, Vec::new()));
+    }
+
+    let roots = paths
+        .iter()
+        .map(|path| {
+            path.canonicalize()
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok((common_ancestor(&roots), roots))
+}
+
+/// Deepest directory containing every one of `paths`
+fn common_ancestor(paths: &[PathBuf]) -> PathBuf {
+    let mut ancestor = paths[0].clone();
+    for path in &paths[1..] {
+        while !path.starts_with(&ancestor) && ancestor.pop() {}
+    }
+    // A single file given twice is its own ancestor
+    if ancestor.is_file() {
+        ancestor.pop();
+    }
+    ancestor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_single_path_is_kept_as_given() {
+        let (path, roots) = resolve(vec![PathBuf::from("src")]).unwrap();
+        assert_eq!(path, PathBuf::from("src"));
+        assert!(roots.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_several_paths_from_common_ancestor() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("apps/web")).unwrap();
+        fs::create_dir_all(root.join("libs")).unwrap();
+
+        let (path, roots) = resolve(vec![root.join("apps/web"), root.join("libs")]).unwrap();
+
+        assert_eq!(path, root);
+        assert_eq!(roots, vec![root.join("apps/web"), root.join("libs")]);
+        assert!(resolve(vec![root.join("apps"), root.join("missing")]).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ThresholdError {
+    #[error("Invalid threshold format: {0}")]
+    InvalidFormat(String),
+
+    #[error("Invalid threshold format: Unknown metric '{0}'")]
+    UnknownMetric(String),
+}
+
+/// Comparison operator of a threshold condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl ThresholdOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            ThresholdOp::Gt => ">",
+            ThresholdOp::Ge => ">=",
+            ThresholdOp::Lt => "<",
+            ThresholdOp::Le => "<=",
+        }
+    }
+}
+
+/// A single `--threshold` condition such as `dead_code_ratio>0.05`
+///
+/// The condition describes a failure: the check is violated when the metric
+/// compares true against the value. The legacy `key=value` form is an upper
+/// bound the metric may reach but not exceed, so it parses as `key>value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdExpr {
+    pub metric: String,
+    pub op: ThresholdOp,
+    pub value: f64,
+}
+
+impl ThresholdExpr {
+    /// Parse every expression, rejecting metrics not in `metrics`
+    pub fn parse_all(exprs: &[String], metrics: &[&str]) -> Result<Vec<Self>, ThresholdError> {
+        exprs
+            .iter()
+            .map(|expr| {
+                let parsed: ThresholdExpr = expr.parse()?;
+                if !metrics.contains(&parsed.metric.as_str()) {
+                    return Err(ThresholdError::UnknownMetric(parsed.metric));
+                }
+                Ok(parsed)
+            })
+            .collect()
+    }
+
+    /// Whether `actual` violates this condition
+    pub fn is_violated_by(&self, actual: f64) -> bool {
+        match self.op {
+            ThresholdOp::Gt => actual > self.value,
+            ThresholdOp::Ge => actual >= self.value,
+            ThresholdOp::Lt => actual < self.value,
+            ThresholdOp::Le => actual <= self.value,
+        }
+    }
+}
+
+impl FromStr for ThresholdExpr {
+    type Err = ThresholdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ThresholdError::InvalidFormat(s.to_string());
+
+        let op_start = s.find(['<', '>', '=']).ok_or_else(invalid)?;
+        let (metric, rest) = s.split_at(op_start);
+        let (op, value) = if let Some(value) = rest.strip_prefix(">=") {
+            (ThresholdOp::Ge, value)
+        } else if let Some(value) = rest.strip_prefix("<=") {
+            (ThresholdOp::Le, value)
+        } else if let Some(value) = rest.strip_prefix('>') {
+            (ThresholdOp::Gt, value)
+        } else if let Some(value) = rest.strip_prefix('<') {
+            (ThresholdOp::Lt, value)
+        } else {
+            // Legacy `key=value` form
+            (ThresholdOp::Gt, &rest[1..])
+        };
+
+        let metric = metric.trim();
+        if metric.is_empty() {
+            return Err(invalid());
+        }
+        let value = value.trim().parse::<f64>().map_err(|_| invalid())?;
+
+        Ok(ThresholdExpr {
+            metric: metric.to_string(),
+            op,
+            value,
+        })
+    }
+}
+
+impl fmt::Display for ThresholdExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.metric, self.op.symbol(), self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_operators() {
+        let cases = [
+            ("loc>500", ThresholdOp::Gt, 500.0),
+            ("loc>=500", ThresholdOp::Ge, 500.0),
+            ("dead_code_ratio<0.5", ThresholdOp::Lt, 0.5),
+            ("dead_code_ratio <= 0.5", ThresholdOp::Le, 0.5),
+        ];
+
+        for (input, op, value) in cases {
+            let expr: ThresholdExpr = input.parse().unwrap();
+            assert_eq!(expr.op, op, "{}", input);
+            assert_eq!(expr.value, value, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_legacy_form_is_upper_bound() {
+        let expr: ThresholdExpr = "loc=500".parse().unwrap();
+        assert_eq!(expr.metric, "loc");
+        assert!(!expr.is_violated_by(500.0));
+        assert!(expr.is_violated_by(501.0));
+    }
+
+    #[test]
+    fn test_parse_all_multiple_conditions() {
+        let exprs = vec![
+            "dead_code_ratio>0.05".to_string(),
+            "dead_functions>=10".to_string(),
+        ];
+        let parsed =
+            ThresholdExpr::parse_all(&exprs, &["dead_code_ratio", "dead_functions"]).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed[0].is_violated_by(0.06));
+        assert!(!parsed[0].is_violated_by(0.05));
+        assert!(parsed[1].is_violated_by(10.0));
+        assert_eq!(parsed[1].to_string(), "dead_functions>=10");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            "loc".parse::<ThresholdExpr>(),
+            Err(ThresholdError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "loc>abc".parse::<ThresholdExpr>(),
+            Err(ThresholdError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            ">5".parse::<ThresholdExpr>(),
+            Err(ThresholdError::InvalidFormat(_))
+        ));
+        assert_eq!(
+            ThresholdExpr::parse_all(&["size>5".to_string()], &["loc"]),
+            Err(ThresholdError::UnknownMetric("size".to_string()))
+        );
+    }
+}
@@ -105,6 +105,85 @@ fn test_e2e_threshold_violation() {
         .stderr(predicate::str::contains("exceed the LOC threshold"));
 }
 
+#[test]
+fn test_e2e_export_count_metric_and_threshold() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("api.ts")
+        .write_str("export function a() {}\nexport const b = 1;\nexport class C {}\nfunction internal() {}\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--format", "json"])
+        .arg(temp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["files"][0]["export_count"], 3);
+
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--threshold", "export_count>2"])
+        .arg(temp.path())
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("api.ts (3 exports)"));
+}
+
+#[test]
+fn test_e2e_exit_codes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("large.ts").write_str(&"x\n".repeat(600)).unwrap();
+
+    // Success
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--threshold", "loc>1000"])
+        .arg(temp.path())
+        .assert()
+        .code(0);
+
+    // Analysis error
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .arg("analyze")
+        .arg(temp.path().join("missing"))
+        .assert()
+        .code(1);
+
+    // Usage errors, from clap and from argument validation
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--no-such-flag"])
+        .assert()
+        .code(2);
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--threshold", "complexity>5"])
+        .arg(temp.path())
+        .assert()
+        .code(2);
+
+    // Threshold violation, still reporting the analysis
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--threshold", "loc>500"])
+        .arg(temp.path())
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("large.ts"));
+
+    // --exit-zero reports the violation but does not fail
+    Command::cargo_bin("code-viz-cli")
+        .unwrap()
+        .args(["analyze", "--threshold", "loc>500", "--exit-zero"])
+        .arg(temp.path())
+        .assert()
+        .code(0)
+        .stderr(predicate::str::contains("exceed the LOC threshold"));
+}
+
 #[test]
 #[ignore = "Config file loading not yet implemented in analyze command"]
 fn test_e2e_config_file_integration() {
@@ -162,3 +241,189 @@ fn test_e2e_config_init() {
         
     assert!(temp.child(".code-viz.toml").exists());
 }
+
+#[test]
+fn test_e2e_versioned_baseline_roundtrip() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/main.ts")
+        .write_str("function main() { console.log('hello'); }")
+        .unwrap();
+    let report = temp.child("report.json");
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg(temp.path())
+        .arg("--format")
+        .arg("json")
+        .arg("--output")
+        .arg(report.path())
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(report.path()).unwrap();
+    let mut json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["payload"]["summary"]["total_files"], 1);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg(temp.path())
+        .arg("--baseline")
+        .arg(report.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Baseline comparison: 1 -> 1"));
+
+    // A report from a newer schema version is rejected with a clear message
+    json["schema_version"] = serde_json::Value::from(99);
+    let future = temp.child("future.json");
+    future.write_str(&json.to_string()).unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg(temp.path())
+        .arg("--baseline")
+        .arg(future.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("schema version 99 is newer than supported version 1"));
+}
+
+#[test]
+fn test_e2e_output_path_template() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/main.ts")
+        .write_str("function main() { console.log('hello'); }")
+        .unwrap();
+
+    // The temp dir is not a git repository, so {commit} becomes "nogit"
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg(temp.path())
+        .arg("--format")
+        .arg("csv")
+        .arg("--output")
+        .arg(temp.path().join("reports/{date}/metrics-{commit}.{format}"))
+        .assert()
+        .success();
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    temp.child(format!("reports/{}/metrics-nogit.csv", today))
+        .assert(predicate::str::contains("src/main.ts"));
+}
+
+#[test]
+#[cfg(feature = "git-integration")]
+fn test_e2e_analyze_git_url() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/main.ts")
+        .write_str("function main() { console.log('hello'); }")
+        .unwrap();
+
+    let repo = git2::Repository::init(temp.path()).unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+    let commit = repo
+        .commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+        .unwrap();
+
+    let url = format!("file://{}", temp.path().display());
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg(&url)
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"total_files\": 1"))
+        .stdout(predicate::str::contains(format!(
+            "\"commit_sha\": \"{}\"",
+            commit
+        )));
+}
+
+#[test]
+#[cfg(not(feature = "git-integration"))]
+fn test_e2e_analyze_git_url_requires_feature() {
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg("https://example.com/org/repo.git")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires the git-integration feature"));
+}
+
+#[test]
+fn test_e2e_analyze_stdin() {
+    let source = "export function add(a: number, b: number) {\n    return a + b;\n}\n\nexport const double = (x: number) => add(x, x);\n";
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("analyze")
+        .arg("--stdin")
+        .arg("--language")
+        .arg("ts")
+        .arg("--format")
+        .arg("json")
+        .write_stdin(source)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["total_files"], 1);
+    assert_eq!(json["summary"]["total_functions"], 2);
+    assert_eq!(json["files"][0]["path"], "<stdin>");
+}
+
+#[test]
+fn test_e2e_analyze_stdin_rejects_unknown_language() {
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg("--stdin")
+        .arg("--language")
+        .arg("cobol")
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported --language: cobol"));
+}
+
+#[test]
+fn test_e2e_log_format_json() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/main.ts").write_str("export function a() {}").unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("analyze")
+        .arg(temp.path())
+        .arg("--format")
+        .arg("json")
+        .arg("--log-format")
+        .arg("json")
+        .arg("--log-level")
+        .arg("info")
+        .arg("--quiet")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // The report on stdout is unaffected by the log format
+    assert!(serde_json::from_slice::<serde_json::Value>(&output.stdout).is_ok());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<serde_json::Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| line["level"].is_string()));
+    assert!(lines
+        .iter()
+        .any(|line| line["fields"]["message"] == "Starting directory scan"));
+}
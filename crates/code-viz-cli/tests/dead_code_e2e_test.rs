@@ -135,6 +135,7 @@ fn test_e2e_analyze_with_dead_code_flag() {
         .arg("json")
         .assert()
         .success()
+        // `analyze` writes the snake_case analysis report, not dead code JSON
         .stdout(predicate::str::contains("\"total_files\""))
         .stdout(predicate::str::contains("\"total_loc\""));
 
@@ -262,7 +263,8 @@ fn test_e2e_output_to_file() {
     assert!(output_file.exists());
     let content = std::fs::read_to_string(output_file.path()).unwrap();
     let json: serde_json::Value = serde_json::from_str(&content).unwrap();
-    assert!(json["summary"].is_object());
+    assert_eq!(json["schema_version"], 1);
+    assert!(json["payload"]["summary"].is_object());
 }
 
 #[test]
@@ -309,7 +311,7 @@ fn test_e2e_verbose_logging() {
         .success();
 
     // We can't easily test for debug log output since it goes to stderr
-    // and the format depends on env_logger configuration,
+    // and the format depends on the --log-format flag,
     // but we verify the command succeeds with --verbose flag
 }
 
@@ -446,6 +448,106 @@ fn test_e2e_json_schema_validation() {
     }
 }
 
+#[test]
+fn test_e2e_json_outputs_share_camel_case_keys() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+    let output_file = temp.child("report.json");
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let stdout = cmd
+        .arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap()
+        .stdout;
+    let stdout: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .arg("--output")
+        .arg(output_file.path())
+        .assert()
+        .success();
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(output_file.path()).unwrap()).unwrap();
+
+    // The report file wraps the same payload in its schema version envelope
+    assert_eq!(report["payload"], stdout);
+
+    fn snake_case_keys(value: &serde_json::Value, found: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, child) in map {
+                    if key.contains('_') {
+                        found.push(key.clone());
+                    }
+                    snake_case_keys(child, found);
+                }
+            }
+            serde_json::Value::Array(items) => {
+                items.iter().for_each(|item| snake_case_keys(item, found))
+            }
+            _ => {}
+        }
+    }
+    let mut found = Vec::new();
+    snake_case_keys(&stdout, &mut found);
+    assert!(found.is_empty(), "snake_case keys in dead code JSON: {:?}", found);
+    assert!(stdout["summary"]["deadFunctions"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_e2e_warns_when_entry_points_look_missed() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    // Only main.ts is recognized as an entry; the real one is server.ts
+    temp.child("src/main.ts")
+        .write_str("export function version() {\n    return '1.0';\n}\n")
+        .unwrap();
+    temp.child("src/server.ts")
+        .write_str(
+            r#"
+function start() {
+    listen();
+    route();
+}
+
+function listen() {
+    const host = '0.0.0.0';
+    const port = 8080;
+    return `${host}:${port}`;
+}
+
+function route() {
+    const method = 'GET';
+    const path = '/';
+    return `${method} ${path}`;
+}
+
+start();
+"#,
+        )
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--no-color")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Warning: "))
+        .stdout(predicate::str::contains("only 1 entry point"));
+}
+
 #[test]
 fn test_e2e_analyze_threshold_dead_code_ratio() {
     let temp = assert_fs::TempDir::new().unwrap();
@@ -494,3 +596,594 @@ fn test_e2e_analyze_threshold_dead_code_ratio() {
             .stderr(predicate::str::contains("exceed the dead code ratio threshold"));
     }
 }
+
+#[test]
+fn test_e2e_dead_code_by_dir() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--by-dir")
+        .arg("--min-confidence")
+        .arg("0")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dead Code by Directory"))
+        .stdout(predicate::str::contains("Dead LOC"))
+        .stdout(predicate::str::contains("src"));
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("dead-code")
+        .arg(temp.path())
+        .arg("--by-dir")
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let dirs = json.as_array().expect("by-dir JSON should be an array");
+    assert_eq!(dirs.len(), 1);
+    assert!(dirs[0]["dir"].as_str().unwrap().ends_with("src"));
+    assert_eq!(dirs[0]["deadSymbols"], 2);
+}
+
+#[test]
+fn test_e2e_summary_json_is_compact() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    for command in ["dead-code", "analyze"] {
+        let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+        let output = cmd
+            .arg(command)
+            .arg(temp.path())
+            .arg("--summary-json")
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{}", command);
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(stdout.lines().count(), 1, "{}: {}", command, stdout);
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            ["dead_classes", "dead_code_ratio", "dead_functions", "total_dead_loc"],
+            "{}",
+            command
+        );
+        assert!(json.get("files").is_none());
+        assert!(json["dead_functions"].as_u64().unwrap() >= 1, "{}", command);
+    }
+}
+
+#[test]
+fn test_e2e_dead_code_explain() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    // Without --explain, penalties are not reported
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("confidencePenalties").not());
+
+    // The exported, recently written function is explained in text output
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--explain")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Explain: confidence 50 (exported -30, recently modified -20)"));
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--explain")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"confidencePenalties\""));
+}
+
+#[test]
+fn test_e2e_multiple_threshold_conditions() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    // Every violated condition is reported before failing
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--threshold")
+        .arg("dead_functions>=1")
+        .arg("--threshold")
+        .arg("dead_code_ratio>0")
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("violates threshold dead_functions>=1"))
+        .stderr(predicate::str::contains("violates threshold dead_code_ratio>0"));
+
+    // Conditions that do not hold pass
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--threshold")
+        .arg("dead_functions>100")
+        .arg("--threshold")
+        .arg("dead_code_ratio<0")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_e2e_dead_code_exit_zero() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--threshold")
+        .arg("dead_functions>=1")
+        .arg("--exit-zero")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("unusedFunction"))
+        .stderr(predicate::str::contains("violates threshold dead_functions>=1"));
+}
+
+#[test]
+fn test_e2e_no_color_output() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.env("NO_COLOR", "1")
+        .arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dead Code Analysis"))
+        .stdout(predicate::str::contains("\x1b[").not());
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.env_remove("NO_COLOR")
+        .arg("dead-code")
+        .arg(temp.path())
+        .arg("--no-color")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_e2e_dead_code_why() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/main.ts")
+        .write_str("import { helper } from './utils';\n\nfunction main() {\n    helper();\n}\n")
+        .unwrap();
+    temp.child("src/utils.ts")
+        .write_str("export function helper() {\n    return 1;\n}\n")
+        .unwrap();
+    temp.child("src/orphan.ts")
+        .write_str("function orphan() {\n    return 2;\n}\n")
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--why")
+        .arg("src/utils.ts::helper")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/utils.ts::helper is reachable via:"))
+        .stdout(predicate::str::contains("(entry point) main"))
+        .stdout(predicate::str::contains("-> helper"));
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--why")
+        .arg("src/orphan.ts::orphan")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is not reachable from any entry point"));
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--why")
+        .arg("helper")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected <file>::<symbol>"));
+}
+
+#[test]
+fn test_e2e_explain_symbol() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("explain")
+        .arg(temp.path())
+        .arg("src/dead.ts::unusedFunction")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "src/dead.ts::unusedFunction is dead: Unreachable from entry points",
+        ))
+        .stdout(predicate::str::contains("Confidence:"))
+        .stdout(predicate::str::contains("base 100"))
+        .stdout(predicate::str::contains("exported -30"));
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("explain")
+        .arg(temp.path())
+        .arg("src/main.ts::main")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/main.ts::main is reachable via:"))
+        .stdout(predicate::str::contains("(entry point) main"));
+}
+
+#[test]
+fn test_e2e_graph_json_round_trips() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("graph")
+        .arg(temp.path())
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let graph: code_viz_dead_code::SymbolGraph = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(graph.symbols.len(), json["symbols"].as_object().unwrap().len());
+    assert!(!graph.symbols.is_empty());
+    // IDs are relative to the analyzed directory
+    assert!(graph.symbols.keys().all(|id| id.starts_with("src/")));
+}
+
+#[test]
+fn test_e2e_config_file_with_cli_precedence() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("src/main.ts")
+        .write_str("function main() {\n    return 0;\n}\n")
+        .unwrap();
+    temp.child("src/unused.ts")
+        .write_str("function orphan() {\n    return 2;\n}\n")
+        .unwrap();
+    temp.child(".code-viz.toml")
+        .write_str("[output]\nformat = \"json\"\n\n[dead_code]\nmin_confidence = 90\n")
+        .unwrap();
+
+    // File settings apply when no flags are given
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd.arg("dead-code").arg(temp.path()).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["deadFunctions"], 0);
+
+    // CLI flags override the file
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("text")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dead Code Analysis Summary"))
+        .stdout(predicate::str::contains("orphan"));
+}
+
+#[test]
+fn test_e2e_package_entry_points_from_current_dir() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("package.json")
+        .write_str(r#"{ "name": "tool", "bin": "./src/tool.ts" }"#)
+        .unwrap();
+    temp.child("src/tool.ts")
+        .write_str("export function runCli() {\n    return 0;\n}\n")
+        .unwrap();
+    temp.child("src/main.ts")
+        .write_str("function main() {\n    return 0;\n}\n\nmain();\n")
+        .unwrap();
+    temp.child(".code-viz.toml")
+        .write_str("[dead_code]\nentry_points = [\"package.json\"]\n")
+        .unwrap();
+
+    // Analyzing `.` gives `./src/tool.ts` paths, which must still match
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .current_dir(temp.path())
+        .arg("dead-code")
+        .arg(".")
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["deadFunctions"], 0);
+}
+
+#[test]
+fn test_e2e_quiet_and_json_have_no_progress_output() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--quiet")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("analyze")
+        .arg(temp.path())
+        .arg("-q")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    for command in ["analyze", "dead-code"] {
+        let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+        let output = cmd
+            .arg(command)
+            .arg(temp.path())
+            .arg("--format")
+            .arg("json")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert!(serde_json::from_slice::<serde_json::Value>(&output.stdout).is_ok());
+        assert!(!String::from_utf8_lossy(&output.stderr).contains('%'));
+    }
+}
+
+#[test]
+fn test_e2e_dead_code_split_output() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+    let split_dir = temp.child("reports");
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("dead-code")
+        .arg(temp.path())
+        .arg("--format")
+        .arg("json")
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--split-output")
+        .arg(split_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let symbol_count = |json: &serde_json::Value| -> usize {
+        json["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["deadCode"].as_array().unwrap().len())
+            .sum()
+    };
+    let unfiltered: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let total = symbol_count(&unfiltered);
+    assert!(total > 0);
+
+    let mut split_total = 0;
+    for tier in ["high", "medium", "low"] {
+        let content = std::fs::read_to_string(split_dir.child(format!("{}.json", tier)).path())
+            .unwrap_or_else(|_| panic!("{}.json should be written", tier));
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert!(json["summary"]["totalDeadLoc"].is_number());
+        split_total += symbol_count(&json);
+    }
+    assert_eq!(split_total, total);
+}
+
+/// Every string in a JSON report
+fn json_strings(value: &serde_json::Value, strings: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => strings.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| json_strings(v, strings)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| json_strings(v, strings)),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_e2e_reports_paths_relative_to_root() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+    let root = temp.path().display().to_string();
+
+    for args in [&["analyze", "--dead-code"][..], &["dead-code", "--min-confidence", "0"][..]] {
+        let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+        let output = cmd
+            .args(args)
+            .arg(temp.path())
+            .arg("--format")
+            .arg("json")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut strings = Vec::new();
+        json_strings(&json, &mut strings);
+        assert!(
+            strings.iter().all(|s| !s.contains(&root)),
+            "{:?} reported an absolute path: {:?}",
+            args,
+            strings
+        );
+        assert!(strings.iter().any(|s| s == "src/dead.ts"));
+
+        // --absolute-paths keeps the analysis root
+        let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+        cmd.args(args)
+            .arg(temp.path())
+            .arg("--format")
+            .arg("json")
+            .arg("--absolute-paths")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(&root));
+    }
+}
+
+#[test]
+fn test_e2e_baseline_suppress_reports_only_new_dead_code() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    create_test_repo(&temp);
+    let baseline = temp.child("baseline.json");
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    cmd.arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .arg("--min-loc")
+        .arg("1")
+        .arg("--output")
+        .arg(baseline.path())
+        .assert()
+        .success();
+
+    // New dead code above the baselined symbols, which move down but keep
+    // their fingerprints
+    let dead = temp.child("src/dead.ts");
+    let previous = std::fs::read_to_string(dead.path()).unwrap();
+    dead.write_str(&format!(
+        "function newlyDead() {{\n    return \"new\";\n}}\n{}",
+        previous
+    ))
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("dead-code")
+        .arg(temp.path())
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .arg("--min-loc")
+        .arg("1")
+        .arg("--baseline-suppress")
+        .arg(baseline.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|f| f["deadCode"].as_array().unwrap())
+        .map(|symbol| symbol["symbol"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["newlyDead"]);
+    assert_eq!(json["summary"]["deadFunctions"], 1);
+}
+
+#[test]
+fn test_e2e_multiple_roots_share_one_graph() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("apps/main.ts")
+        .write_str("import { used } from '../libs/used';\n\nfunction main() {\n    used();\n}\n\nmain();\n")
+        .unwrap();
+    temp.child("libs/used.ts")
+        .write_str("export function used() {\n    return 1;\n}\n")
+        .unwrap();
+    temp.child("libs/orphan.ts")
+        .write_str("export function orphan() {\n    return 2;\n}\n")
+        .unwrap();
+    // Not passed, so its import does not keep `orphan` alive
+    temp.child("tests/orphan.test.ts")
+        .write_str("import { orphan } from '../libs/orphan';\n\norphan();\n")
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("code-viz-cli").unwrap();
+    let output = cmd
+        .arg("dead-code")
+        .arg(temp.path().join("apps"))
+        .arg(temp.path().join("libs"))
+        .arg("--min-confidence")
+        .arg("0")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // Paths are relative to the common ancestor, and the import from apps/
+    // keeps `used` alive
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let dead: Vec<(&str, &str)> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|file| {
+            let path = file["path"].as_str().unwrap();
+            file["deadCode"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(move |symbol| (path, symbol["symbol"].as_str().unwrap()))
+        })
+        .collect();
+    assert_eq!(dead, vec![("libs/orphan.ts", "orphan")]);
+
+    // The cache goes to the first root rather than the common ancestor
+    assert!(temp.path().join("apps/.code-viz/cache").exists());
+    assert!(!temp.path().join(".code-viz").exists());
+}
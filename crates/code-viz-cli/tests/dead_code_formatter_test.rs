@@ -1,5 +1,9 @@
-use code_viz_dead_code::models::SymbolKind;
-use code_viz_dead_code::{DeadCodeResult, DeadCodeSummary, DeadSymbol, FileDeadCode};
+use code_viz_dead_code::fixtures::sample_result;
+use code_viz_dead_code::{
+    AnalysisWarning, AnalysisWarningKind, ConfidencePenalty, DeadCodeResult, DirectoryDeadCode,
+    FileTiming, FileTotals,
+};
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 
 // Import the formatters (these are in src/output/dead_code.rs)
@@ -13,74 +17,21 @@ fn format_json(result: &DeadCodeResult) -> Result<String, String> {
     serde_json::to_string_pretty(result).map_err(|_| "JSON serialization failed".to_string())
 }
 
-fn create_sample_result() -> DeadCodeResult {
-    DeadCodeResult {
-        summary: DeadCodeSummary {
-            total_files: 10,
-            files_with_dead_code: 2,
-            dead_functions: 5,
-            dead_classes: 1,
-            total_dead_loc: 150,
-            dead_code_ratio: 0.15,
-        },
-        files: vec![
-            FileDeadCode {
-                path: PathBuf::from("src/utils.ts"),
-                dead_code: vec![
-                    DeadSymbol {
-                        symbol: "unusedFunction".to_string(),
-                        kind: SymbolKind::Function,
-                        line_start: 10,
-                        line_end: 20,
-                        loc: 10,
-                        confidence: 95,
-                        reason: "Not imported or called anywhere".to_string(),
-                        last_modified: None,
-                    },
-                    DeadSymbol {
-                        symbol: "oldHelper".to_string(),
-                        kind: SymbolKind::ArrowFunction,
-                        line_start: 25,
-                        line_end: 30,
-                        loc: 5,
-                        confidence: 85,
-                        reason: "Exported but never used".to_string(),
-                        last_modified: None,
-                    },
-                ],
-            },
-            FileDeadCode {
-                path: PathBuf::from("src/legacy.ts"),
-                dead_code: vec![DeadSymbol {
-                    symbol: "LegacyClass".to_string(),
-                    kind: SymbolKind::Class,
-                    line_start: 1,
-                    line_end: 100,
-                    loc: 100,
-                    confidence: 65,
-                    reason: "Exported and recently modified".to_string(),
-                    last_modified: None,
-                }],
-            },
-        ],
-    }
-}
-
 #[test]
 fn test_format_json() {
-    let result = create_sample_result();
+    let result = sample_result();
     let json = format_json(&result).unwrap();
 
     // Verify it's valid JSON
     let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
     assert_eq!(parsed["summary"]["totalFiles"], 10);
-    assert_eq!(parsed["summary"]["deadFunctions"], 5);
+    assert_eq!(parsed["summary"]["deadFunctions"], 2);
     assert_eq!(parsed["files"].as_array().unwrap().len(), 2);
 }
 
 #[test]
 fn test_json_roundtrip() {
-    let result = create_sample_result();
+    let result = sample_result();
     let json = format_json(&result).unwrap();
 
     // Verify we can deserialize it back
@@ -95,7 +46,7 @@ fn test_json_roundtrip() {
 
 #[test]
 fn test_json_schema_compliance() {
-    let result = create_sample_result();
+    let result = sample_result();
     let json = format_json(&result).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
@@ -129,3 +80,79 @@ fn test_json_schema_compliance() {
         assert!(symbol["reason"].is_string());
     }
 }
+
+/// Every object key in a JSON document, at any depth
+fn collect_keys(value: &serde_json::Value, keys: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                keys.insert(key.clone());
+                collect_keys(child, keys);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|item| collect_keys(item, keys)),
+        _ => {}
+    }
+}
+
+/// Dead code JSON has one canonical camelCase key set, shared by the CLI,
+/// the web routes and the Tauri commands
+#[test]
+fn test_json_canonical_key_set() {
+    let mut result = sample_result();
+    let symbol = &mut result.files[0].dead_code[0];
+    symbol.last_modified = Some(std::time::SystemTime::UNIX_EPOCH);
+    symbol.confidence_penalties = Some(vec![ConfidencePenalty::new("exported", 30)]);
+    result.by_directory = vec![DirectoryDeadCode {
+        dir: PathBuf::from("src"),
+        dead_symbols: 3,
+        dead_loc: 115,
+        dead_ratio: 0.5,
+    }];
+    result.file_totals = vec![FileTotals {
+        path: PathBuf::from("src/a.ts"),
+        loc: 230,
+        complexity: 12,
+        export_count: 2,
+    }];
+    result.dead_clusters = vec![vec!["src/a.ts:1:a".to_string(), "src/b.ts:1:b".to_string()]];
+    result.warnings = vec![AnalysisWarning {
+        kind: AnalysisWarningKind::SuspiciousDeadCodeRatio,
+        message: "entry points missed".to_string(),
+    }];
+    result.profile = Some(vec![FileTiming {
+        path: PathBuf::from("src/a.ts"),
+        ms: 12.0,
+    }]);
+    result.cache_hit = Some(true);
+
+    let parsed: serde_json::Value = serde_json::from_str(&format_json(&result).unwrap()).unwrap();
+    let mut keys = BTreeSet::new();
+    collect_keys(&parsed, &mut keys);
+
+    let expected: BTreeSet<String> = [
+        // DeadCodeResult
+        "schemaVersion", "summary", "files", "byDirectory", "fileTotals", "deadClusters", "warnings",
+        "profile", "cacheHit",
+        // DeadCodeSummary
+        "totalFiles", "filesWithDeadCode", "deadFunctions", "deadClasses", "deadOther", "totalDeadLoc",
+        "totalLoc", "totalComplexity", "deadCodeRatio", "deadComplexityRatio", "keptByPattern", "filteredSmall", "skippedGenerated",
+        "skippedFiles",
+        // FileDeadCode and FileTotals ("loc" and "complexity" are shared with DeadSymbol)
+        "path", "deadCode", "exportCount",
+        // DeadSymbol and ConfidencePenalty
+        "symbol", "kind", "lineStart", "lineEnd", "byteStart", "byteEnd", "loc", "complexity", "confidence", "reason",
+        "lastModified", "confidencePenalties", "fingerprint", "name", "amount",
+        // DirectoryDeadCode
+        "dir", "deadSymbols", "deadLoc", "deadRatio",
+        // AnalysisWarning ("kind" is shared with DeadSymbol)
+        "message",
+        // FileTiming ("path" is shared with FileDeadCode)
+        "ms",
+    ]
+    .iter()
+    .map(|key| key.to_string())
+    .collect();
+    assert_eq!(keys, expected);
+    assert_eq!(parsed["files"][0]["deadCode"][0]["lastModified"], "1970-01-01T00:00:00.000Z");
+}
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
-use code_viz_core::traits::{AppContext, FileSystem};
-use code_viz_core::models::{AnalysisResult, FileMetrics};
-use code_viz_core::{calculate_summary, parser, metrics};
+use code_viz_core::traits::{AppContext, FileSystem, GitProvider};
+use code_viz_dead_code::AnalysisConfig;
+use code_viz_core::models::{AnalysisResult, AnalysisWarning, FileMetrics};
+use code_viz_core::scanner::FileSelection;
+use code_viz_core::{calculate_summary, parser, metrics, sfc};
 use serde_json::json;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -11,25 +13,80 @@ pub async fn analyze_repository(
     path: &Path,
     ctx: impl AppContext,
     fs: impl FileSystem,
+) -> Result<AnalysisResult> {
+    analyze_repository_with_callback(path, ctx, fs, |_| {}).await
+}
+
+/// Analyze a repository, handing each file's metrics to `on_file` as soon as
+/// it is processed so callers can stream partial results.
+///
+/// Files that fail to analyze are skipped and never reach `on_file`.
+pub async fn analyze_repository_with_callback(
+    path: &Path,
+    ctx: impl AppContext,
+    fs: impl FileSystem,
+    on_file: impl FnMut(&FileMetrics) + Send,
+) -> Result<AnalysisResult> {
+    analyze_selected_files(path, None, ctx, fs, on_file).await
+}
+
+/// Analyze only the scanned files that `selection` keeps (e.g. the include
+/// patterns and languages of `.code-viz.toml`); the others are never read.
+pub async fn analyze_repository_with_selection(
+    path: &Path,
+    selection: &FileSelection,
+    ctx: impl AppContext,
+    fs: impl FileSystem,
+) -> Result<AnalysisResult> {
+    analyze_selected_files(path, Some(selection), ctx, fs, |_| {}).await
+}
+
+async fn analyze_selected_files(
+    path: &Path,
+    selection: Option<&FileSelection>,
+    ctx: impl AppContext,
+    fs: impl FileSystem,
+    mut on_file: impl FnMut(&FileMetrics) + Send,
 ) -> Result<AnalysisResult> {
     ctx.report_progress(0.1, "Scanning directory...").await?;
 
     // 1. Scan directory
-    let all_files = fs.read_dir_recursive(path)
+    let mut scan = fs.read_dir_recursive_detailed(path)
         .with_context(|| format!("Failed to scan directory: {}", path.display()))?;
+    if let Some(selection) = selection {
+        scan.files.retain(|file| selection.is_selected(file));
+        scan.plain_text_files.retain(|file| selection.is_selected(file));
+        scan.skipped.retain(|skipped| selection.is_selected(&skipped.path));
+    }
+    let skipped_files = scan.skipped.len();
+    let warnings: Vec<AnalysisWarning> = AnalysisWarning::skipped_files(&scan.skipped).into_iter().collect();
     
-    // 2. Filter supported files
-    let supported_files: Vec<PathBuf> = all_files.into_iter()
+    // 2. Filter supported files; plain text files have no grammar but still count LOC
+    let scanned_files = scan.files.len();
+    let plain_text_files = scan.plain_text_files;
+    let supported_files: Vec<PathBuf> = scan.files.into_iter()
         .filter(|p| {
-            if let Some(ext) = p.extension() {
+            if plain_text_files.contains(p) {
+                true
+            } else if let Some(ext) = p.extension() {
                 let ext_str = ext.to_string_lossy();
-                matches!(ext_str.as_ref(), "ts" | "tsx" | "js" | "jsx" | "rs" | "py" | "go" | "cpp" | "cc" | "cxx" | "hpp" | "h")
+                matches!(ext_str.as_ref(), "ts" | "tsx" | "js" | "jsx" | "vue" | "svelte" | "rs" | "py" | "go" | "cpp" | "cc" | "cxx" | "hpp" | "h")
             } else {
                 false
             }
         })
         .collect();
 
+    // Only other file types is most likely a mistyped path, not a clean result
+    let unsupported_files = scan.unsupported_files + scanned_files - supported_files.len();
+    if supported_files.is_empty() && unsupported_files > 0 {
+        anyhow::bail!(
+            "No supported source files in {} ({} files of other types)",
+            path.display(),
+            unsupported_files
+        );
+    }
+
     let total_files = supported_files.len();
     ctx.report_progress(0.2, &format!("Found {} files to analyze", total_files)).await?;
 
@@ -42,8 +99,12 @@ pub async fn analyze_repository(
             ctx.report_progress(percentage, &format!("Analyzing files ({}/{})", i, total_files)).await?;
         }
 
-        match analyze_single_file(file_path, &fs).await {
-            Ok(metrics) => results.push(metrics),
+        let plain_text = plain_text_files.contains(file_path);
+        match analyze_single_file(file_path, &fs, plain_text).await {
+            Ok(metrics) => {
+                on_file(&metrics);
+                results.push(metrics);
+            }
             Err(e) => {
                 // Log error but continue with other files
                 // In a real app, we might want to report this to the UI
@@ -58,9 +119,13 @@ pub async fn analyze_repository(
     let summary = calculate_summary(&results);
 
     let final_result = AnalysisResult {
+        schema_version: code_viz_core::report::current_schema_version(),
         summary,
         files: results,
         timestamp: SystemTime::now(),
+        skipped_files,
+        warnings,
+        commit_sha: None,
     };
 
     // 5. Emit completion event
@@ -70,8 +135,37 @@ pub async fn analyze_repository(
     Ok(final_result)
 }
 
-/// Analyze a single file using the FileSystem trait.
-async fn analyze_single_file(path: &Path, fs: &impl FileSystem) -> Result<FileMetrics> {
+/// Analyze a repository and fill in the per-file dead code fields in one run.
+pub async fn analyze_repository_with_dead_code(
+    path: &Path,
+    config: AnalysisConfig,
+    ctx: impl AppContext + Clone,
+    fs: impl FileSystem + Clone,
+    git: impl GitProvider,
+) -> Result<AnalysisResult> {
+    let mut result = analyze_repository(path, ctx.clone(), fs.clone()).await?;
+    let dead_code =
+        crate::dead_code::calculate_dead_code_with_config(path, config, ctx, fs, git).await?;
+    crate::dead_code::merge_dead_code(&mut result.files, &dead_code);
+    crate::dead_code::merge_dead_code_warnings(&mut result.warnings, &dead_code);
+
+    Ok(result)
+}
+
+/// Analyze a single file using the FileSystem trait, counting only its lines
+/// if it is `plain_text`.
+async fn analyze_single_file(
+    path: &Path,
+    fs: &impl FileSystem,
+    plain_text: bool,
+) -> Result<FileMetrics> {
+    if plain_text {
+        let source = fs.read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        return metrics::calculate_metrics(path, &source, &parser::PlainTextParser, None)
+            .with_context(|| format!("Failed to calculate metrics for: {}", path.display()));
+    }
+
     let extension = path.extension()
         .and_then(|e| e.to_str())
         .context("File has no extension")?;
@@ -81,6 +175,7 @@ async fn analyze_single_file(path: &Path, fs: &impl FileSystem) -> Result<FileMe
         "tsx" => "tsx",
         "js" => "javascript",
         "jsx" => "javascript",
+        "vue" | "svelte" => "typescript",
         "rs" => "rust",
         "py" => "python",
         "go" => "go",
@@ -88,11 +183,14 @@ async fn analyze_single_file(path: &Path, fs: &impl FileSystem) -> Result<FileMe
         ext => ext,
     };
 
-    let parser = parser::get_parser(language_key)
-        .with_context(|| format!("Failed to get parser for language: {}", language_key))?;
-
     let source = fs.read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let source = if sfc::is_component(path) { sfc::script_source(&source) } else { source };
+
+    // The extension can be ambiguous, e.g. JSX in a .ts file
+    let language_key = parser::detect_dialect(language_key, &source);
+    let parser = parser::get_parser(language_key)
+        .with_context(|| format!("Failed to get parser for language: {}", language_key))?;
 
     let metrics = metrics::calculate_metrics(path, &source, parser.as_ref(), None)
         .with_context(|| format!("Failed to calculate metrics for: {}", path.display()))?;
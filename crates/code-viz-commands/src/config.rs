@@ -0,0 +1,330 @@
+//! Typed `.code-viz.toml` settings, read by the CLI and the web server
+
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Config parse failed: {0}")]
+    ParseFailed(#[from] toml::de::Error),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Typed schema of `.code-viz.toml`
+///
+/// Values are read from the analyzed directory. Precedence, highest first:
+/// command-line flags, then this file, then built-in defaults.
+#[derive(Debug, Deserialize, Default)]
+pub struct ConfigFile {
+    pub analysis: Option<AnalysisConfigSection>,
+    pub output: Option<OutputConfigSection>,
+    pub cache: Option<CacheConfigSection>,
+    pub dead_code: Option<DeadCodeConfigSection>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct AnalysisConfigSection {
+    /// Glob patterns to exclude (replaces the built-in defaults)
+    pub exclude: Option<Vec<String>>,
+    /// Glob patterns to include; everything else is skipped
+    pub include: Option<Vec<String>>,
+    /// Languages to analyze, e.g. ["typescript", "javascript"]
+    pub languages: Option<Vec<String>>,
+    /// Extra extensions mapped to the language they are parsed as,
+    /// e.g. { "ts.tmpl" = "typescript" }
+    pub extension_overrides: Option<std::collections::HashMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct OutputConfigSection {
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct CacheConfigSection {
+    pub enabled: Option<bool>,
+    /// "read-write", "read-only" or "off"; `enabled = false` means "off"
+    pub mode: Option<code_viz_dead_code::CacheMode>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct DeadCodeConfigSection {
+    pub min_confidence: Option<u8>,
+    /// Glob patterns of files whose symbols are always entry points, where
+    /// "package.json" selects the files package.json declares
+    pub entry_points: Option<Vec<String>>,
+    /// Regexes of symbol names that are never reported, e.g. "^route_"
+    pub keep_patterns: Option<Vec<String>>,
+    /// Whether `import type` keeps its targets alive (default true)
+    pub follow_type_imports: Option<bool>,
+    /// Dead symbols shorter than this many lines are not reported
+    pub min_loc: Option<usize>,
+    /// Dead code ratio above which few entry points trigger a warning
+    pub suspicious_dead_ratio: Option<f64>,
+    /// Report symbols only referenced inside always-false branches
+    pub detect_dead_branches: Option<bool>,
+    /// Leave out dead symbols of files with a generated-code header
+    pub skip_generated: Option<bool>,
+    /// Regexes matched against the first lines of each file, replacing the
+    /// default generated-code markers
+    pub generated_markers: Option<Vec<String>>,
+    pub confidence_weights: Option<ConfidenceWeightsSection>,
+    /// Extra penalties by path, in priority order
+    pub confidence_overrides: Option<Vec<ConfidenceOverrideSection>>,
+}
+
+/// Overrides for the confidence penalty of each heuristic
+#[derive(Debug, Deserialize, Default)]
+pub struct ConfidenceWeightsSection {
+    pub exported: Option<u8>,
+    pub recently_modified: Option<u8>,
+    pub dynamic_pattern: Option<u8>,
+    pub test_coverage: Option<u8>,
+    pub string_reference: Option<u8>,
+}
+
+/// Extra confidence penalty for symbols in files matching `path`
+///
+/// Only the first matching entry applies; a negative penalty raises the score.
+#[derive(Debug, Deserialize)]
+pub struct ConfidenceOverrideSection {
+    pub path: String,
+    pub penalty: i16,
+}
+
+impl ConfigFile {
+    /// Output format from the file, used when `--format` is not given
+    pub fn output_format(&self) -> Option<&str> {
+        self.output.as_ref()?.format.as_deref()
+    }
+
+    /// Exclude patterns from the file, overridden by non-empty CLI patterns
+    pub fn exclude_patterns(&self, cli_exclude: &[String]) -> Option<Vec<String>> {
+        if !cli_exclude.is_empty() {
+            return Some(cli_exclude.to_vec());
+        }
+        self.analysis.as_ref()?.exclude.clone()
+    }
+
+    /// Minimum confidence from the file, used when `--min-confidence` is not given
+    pub fn min_confidence(&self) -> Option<u8> {
+        self.dead_code.as_ref()?.min_confidence
+    }
+
+    /// Dead code analysis settings from the file, with CLI exclude patterns taking precedence
+    pub fn dead_code_config(&self, cli_exclude: &[String]) -> code_viz_dead_code::AnalysisConfig {
+        let mut config = code_viz_dead_code::AnalysisConfig::default();
+
+        if let Some(exclude) = self.exclude_patterns(cli_exclude) {
+            config.exclude_patterns = exclude;
+        }
+        if let Some(analysis) = &self.analysis {
+            config.include_patterns = analysis.include.clone().unwrap_or_default();
+            config.languages = analysis.languages.clone().unwrap_or_default();
+            config.extension_overrides = analysis.extension_overrides.clone().unwrap_or_default();
+        }
+        if let Some(cache) = &self.cache {
+            config.cache_mode = match (cache.mode, cache.enabled) {
+                (_, Some(false)) => code_viz_dead_code::CacheMode::Off,
+                (Some(mode), _) => mode,
+                (None, _) => config.cache_mode,
+            };
+        }
+        if let Some(dead_code) = &self.dead_code {
+            config.entry_point_patterns = dead_code.entry_points.clone().unwrap_or_default();
+            config.keep_patterns = dead_code.keep_patterns.clone().unwrap_or_default();
+            config.confidence_overrides = dead_code
+                .confidence_overrides
+                .iter()
+                .flatten()
+                .map(|o| (o.path.clone(), o.penalty))
+                .collect();
+            if let Some(follow) = dead_code.follow_type_imports {
+                config.follow_type_imports = follow;
+            }
+            if let Some(min_loc) = dead_code.min_loc {
+                config.min_loc = min_loc;
+            }
+            if let Some(ratio) = dead_code.suspicious_dead_ratio {
+                config.suspicious_dead_ratio = ratio;
+            }
+            if let Some(detect) = dead_code.detect_dead_branches {
+                config.detect_dead_branches = detect;
+            }
+            if let Some(skip) = dead_code.skip_generated {
+                config.skip_generated = skip;
+            }
+            if let Some(markers) = &dead_code.generated_markers {
+                config.generated_markers = markers.clone();
+            }
+            if let Some(weights) = &dead_code.confidence_weights {
+                let defaults = config.confidence_weights;
+                config.confidence_weights = code_viz_dead_code::ConfidenceWeights {
+                    exported: weights.exported.unwrap_or(defaults.exported),
+                    recently_modified: weights.recently_modified.unwrap_or(defaults.recently_modified),
+                    dynamic_pattern: weights.dynamic_pattern.unwrap_or(defaults.dynamic_pattern),
+                    test_coverage: weights.test_coverage.unwrap_or(defaults.test_coverage),
+                    string_reference: weights.string_reference.unwrap_or(defaults.string_reference),
+                };
+            }
+        }
+
+        config
+    }
+}
+
+pub fn load_config(project_root: &Path) -> Result<ConfigFile, ConfigError> {
+    let config_path = project_root.join(".code-viz.toml");
+    
+    if !config_path.exists() {
+        return Ok(ConfigFile::default());
+    }
+
+    let content = fs::read_to_string(&config_path)?;
+    let config: ConfigFile = toml::from_str(&content)?;
+    
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use std::io::Write;
+    use std::fs::File;
+
+    #[test]
+    fn test_load_valid_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        
+        let mut f = File::create(root.join(".code-viz.toml")).unwrap();
+        writeln!(f, r#"
+            [analysis]
+            exclude = ["node_modules/**", "dist/**"]
+
+            [output]
+            format = "json"
+            
+            [cache]
+            enabled = true
+        "#).unwrap();
+
+        let config = load_config(root).unwrap();
+        
+        assert!(config.analysis.is_some());
+        let analysis = config.analysis.unwrap();
+        assert_eq!(analysis.exclude.unwrap().len(), 2);
+        
+        assert!(config.output.is_some());
+        assert_eq!(config.output.unwrap().format.unwrap(), "json");
+        
+        assert!(config.cache.is_some());
+        assert_eq!(config.cache.unwrap().enabled.unwrap(), true);
+    }
+
+    #[test]
+    fn test_dead_code_config_precedence() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let mut f = File::create(root.join(".code-viz.toml")).unwrap();
+        writeln!(f, r#"
+            [analysis]
+            exclude = ["vendor/**"]
+            languages = ["typescript"]
+            extension_overrides = {{ es = "javascript" }}
+
+            [cache]
+            enabled = false
+
+            [dead_code]
+            min_confidence = 90
+            entry_points = ["src/routes/**", "package.json"]
+            follow_type_imports = false
+            min_loc = 5
+            suspicious_dead_ratio = 0.95
+            detect_dead_branches = true
+            skip_generated = true
+
+            [dead_code.confidence_weights]
+            exported = 10
+
+            [[dead_code.confidence_overrides]]
+            path = "src/api/**"
+            penalty = 20
+
+            [[dead_code.confidence_overrides]]
+            path = "src/internal/**"
+            penalty = -10
+        "#).unwrap();
+
+        let config = load_config(root).unwrap();
+        assert_eq!(config.min_confidence(), Some(90));
+
+        let dead_code = config.dead_code_config(&[]);
+        assert_eq!(dead_code.exclude_patterns, vec!["vendor/**"]);
+        assert_eq!(dead_code.languages, vec!["typescript"]);
+        assert_eq!(dead_code.extension_overrides["es"], "javascript");
+        assert_eq!(dead_code.entry_point_patterns, vec!["src/routes/**", "package.json"]);
+        assert_eq!(dead_code.cache_mode, code_viz_dead_code::CacheMode::Off);
+        assert!(!dead_code.follow_type_imports);
+        assert_eq!(dead_code.min_loc, 5);
+        assert_eq!(dead_code.suspicious_dead_ratio, 0.95);
+        assert!(dead_code.detect_dead_branches);
+        assert!(dead_code.skip_generated);
+        assert_eq!(dead_code.confidence_weights.exported, 10);
+        assert_eq!(dead_code.confidence_weights.recently_modified, 20);
+        assert_eq!(
+            dead_code.confidence_overrides,
+            vec![("src/api/**".to_string(), 20), ("src/internal/**".to_string(), -10)]
+        );
+
+        // CLI patterns replace the file's
+        let dead_code = config.dead_code_config(&["dist/**".to_string()]);
+        assert_eq!(dead_code.exclude_patterns, vec!["dist/**"]);
+    }
+
+    #[test]
+    fn test_cache_mode_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join(".code-viz.toml"), "[cache]\nmode = \"read-only\"\n").unwrap();
+        let dead_code = load_config(root).unwrap().dead_code_config(&[]);
+        assert_eq!(dead_code.cache_mode, code_viz_dead_code::CacheMode::ReadOnly);
+
+        // Disabling the cache wins over a mode
+        fs::write(
+            root.join(".code-viz.toml"),
+            "[cache]\nenabled = false\nmode = \"read-only\"\n",
+        )
+        .unwrap();
+        let dead_code = load_config(root).unwrap().dead_code_config(&[]);
+        assert_eq!(dead_code.cache_mode, code_viz_dead_code::CacheMode::Off);
+    }
+
+    #[test]
+    fn test_load_missing_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load_config(temp_dir.path()).unwrap();
+        assert!(config.analysis.is_none());
+    }
+
+    #[test]
+    fn test_load_invalid_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        
+        let mut f = File::create(root.join(".code-viz.toml")).unwrap();
+        writeln!(f, "invalid toml [").unwrap();
+
+        let result = load_config(root);
+        assert!(result.is_err());
+        assert!(matches!(result.err().unwrap(), ConfigError::ParseFailed(_)));
+    }
+}
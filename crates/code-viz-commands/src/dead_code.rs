@@ -1,21 +1,143 @@
 use anyhow::Result;
+use code_viz_core::models::{AnalysisWarning, AnalysisWarningKind, FileMetrics};
 use code_viz_core::traits::{AppContext, FileSystem, GitProvider};
-use code_viz_dead_code::{analyze_dead_code, DeadCodeResult};
-use std::path::Path;
+use code_viz_dead_code::models::SymbolKind;
+use code_viz_dead_code::{
+    analyze_dead_code_with_progress, AnalysisConfig, AnalysisError, AnalysisSession, DeadCodeResult,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Orchestrate dead code analysis using trait-based dependencies.
 pub async fn calculate_dead_code(
     path: &Path,
-    _ctx: impl AppContext,
+    ctx: impl AppContext,
+    fs: impl FileSystem,
+    git: impl GitProvider,
+) -> Result<DeadCodeResult> {
+    calculate_dead_code_with_config(path, AnalysisConfig::default(), ctx, fs, git).await
+}
+
+/// Orchestrate dead code analysis with explicit settings (e.g. from `.code-viz.toml`).
+pub async fn calculate_dead_code_with_config(
+    path: &Path,
+    config: AnalysisConfig,
+    ctx: impl AppContext,
     _fs: impl FileSystem,
     _git: impl GitProvider,
 ) -> Result<DeadCodeResult> {
     // Note: code_viz_dead_code currently uses std::fs internally.
     // In a full refactor, we would make it use the FileSystem trait too.
     // For now, we wrap it to satisfy the trait-based command layer.
-    
-    let result = analyze_dead_code(path, None)
+    let path = path.to_path_buf();
+    run_with_progress(ctx, move |progress| {
+        analyze_dead_code_with_progress(&path, Some(config), None, progress)
+    })
+    .await
+}
+
+/// Dead code analysis of `path` with the settings and symbol graphs of
+/// `session`, so a repository that did not change is not parsed again
+pub async fn calculate_dead_code_in_session(
+    path: &Path,
+    session: &AnalysisSession,
+    ctx: impl AppContext,
+) -> Result<DeadCodeResult> {
+    let session = session.clone();
+    let path = path.to_path_buf();
+    run_with_progress(ctx, move |progress| {
+        session.analyze_with_progress(&path, None, progress)
+    })
+    .await
+}
+
+/// Run `analysis` on a blocking thread, forwarding its progress to `ctx`
+async fn run_with_progress<F>(ctx: impl AppContext, analysis: F) -> Result<DeadCodeResult>
+where
+    F: FnOnce(&dyn Fn(f32, &str)) -> Result<DeadCodeResult, AnalysisError> + Send + 'static,
+{
+    // The analysis is synchronous, so run it on a blocking thread and forward
+    // its progress to the (async) context as it arrives
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(f32, String)>();
+    let analysis = tokio::task::spawn_blocking(move || {
+        let progress = |fraction: f32, message: &str| {
+            let _ = tx.send((fraction, message.to_string()));
+        };
+        analysis(&progress)
+    });
+
+    while let Some((fraction, message)) = rx.recv().await {
+        ctx.report_progress(fraction, &message).await?;
+    }
+
+    let result = analysis
+        .await?
         .map_err(|e| anyhow::anyhow!("Dead code analysis failed: {}", e))?;
 
     Ok(result)
-}
\ No newline at end of file
+}
+
+/// Fill the per-file dead code fields of `files` from a dead code result.
+///
+/// Files without dead code are left untouched (their fields stay `None`).
+/// The export count of every file the graph covered is replaced by its
+/// exported symbols, which also see CommonJS and re-exports.
+pub fn merge_dead_code(files: &mut [FileMetrics], dead_code: &DeadCodeResult) {
+    let dead_code_by_file: HashMap<&PathBuf, _> = dead_code
+        .files
+        .iter()
+        .map(|file| (&file.path, file))
+        .collect();
+    let totals_by_file: HashMap<&PathBuf, _> = dead_code
+        .file_totals
+        .iter()
+        .map(|totals| (&totals.path, totals))
+        .collect();
+
+    for file in files.iter_mut() {
+        if let Some(totals) = totals_by_file.get(&file.path) {
+            file.export_count = totals.export_count;
+        }
+
+        let Some(dead) = dead_code_by_file.get(&file.path) else {
+            continue;
+        };
+
+        let dead_function_count = dead
+            .dead_code
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s.kind,
+                    SymbolKind::Function | SymbolKind::ArrowFunction | SymbolKind::Method
+                )
+            })
+            .count();
+        let dead_code_loc = dead.dead_loc();
+        let dead_code_ratio = if file.loc > 0 {
+            dead_code_loc as f64 / file.loc as f64
+        } else {
+            0.0
+        };
+
+        file.dead_function_count = Some(dead_function_count);
+        file.dead_code_loc = Some(dead_code_loc);
+        file.dead_code_ratio = Some(dead_code_ratio);
+    }
+}
+
+/// Add the warnings of a dead code result to those of a metrics analysis.
+///
+/// Skipped files are only reported once, since both analyses scan the same
+/// directory.
+pub fn merge_dead_code_warnings(warnings: &mut Vec<AnalysisWarning>, dead_code: &DeadCodeResult) {
+    for warning in &dead_code.warnings {
+        let reported = warnings.iter().any(|w| {
+            w == warning
+                || (w.kind == AnalysisWarningKind::SkippedFiles && w.kind == warning.kind)
+        });
+        if !reported {
+            warnings.push(warning.clone());
+        }
+    }
+}
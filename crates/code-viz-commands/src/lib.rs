@@ -1,7 +1,16 @@
 pub mod analyze;
+pub mod config;
 pub mod dead_code;
 pub mod export;
+pub mod pr_comment;
 
-pub use analyze::analyze_repository;
-pub use dead_code::calculate_dead_code;
+pub use analyze::{
+    analyze_repository, analyze_repository_with_callback, analyze_repository_with_dead_code,
+    analyze_repository_with_selection,
+};
+pub use dead_code::{
+    calculate_dead_code, calculate_dead_code_in_session, calculate_dead_code_with_config,
+    merge_dead_code, merge_dead_code_warnings,
+};
 pub use export::export_report;
+pub use pr_comment::generate_pr_comment;
@@ -0,0 +1,122 @@
+use code_viz_core::models::AnalysisResult;
+use code_viz_dead_code::DeadCodeResult;
+use std::fmt::Write;
+
+/// GitHub rejects comments longer than this many characters
+const MAX_COMMENT_CHARS: usize = 65_536;
+
+/// Rows shown in the largest files table
+const TOP_FILES: usize = 5;
+
+/// Render a compact Markdown summary for a pull request comment.
+///
+/// Shows a status emoji, total LOC, the dead code delta against `baseline`
+/// (the base branch's dead code result) and a table of the largest files.
+/// The status is 🔴 when dead code grew, 🟡 when any is left and 🟢 otherwise.
+/// Rows that do not fit in a GitHub comment are summarized in a
+/// "… N more" footer.
+pub fn generate_pr_comment(
+    metrics: &AnalysisResult,
+    dead: Option<&DeadCodeResult>,
+    baseline: Option<&DeadCodeResult>,
+) -> String {
+    let dead_loc = dead.map(|d| d.summary.total_dead_loc);
+    let dead_loc_delta = dead_loc
+        .zip(baseline)
+        .map(|(loc, base)| loc as isize - base.summary.total_dead_loc as isize);
+
+    let status = match (dead_loc, dead_loc_delta) {
+        (_, Some(delta)) if delta > 0 => "🔴",
+        (Some(loc), _) if loc > 0 => "🟡",
+        _ => "🟢",
+    };
+
+    let mut header = String::new();
+    writeln!(header, "### {} Code analysis", status).unwrap();
+    writeln!(header).unwrap();
+    writeln!(
+        header,
+        "**Files:** {} · **Total LOC:** {}",
+        metrics.summary.total_files, metrics.summary.total_loc
+    )
+    .unwrap();
+
+    if let Some(dead) = dead {
+        writeln!(
+            header,
+            "**Dead code:** {} LOC ({:.1}%), {} functions, {} classes, {} other",
+            dead.summary.total_dead_loc,
+            dead.summary.dead_code_ratio * 100.0,
+            dead.summary.dead_functions,
+            dead.summary.dead_classes,
+            dead.summary.dead_other
+        )
+        .unwrap();
+    }
+    if let (Some(delta), Some(dead), Some(base)) = (dead_loc_delta, dead, baseline) {
+        let symbols = dead_symbol_count(dead) as isize - dead_symbol_count(base) as isize;
+        writeln!(
+            header,
+            "**Dead code delta:** {:+} LOC ({:+} symbols) vs baseline",
+            delta, symbols
+        )
+        .unwrap();
+    }
+    for warning in dead.into_iter().flat_map(|d| &d.warnings) {
+        writeln!(header, "\n> ⚠️ {}", warning.message).unwrap();
+    }
+
+    let mut files: Vec<_> = metrics.files.iter().collect();
+    files.sort_by(|a, b| b.loc.cmp(&a.loc).then_with(|| a.path.cmp(&b.path)));
+
+    let rows: Vec<String> = files
+        .iter()
+        .map(|file| {
+            let path = file.path.display().to_string().replace('|', "\\|");
+            match dead {
+                Some(_) => format!(
+                    "| `{}` | {} | {} |",
+                    path,
+                    file.loc,
+                    file.dead_code_loc.unwrap_or(0)
+                ),
+                None => format!("| `{}` | {} |", path, file.loc),
+            }
+        })
+        .collect();
+    let table_header = match dead {
+        Some(_) => "| File | LOC | Dead LOC |\n|---|---:|---:|\n",
+        None => "| File | LOC |\n|---|---:|\n",
+    };
+
+    // Drop rows from the end until the comment fits
+    let mut shown = rows.len().min(TOP_FILES);
+    loop {
+        let comment = render(&header, table_header, &rows[..shown], rows.len() - shown);
+        if comment.len() <= MAX_COMMENT_CHARS || shown == 0 {
+            return comment;
+        }
+        shown -= 1;
+    }
+}
+
+fn render(header: &str, table_header: &str, rows: &[String], hidden: usize) -> String {
+    let mut comment = header.to_string();
+    if rows.is_empty() && hidden == 0 {
+        return comment;
+    }
+
+    writeln!(comment, "\n#### Largest files\n").unwrap();
+    comment.push_str(table_header);
+    for row in rows {
+        writeln!(comment, "{}", row).unwrap();
+    }
+    if hidden > 0 {
+        writeln!(comment, "\n… {} more", hidden).unwrap();
+    }
+    comment
+}
+
+fn dead_symbol_count(result: &DeadCodeResult) -> usize {
+    result.files.iter().map(|f| f.dead_code.len()).sum()
+}
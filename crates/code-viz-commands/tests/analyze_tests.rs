@@ -1,5 +1,11 @@
-use code_viz_commands::analyze_repository;
-use code_viz_core::mocks::{MockContext, MockFileSystem};
+use code_viz_commands::{
+    analyze_repository, analyze_repository_with_callback, analyze_repository_with_dead_code,
+    analyze_repository_with_selection, calculate_dead_code_in_session,
+};
+use code_viz_core::context::RealFileSystem;
+use code_viz_core::mocks::{MockContext, MockFileSystem, MockGit};
+use code_viz_core::scanner::{FileSelection, ScanOptions};
+use code_viz_dead_code::{AnalysisConfig, AnalysisSession, CacheMode};
 use std::path::Path;
 
 #[tokio::test]
@@ -34,6 +40,25 @@ async fn test_analyze_repository_success() {
     assert_eq!(last_progress["message"], "Analysis complete");
 }
 
+#[tokio::test]
+async fn test_analyze_repository_with_callback_reports_each_file() {
+    let fs = MockFileSystem::new()
+        .with_file("src/main.rs", "fn main() {}")
+        .with_file("src/lib.rs", "pub fn add(a: i32, b: i32) -> i32 { a + b }")
+        .with_file("src/notes.md", "# Notes");
+
+    let mut streamed = Vec::new();
+    let result = analyze_repository_with_callback(Path::new("src"), MockContext::new(), fs, |file| {
+        streamed.push(file.path.clone())
+    })
+    .await
+    .unwrap();
+
+    let analyzed: Vec<_> = result.files.iter().map(|f| f.path.clone()).collect();
+    assert_eq!(streamed, analyzed);
+    assert_eq!(streamed.len(), 2);
+}
+
 #[tokio::test]
 async fn test_analyze_repository_empty_dir() {
     let ctx = MockContext::new();
@@ -46,6 +71,20 @@ async fn test_analyze_repository_empty_dir() {
     ctx.assert_event_emitted("analysis_complete");
 }
 
+#[tokio::test]
+async fn test_analyze_repository_only_unsupported_files() {
+    let ctx = MockContext::new();
+    let fs = MockFileSystem::new()
+        .with_file("docs/notes.txt", "not code")
+        .with_file("docs/todo.txt", "nor this");
+
+    let error = analyze_repository(Path::new("docs"), ctx, fs).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "No supported source files in docs (2 files of other types)"
+    );
+}
+
 #[tokio::test]
 async fn test_analyze_repository_error_handling() {
     let ctx = MockContext::new();
@@ -60,3 +99,169 @@ async fn test_analyze_repository_error_handling() {
     
     assert!(result.is_ok()); // Should return empty AnalysisResult for empty/non-existent dir in mock
 }
+
+#[tokio::test]
+async fn test_analyze_repository_counts_plain_text_lines() {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::fs::write(temp.path().join("main.ts"), "function main() {}\n").unwrap();
+    std::fs::write(
+        temp.path().join("schema.sql"),
+        "-- Users table\nCREATE TABLE users (\n    id INTEGER PRIMARY KEY\n);\n\nCREATE INDEX users_id ON users (id);\n",
+    )
+    .unwrap();
+
+    let result = analyze_repository(temp.path(), MockContext::new(), RealFileSystem::new())
+        .await
+        .unwrap();
+
+    let schema = result.files.iter().find(|f| f.path.ends_with("schema.sql")).unwrap();
+    assert_eq!(schema.language, "text");
+    assert_eq!(schema.loc, 5);
+    assert_eq!(schema.function_count, 0);
+    assert_eq!(result.summary.total_files, 2);
+    assert_eq!(result.summary.total_loc, 6);
+    assert_eq!(result.summary.total_functions, 1);
+}
+
+#[tokio::test]
+async fn test_analyze_repository_with_selection_skips_unselected_files() {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(temp.path().join("src")).unwrap();
+    std::fs::write(temp.path().join("src/main.ts"), "function main() {}\n").unwrap();
+    std::fs::write(temp.path().join("src/tool.py"), "def tool():\n    pass\n").unwrap();
+    std::fs::write(temp.path().join("build.ts"), "function build() {}\n").unwrap();
+
+    let options = ScanOptions {
+        include_patterns: vec!["src/**".to_string()],
+        languages: vec!["typescript".to_string()],
+        ..Default::default()
+    };
+    let selection = FileSelection::new(temp.path(), &options).unwrap();
+    let result = analyze_repository_with_selection(
+        temp.path(),
+        &selection,
+        MockContext::new(),
+        RealFileSystem::new(),
+    )
+    .await
+    .unwrap();
+
+    let paths: Vec<_> = result.files.iter().map(|f| f.path.clone()).collect();
+    assert_eq!(paths, vec![temp.path().join("src/main.ts")]);
+    assert_eq!(result.summary.total_files, 1);
+}
+
+#[tokio::test]
+async fn test_analyze_repository_with_dead_code_populates_file_ratios() {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp.path().join("main.ts"),
+        "function main() {\n    console.log('hi');\n}\n\nmain();\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp.path().join("util.ts"),
+        "function unused() {\n    return 1;\n}\n",
+    )
+    .unwrap();
+
+    let config = AnalysisConfig {
+        cache_mode: CacheMode::Off,
+        ..Default::default()
+    };
+    let result = analyze_repository_with_dead_code(
+        temp.path(),
+        config,
+        MockContext::new(),
+        RealFileSystem::new(),
+        MockGit::new(),
+    )
+    .await
+    .unwrap();
+
+    let util = result.files.iter().find(|f| f.path.ends_with("util.ts")).unwrap();
+    assert_eq!(util.dead_function_count, Some(1));
+    assert_eq!(util.dead_code_loc, Some(3));
+    assert_eq!(util.dead_code_ratio, Some(1.0));
+
+    // Files without dead code are left untouched
+    let main = result.files.iter().find(|f| f.path.ends_with("main.ts")).unwrap();
+    assert_eq!(main.dead_code_ratio, None);
+}
+
+#[tokio::test]
+async fn test_analyze_repository_with_dead_code_counts_exported_symbols() {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp.path().join("main.ts"),
+        "import { a } from './index';\n\nfunction main() {\n    a();\n}\n\nmain();\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp.path().join("util.ts"),
+        "export function a() {\n    return 1;\n}\n\nexport function b() {\n    return 2;\n}\n",
+    )
+    .unwrap();
+    std::fs::write(temp.path().join("index.ts"), "export * from './util';\n").unwrap();
+    std::fs::write(
+        temp.path().join("legacy.js"),
+        "function c() {\n    return 3;\n}\n\nmodule.exports = { c };\n",
+    )
+    .unwrap();
+
+    let config = AnalysisConfig {
+        cache_mode: CacheMode::Off,
+        ..Default::default()
+    };
+    let result = analyze_repository_with_dead_code(
+        temp.path(),
+        config,
+        MockContext::new(),
+        RealFileSystem::new(),
+        MockGit::new(),
+    )
+    .await
+    .unwrap();
+
+    let export_count = |name: &str| {
+        let file = result.files.iter().find(|f| f.path.ends_with(name));
+        file.unwrap().export_count
+    };
+    assert_eq!(export_count("util.ts"), 2);
+    // A wildcard re-export names nothing itself, as in the count without dead code
+    assert_eq!(export_count("index.ts"), 0);
+    assert_eq!(export_count("legacy.js"), 1);
+}
+
+#[tokio::test]
+async fn test_session_reuses_symbol_graph_across_analyses() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let src = temp.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(
+        src.join("index.ts"),
+        "import { used } from './util';\n\nexport function main() {\n    used();\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        src.join("util.ts"),
+        "export function used() {}\n\nfunction unused() {\n    return 1;\n}\n",
+    )
+    .unwrap();
+
+    let session = AnalysisSession::new(AnalysisConfig::default());
+
+    let first = calculate_dead_code_in_session(&src, &session, MockContext::new())
+        .await
+        .unwrap();
+    assert_eq!(session.rebuilds(), 1);
+
+    // The second analysis, through a clone as a server handler would hold it,
+    // reuses the session's graph
+    let second = calculate_dead_code_in_session(&src, &session.clone(), MockContext::new())
+        .await
+        .unwrap();
+    assert_eq!(session.rebuilds(), 1);
+    assert_eq!(first.summary.dead_functions, 1);
+    assert_eq!(second.summary.dead_functions, 1);
+}
@@ -0,0 +1,78 @@
+use code_viz_commands::generate_pr_comment;
+use code_viz_core::models::{AnalysisResult, FileMetrics, Summary};
+use code_viz_dead_code::fixtures::{dead_code_result, dead_symbol};
+use code_viz_dead_code::DeadCodeResult;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+fn file(path: &str, loc: usize, dead_code_loc: usize) -> FileMetrics {
+    FileMetrics {
+        path: PathBuf::from(path),
+        language: "typescript".to_string(),
+        loc,
+        size_bytes: 0,
+        function_count: 0,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
+        functions: vec![],
+        last_modified: SystemTime::UNIX_EPOCH,
+        dead_function_count: Some(0),
+        dead_code_loc: Some(dead_code_loc),
+        dead_code_ratio: None,
+    }
+}
+
+fn metrics() -> AnalysisResult {
+    let files: Vec<FileMetrics> = (1..=7)
+        .map(|i| file(&format!("src/file{}.ts", i), i * 100, i))
+        .collect();
+    AnalysisResult {
+        schema_version: code_viz_core::report::current_schema_version(),
+        summary: Summary {
+            total_files: files.len(),
+            total_loc: files.iter().map(|f| f.loc).sum(),
+            total_functions: 0,
+            largest_files: vec![],
+            largest_functions: vec![],
+        },
+        files,
+        timestamp: SystemTime::UNIX_EPOCH,
+        skipped_files: 0,
+        warnings: vec![],
+        commit_sha: None,
+    }
+}
+
+fn dead_code(symbols: usize, loc_each: usize) -> DeadCodeResult {
+    let dead_code = (0..symbols)
+        .map(|i| dead_symbol(&format!("unused{}", i), i * 10 + 1, loc_each, 100))
+        .collect();
+    dead_code_result(vec![("src/file1.ts", dead_code)])
+}
+
+#[test]
+fn test_pr_comment_with_baseline() {
+    let comment = generate_pr_comment(&metrics(), Some(&dead_code(3, 10)), Some(&dead_code(1, 10)));
+
+    assert!(comment.starts_with("### 🔴 Code analysis"));
+    assert!(comment.contains("**Total LOC:** 2800"));
+    assert!(comment.contains("**Dead code delta:** +20 LOC (+2 symbols) vs baseline"));
+
+    // Top 5 files by LOC, the rest summarized
+    assert!(comment.contains("| File | LOC | Dead LOC |"));
+    assert!(comment.contains("| `src/file7.ts` | 700 | 7 |"));
+    assert!(comment.contains("| `src/file3.ts` | 300 | 3 |"));
+    assert!(!comment.contains("src/file2.ts"));
+    assert!(comment.ends_with("… 2 more\n"));
+}
+
+#[test]
+fn test_pr_comment_without_dead_code() {
+    let comment = generate_pr_comment(&metrics(), None, None);
+
+    assert!(comment.starts_with("### 🟢 Code analysis"));
+    assert!(!comment.contains("Dead code"));
+    assert!(comment.contains("| File | LOC |\n"));
+}
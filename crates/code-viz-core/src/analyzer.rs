@@ -1,8 +1,9 @@
-use crate::models::{Summary, FileMetrics};
+use crate::models::{Summary, FileMetrics, FunctionMetrics};
 use crate::scanner::ScanError;
 use crate::metrics::{self, MetricsError};
 use crate::cache::CacheError;
 use crate::parser;
+use crate::sfc;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use crate::traits::FileSystem;
@@ -21,6 +22,8 @@ pub fn process_file_with_fs(path: &Path, fs: &impl FileSystem) -> Result<FileMet
         "tsx" => "tsx",
         "js" => "javascript",
         "jsx" => "javascript",
+        // Component scripts are parsed as TypeScript, a superset of JavaScript
+        "vue" | "svelte" => "typescript",
         "rs" => "rust",
         "py" => "python",
         "go" => "go",
@@ -28,15 +31,18 @@ pub fn process_file_with_fs(path: &Path, fs: &impl FileSystem) -> Result<FileMet
         ext => ext,
     };
 
+    let source = fs.read_to_string(path)
+        .map_err(|e| AnalysisError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+    let source = if sfc::is_component(path) { sfc::script_source(&source) } else { source };
+    tracing::debug!(source_size = source.len(), "File read successfully");
+
+    // The extension can be ambiguous, e.g. JSX in a .ts file
+    let language_key = parser::detect_dialect(language_key, &source);
     tracing::debug!(extension = %extension, language = %language_key, "Language detected");
 
     let parser = parser::get_parser(language_key)
         .map_err(|e| AnalysisError::ParseFailed { path: path.to_path_buf(), source: e })?;
 
-    let source = fs.read_to_string(path)
-        .map_err(|e| AnalysisError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-    tracing::debug!(source_size = source.len(), "File read successfully");
-
     let metrics = metrics::calculate_metrics(path, &source, parser.as_ref(), None)
         .map_err(AnalysisError::MetricsFailed)?;
 
@@ -72,11 +78,19 @@ pub fn calculate_summary(files: &[FileMetrics]) -> Summary {
 
     tracing::debug!(largest_files_count = largest_files.len(), "Identified largest files");
 
+    // Find top 10 largest functions across all files
+    let mut functions: Vec<&FunctionMetrics> = files.iter().flat_map(|f| &f.functions).collect();
+    functions.sort_by_key(|f| std::cmp::Reverse(f.loc));
+
+    let largest_functions: Vec<FunctionMetrics> =
+        functions.into_iter().take(10).cloned().collect();
+
     Summary {
         total_files,
         total_loc,
         total_functions,
         largest_files,
+        largest_functions,
     }
 }
 
@@ -101,3 +115,34 @@ pub enum AnalysisError {
     IoError(#[from] std::io::Error),
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::MockFileSystem;
+
+    #[test]
+    fn test_largest_function_is_ranked_first() {
+        let fs = MockFileSystem::new()
+            .with_file("small.ts", "function tiny() {\n    return 1;\n}\n")
+            .with_file(
+                "big.ts",
+                "function short() {}\n\nfunction long(a: number) {\n    if (a > 0) {\n        a++;\n    }\n    a--;\n    a--;\n    return a;\n}\n",
+            );
+
+        let files: Vec<FileMetrics> = ["small.ts", "big.ts"]
+            .iter()
+            .map(|path| process_file_with_fs(Path::new(path), &fs).unwrap())
+            .collect();
+        let summary = calculate_summary(&files);
+
+        let ranked: Vec<(&str, usize)> = summary
+            .largest_functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.loc))
+            .collect();
+        assert_eq!(ranked, vec![("long", 8), ("tiny", 3), ("short", 1)]);
+        assert_eq!(summary.largest_functions[0].path, PathBuf::from("big.ts"));
+        assert_eq!(summary.largest_functions[0].complexity, 2);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod real_filesystem;
 pub mod real_git;
+pub mod retrying_git;
 
 pub use real_filesystem::RealFileSystem;
 pub use real_git::RealGit;
+pub use retrying_git::{retry_git, RetryPolicy, RetryingGit};
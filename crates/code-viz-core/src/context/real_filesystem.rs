@@ -1,17 +1,47 @@
 use anyhow::{Context, Result};
 use crate::traits::FileSystem;
-use crate::scanner::scan_directory;
+use crate::parser::PLAIN_TEXT_EXTENSIONS;
+use crate::scanner::{scan_with_options, ScanOptions, ScanResult};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Production implementation of FileSystem that delegates to std::fs and walkdir.
-#[derive(Clone, Copy)]
-pub struct RealFileSystem;
+#[derive(Clone)]
+pub struct RealFileSystem {
+    /// Deepest directory level scanned by `read_dir_recursive` (unlimited if `None`)
+    max_depth: Option<usize>,
+    /// Extensions scanned and analyzed as plain text, see `ScanOptions::plain_text_extensions`
+    plain_text_extensions: Vec<String>,
+}
 
 impl RealFileSystem {
     /// Create a new RealFileSystem instance.
     pub fn new() -> Self {
-        Self
+        Self {
+            max_depth: None,
+            plain_text_extensions: PLAIN_TEXT_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+        }
+    }
+
+    /// Limit directory scans to `max_depth` levels, see `ScanOptions::max_depth`.
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Analyze files with these extensions as plain text instead of the
+    /// defaults in `PLAIN_TEXT_EXTENSIONS`.
+    pub fn with_plain_text_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.plain_text_extensions = extensions;
+        self
+    }
+
+    fn scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            max_depth: self.max_depth,
+            plain_text_extensions: self.plain_text_extensions.clone(),
+            ..Default::default()
+        }
     }
 }
 
@@ -22,9 +52,15 @@ impl FileSystem for RealFileSystem {
     }
 
     fn read_dir_recursive(&self, path: &Path) -> Result<Vec<PathBuf>> {
-        // Use scan_directory which respects .gitignore files
-        // No additional exclude patterns (empty array)
-        scan_directory(path, &[])
+        // Use the scanner which respects .gitignore files
+        // No additional exclude patterns
+        scan_with_options(path, &self.scan_options())
+            .map(|result| result.files)
+            .map_err(|e| anyhow::anyhow!("Failed to scan directory: {}", e))
+    }
+
+    fn read_dir_recursive_detailed(&self, path: &Path) -> Result<ScanResult> {
+        scan_with_options(path, &self.scan_options())
             .map_err(|e| anyhow::anyhow!("Failed to scan directory: {}", e))
     }
 
@@ -1,7 +1,8 @@
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use crate::traits::{Commit, Diff, BlameInfo, GitProvider};
-use git2::Repository;
+use git2::build::RepoBuilder;
+use git2::{FetchOptions, Repository};
 use std::path::Path;
 use tokio::task;
 
@@ -15,6 +16,23 @@ impl RealGit {
     pub fn new() -> Self {
         Self
     }
+
+    /// Shallow-clone `url` into `dest`, returning the SHA of the checked-out commit.
+    pub fn clone_shallow(&self, url: &str, dest: &Path) -> Result<String> {
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.depth(1);
+
+        let repo = RepoBuilder::new()
+            .fetch_options(fetch_options)
+            .clone(url, dest)
+            .with_context(|| format!("Failed to clone {}", url))?;
+
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .with_context(|| format!("Failed to resolve HEAD of {}", url))?;
+        Ok(head.id().to_string())
+    }
 }
 
 #[async_trait]
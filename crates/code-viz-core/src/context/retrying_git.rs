@@ -0,0 +1,189 @@
+use crate::traits::{BlameInfo, Commit, Diff, GitProvider};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::Path;
+use std::time::Duration;
+
+/// How often and how patiently transient git errors are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first one
+    pub attempts: u32,
+    /// Wait before the first retry; doubled before each further retry
+    pub initial_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_delay: Duration::from_millis(50),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (0 for the first retry).
+    fn delay(&self, retry: u32) -> Duration {
+        self.initial_delay.saturating_mul(1 << retry.min(16))
+    }
+}
+
+/// Whether a git2 error may go away on its own, such as another process
+/// holding the index lock.
+///
+/// A missing repository, an unborn HEAD or a missing object are answers,
+/// not failures: retrying them cannot succeed.
+pub fn is_transient(err: &git2::Error) -> bool {
+    err.code() == git2::ErrorCode::Locked
+}
+
+/// Whether an error returned by a `GitProvider` wraps a transient git2 error.
+pub fn is_transient_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<git2::Error>()
+            .is_some_and(is_transient)
+    })
+}
+
+/// Run a blocking git operation, retrying transient errors with backoff.
+///
+/// Other errors, and the last transient one, are returned as they are.
+pub fn retry_git<T>(
+    policy: RetryPolicy,
+    mut operation: impl FnMut() -> std::result::Result<T, git2::Error>,
+) -> std::result::Result<T, git2::Error> {
+    let mut retry = 0;
+    loop {
+        match operation() {
+            Err(err) if is_transient(&err) && retry + 1 < policy.attempts => {
+                tracing::debug!(error = %err, retry, "Transient git error, retrying");
+                std::thread::sleep(policy.delay(retry));
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// GitProvider wrapper that retries transient errors of the wrapped provider
+/// with backoff, so a briefly held lock does not read as a failure.
+#[derive(Clone)]
+pub struct RetryingGit<G> {
+    inner: G,
+    policy: RetryPolicy,
+}
+
+impl<G: GitProvider> RetryingGit<G> {
+    /// Wrap `inner` with the default retry policy.
+    pub fn new(inner: G) -> Self {
+        Self::with_policy(inner, RetryPolicy::default())
+    }
+
+    /// Wrap `inner`, retrying as configured by `policy`.
+    pub fn with_policy(inner: G, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    async fn retry<'a, T, F, Fut>(&'a self, operation: F) -> Result<T>
+    where
+        F: Fn(&'a G) -> Fut,
+        Fut: std::future::Future<Output = Result<T>> + 'a,
+    {
+        let mut retry = 0;
+        loop {
+            match operation(&self.inner).await {
+                Err(err) if is_transient_error(&err) && retry + 1 < self.policy.attempts => {
+                    tracing::debug!(error = %err, retry, "Transient git error, retrying");
+                    tokio::time::sleep(self.policy.delay(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<G: GitProvider> GitProvider for RetryingGit<G> {
+    async fn get_history(&self, path: &Path) -> Result<Vec<Commit>> {
+        self.retry(|git| git.get_history(path)).await
+    }
+
+    async fn get_diff(&self, path: &Path, from: Option<&str>, to: &str) -> Result<Diff> {
+        self.retry(|git| git.get_diff(path, from, to)).await
+    }
+
+    async fn get_blame(&self, file_path: &Path) -> Result<BlameInfo> {
+        self.retry(|git| git.get_blame(file_path)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::MockGit;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            attempts: 3,
+            initial_delay: Duration::from_millis(1),
+        }
+    }
+
+    fn locked() -> git2::Error {
+        git2::Error::new(
+            git2::ErrorCode::Locked,
+            git2::ErrorClass::Index,
+            "index is locked",
+        )
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_retrying_git_recovers_from_transient_failure() {
+        let mock = MockGit::new()
+            .add_commit("abc123", "Dev", "change")
+            .with_history_failures(vec![locked()]);
+        let git = RetryingGit::with_policy(mock.clone(), policy());
+
+        let history = block_on(git.get_history(Path::new("."))).unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(mock.history_calls(), 2);
+    }
+
+    #[test]
+    fn test_retrying_git_does_not_retry_missing_repository() {
+        let not_found = git2::Error::new(
+            git2::ErrorCode::NotFound,
+            git2::ErrorClass::Repository,
+            "no repo",
+        );
+        let mock = MockGit::new().with_history_failures(vec![not_found]);
+        let git = RetryingGit::with_policy(mock.clone(), policy());
+
+        assert!(block_on(git.get_history(Path::new("."))).is_err());
+        assert_eq!(mock.history_calls(), 1);
+    }
+
+    #[test]
+    fn test_retry_git_gives_up_after_last_attempt() {
+        let mut calls = 0;
+        let result: std::result::Result<(), _> = retry_git(policy(), || {
+            calls += 1;
+            Err(locked())
+        });
+
+        assert!(is_transient(&result.unwrap_err()));
+        assert_eq!(calls, 3);
+    }
+}
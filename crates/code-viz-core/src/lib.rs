@@ -1,14 +1,24 @@
 #![allow(dead_code)]
 
+// Parsing needs the C grammars and the contexts need libgit2, so browser
+// (wasm32) builds only get the models, reports and scanner types
+#[cfg(not(target_arch = "wasm32"))]
 pub mod analyzer;
 pub mod cache;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod metrics;
 pub mod models;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod parser;
+pub mod report;
 pub mod scanner;
+pub mod sfc;
 pub mod traits;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod mocks;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod context;
 
+#[cfg(not(target_arch = "wasm32"))]
 pub use analyzer::calculate_summary;
-pub use models::*;
\ No newline at end of file
+pub use models::*;
@@ -1,5 +1,6 @@
-use crate::models::FileMetrics;
-use crate::parser::LanguageParser;
+use crate::models::{comment_ratio, FileMetrics, FunctionMetrics};
+use crate::parser::{LanguageParser, ParseError};
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::time::SystemTime;
 use thiserror::Error;
@@ -10,11 +11,35 @@ pub fn calculate_metrics(
     parser: &dyn LanguageParser,
     last_modified: Option<SystemTime>,
 ) -> Result<FileMetrics, MetricsError> {
-    let tree = parser.parse(source).map_err(MetricsError::ParseFailed)?;
-    let function_count = parser.count_functions(&tree);
-    let comment_ranges = parser.find_comment_ranges(&tree);
+    // Without a grammar only the lines are counted
+    let tree = match parser.parse(source) {
+        Ok(tree) => Some(tree),
+        Err(ParseError::NoGrammar(_)) => None,
+        Err(e) => return Err(MetricsError::ParseFailed(e)),
+    };
+    let function_count = tree.as_ref().map_or(0, |tree| parser.count_functions(tree));
+    let max_nesting_depth = tree.as_ref().map_or(0, |tree| parser.max_nesting_depth(tree));
+    let export_count = tree.as_ref().map_or(0, |tree| parser.count_exports(tree, source));
+    let comment_ranges = tree
+        .as_ref()
+        .map(|tree| parser.find_comment_ranges(tree))
+        .unwrap_or_default();
 
     let loc = calculate_loc(source, &comment_ranges);
+    let comment_lines = count_comment_lines(source, loc);
+    let comment_ratio = comment_ratio(comment_lines, loc);
+    let functions = tree
+        .as_ref()
+        .map(|tree| parser.find_functions(tree, source))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|function| FunctionMetrics {
+            path: path.to_path_buf(),
+            loc: count_code_lines(source, function.start_row..=function.end_row, &comment_ranges),
+            name: function.name,
+            complexity: function.complexity,
+        })
+        .collect();
     let size_bytes = source.len() as u64;
 
     // Use provided last_modified or fallback to now()
@@ -26,6 +51,11 @@ pub fn calculate_metrics(
         loc,
         size_bytes,
         function_count,
+        max_nesting_depth,
+        comment_lines,
+        comment_ratio,
+        export_count,
+        functions,
         last_modified,
         dead_function_count: None,
         dead_code_loc: None,
@@ -34,9 +64,30 @@ pub fn calculate_metrics(
 }
 
 fn calculate_loc(source: &str, comment_ranges: &[tree_sitter::Range]) -> usize {
+    count_code_lines(source, 0..=usize::MAX, comment_ranges)
+}
+
+/// Non-blank lines that are only comments, given the file's `loc`
+///
+/// Every non-blank line without code is inside a comment, so those are the
+/// lines counted.
+fn count_comment_lines(source: &str, loc: usize) -> usize {
+    let total = source.lines().filter(|line| !line.trim().is_empty()).count();
+    total.saturating_sub(loc)
+}
+
+/// Lines of code within `rows` (0-based, inclusive)
+fn count_code_lines(
+    source: &str,
+    rows: RangeInclusive<usize>,
+    comment_ranges: &[tree_sitter::Range],
+) -> usize {
     let mut loc = 0;
     
-    for (i, line) in source.lines().enumerate() {
+    for (i, line) in source.lines().enumerate().skip(*rows.start()) {
+        if i > *rows.end() {
+            break;
+        }
         if line.trim().is_empty() {
             continue;
         }
@@ -287,6 +338,93 @@ fn main() {
         let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
         assert_eq!(metrics.function_count, 2);
     }
+
+    #[test]
+    fn test_max_nesting_depth_is_deepest_function() {
+        let parser = get_parser("typescript").unwrap();
+        let source = "function flat() { return 1; }\nfunction nested(a) { if (a) { while (a) { a--; } } }";
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.ts");
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.max_nesting_depth, 2);
+    }
+
+    #[test]
+    fn test_export_count() {
+        let parser = get_parser("typescript").unwrap();
+        let source = "export function a() {}\nexport const b = 1;\nexport class C {}\nfunction internal() {}\nexport * from './other';\n";
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.ts");
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.export_count, 3);
+
+        // Declarators, specifiers and default exports count one each
+        let source = "export const d = 1, e = 2;\nconst f = 3;\nexport { f as g };\nexport default f;\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.export_count, 4);
+
+        // `export =` counts once
+        let source = "class Client {}\nexport = Client;\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.export_count, 1);
+    }
+
+    #[test]
+    fn test_export_count_commonjs() {
+        let parser = get_parser("javascript").unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.js");
+
+        // Each property of an object assigned to module.exports counts
+        let source = "function a() {}\nconst b = 1;\nmodule.exports = { a, c: b, d() {} };\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.export_count, 3);
+
+        // Named assignments count one each; other assignments none
+        let source = "exports.a = 1;\nmodule.exports.b = () => 2;\nconfig.c = 3;\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.export_count, 2);
+
+        let source = "class Client {}\nmodule.exports = Client;\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.export_count, 1);
+    }
+
+    #[test]
+    fn test_comment_ratio() {
+        let parser = get_parser("typescript").unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.ts");
+
+        let source = "// Adds one\n/* to x */\nfunction inc(x) {\n    return x + 1; // result\n}\n\n// end\nexport { inc };\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.loc, 4);
+        assert_eq!(metrics.comment_lines, 3);
+        assert!((metrics.comment_ratio - 3.0 / 7.0).abs() < 1e-9);
+
+        let source = "// one\nconst a = 1;\n/*\n * two\n */\nconst b = 2;\nconst c = 3;\nconst d = 4;\n";
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+        assert!((metrics.comment_ratio - 0.5).abs() < 1e-9);
+
+        // No lines at all is a ratio of 0, not NaN
+        let metrics = calculate_metrics(&path, "\n\n", parser.as_ref(), None).unwrap();
+        assert_eq!(metrics.comment_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_function_loc_excludes_comments() {
+        let parser = get_parser("typescript").unwrap();
+        let source = "function a() {\n    // note\n\n    return 1;\n}\nfunction b() {}\n";
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.ts");
+        let metrics = calculate_metrics(&path, source, parser.as_ref(), None).unwrap();
+
+        assert_eq!(metrics.functions.len(), 2);
+        assert_eq!(metrics.functions[0].name, "a");
+        assert_eq!(metrics.functions[0].loc, 3);
+        assert_eq!(metrics.functions[1].loc, 1);
+        assert_eq!(metrics.functions[1].path, path);
+    }
     
     #[test]
     fn test_file_metadata_defaults_to_now() {
@@ -317,4 +455,18 @@ fn main() {
 
         assert_eq!(metrics.last_modified, provided_time);
     }
+
+    #[test]
+    fn test_plain_text_counts_lines_only() {
+        let parser = crate::parser::PlainTextParser;
+        let source = "SELECT 1;\n\n-- note\nSELECT 2;\n";
+        assert!(matches!(parser.parse(source), Err(ParseError::NoGrammar(_))));
+
+        let metrics = calculate_metrics(Path::new("query.sql"), source, &parser, None).unwrap();
+        assert_eq!(metrics.language, "text");
+        assert_eq!(metrics.loc, 3);
+        assert_eq!(metrics.function_count, 0);
+        assert_eq!(metrics.comment_lines, 0);
+        assert!(metrics.functions.is_empty());
+    }
 }
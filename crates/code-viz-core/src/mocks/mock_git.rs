@@ -1,7 +1,9 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use crate::traits::{Commit, Diff, BlameInfo, GitProvider};
+use std::collections::VecDeque;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Mock implementation of GitProvider for unit testing.
@@ -10,6 +12,8 @@ use std::sync::{Arc, Mutex};
 pub struct MockGit {
     commits: Arc<Mutex<Vec<Commit>>>,
     diffs: Arc<Mutex<Vec<(String, Option<String>, String)>>>,
+    history_failures: Arc<Mutex<VecDeque<git2::Error>>>,
+    history_calls: Arc<AtomicUsize>,
 }
 
 impl MockGit {
@@ -18,6 +22,8 @@ impl MockGit {
         Self {
             commits: Arc::new(Mutex::new(Vec::new())),
             diffs: Arc::new(Mutex::new(Vec::new())),
+            history_failures: Arc::new(Mutex::new(VecDeque::new())),
+            history_calls: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -42,11 +48,27 @@ impl MockGit {
             message: message.to_string(),
         })
     }
+
+    /// Fail the next calls to `get_history` with these errors, in order,
+    /// before returning the history again.
+    pub fn with_history_failures(self, errors: Vec<git2::Error>) -> Self {
+        self.history_failures.lock().unwrap().extend(errors);
+        self
+    }
+
+    /// Number of `get_history` calls so far, failed ones included.
+    pub fn history_calls(&self) -> usize {
+        self.history_calls.load(Ordering::SeqCst)
+    }
 }
 
 #[async_trait]
 impl GitProvider for MockGit {
     async fn get_history(&self, _path: &Path) -> Result<Vec<Commit>> {
+        self.history_calls.fetch_add(1, Ordering::SeqCst);
+        if let Some(err) = self.history_failures.lock().unwrap().pop_front() {
+            return Err(err.into());
+        }
         Ok(self.commits.lock().unwrap().clone())
     }
 
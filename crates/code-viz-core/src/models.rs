@@ -1,5 +1,6 @@
+use crate::scanner::{SkipReason, SkippedFile};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -19,24 +20,64 @@ pub struct FileMetrics {
     /// Number of functions/methods
     pub function_count: usize,
 
+    /// Deepest control-flow nesting of any function in the file
+    #[serde(default)]
+    pub max_nesting_depth: usize,
+
+    /// Non-blank lines that are only comments
+    #[serde(default)]
+    pub comment_lines: usize,
+
+    /// Share of non-blank lines that are only comments (0.0 to 1.0)
+    #[serde(default)]
+    pub comment_ratio: f64,
+
+    /// Number of exported symbols, the size of the file's public API
+    #[serde(default)]
+    pub export_count: usize,
+
+    /// Per-function metrics, ranked into `Summary::largest_functions`
+    /// (not serialized, so reports keep one entry per file)
+    #[serde(skip)]
+    pub functions: Vec<FunctionMetrics>,
+
     /// Last modified timestamp (for cache invalidation)
     pub last_modified: SystemTime,
 
     /// Number of dead functions (only present when dead code analysis enabled)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dead_function_count: Option<usize>,
 
     /// Lines of dead code (only present when dead code analysis enabled)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dead_code_loc: Option<usize>,
 
     /// Ratio of dead code to total code (only present when dead code analysis enabled)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dead_code_ratio: Option<f64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FunctionMetrics {
+    /// Relative path of the file declaring the function
+    pub path: PathBuf,
+
+    /// Function name (`<anonymous>` for unnamed closures)
+    pub name: String,
+
+    /// Lines of code (excluding comments and blank lines)
+    pub loc: usize,
+
+    /// 1 plus the number of control-flow statements in the function
+    pub complexity: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
+    /// Version of this JSON layout, serialized first (see `report`)
+    #[serde(default = "crate::report::current_schema_version")]
+    pub schema_version: u32,
+
     /// Aggregated summary statistics
     pub summary: Summary,
 
@@ -45,6 +86,117 @@ pub struct AnalysisResult {
 
     /// When this analysis was performed
     pub timestamp: SystemTime,
+
+    /// Source files left out of the scan (too large or unreadable)
+    #[serde(default)]
+    pub skipped_files: usize,
+
+    /// Signs that the result is incomplete, e.g. skipped files
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+
+    /// Commit analyzed when the input was a cloned git URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+}
+
+impl AnalysisResult {
+    /// Make every path relative to the analysis `root`
+    ///
+    /// Paths outside `root` are left unchanged.
+    pub fn strip_root(&mut self, root: &Path) {
+        for file in &mut self.files {
+            file.path = relative_path(&file.path, root);
+        }
+        for path in &mut self.summary.largest_files {
+            *path = relative_path(path, root);
+        }
+        for function in &mut self.summary.largest_functions {
+            function.path = relative_path(&function.path, root);
+        }
+    }
+}
+
+/// Warning that an analysis result is incomplete or probably wrong as a whole
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct AnalysisWarning {
+    /// What looks wrong
+    pub kind: AnalysisWarningKind,
+
+    /// Human-readable explanation and suggested fix
+    pub message: String,
+}
+
+impl AnalysisWarning {
+    /// Warning about the source files a scan left out, with how many were
+    /// skipped for each reason, or `None` if it skipped none
+    pub fn skipped_files(skipped: &[SkippedFile]) -> Option<Self> {
+        if skipped.is_empty() {
+            return None;
+        }
+        let count = |reason: SkipReason| skipped.iter().filter(|f| f.reason == reason).count();
+        let reasons: Vec<String> = [
+            (SkipReason::TooLarge, "larger than 10MB"),
+            (SkipReason::PermissionDenied, "unreadable"),
+            (SkipReason::Binary, "binary"),
+        ]
+        .into_iter()
+        .map(|(reason, label)| (count(reason), label))
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, label)| format!("{} {}", n, label))
+        .collect();
+        Some(Self {
+            kind: AnalysisWarningKind::SkippedFiles,
+            message: format!(
+                "{} source file(s) were left out of the analysis ({})",
+                skipped.len(),
+                reasons.join(", ")
+            ),
+        })
+    }
+}
+
+/// Type of analysis warning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub enum AnalysisWarningKind {
+    /// Most code is dead and few entry points were found, so entry point
+    /// detection probably missed the real ones
+    SuspiciousDeadCodeRatio,
+
+    /// A `tsconfig.json` or `package.json` could not be parsed, so imports
+    /// it would have resolved fell back to heuristic resolution
+    InvalidManifest,
+
+    /// Relative imports match no analyzed file, e.g. a typo or an excluded
+    /// directory, so the code they use may be missing from the graph
+    UnresolvedImport,
+
+    /// Source files were left out of the scan (too large or unreadable), so
+    /// their metrics are missing and code only they use may be reported dead
+    SkippedFiles,
+}
+
+/// `path` relative to `root`, or unchanged if it is not inside `root`
+pub fn relative_path(path: &Path, root: &Path) -> PathBuf {
+    match path.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative.to_path_buf(),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Share of `comment_lines` among comment and code lines (0 when there are none)
+///
+/// Directories pass the sums over their files, so the ratio is weighted by
+/// each file's comment and code lines rather than averaged per file.
+pub fn comment_ratio(comment_lines: usize, loc: usize) -> f64 {
+    let total = comment_lines + loc;
+    if total == 0 {
+        return 0.0;
+    }
+    comment_lines as f64 / total as f64
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +212,10 @@ pub struct Summary {
 
     /// Top 10 largest files by LOC (sorted descending)
     pub largest_files: Vec<PathBuf>,
+
+    /// Top 10 largest functions by LOC (sorted descending)
+    #[serde(default)]
+    pub largest_functions: Vec<FunctionMetrics>,
 }
 
 #[derive(Debug, Clone)]
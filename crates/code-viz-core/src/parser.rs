@@ -5,9 +5,34 @@ use tree_sitter::{Language, Parser, Query, QueryCursor, Tree};
 
 pub trait LanguageParser: Send + Sync {
     fn language(&self) -> &str;
-    fn parse(&self, source: &str) -> Result<Tree, ParseError>;
+    /// Syntax tree of `source`; languages without a Tree-sitter grammar keep
+    /// the default, which fails with `ParseError::NoGrammar`
+    fn parse(&self, _source: &str) -> Result<Tree, ParseError> {
+        Err(ParseError::NoGrammar(self.language().to_string()))
+    }
     fn count_functions(&self, tree: &Tree) -> usize;
     fn find_comment_ranges(&self, tree: &Tree) -> Vec<tree_sitter::Range>;
+    /// Deepest control-flow nesting inside any function (0 if there are none)
+    fn max_nesting_depth(&self, tree: &Tree) -> usize;
+    /// Every function in the tree, in source order
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan>;
+    /// Number of symbols the file exports (0 for languages without `export`)
+    fn count_exports(&self, _tree: &Tree, _source: &str) -> usize {
+        0
+    }
+}
+
+/// A function located in a syntax tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSpan {
+    /// Declared name, or `<anonymous>` for unnamed closures
+    pub name: String,
+    /// First line of the function (0-based)
+    pub start_row: usize,
+    /// Last line of the function (0-based)
+    pub end_row: usize,
+    /// 1 plus the control-flow statements in the body, excluding nested functions
+    pub complexity: usize,
 }
 
 thread_local! {
@@ -36,6 +61,268 @@ fn parse_with_language(language: Language, source: &str) -> Result<Tree, ParseEr
     })
 }
 
+/// Node kinds that nest control flow in the C-family grammars (TS/JS/C++)
+const C_FAMILY_NESTING: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "for_in_statement",
+    "for_range_loop",
+    "while_statement",
+    "do_statement",
+    "switch_statement",
+    "try_statement",
+];
+
+const JS_FUNCTIONS: &[&str] = &[
+    "function_declaration",
+    "function_expression",
+    "generator_function",
+    "generator_function_declaration",
+    "arrow_function",
+    "method_definition",
+];
+
+const RUST_FUNCTIONS: &[&str] = &["function_item"];
+const RUST_NESTING: &[&str] = &[
+    "if_expression",
+    "for_expression",
+    "while_expression",
+    "loop_expression",
+    "match_expression",
+];
+
+const PYTHON_FUNCTIONS: &[&str] = &["function_definition"];
+const PYTHON_NESTING: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "while_statement",
+    "try_statement",
+    "with_statement",
+    "match_statement",
+];
+
+const GO_FUNCTIONS: &[&str] = &["function_declaration", "method_declaration", "func_literal"];
+const GO_NESTING: &[&str] = &[
+    "if_statement",
+    "for_statement",
+    "expression_switch_statement",
+    "type_switch_statement",
+    "select_statement",
+];
+
+const CPP_FUNCTIONS: &[&str] = &["function_definition"];
+
+/// Deepest nesting of `nesting_kinds` nodes within any `function_kinds` node
+///
+/// A function body counts as depth 1 even without control flow, and nested
+/// functions start counting afresh. `else if` chains do not add depth.
+fn max_nesting_depth_of(tree: &Tree, function_kinds: &[&str], nesting_kinds: &[&str]) -> usize {
+    fn is_else_if(node: tree_sitter::Node) -> bool {
+        node.parent().is_some_and(|parent| {
+            parent.kind() == "else_clause"
+                || parent.child_by_field_name("alternative") == Some(node)
+        })
+    }
+
+    fn walk(
+        node: tree_sitter::Node,
+        depth: Option<usize>,
+        function_kinds: &[&str],
+        nesting_kinds: &[&str],
+    ) -> usize {
+        let depth = if function_kinds.contains(&node.kind()) {
+            Some(0)
+        } else if nesting_kinds.contains(&node.kind()) && !is_else_if(node) {
+            depth.map(|d| d + 1)
+        } else {
+            depth
+        };
+
+        let mut max = depth.map_or(0, |d| d.max(1));
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            max = max.max(walk(child, depth, function_kinds, nesting_kinds));
+        }
+        max
+    }
+
+    walk(tree.root_node(), None, function_kinds, nesting_kinds)
+}
+
+/// Symbols exported by the top-level statements of a JS-family tree
+///
+/// Each declaration, variable declarator and specifier counts once, as does a
+/// default export and `export = Foo`. `export * from` adds none, since the
+/// names are another file's. CommonJS counts each `exports.foo =` or
+/// `module.exports.foo =` assignment, and `module.exports =` counts the
+/// properties of an object literal or else one export.
+fn count_exports_of(tree: &Tree, source: &str) -> usize {
+    fn count_named(node: tree_sitter::Node, kind: &str) -> usize {
+        node.named_children(&mut node.walk())
+            .filter(|child| child.kind() == kind)
+            .count()
+    }
+
+    fn is_identifier(node: tree_sitter::Node, source: &str, name: &str) -> bool {
+        node.kind() == "identifier" && node.utf8_text(source.as_bytes()).ok() == Some(name)
+    }
+
+    fn is_module_exports(node: tree_sitter::Node, source: &str) -> bool {
+        node.kind() == "member_expression"
+            && node
+                .child_by_field_name("object")
+                .is_some_and(|object| is_identifier(object, source, "module"))
+            && node
+                .child_by_field_name("property")
+                .and_then(|property| property.utf8_text(source.as_bytes()).ok())
+                == Some("exports")
+    }
+
+    fn count_export_statement(statement: tree_sitter::Node) -> usize {
+        if let Some(declaration) = statement.child_by_field_name("declaration") {
+            return match declaration.kind() {
+                "lexical_declaration" | "variable_declaration" => {
+                    count_named(declaration, "variable_declarator")
+                }
+                _ => 1,
+            };
+        }
+
+        let mut cursor = statement.walk();
+        let children: Vec<_> = statement.children(&mut cursor).collect();
+        match children.iter().find(|child| child.kind() == "export_clause") {
+            Some(clause) => count_named(*clause, "export_specifier"),
+            None => children
+                .iter()
+                .filter(|child| matches!(child.kind(), "default" | "namespace_export" | "="))
+                .count()
+                .min(1),
+        }
+    }
+
+    fn count_commonjs(statement: tree_sitter::Node, source: &str) -> usize {
+        let Some(assignment) = statement
+            .named_child(0)
+            .filter(|e| e.kind() == "assignment_expression")
+        else {
+            return 0;
+        };
+        let (Some(left), Some(right)) = (
+            assignment.child_by_field_name("left"),
+            assignment.child_by_field_name("right"),
+        ) else {
+            return 0;
+        };
+
+        if is_module_exports(left, source) {
+            if right.kind() != "object" {
+                return 1;
+            }
+            let mut cursor = right.walk();
+            return right
+                .named_children(&mut cursor)
+                .filter(|property| {
+                    matches!(
+                        property.kind(),
+                        "pair" | "shorthand_property_identifier" | "method_definition"
+                    )
+                })
+                .count();
+        }
+        let is_named_export = left.kind() == "member_expression"
+            && left.child_by_field_name("object").is_some_and(|object| {
+                is_module_exports(object, source) || is_identifier(object, source, "exports")
+            });
+        usize::from(is_named_export)
+    }
+
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    root.named_children(&mut cursor)
+        .map(|statement| match statement.kind() {
+            "export_statement" => count_export_statement(statement),
+            "expression_statement" => count_commonjs(statement, source),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Locate every `function_kinds` node, scoring complexity with `branch_kinds`
+///
+/// Uses the same node kinds as [`max_nesting_depth_of`], so functions and
+/// their control flow are recognized identically by both metrics.
+fn find_functions_of(
+    tree: &Tree,
+    source: &str,
+    function_kinds: &[&str],
+    branch_kinds: &[&str],
+) -> Vec<FunctionSpan> {
+    fn branches(node: tree_sitter::Node, function_kinds: &[&str], branch_kinds: &[&str]) -> usize {
+        let mut count = 0;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            // Nested functions are scored on their own
+            if function_kinds.contains(&child.kind()) {
+                continue;
+            }
+            if branch_kinds.contains(&child.kind()) {
+                count += 1;
+            }
+            count += branches(child, function_kinds, branch_kinds);
+        }
+        count
+    }
+
+    fn collect(
+        node: tree_sitter::Node,
+        source: &str,
+        function_kinds: &[&str],
+        branch_kinds: &[&str],
+        functions: &mut Vec<FunctionSpan>,
+    ) {
+        if function_kinds.contains(&node.kind()) {
+            functions.push(FunctionSpan {
+                name: function_name(node, source),
+                start_row: node.start_position().row,
+                end_row: node.end_position().row,
+                complexity: 1 + branches(node, function_kinds, branch_kinds),
+            });
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            collect(child, source, function_kinds, branch_kinds, functions);
+        }
+    }
+
+    let mut functions = Vec::new();
+    collect(tree.root_node(), source, function_kinds, branch_kinds, &mut functions);
+    functions
+}
+
+/// Name of a function node across the supported grammars
+fn function_name(node: tree_sitter::Node, source: &str) -> String {
+    let text = |n: tree_sitter::Node| n.utf8_text(source.as_bytes()).unwrap_or_default().to_string();
+
+    if let Some(name) = node.child_by_field_name("name") {
+        return text(name);
+    }
+
+    // C++ nests the name in declarators: `int *Foo::bar()` ends in `Foo::bar`
+    if let Some(mut declarator) = node.child_by_field_name("declarator") {
+        while let Some(inner) = declarator.child_by_field_name("declarator") {
+            declarator = inner;
+        }
+        return text(declarator);
+    }
+
+    // Closures take the name of the variable they are assigned to
+    node.parent()
+        .filter(|parent| parent.kind() == "variable_declarator")
+        .and_then(|parent| parent.child_by_field_name("name"))
+        .map_or_else(|| "<anonymous>".to_string(), text)
+}
+
 pub struct TypeScriptParser;
 impl LanguageParser for TypeScriptParser {
     fn language(&self) -> &str {
@@ -49,7 +336,7 @@ impl LanguageParser for TypeScriptParser {
         let query = QUERY.get_or_init(|| {
             Query::new(
                 tree_sitter_typescript::language_typescript(),
-                "(function_declaration) @f (arrow_function) @f (method_definition) @f"
+                "(function_declaration) @f (function_expression) @f (generator_function) @f (generator_function_declaration) @f (arrow_function) @f (method_definition) @f"
             ).expect("Invalid TypeScript query")
         });
         
@@ -70,6 +357,15 @@ impl LanguageParser for TypeScriptParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, JS_FUNCTIONS, C_FAMILY_NESTING)
+    }
+    fn count_exports(&self, tree: &Tree, source: &str) -> usize {
+        count_exports_of(tree, source)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, JS_FUNCTIONS, C_FAMILY_NESTING)
+    }
 }
 
 pub struct TsxParser;
@@ -85,7 +381,7 @@ impl LanguageParser for TsxParser {
         let query = QUERY.get_or_init(|| {
             Query::new(
                 tree_sitter_typescript::language_tsx(),
-                "(function_declaration) @f (arrow_function) @f (method_definition) @f"
+                "(function_declaration) @f (function_expression) @f (generator_function) @f (generator_function_declaration) @f (arrow_function) @f (method_definition) @f"
             ).expect("Invalid TSX query")
         });
         
@@ -106,6 +402,15 @@ impl LanguageParser for TsxParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, JS_FUNCTIONS, C_FAMILY_NESTING)
+    }
+    fn count_exports(&self, tree: &Tree, source: &str) -> usize {
+        count_exports_of(tree, source)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, JS_FUNCTIONS, C_FAMILY_NESTING)
+    }
 }
 
 pub struct JavaScriptParser;
@@ -121,7 +426,7 @@ impl LanguageParser for JavaScriptParser {
         let query = QUERY.get_or_init(|| {
             Query::new(
                 tree_sitter_javascript::language(),
-                "(function_declaration) @f (arrow_function) @f (method_definition) @f"
+                "(function_declaration) @f (function_expression) @f (generator_function) @f (generator_function_declaration) @f (arrow_function) @f (method_definition) @f"
             ).expect("Invalid JavaScript query")
         });
         
@@ -142,6 +447,15 @@ impl LanguageParser for JavaScriptParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, JS_FUNCTIONS, C_FAMILY_NESTING)
+    }
+    fn count_exports(&self, tree: &Tree, source: &str) -> usize {
+        count_exports_of(tree, source)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, JS_FUNCTIONS, C_FAMILY_NESTING)
+    }
 }
 
 pub struct RustParser;
@@ -178,6 +492,12 @@ impl LanguageParser for RustParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, RUST_FUNCTIONS, RUST_NESTING)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, RUST_FUNCTIONS, RUST_NESTING)
+    }
 }
 
 pub struct PythonParser;
@@ -214,6 +534,12 @@ impl LanguageParser for PythonParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, PYTHON_FUNCTIONS, PYTHON_NESTING)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, PYTHON_FUNCTIONS, PYTHON_NESTING)
+    }
 }
 
 pub struct GoParser;
@@ -250,6 +576,12 @@ impl LanguageParser for GoParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, GO_FUNCTIONS, GO_NESTING)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, GO_FUNCTIONS, GO_NESTING)
+    }
 }
 
 pub struct CppParser;
@@ -286,6 +618,39 @@ impl LanguageParser for CppParser {
             .map(|m| m.captures[0].node.range())
             .collect()
     }
+    fn max_nesting_depth(&self, tree: &Tree) -> usize {
+        max_nesting_depth_of(tree, CPP_FUNCTIONS, C_FAMILY_NESTING)
+    }
+    fn find_functions(&self, tree: &Tree, source: &str) -> Vec<FunctionSpan> {
+        find_functions_of(tree, source, CPP_FUNCTIONS, C_FAMILY_NESTING)
+    }
+}
+
+/// Extensions analyzed as plain text unless configured otherwise
+pub const PLAIN_TEXT_EXTENSIONS: &[&str] = &["sql", "yaml", "yml"];
+
+/// Parser for files of languages without a Tree-sitter grammar
+///
+/// Finds no functions, comments or nesting, so such files contribute their
+/// lines to LOC and nothing else. There is no grammar to parse them with, so
+/// `parse` is left to fail with `ParseError::NoGrammar`.
+pub struct PlainTextParser;
+impl LanguageParser for PlainTextParser {
+    fn language(&self) -> &str {
+        "text"
+    }
+    fn count_functions(&self, _tree: &Tree) -> usize {
+        0
+    }
+    fn find_comment_ranges(&self, _tree: &Tree) -> Vec<tree_sitter::Range> {
+        Vec::new()
+    }
+    fn max_nesting_depth(&self, _tree: &Tree) -> usize {
+        0
+    }
+    fn find_functions(&self, _tree: &Tree, _source: &str) -> Vec<FunctionSpan> {
+        Vec::new()
+    }
 }
 
 #[tracing::instrument]
@@ -310,6 +675,74 @@ pub fn get_parser(language: &str) -> Result<Box<dyn LanguageParser>, ParseError>
     Ok(parser)
 }
 
+/// Refine the language picked from a file's extension by looking at its source
+///
+/// `.ts` files containing JSX (closing tags such as `</div>`, fragments or
+/// self-closing tags such as `<Button />`) outside strings and comments are
+/// switched to `tsx`, since the TypeScript grammar rejects JSX. Every other
+/// language is returned as is; `.h` headers stay `cpp` because the C++
+/// grammar also parses C.
+pub fn detect_dialect<'a>(language: &'a str, source: &str) -> &'a str {
+    match language {
+        "typescript" | "ts" if looks_like_jsx(source) => "tsx",
+        _ => language,
+    }
+}
+
+fn looks_like_jsx(source: &str) -> bool {
+    let code = code_outside_literals(source);
+    let closing_tag = code
+        .as_bytes()
+        .windows(3)
+        .any(|w| w[0] == b'<' && w[1] == b'/' && (w[2].is_ascii_alphabetic() || w[2] == b'>'));
+    closing_tag || code.contains(" />")
+}
+
+/// `source` without the contents of strings, template literals and comments
+///
+/// Quotes end at the line's end as well, so an apostrophe in JSX text only
+/// hides the rest of its own line.
+fn code_outside_literals(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' | '`' => {
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '\n' if c != '`' => {
+                            code.push('\n');
+                            break;
+                        }
+                        next if next == c => break,
+                        _ => {}
+                    }
+                }
+                code.push(' ');
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|&next| next == '\n');
+                code.push('\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                chars.by_ref().find(|&next| {
+                    let closed = previous == '*' && next == '/';
+                    previous = next;
+                    closed
+                });
+                code.push(' ');
+            }
+            c => code.push(c),
+        }
+    }
+    code
+}
+
 #[derive(Debug, Error)]
 pub enum ParseError {
     #[error("Unsupported language: {0}")]
@@ -317,6 +750,9 @@ pub enum ParseError {
 
     #[error("Tree-sitter parse failed: {0}")]
     TreeSitterError(String),
+
+    #[error("No grammar to parse {0} with")]
+    NoGrammar(String),
 }
 
 #[cfg(test)]
@@ -370,6 +806,25 @@ mod tests {
         assert_eq!(count, 3);
     }
     
+    #[test]
+    fn test_detect_dialect_routes_jsx_in_ts_to_tsx() {
+        let source = "export const App = () => <div className=\"app\"><Header /></div>;\n";
+        assert!(get_parser("typescript").unwrap().parse(source).unwrap().root_node().has_error());
+
+        let language = detect_dialect("typescript", source);
+        assert_eq!(language, "tsx");
+        let tree = get_parser(language).unwrap().parse(source).unwrap();
+        assert!(!tree.root_node().has_error());
+
+        // Plain TypeScript and other languages keep the extension's mapping
+        assert_eq!(detect_dialect("typescript", "const n = <number>value;"), "typescript");
+        let quoted = "const html = '<p>hi</p>'; // closes with </p>\nconst t = `<br />`;\n";
+        assert_eq!(detect_dialect("typescript", quoted), "typescript");
+        let apostrophe = "const A = () => <p>Don't</p>;\nconst B = () => <div></div>;\n";
+        assert_eq!(detect_dialect("typescript", apostrophe), "tsx");
+        assert_eq!(detect_dialect("cpp", "int main() { return 0; }"), "cpp");
+    }
+
     #[test]
     fn test_count_functions_tsx() {
         let parser = get_parser("tsx").unwrap();
@@ -382,6 +837,80 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn test_max_nesting_depth() {
+        let parser = get_parser("typescript").unwrap();
+        let source = r#"
+            function nested(a: number) {
+                if (a > 0) {
+                    if (a > 1) {
+                        if (a > 2) {
+                            if (a > 3) {
+                                return 4;
+                            }
+                        }
+                    } else if (a < -1) {
+                        return -1;
+                    }
+                }
+                return 0;
+            }
+        "#;
+        let tree = parser.parse(source).unwrap();
+        assert_eq!(parser.max_nesting_depth(&tree), 4);
+
+        let source = "function flat() { const x = 1; return x; }";
+        let tree = parser.parse(source).unwrap();
+        assert_eq!(parser.max_nesting_depth(&tree), 1);
+
+        let tree = parser.parse("const x = 1;").unwrap();
+        assert_eq!(parser.max_nesting_depth(&tree), 0);
+    }
+
+    #[test]
+    fn test_max_nesting_depth_python() {
+        let parser = get_parser("python").unwrap();
+        let source = "def f(xs):\n    for x in xs:\n        if x:\n            pass\n        elif not x:\n            pass\n";
+        let tree = parser.parse(source).unwrap();
+        assert_eq!(parser.max_nesting_depth(&tree), 2);
+    }
+
+    #[test]
+    fn test_find_functions() {
+        let parser = get_parser("typescript").unwrap();
+        let source = "function outer(a: number) {\n    if (a > 0) {\n        return 1;\n    } else if (a < 0) {\n        return -1;\n    }\n    const inner = () => {\n        for (;;) {}\n    };\n}\n";
+        let tree = parser.parse(source).unwrap();
+        let functions = parser.find_functions(&tree, source);
+
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["outer", "inner"]);
+        assert_eq!((functions[0].start_row, functions[0].end_row), (0, 9));
+        // Both ifs count; the loop belongs to the nested function
+        assert_eq!(functions[0].complexity, 3);
+        assert_eq!(functions[1].complexity, 2);
+
+        let parser = get_parser("cpp").unwrap();
+        let source = "int *Widget::build() {\n    return nullptr;\n}\n";
+        let tree = parser.parse(source).unwrap();
+        assert_eq!(parser.find_functions(&tree, source)[0].name, "Widget::build");
+    }
+
+    #[test]
+    fn test_function_expressions_and_generators() {
+        let source = "function* gen() { yield 1; }\nconst named = function () {\n    if (gen) {}\n};\nconst lazy = function* () {};\n";
+        for language in ["typescript", "tsx", "javascript"] {
+            let parser = get_parser(language).unwrap();
+            let tree = parser.parse(source).unwrap();
+            assert_eq!(parser.count_functions(&tree), 3, "{}", language);
+
+            let functions = parser.find_functions(&tree, source);
+            let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(names, vec!["gen", "named", "lazy"], "{}", language);
+            assert_eq!(functions[1].complexity, 2, "{}", language);
+            assert_eq!(parser.max_nesting_depth(&tree), 1, "{}", language);
+        }
+    }
+
     #[test]
     fn test_snapshot_typescript_ast() {
         let parser = get_parser("typescript").unwrap();
@@ -0,0 +1,199 @@
+//! Versioned on-disk report format.
+//!
+//! Reports written to disk (`--output`, exports) are wrapped in a
+//! [`VersionedReport`] so that later model changes can be detected and
+//! migrated when old files are loaded back as baselines. Results also carry
+//! the version themselves, so JSON printed to stdout can be checked too.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Current on-disk report schema version
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Schema version of results created by this build, the serde default for
+/// results loaded from JSON written before they carried one
+pub fn current_schema_version() -> u32 {
+    REPORT_SCHEMA_VERSION
+}
+
+/// Version assigned to legacy reports written before versioning existed
+const LEGACY_SCHEMA_VERSION: u32 = 0;
+
+#[derive(Error, Debug)]
+pub enum ReportError {
+    #[error("Invalid report JSON: {0}")]
+    ParseFailed(#[from] serde_json::Error),
+
+    #[error(
+        "Report schema version {found} is newer than supported version {supported}; \
+         upgrade code-viz to read this report"
+    )]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Failed to migrate report from schema version {from}: {message}")]
+    MigrationFailed { from: u32, message: String },
+}
+
+/// Report payload tagged with its schema version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedReport<T> {
+    /// Schema version of `payload`
+    pub schema_version: u32,
+
+    /// The report itself (e.g. `AnalysisResult` or `DeadCodeResult`)
+    pub payload: T,
+}
+
+impl<T> VersionedReport<T> {
+    /// Wrap a payload with the current schema version
+    pub fn new(payload: T) -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            payload,
+        }
+    }
+}
+
+impl<T: Serialize> VersionedReport<T> {
+    /// Serialize to pretty-printed JSON
+    pub fn to_json_pretty(&self) -> Result<String, ReportError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Load a report, migrating older schema versions to the current one
+///
+/// Accepts both versioned reports and legacy unversioned JSON (treated as
+/// schema version 0). Reports from a newer schema version are rejected with
+/// `ReportError::UnsupportedVersion`.
+pub fn load_report<T: DeserializeOwned>(json: &str) -> Result<T, ReportError> {
+    let value: Value = serde_json::from_str(json)?;
+
+    let (version, mut payload) = match value.get("schema_version").and_then(Value::as_u64) {
+        Some(version) if value.get("payload").is_some() => {
+            let version = u32::try_from(version).unwrap_or(u32::MAX);
+            let payload = value.get("payload").cloned().unwrap_or(Value::Null);
+            (version, payload)
+        }
+        // A bare result, which names its own version unless it is legacy
+        _ => {
+            let version = value
+                .get("schema_version")
+                .or_else(|| value.get("schemaVersion"))
+                .and_then(Value::as_u64)
+                .map_or(LEGACY_SCHEMA_VERSION, |version| {
+                    u32::try_from(version).unwrap_or(u32::MAX)
+                });
+            (version, value)
+        }
+    };
+
+    if version > REPORT_SCHEMA_VERSION {
+        return Err(ReportError::UnsupportedVersion {
+            found: version,
+            supported: REPORT_SCHEMA_VERSION,
+        });
+    }
+
+    for from in version..REPORT_SCHEMA_VERSION {
+        payload = migrate(from, payload)?;
+    }
+
+    Ok(serde_json::from_value(payload)?)
+}
+
+/// Migrate a payload from schema version `from` to `from + 1`
+fn migrate(from: u32, payload: Value) -> Result<Value, ReportError> {
+    match from {
+        // v0 -> v1: legacy reports are the bare payload; the contents are unchanged
+        0 => Ok(payload),
+        _ => Err(ReportError::MigrationFailed {
+            from,
+            message: "no migration available".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AnalysisResult, Summary};
+    use std::time::SystemTime;
+
+    fn sample_result() -> AnalysisResult {
+        AnalysisResult {
+            schema_version: REPORT_SCHEMA_VERSION,
+            summary: Summary {
+                total_files: 1,
+                total_loc: 42,
+                total_functions: 3,
+                largest_files: vec![],
+                largest_functions: vec![],
+            },
+            files: vec![],
+            timestamp: SystemTime::UNIX_EPOCH,
+            skipped_files: 0,
+            warnings: vec![],
+            commit_sha: None,
+        }
+    }
+
+    #[test]
+    fn test_write_and_load_v1() {
+        let json = VersionedReport::new(sample_result()).to_json_pretty().unwrap();
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], REPORT_SCHEMA_VERSION);
+        assert_eq!(value["payload"]["summary"]["total_loc"], 42);
+
+        let loaded: AnalysisResult = load_report(&json).unwrap();
+        assert_eq!(loaded.summary.total_loc, 42);
+    }
+
+    #[test]
+    fn test_load_legacy_unversioned() {
+        let mut value = serde_json::to_value(sample_result()).unwrap();
+        value.as_object_mut().unwrap().remove("schema_version");
+        let loaded: AnalysisResult = load_report(&value.to_string()).unwrap();
+        assert_eq!(loaded.summary.total_functions, 3);
+        assert_eq!(loaded.schema_version, REPORT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_bare_result_is_versioned_and_serializes_identically() {
+        let result = sample_result();
+        let first = serde_json::to_string(&result).unwrap();
+        let second = serde_json::to_string(&result.clone()).unwrap();
+
+        assert_eq!(first, second);
+        assert!(first.starts_with(&format!("{{\"schema_version\":{}", REPORT_SCHEMA_VERSION)));
+
+        // A bare result from a newer build is rejected like a newer report
+        let newer = first.replacen(
+            &format!("\"schema_version\":{}", REPORT_SCHEMA_VERSION),
+            &format!("\"schema_version\":{}", REPORT_SCHEMA_VERSION + 1),
+            1,
+        );
+        assert!(matches!(
+            load_report::<AnalysisResult>(&newer),
+            Err(ReportError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_newer_version_fails() {
+        let mut report = serde_json::to_value(VersionedReport::new(sample_result())).unwrap();
+        report["schema_version"] = Value::from(REPORT_SCHEMA_VERSION + 1);
+
+        let err = load_report::<AnalysisResult>(&report.to_string()).unwrap_err();
+        assert!(matches!(
+            err,
+            ReportError::UnsupportedVersion { found, supported }
+                if found == REPORT_SCHEMA_VERSION + 1 && supported == REPORT_SCHEMA_VERSION
+        ));
+        assert!(err.to_string().contains("newer than supported"));
+    }
+}
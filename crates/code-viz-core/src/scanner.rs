@@ -1,13 +1,273 @@
-use globset::{Glob, GlobSetBuilder};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-#[tracing::instrument(skip(exclude_patterns), fields(path = %path.display(), pattern_count = exclude_patterns.len()))]
+/// Largest source file the scanner accepts
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Leading bytes checked for NUL when deciding whether a file is binary, as git does
+const BINARY_SNIFF_LEN: u64 = 8000;
+
+/// File in the scanned directory listing paths to leave out, in .gitignore syntax
+pub const EXCLUDE_FILE: &str = ".code-viz-exclude";
+
+/// Why a source file was left out of a scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Larger than 10MB
+    TooLarge,
+    /// Metadata could not be read due to permissions
+    PermissionDenied,
+    /// Contains NUL bytes, e.g. a compiled artifact with a source extension
+    Binary,
+}
+
+/// A source file the scanner found but did not return
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+/// Files found by `scan_directory_detailed`, along with the ones it skipped
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    pub files: Vec<PathBuf>,
+    pub skipped: Vec<SkippedFile>,
+    /// Files left out because no analyzer supports their extension
+    pub unsupported_files: usize,
+    /// Files in `files` without a grammar, to be analyzed as plain text
+    pub plain_text_files: Vec<PathBuf>,
+}
+
+/// Settings of a directory scan, on top of .gitignore, the scanned directory's
+/// [`EXCLUDE_FILE`] and the built-in filters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// Glob patterns to exclude, relative to the scanned directory; a negated
+    /// line of the exclude file cannot bring back a path matching one of them.
+    /// Patterns starting with `!` re-include paths instead (see [`ExcludeMatcher`])
+    pub exclude_patterns: Vec<String>,
+    /// Deepest level to descend to, where files directly in the scanned
+    /// directory are at depth 1 (`None` for unlimited)
+    pub max_depth: Option<usize>,
+    /// Extra extensions to scan, mapped to the language they are parsed as,
+    /// e.g. `"es" => "javascript"` (see [`override_language`])
+    pub extension_overrides: HashMap<String, String>,
+    /// Extensions of languages without a grammar to scan anyway, without the
+    /// leading dot (see [`crate::parser::PlainTextParser`])
+    pub plain_text_extensions: Vec<String>,
+    /// Glob patterns relative to the scanned directory; when any are given,
+    /// only files matching one of them are returned
+    pub include_patterns: Vec<String>,
+    /// Languages to return, as named by [`file_language`]; all if empty
+    pub languages: Vec<String>,
+}
+
+/// The exclude, include and language settings of [`ScanOptions`], for
+/// narrowing down files found by another scan of `root`
+#[derive(Debug, Clone)]
+pub struct FileSelection {
+    root: PathBuf,
+    excludes: ExcludeMatcher,
+    include: Option<GlobSet>,
+    languages: Vec<String>,
+    extension_overrides: HashMap<String, String>,
+}
+
+impl FileSelection {
+    /// Build the selection for paths under `root`
+    pub fn new(root: &Path, options: &ScanOptions) -> Result<Self, ScanError> {
+        let include = if options.include_patterns.is_empty() {
+            None
+        } else {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in &options.include_patterns {
+                builder.add(Glob::new(pattern).map_err(|e| ScanError::InvalidPattern(e.to_string()))?);
+            }
+            Some(builder.build().map_err(|e| ScanError::InvalidPattern(e.to_string()))?)
+        };
+        Ok(Self {
+            root: root.to_path_buf(),
+            excludes: ExcludeMatcher::new(root, &options.exclude_patterns)?,
+            include,
+            languages: options.languages.clone(),
+            extension_overrides: options.extension_overrides.clone(),
+        })
+    }
+
+    /// Whether `path` is not excluded, matches an include pattern if there
+    /// are any and is in one of the selected languages
+    ///
+    /// Files without a grammar are in the `text` language they are analyzed as.
+    pub fn is_selected(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let language = file_language(path, &self.extension_overrides).unwrap_or("text");
+        let language_enabled =
+            self.languages.is_empty() || self.languages.iter().any(|l| l == language);
+        !self.excludes.is_excluded(relative, false)
+            && self.include.as_ref().is_none_or(|include| include.is_match(relative))
+            && language_enabled
+    }
+}
+
+/// Language a source file is analyzed as, by the names `ScanOptions::languages`
+/// filters on: `typescript`, `javascript`, `vue`, `svelte`, `rust`, `python`,
+/// `go` or `cpp`
+///
+/// Extension overrides take precedence; `.tsx` and `.jsx` files count as
+/// TypeScript and JavaScript. Files without a grammar have no language.
+pub fn file_language<'a>(path: &Path, extension_overrides: &'a HashMap<String, String>) -> Option<&'a str> {
+    if let Some(language) = override_language(path, extension_overrides) {
+        return Some(match language {
+            "tsx" => "typescript",
+            "jsx" => "javascript",
+            language => language,
+        });
+    }
+    match path.extension()?.to_str()? {
+        "ts" | "tsx" => Some("typescript"),
+        "js" | "jsx" | "mjs" | "cjs" => Some("javascript"),
+        "vue" => Some("vue"),
+        "svelte" => Some("svelte"),
+        "rs" => Some("rust"),
+        "py" => Some("python"),
+        "go" => Some("go"),
+        "cpp" | "cc" | "cxx" | "hpp" | "h" => Some("cpp"),
+        _ => None,
+    }
+}
+
+/// Exclude patterns where a `!` prefix re-includes a subtree
+///
+/// `["node_modules/**", "!node_modules/@myorg/**"]` excludes every package
+/// but `@myorg` ones. A re-include wins over every exclude pattern, wherever
+/// it appears in the list, and over .gitignore, since the scan walks
+/// re-included paths with the `ignore` crate's override globs. Patterns
+/// containing a `/` are anchored to the root.
+#[derive(Debug, Clone)]
+pub struct ExcludeMatcher {
+    excluded: GlobSet,
+    reincluded: Override,
+    /// Whitelist of the re-includes and the directories leading to them
+    walk_overrides: Option<Override>,
+}
+
+impl ExcludeMatcher {
+    /// Build the matcher for paths relative to `root`
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self, ScanError> {
+        let invalid = |pattern: &str, e: &dyn std::fmt::Display| {
+            tracing::error!(pattern = %pattern, error = %e, "Invalid glob pattern");
+            ScanError::InvalidPattern(e.to_string())
+        };
+
+        let mut excluded = GlobSetBuilder::new();
+        let mut reincluded = OverrideBuilder::new(root);
+        let mut walk_overrides = OverrideBuilder::new(root);
+        let mut has_reincludes = false;
+        for pattern in patterns {
+            match pattern.strip_prefix('!') {
+                Some(include) => {
+                    reincluded.add(include).map_err(|e| invalid(pattern, &e))?;
+                    walk_overrides.add(include).map_err(|e| invalid(pattern, &e))?;
+                    for dir in literal_parent_dirs(include) {
+                        walk_overrides.add(&dir).map_err(|e| invalid(pattern, &e))?;
+                    }
+                    has_reincludes = true;
+                }
+                None => {
+                    excluded.add(Glob::new(pattern).map_err(|e| invalid(pattern, &e))?);
+                }
+            }
+        }
+
+        Ok(Self {
+            excluded: excluded.build().map_err(|e| invalid("<set>", &e))?,
+            reincluded: reincluded.build().map_err(|e| invalid("<set>", &e))?,
+            walk_overrides: if has_reincludes {
+                Some(walk_overrides.build().map_err(|e| invalid("<set>", &e))?)
+            } else {
+                None
+            },
+        })
+    }
+
+    /// Whether an exclude pattern matches `relative` and no re-include does
+    pub fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        self.excluded.is_match(relative) && !self.is_reincluded(relative, is_dir)
+    }
+
+    /// Whether a `!` pattern matches `relative`
+    pub fn is_reincluded(&self, relative: &Path, is_dir: bool) -> bool {
+        self.reincluded.matched(relative, is_dir).is_whitelist()
+    }
+}
+
+/// Anchored globs of the directories a re-include pattern names before its
+/// first wildcard, e.g. `/node_modules` and `/node_modules/@myorg` for
+/// `node_modules/@myorg/**`, so a walk descends to it through directories
+/// .gitignore would prune
+fn literal_parent_dirs(pattern: &str) -> Vec<String> {
+    let pattern = pattern.trim_start_matches('/');
+    let components: Vec<&str> = pattern.split('/').collect();
+    if components.len() < 2 {
+        // Unanchored, so there is no single directory leading to it
+        return Vec::new();
+    }
+    components[..components.len() - 1]
+        .iter()
+        .take_while(|component| !component.contains(['*', '?', '[', '{']))
+        .scan(String::new(), |dir, component| {
+            dir.push('/');
+            dir.push_str(component);
+            Some(dir.clone())
+        })
+        .collect()
+}
+
+/// Language that `overrides` assigns to `path`, if any of its extensions match
+///
+/// Keys are extensions with or without the leading dot and may span several
+/// dots, e.g. `ts.tmpl`. The longest matching key wins.
+pub fn override_language<'a>(path: &Path, overrides: &'a HashMap<String, String>) -> Option<&'a str> {
+    let name = path.file_name()?.to_str()?;
+    overrides
+        .iter()
+        .filter(|(ext, _)| {
+            name.strip_suffix(ext.trim_start_matches('.'))
+                .and_then(|stem| stem.strip_suffix('.'))
+                .is_some_and(|stem| !stem.is_empty())
+        })
+        .max_by_key(|(ext, _)| ext.trim_start_matches('.').len())
+        .map(|(_, language)| language.as_str())
+}
+
 pub fn scan_directory(
     path: &Path,
     exclude_patterns: &[String],
 ) -> Result<Vec<PathBuf>, ScanError> {
+    scan_directory_detailed(path, exclude_patterns).map(|result| result.files)
+}
+
+/// Scan for source files, reporting which ones were skipped and why
+pub fn scan_directory_detailed(
+    path: &Path,
+    exclude_patterns: &[String],
+) -> Result<ScanResult, ScanError> {
+    let options = ScanOptions {
+        exclude_patterns: exclude_patterns.to_vec(),
+        ..Default::default()
+    };
+    scan_with_options(path, &options)
+}
+
+/// Scan for source files with the given options, reporting skipped files
+#[tracing::instrument(skip(options), fields(path = %path.display(), pattern_count = options.exclude_patterns.len(), max_depth = ?options.max_depth))]
+pub fn scan_with_options(path: &Path, options: &ScanOptions) -> Result<ScanResult, ScanError> {
     tracing::info!("Starting directory scan");
 
     if !path.exists() {
@@ -17,32 +277,35 @@ pub fn scan_directory(
         return Err(ScanError::NotADirectory(path.to_path_buf()));
     }
 
-    let mut builder = GlobSetBuilder::new();
-    for pattern in exclude_patterns {
-        builder.add(Glob::new(pattern).map_err(|e| {
-            tracing::error!(pattern = %pattern, error = %e, "Invalid glob pattern");
-            ScanError::InvalidPattern(e.to_string())
-        })?);
-    }
-    let glob_set = builder
-        .build()
-        .map_err(|e| {
-            tracing::error!(error = %e, "Failed to build glob set");
-            ScanError::InvalidPattern(e.to_string())
-        })?;
+    let excludes = ExcludeMatcher::new(path, &options.exclude_patterns)?;
+    let selection = FileSelection::new(path, options)?;
 
     tracing::debug!("Glob patterns configured");
 
+    let exclude_file = load_exclude_file(path);
     let root_path = path.to_path_buf(); // Capture for closure
 
     // Use ignore::WalkBuilder which respects .gitignore, .ignore, etc.
-    let walker = WalkBuilder::new(path)
+    let mut builder = WalkBuilder::new(path);
+    builder
         .follow_links(false)
         .git_ignore(true) // Respect .gitignore files in git repos
         .git_global(true) // Respect global gitignore
         .git_exclude(true) // Respect .git/info/exclude
         .add_custom_ignore_filename(".gitignore") // Also respect .gitignore in non-git dirs
         .hidden(true) // Skip hidden files/dirs
+        .max_depth(options.max_depth);
+
+    // Re-included paths may be in directories pruned by .gitignore or the
+    // exclude file, so they are walked again with override globs, which are
+    // checked before any ignore file
+    let reincluded = excludes.walk_overrides.clone().map(|overrides| {
+        let mut builder = builder.clone();
+        builder.overrides(overrides);
+        builder.build().filter_map(|result| result.ok())
+    });
+
+    let walker = builder
         .build()
         .filter_map(|result| result.ok()) // Skip errors, log them separately
         .filter(move |entry| {
@@ -55,7 +318,15 @@ pub fn scan_directory(
 
             // Check additional exclude patterns (on top of gitignore)
             let relative_path = path.strip_prefix(&root_path).unwrap_or(path);
-            if glob_set.is_match(relative_path) {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            if excludes.is_excluded(relative_path, is_dir) {
+                return false;
+            }
+
+            if exclude_file
+                .matched_path_or_any_parents(relative_path, is_dir)
+                .is_ignore()
+            {
                 return false;
             }
 
@@ -63,14 +334,36 @@ pub fn scan_directory(
         });
 
     let mut files = Vec::new();
-    let mut skipped_large = 0;
-    let mut skipped_permission = 0;
+    let mut skipped = Vec::new();
+    let mut unsupported_files = 0;
+    let mut plain_text_files = Vec::new();
+    let mut seen = std::collections::HashSet::new();
 
-    for entry in walker {
+    for entry in walker.chain(reincluded.into_iter().flatten()) {
         let path = entry.path();
 
-        // Skip directories
-        if path.is_dir() {
+        // Skip directories, and files both walks found
+        if path.is_dir() || !seen.insert(path.to_path_buf()) {
+            continue;
+        }
+
+        // Filter by extension
+        let is_source = path.extension().is_some_and(|ext| {
+            matches!(
+                ext.to_string_lossy().as_ref(),
+                "ts" | "tsx" | "js" | "jsx" | "vue" | "svelte" | "rs" | "py" | "go" | "cpp" | "cc"
+                    | "cxx" | "hpp" | "h"
+            )
+        }) || override_language(path, &options.extension_overrides).is_some();
+        let is_plain_text = !is_source
+            && path.extension().is_some_and(|ext| {
+                options.plain_text_extensions.iter().any(|plain| ext == plain.as_str())
+            });
+        if !is_source && !is_plain_text {
+            unsupported_files += 1;
+            continue;
+        }
+        if !selection.is_selected(path) {
             continue;
         }
 
@@ -78,20 +371,34 @@ pub fn scan_directory(
         // Use std::fs::metadata directly since ignore::DirEntry might not have metadata cached
         match std::fs::metadata(path) {
             Ok(metadata) => {
-                if metadata.len() > 10 * 1024 * 1024 {
+                if metadata.len() > MAX_FILE_SIZE {
                     tracing::warn!(
                         path = %path.display(),
                         size_mb = metadata.len() / (1024 * 1024),
                         "Skipping large file (>10MB)"
                     );
-                    skipped_large += 1;
+                    skipped.push(SkippedFile {
+                        path: path.to_path_buf(),
+                        reason: SkipReason::TooLarge,
+                    });
+                    continue;
+                }
+                if looks_binary(path) {
+                    tracing::warn!(path = %path.display(), "Skipping binary file");
+                    skipped.push(SkippedFile {
+                        path: path.to_path_buf(),
+                        reason: SkipReason::Binary,
+                    });
                     continue;
                 }
             }
             Err(e) => {
                 if e.kind() == std::io::ErrorKind::PermissionDenied {
                     tracing::warn!(path = %path.display(), "Permission denied");
-                    skipped_permission += 1;
+                    skipped.push(SkippedFile {
+                        path: path.to_path_buf(),
+                        reason: SkipReason::PermissionDenied,
+                    });
                 } else {
                     tracing::warn!(path = %path.display(), error = %e, "Failed to get metadata");
                 }
@@ -99,28 +406,63 @@ pub fn scan_directory(
             }
         }
 
-        // Filter by extension
-        if let Some(ext) = path.extension() {
-            let ext_str = ext.to_string_lossy();
-            match ext_str.as_ref() {
-                "ts" | "tsx" | "js" | "jsx" | "rs" | "py" | "go" | "cpp" | "cc" | "cxx" | "hpp" | "h" => {
-                    files.push(path.to_path_buf());
-                }
-                _ => {}
-            }
+        if is_plain_text {
+            plain_text_files.push(path.to_path_buf());
         }
+        files.push(path.to_path_buf());
     }
 
     files.sort();
+    plain_text_files.sort();
+    skipped.sort_by(|a, b| a.path.cmp(&b.path));
 
+    let count = |reason| skipped.iter().filter(|s| s.reason == reason).count();
     tracing::info!(
         files_found = files.len(),
-        skipped_large = skipped_large,
-        skipped_permission = skipped_permission,
+        skipped_large = count(SkipReason::TooLarge),
+        skipped_permission = count(SkipReason::PermissionDenied),
+        skipped_binary = count(SkipReason::Binary),
+        unsupported_files,
         "Directory scan completed"
     );
 
-    Ok(files)
+    Ok(ScanResult { files, skipped, unsupported_files, plain_text_files })
+}
+
+/// Matcher for the [`EXCLUDE_FILE`] in `root`, empty if there is none
+///
+/// Lines that are not valid patterns are skipped with a warning, as git does.
+fn load_exclude_file(root: &Path) -> Gitignore {
+    let file = root.join(EXCLUDE_FILE);
+    if !file.is_file() {
+        return Gitignore::empty();
+    }
+
+    let mut builder = GitignoreBuilder::new(root);
+    if let Some(e) = builder.add(&file) {
+        tracing::warn!(path = %file.display(), error = %e, "Ignoring invalid exclude file lines");
+    }
+    match builder.build() {
+        Ok(matcher) => {
+            tracing::debug!(patterns = matcher.num_ignores(), "Exclude file loaded");
+            matcher
+        }
+        Err(e) => {
+            tracing::warn!(path = %file.display(), error = %e, "Failed to load exclude file");
+            Gitignore::empty()
+        }
+    }
+}
+
+/// Whether the start of a file contains a NUL byte, which source text never does
+fn looks_binary(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(BINARY_SNIFF_LEN).read_to_end(&mut head))
+        .is_ok()
+        && head.contains(&0)
 }
 
 #[derive(Debug, Error)]
@@ -144,6 +486,31 @@ mod tests {
     use std::fs::{self, File};
     use tempfile::TempDir;
 
+    #[test]
+    fn test_scan_extension_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        File::create(temp_dir.path().join("app.es")).unwrap();
+        File::create(temp_dir.path().join("page.ts.tmpl")).unwrap();
+        File::create(temp_dir.path().join("notes.tmpl")).unwrap();
+
+        let options = ScanOptions {
+            extension_overrides: HashMap::from([
+                (".es".to_string(), "javascript".to_string()),
+                ("ts.tmpl".to_string(), "typescript".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let mut files = scan_with_options(temp_dir.path(), &options).unwrap().files;
+        files.sort();
+        assert_eq!(files, vec![temp_dir.path().join("app.es"), temp_dir.path().join("page.ts.tmpl")]);
+
+        let overrides = &options.extension_overrides;
+        assert_eq!(override_language(Path::new("src/app.es"), overrides), Some("javascript"));
+        assert_eq!(override_language(Path::new("src/page.ts.tmpl"), overrides), Some("typescript"));
+        assert_eq!(override_language(Path::new("src/notes.tmpl"), overrides), None);
+        assert_eq!(override_language(Path::new("src/es"), overrides), None);
+    }
+
     #[test]
     fn test_scan_empty_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -185,6 +552,60 @@ mod tests {
         assert_eq!(result[0].file_name().unwrap().to_str().unwrap(), "main.ts");
     }
 
+    #[test]
+    fn test_scan_max_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let nested = root.join("vendor").join("lib");
+        fs::create_dir_all(&nested).unwrap();
+        File::create(root.join("main.ts")).unwrap();
+        File::create(root.join("vendor").join("index.ts")).unwrap();
+        File::create(nested.join("deep.ts")).unwrap();
+
+        let scan = |max_depth| {
+            let options = ScanOptions { max_depth, ..Default::default() };
+            scan_with_options(root, &options).unwrap().files
+        };
+
+        let files = scan(Some(2));
+        assert_eq!(files.len(), 2);
+        assert!(!files.contains(&nested.join("deep.ts")));
+
+        let files = scan(Some(3));
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&nested.join("deep.ts")));
+
+        assert_eq!(scan(None).len(), 3);
+    }
+
+    #[test]
+    fn test_scan_include_patterns_and_languages() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        File::create(root.join("src/main.ts")).unwrap();
+        File::create(root.join("src/tool.py")).unwrap();
+        File::create(root.join("build.ts")).unwrap();
+
+        let scan = |include_patterns: &[&str], languages: &[&str]| {
+            let options = ScanOptions {
+                include_patterns: include_patterns.iter().map(|p| p.to_string()).collect(),
+                languages: languages.iter().map(|l| l.to_string()).collect(),
+                ..Default::default()
+            };
+            let files = scan_with_options(root, &options).unwrap().files;
+            files
+                .iter()
+                .map(|f| f.strip_prefix(root).unwrap().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(scan(&["src/**"], &[]), vec!["src/main.ts", "src/tool.py"]);
+        assert_eq!(scan(&[], &["python"]), vec!["src/tool.py"]);
+        assert_eq!(scan(&["src/**"], &["typescript"]), vec!["src/main.ts"]);
+    }
+
     #[test]
     fn test_scan_excludes_custom_pattern() {
         let temp_dir = TempDir::new().unwrap();
@@ -198,6 +619,68 @@ mod tests {
         assert_eq!(result[0].file_name().unwrap().to_str().unwrap(), "main.ts");
     }
 
+    #[test]
+    fn test_scan_excludes_paths_from_exclude_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(
+            root.join(EXCLUDE_FILE),
+            "# Build output\ngenerated/**\n!generated/keep.ts\n/vendor.ts\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("generated/api")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        File::create(root.join("generated/api/client.ts")).unwrap();
+        File::create(root.join("generated/keep.ts")).unwrap();
+        File::create(root.join("generated/legacy.ts")).unwrap();
+        File::create(root.join("vendor.ts")).unwrap();
+        File::create(root.join("src/vendor.ts")).unwrap();
+        File::create(root.join("src/main.ts")).unwrap();
+
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| f.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect()
+        };
+
+        // Negation re-includes a file, and `/` anchors a pattern to the root
+        let files = scan_directory(root, &[]).unwrap();
+        assert_eq!(relative(files), ["generated/keep.ts", "src/main.ts", "src/vendor.ts"]);
+
+        // --exclude patterns apply on top of the exclude file
+        let files = scan_directory(root, &["generated/keep.ts".to_string()]).unwrap();
+        assert_eq!(relative(files), ["src/main.ts", "src/vendor.ts"]);
+    }
+
+    #[test]
+    fn test_scan_reincludes_negated_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("node_modules/@myorg")).unwrap();
+        fs::create_dir_all(root.join("node_modules/lodash")).unwrap();
+        File::create(root.join("node_modules/@myorg/x.ts")).unwrap();
+        File::create(root.join("node_modules/lodash/y.js")).unwrap();
+        File::create(root.join("main.ts")).unwrap();
+
+        let patterns = ["node_modules/**".to_string(), "!node_modules/@myorg/**".to_string()];
+        let files = scan_directory(root, &patterns).unwrap();
+        assert!(files.contains(&root.join("node_modules/@myorg/x.ts")));
+        assert!(!files.contains(&root.join("node_modules/lodash/y.js")));
+        assert!(files.contains(&root.join("main.ts")));
+
+        // A re-include also beats .gitignore, which prunes node_modules
+        // before the exclude patterns are checked
+        fs::write(root.join(".gitignore"), "node_modules\n").unwrap();
+        let files = scan_directory(root, &["!node_modules/@myorg/**".to_string()]).unwrap();
+        assert_eq!(
+            files,
+            vec![root.join("main.ts"), root.join("node_modules/@myorg/x.ts")]
+        );
+    }
+
     #[test]
     fn test_scan_filters_extensions() {
         let temp_dir = TempDir::new().unwrap();
@@ -217,6 +700,58 @@ mod tests {
         assert!(file_names.contains(&"app.py"));
     }
 
+    #[test]
+    fn test_scan_counts_unsupported_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        File::create(root.join("notes.txt")).unwrap();
+        File::create(root.join("todo.txt")).unwrap();
+
+        let result = scan_directory_detailed(root, &[]).unwrap();
+        assert!(result.files.is_empty());
+        assert_eq!(result.unsupported_files, 2);
+    }
+
+    #[test]
+    fn test_scan_reports_oversized_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        File::create(root.join("main.ts")).unwrap();
+        let large = File::create(root.join("bundle.js")).unwrap();
+        large.set_len(MAX_FILE_SIZE + 1).unwrap();
+
+        let result = scan_directory_detailed(root, &[]).unwrap();
+        assert_eq!(result.files, vec![root.join("main.ts")]);
+        assert_eq!(
+            result.skipped,
+            vec![SkippedFile {
+                path: root.join("bundle.js"),
+                reason: SkipReason::TooLarge,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_scan_reports_binary_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        std::fs::write(root.join("main.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(root.join("compiled.js"), b"\x7fELF\x02\x01\x00\x00\xff").unwrap();
+
+        let result = scan_directory_detailed(root, &[]).unwrap();
+        assert_eq!(result.files, vec![root.join("main.ts")]);
+        assert_eq!(
+            result.skipped,
+            vec![SkippedFile {
+                path: root.join("compiled.js"),
+                reason: SkipReason::Binary,
+            }]
+        );
+    }
+
     #[test]
     fn test_scan_hidden_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,178 @@
+//! Vue and Svelte single-file components.
+//!
+//! A component mixes markup, styles and one or more `<script>` blocks. Only
+//! the scripts are analyzed: [`script_source`] keeps each script at its
+//! original lines and blanks everything else, so a TypeScript parser can read
+//! the result and the line numbers it reports match the component file.
+
+use std::ops::Range;
+use std::path::Path;
+
+/// A `<script>` block of a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptBlock {
+    /// Code between the opening and closing tags
+    pub content: String,
+    /// Number of lines before the code starts in the component file
+    pub line_offset: usize,
+}
+
+/// Whether `path` is a Vue or Svelte component
+pub fn is_component(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e, "vue" | "svelte"))
+}
+
+/// Extract the `<script>` blocks of a component, in file order
+pub fn extract_script_blocks(source: &str) -> Vec<ScriptBlock> {
+    find_blocks(source, "script")
+        .into_iter()
+        .map(|block| ScriptBlock {
+            content: source[block.content.clone()].to_string(),
+            line_offset: source[..block.content.start].matches('\n').count(),
+        })
+        .collect()
+}
+
+/// Component source reduced to its scripts, with each script at its original lines
+pub fn script_source(source: &str) -> String {
+    let mut script = String::new();
+    let mut line = 0;
+
+    for block in extract_script_blocks(source) {
+        script.push_str(&"\n".repeat(block.line_offset.saturating_sub(line)));
+        line = block.line_offset.max(line) + block.content.matches('\n').count();
+        script.push_str(&block.content);
+    }
+
+    script
+}
+
+/// Component source without its `<script>` and `<style>` blocks
+///
+/// This is the markup, whose expressions may reference script bindings.
+pub fn template_source(source: &str) -> String {
+    let mut removed: Vec<Range<usize>> = find_blocks(source, "script")
+        .into_iter()
+        .chain(find_blocks(source, "style"))
+        .map(|block| block.outer)
+        .collect();
+    removed.sort_by_key(|range| range.start);
+
+    let mut template = String::new();
+    let mut position = 0;
+    for range in removed {
+        template.push_str(&source[position..range.start]);
+        position = range.end;
+    }
+    template.push_str(&source[position..]);
+
+    template
+}
+
+/// Byte ranges of one `<tag>...</tag>` block
+struct Block {
+    /// The whole block, tags included
+    outer: Range<usize>,
+    /// The text between the tags
+    content: Range<usize>,
+}
+
+fn find_blocks(source: &str, tag: &str) -> Vec<Block> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}", tag);
+    let mut blocks = Vec::new();
+    let mut position = 0;
+
+    while let Some(found) = source[position..].find(&open) {
+        let start = position + found;
+        let after_name = start + open.len();
+        position = after_name;
+
+        // `<scripts>` or `<style-guide>` are different tags
+        let is_tag = source[after_name..]
+            .chars()
+            .next()
+            .is_some_and(|c| c == '>' || c == '/' || c.is_whitespace());
+        if !is_tag {
+            continue;
+        }
+
+        let Some(tag_end) = source[after_name..].find('>') else {
+            break;
+        };
+        let content_start = after_name + tag_end + 1;
+        if source[..content_start].ends_with("/>") {
+            position = content_start;
+            continue;
+        }
+
+        let (content_end, end) = match source[content_start..].find(&close) {
+            Some(found) => {
+                let content_end = content_start + found;
+                let end = source[content_end..]
+                    .find('>')
+                    .map_or(source.len(), |i| content_end + i + 1);
+                (content_end, end)
+            }
+            None => (source.len(), source.len()),
+        };
+
+        blocks.push(Block {
+            outer: start..end,
+            content: content_start..content_end,
+        });
+        position = end;
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPONENT: &str = "<template>\n  <button @click=\"increment\">{{ count }}</button>\n</template>\n\n<script setup lang=\"ts\">\nimport { ref } from 'vue';\nconst count = ref(0);\nfunction increment() {\n  count.value++;\n}\n</script>\n\n<style scoped>\nbutton { color: red; }\n</style>\n";
+
+    #[test]
+    fn test_is_component() {
+        assert!(is_component(Path::new("src/App.vue")));
+        assert!(is_component(Path::new("src/Counter.svelte")));
+        assert!(!is_component(Path::new("src/main.ts")));
+    }
+
+    #[test]
+    fn test_extract_script_blocks() {
+        let blocks = extract_script_blocks(COMPONENT);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].line_offset, 4);
+        assert!(blocks[0].content.starts_with("\nimport { ref }"));
+    }
+
+    #[test]
+    fn test_extract_multiple_script_blocks() {
+        let source = "<script context=\"module\">\nexport const a = 1;\n</script>\n\n<script>\nlet b = 2;\n</script>\n\n<p>{b}</p>\n";
+        let blocks = extract_script_blocks(source);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].line_offset, 0);
+        assert_eq!(blocks[1].line_offset, 4);
+    }
+
+    #[test]
+    fn test_script_source_keeps_original_lines() {
+        let script = script_source(COMPONENT);
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(lines[7], "function increment() {");
+        assert!(!script.contains("<template>"));
+        assert!(!script.contains("color: red"));
+    }
+
+    #[test]
+    fn test_template_source() {
+        let template = template_source(COMPONENT);
+        assert!(template.contains("@click=\"increment\""));
+        assert!(!template.contains("ref(0)"));
+        assert!(!template.contains("color: red"));
+    }
+}
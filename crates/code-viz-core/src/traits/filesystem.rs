@@ -1,3 +1,4 @@
+use crate::scanner::ScanResult;
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
@@ -9,6 +10,16 @@ pub trait FileSystem: Send + Sync {
     /// Read all files in a directory recursively.
     fn read_dir_recursive(&self, path: &Path) -> Result<Vec<PathBuf>>;
 
+    /// Read all files in a directory recursively, reporting the files that were skipped.
+    fn read_dir_recursive_detailed(&self, path: &Path) -> Result<ScanResult> {
+        Ok(ScanResult {
+            files: self.read_dir_recursive(path)?,
+            skipped: Vec::new(),
+            unsupported_files: 0,
+            plain_text_files: Vec::new(),
+        })
+    }
+
     /// Write content to a file.
     fn write(&self, path: &Path, content: &str) -> Result<()>;
 
@@ -0,0 +1,55 @@
+//! Benchmarks for symbol graph construction and reachability analysis.
+//!
+//! Run with `cargo bench -p code-viz-dead-code`.
+
+use code_viz_dead_code::{ReachabilityAnalyzer, SymbolGraphBuilder};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::path::Path;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+const FILE_COUNTS: [usize; 3] = [100, 500, 2000];
+const FAN_OUT: usize = 3;
+
+fn bench_build_graph(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_graph");
+    group.sample_size(10);
+
+    for file_count in FILE_COUNTS {
+        let files = common::synthetic_files(file_count, FAN_OUT);
+        group.bench_with_input(BenchmarkId::from_parameter(file_count), &files, |b, files| {
+            b.iter(|| SymbolGraphBuilder::new().build_graph(files.clone()).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_reachability(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reachability");
+
+    for file_count in FILE_COUNTS {
+        let graph = SymbolGraphBuilder::new()
+            .build_graph(common::synthetic_files(file_count, FAN_OUT))
+            .unwrap();
+        let entry_points: Vec<String> = graph
+            .exports
+            .get(Path::new("src/mod0.ts"))
+            .cloned()
+            .unwrap_or_default();
+
+        group.bench_with_input(BenchmarkId::from_parameter(file_count), &graph, |b, graph| {
+            b.iter(|| {
+                ReachabilityAnalyzer::new(graph.clone())
+                    .analyze(entry_points.clone())
+                    .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_build_graph, bench_reachability);
+criterion_main!(benches);
@@ -0,0 +1,403 @@
+//! Source files read straight out of `.tar`, `.tar.gz` and `.zip` archives.
+//!
+//! CI artifacts are often tarballs, so these are analyzed in memory instead of
+//! being extracted first. Each entry gets a virtual path under the archive,
+//! e.g. `build.tar.gz/src/main.ts`, which strips to the entry's own path
+//! relative to the analyzed path.
+
+use crate::{
+    check_file_count, decode_source, file_selection, scan_options, template_identifiers,
+    AnalysisConfig, AnalysisError, Templates,
+};
+use code_viz_core::scanner::{
+    file_language, ExcludeMatcher, FileSelection, ScanResult, SkipReason, SkippedFile,
+};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// Entries larger than this are skipped, like files on disk
+const MAX_ENTRY_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Archives with more entries than this are rejected
+const MAX_ENTRIES: usize = 100_000;
+
+/// Total bytes read from all entries before the archive is rejected, so a
+/// highly compressed archive cannot exhaust memory
+const MAX_TOTAL_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Leading bytes checked for NUL when detecting binary entries
+const BINARY_SNIFF_LEN: usize = 8000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else {
+        None
+    }
+}
+
+/// Whether `path` is a `.tar`, `.tar.gz`/`.tgz` or `.zip` file
+pub fn is_archive(path: &Path) -> bool {
+    archive_kind(path).is_some() && path.is_file()
+}
+
+/// Source files of an archive selected by the analysis settings
+#[derive(Debug, Default)]
+pub(crate) struct ArchiveSources {
+    /// Virtual path and decoded source of each selected entry
+    pub sources: Vec<(PathBuf, String)>,
+    /// Markup identifiers of the selected components
+    pub templates: Templates,
+    /// Source entries that were too large or binary
+    pub skipped: Vec<SkippedFile>,
+    /// Entries left out because no analyzer supports their extension
+    pub unsupported_files: usize,
+}
+
+impl ArchiveSources {
+    /// The selected entries as the equivalent directory scan
+    pub(crate) fn scan_result(&self) -> ScanResult {
+        ScanResult {
+            files: self.sources.iter().map(|(path, _)| path.clone()).collect(),
+            skipped: self.skipped.clone(),
+            unsupported_files: self.unsupported_files,
+            plain_text_files: Vec::new(),
+        }
+    }
+}
+
+/// Read the source entries of the archive at `path`
+///
+/// Entries are filtered like a directory scan: hidden paths, exclude and
+/// include patterns, `max_depth` and `languages` all apply to the path inside
+/// the archive.
+pub(crate) fn read_archive(
+    path: &Path,
+    config: &AnalysisConfig,
+) -> Result<ArchiveSources, AnalysisError> {
+    let kind = archive_kind(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a supported archive", path.display()),
+        )
+    })?;
+    let mut selector = EntrySelector {
+        root: path,
+        config,
+        exclude: ExcludeMatcher::new(path, &config.exclude_patterns)?,
+        selection: file_selection(path, &scan_options(config))?,
+        selected: ArchiveSources::default(),
+        max_entries: MAX_ENTRIES,
+        max_total_size: MAX_TOTAL_SIZE,
+        entries: 0,
+        total_size: 0,
+    };
+
+    tracing::info!(path = %path.display(), "Reading archive");
+    let file = File::open(path)?;
+    match kind {
+        ArchiveKind::Tar => read_tar(tar::Archive::new(file), &mut selector)?,
+        ArchiveKind::TarGz => read_tar(
+            tar::Archive::new(flate2::read::GzDecoder::new(file)),
+            &mut selector,
+        )?,
+        ArchiveKind::Zip => read_zip(file, &mut selector)?,
+    }
+    Ok(selector.selected)
+}
+
+fn read_tar(
+    mut archive: tar::Archive<impl Read>,
+    selector: &mut EntrySelector,
+) -> Result<(), AnalysisError> {
+    for entry in archive.entries()? {
+        let entry = entry?;
+        selector.count_entry()?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.into_owned();
+        let size = entry.size();
+        selector.add(&name, size, entry)?;
+    }
+    Ok(())
+}
+
+fn read_zip(file: File, selector: &mut EntrySelector) -> Result<(), AnalysisError> {
+    let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+    for index in 0..archive.len() {
+        selector.count_entry()?;
+        let entry = archive.by_index(index).map_err(io::Error::other)?;
+        if !entry.is_file() {
+            continue;
+        }
+        // Names escaping the archive root are not valid entry paths
+        let Some(name) = entry.enclosed_name() else {
+            tracing::warn!(
+                name = entry.name(),
+                "Skipping archive entry with unsafe path"
+            );
+            continue;
+        };
+        let size = entry.size();
+        selector.add(&name, size, entry)?;
+    }
+    Ok(())
+}
+
+/// Applies the scan filters to archive entries and decodes the ones kept
+struct EntrySelector<'a> {
+    root: &'a Path,
+    config: &'a AnalysisConfig,
+    exclude: ExcludeMatcher,
+    selection: FileSelection,
+    selected: ArchiveSources,
+    /// Entries allowed before the archive is rejected
+    max_entries: usize,
+    /// Bytes allowed to be read from all entries together
+    max_total_size: u64,
+    /// Entries seen so far
+    entries: usize,
+    /// Bytes read from entries so far
+    total_size: u64,
+}
+
+impl EntrySelector<'_> {
+    /// Count the next entry, failing once the archive has too many
+    fn count_entry(&mut self) -> io::Result<()> {
+        self.entries += 1;
+        if self.entries > self.max_entries {
+            tracing::error!(limit = self.max_entries, "Too many archive entries");
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} has more than {} entries",
+                    self.root.display(),
+                    self.max_entries
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, name: &Path, size: u64, entry: impl Read) -> Result<(), AnalysisError> {
+        // Entry path without `./` prefixes; `..` cannot be placed under the root
+        let mut relative = PathBuf::new();
+        for component in name.components() {
+            match component {
+                Component::Normal(part) => relative.push(part),
+                Component::CurDir => {}
+                _ => return Ok(()),
+            }
+        }
+
+        let hidden = relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        let too_deep = self
+            .config
+            .max_depth
+            .is_some_and(|depth| relative.components().count() > depth);
+        if relative.as_os_str().is_empty()
+            || hidden
+            || too_deep
+            || self.exclude.is_excluded(&relative, false)
+        {
+            return Ok(());
+        }
+
+        if file_language(&relative, &self.config.extension_overrides).is_none() {
+            self.selected.unsupported_files += 1;
+            return Ok(());
+        }
+        if !self.selection.is_selected(&relative) {
+            return Ok(());
+        }
+
+        let path = self.root.join(&relative);
+        if size > MAX_ENTRY_SIZE {
+            tracing::warn!(path = %path.display(), size, "Skipping large archive entry");
+            self.selected.skipped.push(SkippedFile {
+                path,
+                reason: SkipReason::TooLarge,
+            });
+            return Ok(());
+        }
+
+        // Declared sizes can lie, so the reads themselves are capped
+        let remaining = self.max_total_size - self.total_size;
+        let mut bytes = Vec::with_capacity(size.min(remaining) as usize);
+        entry
+            .take(MAX_ENTRY_SIZE.min(remaining + 1))
+            .read_to_end(&mut bytes)?;
+        self.total_size += bytes.len() as u64;
+        if self.total_size > self.max_total_size {
+            tracing::error!(limit = self.max_total_size, "Archive expands too far");
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{} expands to more than {} bytes",
+                    self.root.display(),
+                    self.max_total_size
+                ),
+            )
+            .into());
+        }
+        if bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+            tracing::warn!(path = %path.display(), "Skipping binary archive entry");
+            self.selected.skipped.push(SkippedFile {
+                path,
+                reason: SkipReason::Binary,
+            });
+            return Ok(());
+        }
+
+        if code_viz_core::sfc::is_component(&path) {
+            let identifiers = template_identifiers(&String::from_utf8_lossy(&bytes));
+            self.selected.templates.insert(path.clone(), identifiers);
+        }
+        let source = decode_source(&path, bytes);
+        self.selected.sources.push((path, source));
+        // Fail on the first file over the limit instead of reading the rest
+        check_file_count(self.selected.sources.len(), self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const ENTRIES: [(&str, &[u8]); 6] = [
+        ("./src/main.ts", b"export function main() {}\n"),
+        ("src/util.js", b"export const util = 1;\n"),
+        ("src/blob.ts", b"\0\0\0"),
+        ("node_modules/dep/index.js", b"module.exports = {};\n"),
+        (".cache/cached.ts", b"export const cached = 1;\n"),
+        ("README.md", b"# Readme\n"),
+    ];
+
+    fn tar_bytes() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, contents) in ENTRIES {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, contents).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn zip_bytes() -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+        for (name, contents) in ENTRIES {
+            let name = name.trim_start_matches("./");
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn read(name: &str, bytes: &[u8]) -> (Vec<String>, ArchiveSources) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, bytes).unwrap();
+        assert!(is_archive(&path));
+
+        let selected = read_archive(&path, &AnalysisConfig::default()).unwrap();
+        let mut names: Vec<String> = selected
+            .sources
+            .iter()
+            .map(|(p, _)| p.strip_prefix(&path).unwrap().display().to_string())
+            .collect();
+        names.sort();
+        (names, selected)
+    }
+
+    #[test]
+    fn test_read_archive_formats_apply_scan_filters() {
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(&tar_bytes()).unwrap();
+        let archives = [
+            ("app.tar", tar_bytes()),
+            ("app.tar.gz", gz.finish().unwrap()),
+            ("app.zip", zip_bytes()),
+        ];
+
+        for (name, bytes) in archives {
+            let (names, selected) = read(name, &bytes);
+            assert_eq!(names, vec!["src/main.ts", "src/util.js"], "{}", name);
+            assert_eq!(selected.unsupported_files, 1, "{}", name);
+            assert_eq!(selected.skipped.len(), 1, "{}", name);
+            assert_eq!(selected.skipped[0].reason, SkipReason::Binary);
+        }
+    }
+
+    #[test]
+    fn test_read_archive_limits_apply_while_reading() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("app.tar");
+        let config = AnalysisConfig {
+            max_files: Some(1),
+            ..Default::default()
+        };
+        let read_with = |max_entries, max_total_size| {
+            let mut selector = EntrySelector {
+                root: &path,
+                config: &config,
+                exclude: ExcludeMatcher::new(&path, &[]).unwrap(),
+                selection: file_selection(&path, &scan_options(&config)).unwrap(),
+                selected: ArchiveSources::default(),
+                max_entries,
+                max_total_size,
+                entries: 0,
+                total_size: 0,
+            };
+            let result = read_tar(tar::Archive::new(tar_bytes().as_slice()), &mut selector);
+            (result, selector.entries, selector.selected.sources.len())
+        };
+
+        // Rejected on the second source file, before the remaining entries
+        let (result, entries, sources) = read_with(MAX_ENTRIES, MAX_TOTAL_SIZE);
+        assert!(matches!(
+            result,
+            Err(AnalysisError::TooManyFiles { found: 2, limit: 1 })
+        ));
+        assert_eq!((entries, sources), (2, 2));
+
+        let (result, entries, _) = read_with(1, MAX_TOTAL_SIZE);
+        assert!(matches!(result, Err(AnalysisError::Io(_))));
+        assert_eq!(entries, 2);
+
+        // The first entry alone is larger than the whole budget
+        let (result, _, sources) = read_with(MAX_ENTRIES, 10);
+        assert!(matches!(result, Err(AnalysisError::Io(_))));
+        assert_eq!(sources, 0);
+    }
+
+    #[test]
+    fn test_is_archive_requires_existing_file() {
+        assert!(!is_archive(Path::new("missing.tar.gz")));
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("looks.zip");
+        std::fs::create_dir(&dir).unwrap();
+        assert!(!is_archive(&dir));
+    }
+}
@@ -3,13 +3,23 @@
 //! This module handles persisting and loading symbol graphs to/from
 //! disk for incremental analysis. The cache stores symbol graphs
 //! with file hashes for invalidation.
+//!
+//! Long-running processes such as the web server also keep recently loaded
+//! graphs in a [`MemoryGraphCache`], so repeated requests for the same
+//! repository skip opening and deserializing the sled database.
+
+pub use crate::CacheMode;
 
+use crate::session::{fingerprint_files, Fingerprint};
 use crate::symbol_graph::SymbolGraph;
 use ahash::AHashMap as HashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
 
 /// Error type for cache operations
@@ -33,7 +43,7 @@ pub enum CacheError {
 }
 
 /// Current cache schema version
-const CACHE_VERSION: u32 = 1;
+const CACHE_VERSION: u32 = 19;
 
 /// Cached symbol graph with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,28 +103,54 @@ impl SymbolGraphCache {
         })
     }
 
+    /// Open a copy of an existing cache, leaving the cache itself untouched
+    ///
+    /// Sled writes to its files even when only reading, so the database is
+    /// copied to a temporary directory that is removed again on drop. Every
+    /// call copies the whole database, which takes time and temporary disk
+    /// space in proportion to the cache size; only `CacheMode::ReadOnly`
+    /// runs pay for it.
+    ///
+    /// # Returns
+    /// `None` if there is no cache database in `cache_dir`
+    pub fn open_read_only(cache_dir: &Path) -> Result<Option<Self>, CacheError> {
+        static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+        let db_path = cache_dir.join("symbols.db");
+        if !db_path.exists() {
+            return Ok(None);
+        }
+        let copy = std::env::temp_dir().join(format!(
+            "code-viz-cache-{}-{}",
+            std::process::id(),
+            COPIES.fetch_add(1, Ordering::Relaxed)
+        ));
+        if let Err(e) = copy_dir(&db_path, &copy) {
+            let _ = fs::remove_dir_all(&copy);
+            return Err(e.into());
+        }
+        let db = sled::Config::new()
+            .path(&copy)
+            .temporary(true)
+            .open()
+            .map_err(|e| CacheError::DatabaseOpen(e.to_string()))?;
+        Ok(Some(Self {
+            db,
+            cache_dir: cache_dir.to_path_buf(),
+        }))
+    }
+
     /// Save symbol graph to cache
     ///
     /// # Arguments
     /// * `graph` - The symbol graph to save
+    /// * `files` - Files the graph was built from, including those without
+    ///   exports, so the cache stays current until one of them changes
     ///
     /// # Returns
     /// Ok if saved successfully
-    pub fn save(&self, graph: &SymbolGraph) -> Result<(), CacheError> {
-        // Calculate file hashes from the graph
-        let mut file_hashes = HashMap::new();
-        for path in graph.exports.keys() {
-            if let Ok(metadata) = fs::metadata(path) {
-                if let Ok(modified) = metadata.modified() {
-                    // Use modification time as hash (simple but effective)
-                    let hash = modified
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs();
-                    file_hashes.insert(path.clone(), hash);
-                }
-            }
-        }
+    pub fn save(&self, graph: &SymbolGraph, files: &[PathBuf]) -> Result<(), CacheError> {
+        let file_hashes = file_hashes(files);
 
         let cached = CachedSymbolGraph {
             version: CACHE_VERSION,
@@ -180,6 +216,16 @@ impl SymbolGraphCache {
         Ok(Some(cached.graph))
     }
 
+    /// Cached symbol graph if it is current for `files`
+    ///
+    /// Unlike `invalidate_if_stale` and `load`, nothing is removed from the
+    /// database when the cached graph is stale, outdated or corrupted.
+    pub fn peek(&self, files: &[PathBuf]) -> Option<SymbolGraph> {
+        let value = self.db.get(b"symbol_graph").ok()??;
+        let cached: CachedSymbolGraph = bincode::deserialize(&value).ok()?;
+        (cached.version == CACHE_VERSION && !is_stale(&cached, files)).then_some(cached.graph)
+    }
+
     /// Check if cache is stale and invalidate if needed
     ///
     /// Compares file modification times with cached hashes.
@@ -212,53 +258,208 @@ impl SymbolGraphCache {
             return Ok(true);
         }
 
-        // Check if any file has been modified
-        for file in files {
-            if let Ok(metadata) = fs::metadata(file) {
-                if let Ok(modified) = metadata.modified() {
-                    let current_hash = modified
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs();
-
-                    // Compare with cached hash
-                    match cached.file_hashes.get(file) {
-                        Some(&cached_hash) if cached_hash == current_hash => {
-                            // File hasn't changed
-                            continue;
-                        }
-                        _ => {
-                            // File changed or not in cache - invalidate
-                            tracing::debug!("File changed, invalidating cache: {:?}", file);
-                            let _ = self.db.remove(b"symbol_graph");
-                            return Ok(true);
-                        }
+        if is_stale(&cached, files) {
+            let _ = self.db.remove(b"symbol_graph");
+            return Ok(true);
+        }
+
+        // Cache is valid
+        Ok(false)
+    }
+}
+
+/// Copy the directory `from` with everything in it to `to`
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether any of `files` changed, appeared or disappeared since `cached` was saved
+fn is_stale(cached: &CachedSymbolGraph, files: &[PathBuf]) -> bool {
+    // Check if any file has been modified
+    for file in files {
+        if fs::metadata(file).is_ok() {
+            if let Some(current_hash) = file_hash(file) {
+                // Compare with cached hash
+                match cached.file_hashes.get(file) {
+                    Some(&cached_hash) if cached_hash == current_hash => {
+                        // File hasn't changed
+                        continue;
+                    }
+                    _ => {
+                        // File changed or not in cache
+                        tracing::debug!("File changed, cache is stale: {:?}", file);
+                        return true;
                     }
                 }
-            } else {
-                // File doesn't exist anymore - invalidate
-                tracing::debug!("File missing, invalidating cache: {:?}", file);
-                let _ = self.db.remove(b"symbol_graph");
-                return Ok(true);
             }
+        } else {
+            // File doesn't exist anymore
+            tracing::debug!("File missing, cache is stale: {:?}", file);
+            return true;
         }
+    }
 
-        // Also check if there are files in cache that are no longer being analyzed
-        // This happens when files are deleted from the project
-        let current_files: std::collections::HashSet<_> = files.iter().collect();
-        for cached_file in cached.file_hashes.keys() {
-            if !current_files.contains(cached_file) {
-                tracing::debug!(
-                    "File removed from analysis, invalidating cache: {:?}",
-                    cached_file
-                );
-                let _ = self.db.remove(b"symbol_graph");
-                return Ok(true);
-            }
+    // Also check if there are files in cache that are no longer being analyzed
+    // This happens when files are deleted from the project
+    let current_files: std::collections::HashSet<_> = files.iter().collect();
+    for cached_file in cached.file_hashes.keys() {
+        if !current_files.contains(cached_file) {
+            tracing::debug!(
+                "File removed from analysis, cache is stale: {:?}",
+                cached_file
+            );
+            return true;
         }
+    }
 
-        // Cache is valid
-        Ok(false)
+    false
+}
+
+/// Modification time of `path` in nanoseconds, used as its hash (simple but
+/// effective); whole seconds would miss a file rewritten within the second
+fn file_hash(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        modified
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64,
+    )
+}
+
+fn file_hashes<'a>(paths: impl IntoIterator<Item = &'a PathBuf>) -> HashMap<PathBuf, u64> {
+    paths
+        .into_iter()
+        .filter_map(|path| Some((path.clone(), file_hash(path)?)))
+        .collect()
+}
+
+/// Key of a graph in the [`MemoryGraphCache`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphCacheKey {
+    /// Root directory that was analyzed
+    pub root: PathBuf,
+
+    /// Hash of the analysis settings that affect which files are in the graph
+    pub config_hash: u64,
+}
+
+struct MemoryEntry {
+    key: GraphCacheKey,
+    loaded_at: Instant,
+    graph: SymbolGraph,
+    fingerprints: HashMap<PathBuf, Fingerprint>,
+}
+
+/// In-process LRU of symbol graphs, checked before the sled cache
+///
+/// Entries expire after a TTL and are dropped as soon as a file they were
+/// built from is added, removed or modified.
+#[derive(Default)]
+pub struct MemoryGraphCache {
+    /// Least recently used first
+    entries: Mutex<VecDeque<MemoryEntry>>,
+    hits: AtomicUsize,
+}
+
+impl MemoryGraphCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache shared by every analysis in this process
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<MemoryGraphCache> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Number of lookups answered from memory
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Get the graph for `key` if it is younger than `ttl` and none of
+    /// `files` changed since it was inserted
+    ///
+    /// Expired and stale entries are removed.
+    pub fn get(
+        &self,
+        key: &GraphCacheKey,
+        files: &[PathBuf],
+        ttl: Duration,
+    ) -> Option<SymbolGraph> {
+        let mut entries = self.entries.lock().unwrap();
+        let index = entries.iter().position(|e| e.key == *key)?;
+        let entry = entries.remove(index)?;
+
+        if entry.loaded_at.elapsed() > ttl {
+            tracing::debug!("In-memory symbol graph expired: {:?}", key.root);
+            return None;
+        }
+        if entry.fingerprints != fingerprint_files(files) {
+            tracing::debug!(
+                "Files changed, dropping in-memory symbol graph: {:?}",
+                key.root
+            );
+            return None;
+        }
+
+        let graph = entry.graph.clone();
+        entries.push_back(entry);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(graph)
+    }
+
+    /// Store `graph` as built from `files`, evicting the least recently
+    /// used entries beyond `capacity` (0 disables the cache)
+    pub fn insert(
+        &self,
+        key: GraphCacheKey,
+        graph: SymbolGraph,
+        files: &[PathBuf],
+        capacity: usize,
+    ) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|e| e.key != key);
+        if capacity == 0 {
+            return;
+        }
+
+        entries.push_back(MemoryEntry {
+            key,
+            loaded_at: Instant::now(),
+            graph,
+            fingerprints: fingerprint_files(files),
+        });
+        while entries.len() > capacity {
+            entries.pop_front();
+        }
+    }
+
+    /// Drop the graph for `key`, if any
+    pub fn remove(&self, key: &GraphCacheKey) {
+        self.entries.lock().unwrap().retain(|e| e.key != *key);
+    }
+
+    /// Number of graphs currently held
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether no graph is held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -282,8 +483,14 @@ mod tests {
             path: PathBuf::from("test.ts"),
             line_start: 1,
             line_end: 5,
+            byte_start: 0,
+            byte_end: 0,
             is_exported: true,
             is_test: false,
+            complexity: 0,
+            fingerprint: String::new(),
+            body_fingerprint: String::new(),
+            accessibility: None,
         };
 
         symbols.insert(symbol_id.clone(), symbol);
@@ -293,7 +500,12 @@ mod tests {
         SymbolGraph {
             symbols,
             imports,
+            type_imports: HashMap::new(),
             exports,
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         }
     }
 
@@ -315,7 +527,7 @@ mod tests {
         let graph = create_test_graph();
 
         // Save graph
-        cache.save(&graph).unwrap();
+        cache.save(&graph, &[]).unwrap();
 
         // Load graph
         let loaded = cache.load().unwrap();
@@ -369,10 +581,10 @@ mod tests {
         let graph = create_test_graph();
 
         // Save graph
-        cache.save(&graph).unwrap();
+        let files: Vec<PathBuf> = graph.exports.keys().cloned().collect();
+        cache.save(&graph, &files).unwrap();
 
         // Check if stale with same files (should not be stale)
-        let files: Vec<PathBuf> = graph.exports.keys().cloned().collect();
         let is_stale = cache.invalidate_if_stale(&files).unwrap();
 
         // Note: This will be true because the files in the graph don't exist on disk
@@ -380,6 +592,31 @@ mod tests {
         assert!(is_stale);
     }
 
+    #[test]
+    fn test_cache_stale_after_rewrite_within_same_second() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = SymbolGraphCache::new(&temp_dir.path().join("cache")).unwrap();
+        let file = temp_dir.path().join("test.ts");
+        fs::write(&file, "export function a() {}\n").unwrap();
+
+        let second = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let set_modified = |time| {
+            fs::File::options()
+                .write(true)
+                .open(&file)
+                .unwrap()
+                .set_modified(time)
+                .unwrap()
+        };
+        set_modified(second);
+        let files = vec![file.clone()];
+        cache.save(&create_test_graph(), &files).unwrap();
+        assert!(!cache.invalidate_if_stale(&files).unwrap());
+
+        set_modified(second + std::time::Duration::from_millis(300));
+        assert!(cache.invalidate_if_stale(&files).unwrap());
+    }
+
     #[test]
     fn test_cache_version_mismatch() {
         let temp_dir = TempDir::new().unwrap();
@@ -432,7 +669,7 @@ mod tests {
         // Save in one cache instance
         {
             let cache = SymbolGraphCache::new(&cache_dir).unwrap();
-            cache.save(&graph).unwrap();
+            cache.save(&graph, &[]).unwrap();
         }
 
         // Load in another cache instance
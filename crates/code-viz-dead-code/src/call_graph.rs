@@ -0,0 +1,165 @@
+//! Call edges between symbols of the same file.
+//!
+//! The symbol graph links a file's symbols to the exports of the files it
+//! imports, but not to the functions it calls itself, so a local helper only
+//! called by live code is reported dead. In call-graph mode every call inside
+//! a symbol adds an edge from that symbol to the symbols of the same file
+//! named like the callee.
+//!
+//! Callees are found in the syntax tree rather than by name, so optional
+//! chaining (`maybeFn?.()`, `obj?.method()`), parentheses and non-null
+//! assertions (`fn!()`) are looked through, and calls in comments or strings
+//! never count.
+
+use crate::members::parser_for;
+use crate::models::{Symbol, SymbolId, SymbolKind};
+use ahash::AHashMap as HashMap;
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+/// Edges from each symbol to the same-file symbols it calls
+///
+/// A call inside nested symbols, such as a method of a class, adds an edge
+/// from each of them. Files that cannot be parsed are skipped.
+pub fn find_call_edges<'a>(
+    sources: &[(PathBuf, String)],
+    symbols: impl IntoIterator<Item = &'a Symbol>,
+) -> HashMap<SymbolId, Vec<SymbolId>> {
+    let mut by_file: HashMap<&Path, Vec<&Symbol>> = HashMap::new();
+    for symbol in symbols {
+        if symbol.kind != SymbolKind::ReExport {
+            by_file
+                .entry(symbol.path.as_path())
+                .or_default()
+                .push(symbol);
+        }
+    }
+
+    let mut edges: HashMap<SymbolId, Vec<SymbolId>> = HashMap::new();
+    for (path, source) in sources {
+        let Some(file_symbols) = by_file.get(path.as_path()) else {
+            continue;
+        };
+        let tree = match parser_for(path, source).parse(source) {
+            Ok(tree) => tree,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to parse file");
+                continue;
+            }
+        };
+
+        let mut calls = Vec::new();
+        collect_calls(tree.root_node(), source, &mut calls);
+        for (offset, callee) in calls {
+            let targets: Vec<&Symbol> = file_symbols
+                .iter()
+                .filter(|s| s.name == callee)
+                .copied()
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+            let callers = file_symbols
+                .iter()
+                .filter(|s| s.byte_start <= offset && offset < s.byte_end);
+            for caller in callers {
+                let deps = edges.entry(caller.id.clone()).or_default();
+                for target in targets.iter().filter(|t| t.id != caller.id) {
+                    if !deps.contains(&target.id) {
+                        deps.push(target.id.clone());
+                    }
+                }
+            }
+        }
+    }
+    edges.retain(|_, deps| !deps.is_empty());
+    edges
+}
+
+/// Byte offset and callee name of every call and `new` expression under `node`
+fn collect_calls<'a>(node: Node, source: &'a str, calls: &mut Vec<(usize, &'a str)>) {
+    let callee = match node.kind() {
+        "call_expression" => node.child_by_field_name("function"),
+        "new_expression" => node.child_by_field_name("constructor"),
+        _ => None,
+    };
+    if let Some(name) = callee.and_then(|callee| callee_name(callee, source)) {
+        calls.push((node.start_byte(), name));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_calls(child, source, calls);
+    }
+}
+
+/// Name a callee expression calls: the identifier of `fn()` and `fn?.()`,
+/// or the property of `obj.method()` and `obj?.method()`
+fn callee_name<'a>(mut callee: Node, source: &'a str) -> Option<&'a str> {
+    loop {
+        match callee.kind() {
+            "identifier" => return callee.utf8_text(source.as_bytes()).ok(),
+            "member_expression" => {
+                let property = callee.child_by_field_name("property")?;
+                return property.utf8_text(source.as_bytes()).ok();
+            }
+            // `(fn)()` and `fn!()`
+            "parenthesized_expression" | "non_null_expression" => {
+                callee = callee.named_child(0)?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_graph::SymbolGraphBuilder;
+
+    fn call_edges(source: &str) -> Vec<(String, String)> {
+        let sources = vec![(PathBuf::from("app.ts"), source.to_string())];
+        let graph = SymbolGraphBuilder::new()
+            .build_graph(sources.clone())
+            .unwrap();
+        let name = |id: &SymbolId| graph.symbols[id].name.clone();
+
+        let mut found: Vec<(String, String)> = find_call_edges(&sources, graph.symbols.values())
+            .iter()
+            .flat_map(|(from, deps)| deps.iter().map(move |to| (name(from), name(to))))
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn test_find_call_edges_through_optional_chaining() {
+        let source = r#"function plain() {}
+function optional() {}
+function wrapped() {}
+class Store {
+    save() {}
+}
+
+export function run(store?: Store) {
+    plain();
+    optional?.();
+    (wrapped)!();
+    store?.save();
+    // orphan();
+    return 'orphan()';
+}
+
+function orphan() {}
+"#;
+        assert_eq!(
+            call_edges(source),
+            vec![
+                ("run".to_string(), "optional".to_string()),
+                ("run".to_string(), "plain".to_string()),
+                ("run".to_string(), "save".to_string()),
+                ("run".to_string(), "wrapped".to_string()),
+            ]
+        );
+    }
+}
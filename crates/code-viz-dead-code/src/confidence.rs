@@ -4,8 +4,13 @@
 //! heuristics including export status, recent changes, dynamic import patterns,
 //! and test coverage.
 
-use crate::models::Symbol;
+#[cfg(feature = "git-integration")]
+use crate::git_history::GitHistoryCache;
+use crate::models::{ConfidencePenalty, Symbol};
 use crate::symbol_graph::SymbolGraph;
+use ahash::AHashSet as HashSet;
+#[cfg(feature = "git-integration")]
+use code_viz_core::context::{retry_git, RetryPolicy};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
@@ -23,12 +28,84 @@ const DYNAMIC_IMPORT_PATTERNS: &[&str] = &[
     "hook_",
 ];
 
+/// Starting confidence score before any penalties are applied
+const BASE_CONFIDENCE: u8 = 100;
+
+/// Files changed within this period count as recently modified
+const RECENT: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Penalty for symbols only referenced in always-false branches, which puts
+/// them in the low confidence tier
+pub(crate) const DEAD_BRANCH_PENALTY: i16 = 50;
+
+/// Penalty applied for each confidence heuristic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfidenceWeights {
+    /// Penalty for exported symbols
+    pub exported: u8,
+
+    /// Penalty for symbols in recently modified files
+    pub recently_modified: u8,
+
+    /// Penalty for names matching dynamic import patterns
+    pub dynamic_pattern: u8,
+
+    /// Penalty for symbols referenced from test files
+    pub test_coverage: u8,
+
+    /// Penalty for symbols whose name appears as a string literal
+    pub string_reference: u8,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        Self {
+            exported: 30,
+            recently_modified: 20,
+            dynamic_pattern: 25,
+            test_coverage: 15,
+            string_reference: 20,
+        }
+    }
+}
+
+/// Detailed explanation of how a confidence score was derived
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceBreakdown {
+    /// Score before penalties
+    pub base: u8,
+
+    /// Penalties applied, in evaluation order (path overrides last)
+    pub penalties: Vec<ConfidencePenalty>,
+}
+
+impl ConfidenceBreakdown {
+    /// Final score: base minus all penalties, clamped to 0-100
+    pub fn score(&self) -> u8 {
+        let total: i16 = self.penalties.iter().map(|p| p.amount).sum();
+        (self.base as i16 - total).clamp(0, 100) as u8
+    }
+}
+
 /// Confidence score calculator for dead code
 pub struct ConfidenceCalculator {
     /// The symbol graph for context
     graph: SymbolGraph,
     /// Repository root for git operations
+    #[cfg(feature = "git-integration")]
     repo_root: Option<PathBuf>,
+    /// Last commit time per file, walked once on first use and shared by
+    /// every symbol (`None` inside if the history cannot be read)
+    #[cfg(feature = "git-integration")]
+    git_history: std::sync::OnceLock<Option<GitHistoryCache>>,
+    /// Penalty per heuristic
+    weights: ConfidenceWeights,
+    /// Directory that path overrides are matched relative to
+    override_root: PathBuf,
+    /// Extra penalty (negative for a bonus) per path glob, first match wins
+    path_overrides: Vec<(globset::GlobMatcher, i16)>,
+    /// Contents of the string literals found in the analyzed sources
+    string_literals: HashSet<String>,
 }
 
 impl ConfidenceCalculator {
@@ -37,19 +114,67 @@ impl ConfidenceCalculator {
     /// # Arguments
     /// * `graph` - The symbol graph for context
     pub fn new(graph: SymbolGraph) -> Self {
-        // Try to find git repository root
-        let repo_root = find_git_root(&graph);
+        Self::with_weights(graph, ConfidenceWeights::default())
+    }
 
-        Self { graph, repo_root }
+    /// Create a confidence calculator with custom penalty weights
+    ///
+    /// # Arguments
+    /// * `graph` - The symbol graph for context
+    /// * `weights` - Penalty applied for each heuristic
+    pub fn with_weights(graph: SymbolGraph, weights: ConfidenceWeights) -> Self {
+        Self {
+            // Try to find git repository root
+            #[cfg(feature = "git-integration")]
+            repo_root: find_git_root(&graph),
+            #[cfg(feature = "git-integration")]
+            git_history: std::sync::OnceLock::new(),
+            graph,
+            weights,
+            override_root: PathBuf::new(),
+            path_overrides: Vec::new(),
+            string_literals: HashSet::new(),
+        }
+    }
+
+    /// Penalize symbols whose exact name is one of these string literals,
+    /// since reflection and DI containers look symbols up by name
+    ///
+    /// # Arguments
+    /// * `literals` - String literal contents, see [`crate::string_references`]
+    pub fn with_string_literals(mut self, literals: HashSet<String>) -> Self {
+        self.string_literals = literals;
+        self
+    }
+
+    /// Adjust the score of symbols under paths matching a glob
+    ///
+    /// Globs are matched against paths relative to `root`. Only the first
+    /// matching glob applies, so list specific globs before broad ones. A
+    /// positive value is subtracted from the score, a negative one added.
+    ///
+    /// # Arguments
+    /// * `root` - Directory the globs are relative to
+    /// * `overrides` - Glob and penalty pairs, in priority order
+    pub fn with_path_overrides(
+        mut self,
+        root: &Path,
+        overrides: Vec<(globset::GlobMatcher, i16)>,
+    ) -> Self {
+        self.override_root = root.to_path_buf();
+        self.path_overrides = overrides;
+        self
     }
 
     /// Calculate deletion confidence score for a symbol
     ///
-    /// Score starts at 100 and is reduced based on:
+    /// Score starts at 100 and is reduced based on (default weights):
     /// - Exported symbols (-30)
     /// - Recently modified (-20)
     /// - Dynamic import patterns (-25)
     /// - Test coverage (-15)
+    /// - Name appearing as a string literal (-20)
+    /// - First matching path override, if any
     ///
     /// # Arguments
     /// * `symbol` - The symbol to score
@@ -57,30 +182,149 @@ impl ConfidenceCalculator {
     /// # Returns
     /// Confidence score (0-100), where 100 is highest confidence for deletion
     pub fn calculate(&self, symbol: &Symbol) -> u8 {
-        let mut score = 100i16; // Use i16 to prevent underflow
+        self.calculate_detailed(symbol).score()
+    }
+
+    /// Calculate the confidence score along with every penalty that was applied
+    ///
+    /// # Arguments
+    /// * `symbol` - The symbol to score
+    ///
+    /// # Returns
+    /// Breakdown with the base score and named penalties
+    pub fn calculate_detailed(&self, symbol: &Symbol) -> ConfidenceBreakdown {
+        let mut penalties = Vec::new();
 
         // Reduce confidence if exported (might be public API)
         if symbol.is_exported {
-            score -= 30;
+            penalties.push(ConfidencePenalty::new(
+                "exported",
+                self.weights.exported.into(),
+            ));
         }
 
         // Reduce confidence if recently modified
-        if recently_modified(&symbol.path, self.repo_root.as_ref()) {
-            score -= 20;
+        if self.recently_modified(&symbol.path) {
+            penalties.push(ConfidencePenalty::new(
+                "recently modified",
+                self.weights.recently_modified.into(),
+            ));
         }
 
         // Reduce confidence if symbol name matches dynamic import patterns
-        if could_be_dynamic_import(&symbol.name) {
-            score -= 25;
+        if let Some(pattern) = dynamic_import_pattern(&symbol.name) {
+            let name = format!("{} pattern", pattern.trim_matches('_'));
+            penalties.push(ConfidencePenalty::new(
+                name,
+                self.weights.dynamic_pattern.into(),
+            ));
         }
 
         // Reduce confidence if symbol has test coverage
         if has_test_coverage(symbol, &self.graph) {
-            score -= 15;
+            penalties.push(ConfidencePenalty::new(
+                "test coverage",
+                self.weights.test_coverage.into(),
+            ));
+        }
+
+        // Reduce confidence if the name is spelled out in a string, e.g.
+        // `container.get('UserService')`
+        if self.string_literals.contains(&symbol.name) {
+            penalties.push(ConfidencePenalty::new(
+                "string reference",
+                self.weights.string_reference.into(),
+            ));
+        }
+
+        // Apply the first configured override matching the symbol's path
+        let relative = symbol
+            .path
+            .strip_prefix(&self.override_root)
+            .unwrap_or(&symbol.path);
+        if let Some((glob, amount)) = self
+            .path_overrides
+            .iter()
+            .find(|(glob, _)| glob.is_match(relative))
+        {
+            penalties.push(ConfidencePenalty::new(
+                format!("path {}", glob.glob()),
+                *amount,
+            ));
+        }
+
+        ConfidenceBreakdown {
+            base: BASE_CONFIDENCE,
+            penalties,
+        }
+    }
+
+    /// Whole days since the last commit changing the symbol's lines
+    ///
+    /// Uncommitted changes count as made now. `None` outside a git
+    /// repository, for untracked files, or without git integration.
+    ///
+    /// # Arguments
+    /// * `symbol` - The symbol to date
+    #[cfg(feature = "git-integration")]
+    pub fn age_days(&self, symbol: &Symbol) -> Option<u64> {
+        let changed = self.git_history()?.last_line_change(
+            &symbol.path,
+            symbol.line_start,
+            symbol.line_end,
+        )?;
+        let elapsed = SystemTime::now()
+            .duration_since(changed)
+            .unwrap_or_default();
+        Some(elapsed.as_secs() / (24 * 60 * 60))
+    }
+
+    /// Whole days since the last commit changing the symbol's lines, which
+    /// needs git integration
+    #[cfg(not(feature = "git-integration"))]
+    pub fn age_days(&self, _symbol: &Symbol) -> Option<u64> {
+        None
+    }
+
+    /// Git history of the repository, walked on first use
+    #[cfg(feature = "git-integration")]
+    fn git_history(&self) -> Option<&GitHistoryCache> {
+        let root = self.repo_root.as_ref()?;
+        self.git_history
+            .get_or_init(|| {
+                // A held index lock is retried; an unborn HEAD just has no history
+                retry_git(RetryPolicy::default(), || GitHistoryCache::build(root))
+                    .map_err(|e| match e.code() {
+                        git2::ErrorCode::NotFound | git2::ErrorCode::UnbornBranch => {
+                            tracing::debug!(error = %e, "No git history to read");
+                        }
+                        _ => tracing::warn!(error = %e, "Failed to read git history"),
+                    })
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    /// Check if a file was recently modified (last 30 days)
+    ///
+    /// Inside a git repository this is the time of the last commit changing
+    /// the file, otherwise the file's modification time.
+    ///
+    /// # Arguments
+    /// * `path` - File path to check
+    fn recently_modified(&self, path: &Path) -> bool {
+        #[cfg(feature = "git-integration")]
+        if self.repo_root.is_some() {
+            return self
+                .git_history()
+                .and_then(|history| history.last_commit_time(path))
+                .is_some_and(is_recent);
         }
 
-        // Clamp to 0-100 range
-        score.clamp(0, 100) as u8
+        // Fallback: check file system modification time
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(is_recent)
     }
 }
 
@@ -91,6 +335,7 @@ impl ConfidenceCalculator {
 ///
 /// # Returns
 /// Optional path to git repository root
+#[cfg(feature = "git-integration")]
 fn find_git_root(graph: &SymbolGraph) -> Option<PathBuf> {
     // Get any file path from the graph
     if let Some(symbol) = graph.symbols.values().next() {
@@ -109,97 +354,11 @@ fn find_git_root(graph: &SymbolGraph) -> Option<PathBuf> {
     None
 }
 
-/// Check if a file was recently modified (last 30 days)
-///
-/// # Arguments
-/// * `path` - File path to check
-/// * `repo_root` - Optional git repository root
-///
-/// # Returns
-/// True if file was modified in last 30 days
-fn recently_modified(path: &Path, #[allow(unused_variables)] repo_root: Option<&PathBuf>) -> bool {
-    #[cfg(feature = "git-integration")]
-    {
-        if let Some(root) = repo_root {
-            return check_git_modification(path, root);
-        }
-    }
-
-    // Fallback: check file system modification time
-    if let Ok(metadata) = std::fs::metadata(path) {
-        if let Ok(modified) = metadata.modified() {
-            if let Ok(elapsed) = SystemTime::now().duration_since(modified) {
-                return elapsed < Duration::from_secs(30 * 24 * 60 * 60); // 30 days
-            }
-        }
-    }
-
-    false
-}
-
-/// Check git history for recent modifications
-#[cfg(feature = "git-integration")]
-fn check_git_modification(path: &Path, repo_root: &Path) -> bool {
-    use std::time::UNIX_EPOCH;
-
-    // Try to open git repository
-    let repo = match git2::Repository::open(repo_root) {
-        Ok(r) => r,
-        Err(_) => return false, // Not a git repo, fail gracefully
-    };
-
-    // Get relative path from repo root
-    let rel_path = match path.strip_prefix(repo_root) {
-        Ok(p) => p,
-        Err(_) => return false,
-    };
-
-    // Get HEAD commit
-    let head = match repo.head() {
-        Ok(h) => h,
-        Err(_) => return false,
-    };
-
-    let commit = match head.peel_to_commit() {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-
-    // Walk commit history for this file
-    let mut revwalk = match repo.revwalk() {
-        Ok(r) => r,
-        Err(_) => return false,
-    };
-
-    if revwalk.push_head().is_err() {
-        return false;
-    }
-
-    // Check last commit that touched this file
-    for oid in revwalk.take(100) {
-        // Limit to last 100 commits for performance
-        if let Ok(oid) = oid {
-            if let Ok(commit) = repo.find_commit(oid) {
-                let tree = match commit.tree() {
-                    Ok(t) => t,
-                    Err(_) => continue,
-                };
-
-                // Check if file exists in this commit
-                if tree.get_path(rel_path).is_ok() {
-                    // Found the file, check commit time
-                    let commit_time =
-                        UNIX_EPOCH + Duration::from_secs(commit.time().seconds() as u64);
-                    if let Ok(elapsed) = SystemTime::now().duration_since(commit_time) {
-                        return elapsed < Duration::from_secs(30 * 24 * 60 * 60);
-                    }
-                    return false;
-                }
-            }
-        }
-    }
-
-    false
+/// Whether `time` lies within the recent period
+fn is_recent(time: SystemTime) -> bool {
+    SystemTime::now()
+        .duration_since(time)
+        .is_ok_and(|elapsed| elapsed < RECENT)
 }
 
 /// Check if symbol name matches dynamic import patterns
@@ -212,9 +371,14 @@ fn check_git_modification(path: &Path, repo_root: &Path) -> bool {
 /// # Returns
 /// True if name suggests dynamic usage
 fn could_be_dynamic_import(name: &str) -> bool {
+    dynamic_import_pattern(name).is_some()
+}
+
+/// Find the first dynamic import pattern matching a symbol name
+fn dynamic_import_pattern(name: &str) -> Option<&'static str> {
     let name_lower = name.to_lowercase();
 
-    DYNAMIC_IMPORT_PATTERNS.iter().any(|pattern| {
+    DYNAMIC_IMPORT_PATTERNS.iter().copied().find(|pattern| {
         if pattern.starts_with('_') {
             name_lower.ends_with(pattern)
         } else if pattern.ends_with('_') {
@@ -259,10 +423,15 @@ fn has_test_coverage(symbol: &Symbol, graph: &SymbolGraph) -> bool {
     for test_path in test_paths {
         if let Some(test_file_symbols) = graph.exports.get(test_path) {
             for test_sym_id in test_file_symbols {
-                if let Some(deps) = graph.imports.get(test_sym_id) {
-                    if deps.contains(&symbol.id) {
-                        return true;
-                    }
+                // Type-only imports count too, e.g. a test typing its fixtures
+                let mut deps = graph
+                    .imports
+                    .get(test_sym_id)
+                    .into_iter()
+                    .chain(graph.type_imports.get(test_sym_id))
+                    .flatten();
+                if deps.any(|dep| dep == &symbol.id) {
+                    return true;
                 }
             }
         }
@@ -286,8 +455,14 @@ mod tests {
             path: PathBuf::from(path),
             line_start: 1,
             line_end: 10,
+            byte_start: 0,
+            byte_end: 0,
             is_exported,
             is_test,
+            complexity: 0,
+            fingerprint: String::new(),
+            body_fingerprint: String::new(),
+            accessibility: None,
         }
     }
 
@@ -301,7 +476,12 @@ mod tests {
         SymbolGraph {
             symbols: symbol_map,
             imports: HashMap::new(),
+            type_imports: HashMap::new(),
             exports: HashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         }
     }
 
@@ -402,6 +582,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_has_test_coverage_by_type_import() {
+        let symbol = create_test_symbol("Props", false, false, "/src/button.ts");
+        let test_symbol = create_test_symbol("renders", false, true, "/tests/button.test.ts");
+
+        let mut graph = create_test_graph(vec![symbol.clone(), test_symbol.clone()]);
+        graph
+            .exports
+            .insert(test_symbol.path.clone(), vec![test_symbol.id.clone()]);
+        graph
+            .type_imports
+            .insert(test_symbol.id.clone(), vec![symbol.id.clone()]);
+        let calculator = ConfidenceCalculator::new(graph);
+
+        // Base 100 - 15 (test coverage) = 85
+        assert_eq!(calculator.calculate(&symbol), 85);
+    }
+
     #[test]
     fn test_could_be_dynamic_import_patterns() {
         assert!(could_be_dynamic_import("my_handler"));
@@ -433,6 +631,25 @@ mod tests {
         assert_eq!(score, 45);
     }
 
+    #[test]
+    fn test_combined_penalties_breakdown() {
+        let symbol = create_test_symbol("exported_handler", true, false, "/tmp/test.ts");
+        let graph = create_test_graph(vec![symbol.clone()]);
+        let calculator = ConfidenceCalculator::new(graph);
+
+        let breakdown = calculator.calculate_detailed(&symbol);
+        assert_eq!(breakdown.base, 100);
+        assert_eq!(
+            breakdown.penalties,
+            vec![
+                ConfidencePenalty::new("exported", 30),
+                ConfidencePenalty::new("handler pattern", 25),
+            ]
+        );
+        assert_eq!(breakdown.score(), 45);
+        assert_eq!(calculator.calculate(&symbol), breakdown.score());
+    }
+
     #[test]
     fn test_test_symbol_not_in_graph() {
         // Symbol with no test coverage (no test symbols in graph)
@@ -539,4 +756,65 @@ mod tests {
         assert!(score <= 100, "Score must not exceed 100");
         assert_eq!(score, 10, "Expected score with all penalties");
     }
+
+    /// Repository with one commit adding a file per name, and a symbol in each
+    #[cfg(feature = "git-integration")]
+    fn committed_symbols(names: &[&str]) -> (tempfile::TempDir, Vec<Symbol>) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let mut index = repo.index().unwrap();
+        let mut symbols = Vec::new();
+        for name in names {
+            let file = format!("{}.ts", name);
+            std::fs::write(temp_dir.path().join(&file), "export function f() {}\n").unwrap();
+            index.add_path(Path::new(&file)).unwrap();
+            let path = temp_dir.path().join(&file);
+            symbols.push(create_test_symbol(
+                name,
+                false,
+                false,
+                path.to_str().unwrap(),
+            ));
+        }
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        (temp_dir, symbols)
+    }
+
+    #[cfg(feature = "git-integration")]
+    #[test]
+    fn test_git_history_walked_once_for_all_symbols() {
+        use crate::git_history::REVWALKS;
+
+        let (_temp_dir, symbols) = committed_symbols(&["a", "b", "c"]);
+        REVWALKS.with(|count| count.set(0));
+        let calculator = ConfidenceCalculator::new(create_test_graph(symbols.clone()));
+        for symbol in &symbols {
+            // Committed just now, so recently modified
+            assert_eq!(calculator.calculate(symbol), 80);
+        }
+        assert_eq!(REVWALKS.with(|count| count.get()), 1);
+    }
+    #[cfg(feature = "git-integration")]
+    #[test]
+    fn test_git_history_retries_held_index_lock() {
+        use crate::git_history::{LOCKED_BUILDS, REVWALKS};
+
+        let (_temp_dir, symbols) = committed_symbols(&["a"]);
+        REVWALKS.with(|count| count.set(0));
+        LOCKED_BUILDS.with(|count| count.set(2));
+        let calculator = ConfidenceCalculator::new(create_test_graph(symbols.clone()));
+        assert_eq!(calculator.calculate(&symbols[0]), 80);
+        assert_eq!(LOCKED_BUILDS.with(|count| count.get()), 0);
+        assert_eq!(REVWALKS.with(|count| count.get()), 1);
+
+        // A lock held through every attempt leaves the history unread
+        LOCKED_BUILDS.with(|count| count.set(5));
+        let calculator = ConfidenceCalculator::new(create_test_graph(symbols.clone()));
+        calculator.calculate(&symbols[0]);
+        assert_eq!(LOCKED_BUILDS.with(|count| count.get()), 2);
+        assert_eq!(REVWALKS.with(|count| count.get()), 1);
+    }
 }
@@ -0,0 +1,316 @@
+//! Symbols referenced only inside always-false branches.
+//!
+//! Reachability follows imports, so a symbol imported by a live file stays
+//! alive even when its only call sits in code that can never run:
+//!
+//! ```ts
+//! const NEW_CHECKOUT = false;
+//! if (NEW_CHECKOUT) { renderNewCheckout(); }
+//! if (false) { legacyCleanup(); }
+//! ```
+//!
+//! This pass folds the conditions of `if` statements that are the literal
+//! `false` (or `true`, killing the `else` branch) or a module-level
+//! `const FLAG = false` of the same file, and finds symbol names whose every
+//! code reference lies inside such a branch. Like [`crate::members`], it is
+//! name-based: any live occurrence of a name, including in strings, keeps all
+//! symbols of that name alive.
+
+use crate::members::parser_for;
+use crate::models::{Symbol, SymbolId, SymbolKind};
+use crate::symbol_graph::references::{exclude_comment_references, find_name_occurrences};
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::ops::Range;
+use std::path::PathBuf;
+use tree_sitter::Node;
+
+/// A parsed file with the byte ranges of its always-false branches
+struct BranchFile<'a> {
+    source: &'a str,
+    tree: tree_sitter::Tree,
+    comments: Vec<tree_sitter::Range>,
+    dead_ranges: Vec<Range<usize>>,
+}
+
+/// IDs of `symbols` whose every reference in `sources` sits inside an
+/// always-false branch, with at least one such reference
+///
+/// Declarations, import statements and comments do not count as references.
+/// Files that cannot be parsed are skipped.
+pub fn find_dead_branch_only<'a>(
+    sources: &[(PathBuf, String)],
+    symbols: impl IntoIterator<Item = &'a Symbol>,
+) -> HashSet<SymbolId> {
+    let files: Vec<BranchFile> = sources
+        .iter()
+        .filter_map(|(path, source)| {
+            let parser = parser_for(path, source);
+            let tree = match parser.parse(source) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to parse file");
+                    return None;
+                }
+            };
+            let dead_ranges = dead_branch_ranges(tree.root_node(), source);
+            Some(BranchFile {
+                source,
+                comments: parser.find_comment_ranges(&tree),
+                tree,
+                dead_ranges,
+            })
+        })
+        .collect();
+    if files.iter().all(|f| f.dead_ranges.is_empty()) {
+        return HashSet::new();
+    }
+
+    // Only names appearing in some dead branch can qualify
+    let mut by_name: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+    for symbol in symbols {
+        if symbol.kind != SymbolKind::ReExport && !symbol.name.contains('.') {
+            by_name
+                .entry(symbol.name.as_str())
+                .or_default()
+                .push(symbol);
+        }
+    }
+    by_name.retain(|name, _| {
+        files.iter().any(|f| {
+            f.dead_ranges
+                .iter()
+                .any(|range| !find_name_occurrences(&f.source[range.clone()], name).is_empty())
+        })
+    });
+
+    let mut dead_only = HashSet::new();
+    for (name, symbols) in by_name {
+        let mut in_dead_branch = false;
+        let mut live = false;
+        for file in &files {
+            let occurrences = exclude_comment_references(
+                find_name_occurrences(file.source, name),
+                &file.comments,
+            );
+            for occurrence in occurrences {
+                let root = file.tree.root_node();
+                let Some(node) = root.descendant_for_byte_range(occurrence.start, occurrence.end)
+                else {
+                    continue;
+                };
+                if is_declaration_name(node) || is_inside(node, "import_statement") {
+                    continue;
+                }
+                if file
+                    .dead_ranges
+                    .iter()
+                    .any(|range| range.start <= occurrence.start && occurrence.end <= range.end)
+                {
+                    in_dead_branch = true;
+                } else {
+                    live = true;
+                }
+            }
+        }
+        if in_dead_branch && !live {
+            dead_only.extend(symbols.into_iter().map(|s| s.id.clone()));
+        }
+    }
+
+    dead_only
+}
+
+/// Byte ranges of the `if` branches in a file that can never run
+fn dead_branch_ranges(root: Node, source: &str) -> Vec<Range<usize>> {
+    let false_constants = false_constants(root, source);
+    let mut ranges = Vec::new();
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if node.kind() == "if_statement" {
+            let condition = node.child_by_field_name("condition").map(unwrap_parens);
+            let dead_branch = match condition.map(|c| (c.kind(), c)) {
+                Some(("false", _)) => node.child_by_field_name("consequence"),
+                Some(("identifier", c)) if false_constants.contains(text(c, source)) => {
+                    node.child_by_field_name("consequence")
+                }
+                Some(("true", _)) => node.child_by_field_name("alternative"),
+                _ => None,
+            };
+            if let Some(branch) = dead_branch {
+                // Nested branches are already covered
+                ranges.push(branch.byte_range());
+                let mut cursor = node.walk();
+                stack.extend(
+                    node.named_children(&mut cursor)
+                        .filter(|child| child.id() != branch.id()),
+                );
+                continue;
+            }
+        }
+
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+
+    ranges
+}
+
+/// Names of top-level `const NAME = false` declarations, exported or not
+fn false_constants<'a>(root: Node, source: &'a str) -> HashSet<&'a str> {
+    let mut names = HashSet::new();
+    let mut cursor = root.walk();
+
+    for statement in root.named_children(&mut cursor) {
+        let declaration = if statement.kind() == "export_statement" {
+            match statement.child_by_field_name("declaration") {
+                Some(declaration) => declaration,
+                None => continue,
+            }
+        } else {
+            statement
+        };
+        let is_const = declaration.kind() == "lexical_declaration"
+            && declaration
+                .child(0)
+                .is_some_and(|keyword| keyword.kind() == "const");
+        if !is_const {
+            continue;
+        }
+
+        let mut declarators = declaration.walk();
+        for declarator in declaration.named_children(&mut declarators) {
+            let value = declarator.child_by_field_name("value").map(unwrap_parens);
+            if let (Some(name), Some(value)) = (declarator.child_by_field_name("name"), value) {
+                if name.kind() == "identifier" && value.kind() == "false" {
+                    names.insert(text(name, source));
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// The expression inside any number of parentheses
+fn unwrap_parens(mut node: Node) -> Node {
+    while node.kind() == "parenthesized_expression" {
+        match node.named_child(0) {
+            Some(inner) => node = inner,
+            None => break,
+        }
+    }
+    node
+}
+
+/// Whether `node` is the name being declared by its parent, e.g. `foo` in
+/// `function foo() {}` or `const foo = ...`
+fn is_declaration_name(node: Node) -> bool {
+    node.parent()
+        .and_then(|parent| parent.child_by_field_name("name"))
+        .is_some_and(|name| name.id() == node.id())
+}
+
+fn is_inside(node: Node, kind: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == kind {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn symbol(name: &str, path: &str) -> Symbol {
+        Symbol {
+            id: format!("{}:1:{}", path, name),
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            path: PathBuf::from(path),
+            line_start: 1,
+            line_end: 3,
+            byte_start: 0,
+            byte_end: 0,
+            is_exported: true,
+            is_test: false,
+            complexity: 1,
+            fingerprint: String::new(),
+            body_fingerprint: String::new(),
+            accessibility: None,
+        }
+    }
+
+    fn dead_only(sources: &[(&str, &str)], names: &[&str]) -> Vec<String> {
+        let sources: Vec<(PathBuf, String)> = sources
+            .iter()
+            .map(|(path, source)| (PathBuf::from(path), source.to_string()))
+            .collect();
+        let symbols: Vec<Symbol> = names.iter().map(|n| symbol(n, "lib.ts")).collect();
+        let mut names: Vec<String> = find_dead_branch_only(&sources, &symbols)
+            .into_iter()
+            .map(|id| id.rsplit(':').next().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_literal_and_constant_false_branches() {
+        let main = r#"
+import { legacy, flagged, used, elseOnly } from "./lib";
+const NEW_UI = false;
+export const OLD_UI = (false);
+if (false) { legacy(); }
+if (NEW_UI) { flagged(); }
+if (OLD_UI) { flagged(); } else { used(); }
+if (true) { used(); } else { elseOnly(); }
+"#;
+        assert_eq!(
+            dead_only(
+                &[("main.ts", main)],
+                &["legacy", "flagged", "used", "elseOnly"]
+            ),
+            vec!["elseOnly", "flagged", "legacy"]
+        );
+    }
+
+    #[test]
+    fn test_live_reference_elsewhere_keeps_symbol() {
+        let main = "import { legacy } from \"./lib\";\nif (false) { legacy(); }\n";
+        let other = "import { legacy } from \"./lib\";\n// legacy() in a comment\nlegacy();\n";
+        assert_eq!(
+            dead_only(&[("main.ts", main), ("other.ts", other)], &["legacy"]),
+            Vec::<String>::new()
+        );
+        assert_eq!(dead_only(&[("main.ts", main)], &["legacy"]), vec!["legacy"]);
+    }
+
+    #[test]
+    fn test_non_constant_conditions_are_live() {
+        let main = r#"
+import { a, b } from "./lib";
+let enabled = false;
+if (enabled) { a(); }
+if (!true) { b(); }
+"#;
+        assert!(dead_only(&[("main.ts", main)], &["a", "b"]).is_empty());
+        assert!(dead_branch_ranges(
+            parser_for(Path::new("main.ts"), main)
+                .parse(main)
+                .unwrap()
+                .root_node(),
+            main
+        )
+        .is_empty());
+    }
+}
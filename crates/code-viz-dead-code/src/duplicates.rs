@@ -0,0 +1,180 @@
+//! Duplicate symbol detection.
+//!
+//! Every function, method and class in the symbol graph carries a fingerprint
+//! of its source with its own name masked (see
+//! [`crate::models::body_fingerprint`]), which ignores indentation and line
+//! breaks. Symbols sharing it are copies of each other, typically helpers
+//! pasted into several files and renamed.
+
+use crate::models::{DuplicateGroup, DuplicateSymbol, Symbol, SymbolId, SymbolKind};
+use crate::symbol_graph::SymbolGraph;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+
+/// Group the functions, methods and classes of `graph` with identical source
+///
+/// Symbols shorter than `min_loc` lines are skipped, so trivial bodies such
+/// as a single `return` do not form groups. Groups whose every copy lies
+/// inside a copy of a larger duplicate, such as the methods of a duplicated
+/// class, are left out. Groups are sorted by size, largest first, then by
+/// the position of their first copy.
+pub fn find_duplicates(graph: &SymbolGraph, min_loc: usize) -> Vec<DuplicateGroup> {
+    let mut by_fingerprint: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+    for symbol in graph.symbols.values() {
+        let is_code = matches!(
+            symbol.kind,
+            SymbolKind::Function
+                | SymbolKind::ArrowFunction
+                | SymbolKind::Method
+                | SymbolKind::Class
+        );
+        if is_code && !symbol.body_fingerprint.is_empty() && crate::symbol_loc(symbol) >= min_loc {
+            by_fingerprint
+                .entry(symbol.body_fingerprint.as_str())
+                .or_default()
+                .push(symbol);
+        }
+    }
+
+    let groups: Vec<Vec<&Symbol>> = by_fingerprint
+        .into_values()
+        .filter(|symbols| symbols.len() > 1)
+        .collect();
+    let nested = nested_symbols(groups.iter().flatten().copied().collect());
+
+    let mut duplicates: Vec<DuplicateGroup> = groups
+        .into_iter()
+        .filter(|symbols| !symbols.iter().all(|symbol| nested.contains(&symbol.id)))
+        .map(|mut symbols| {
+            symbols.sort_by(|a, b| (&a.path, a.line_start).cmp(&(&b.path, b.line_start)));
+            DuplicateGroup {
+                fingerprint: symbols[0].body_fingerprint.clone(),
+                loc: crate::symbol_loc(symbols[0]),
+                symbols: symbols
+                    .into_iter()
+                    .map(|s| DuplicateSymbol {
+                        id: s.id.clone(),
+                        symbol: s.name.clone(),
+                        kind: s.kind,
+                        path: s.path.clone(),
+                        line_start: s.line_start,
+                        line_end: s.line_end,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    duplicates.sort_by(|a, b| {
+        b.loc.cmp(&a.loc).then_with(|| {
+            (&a.symbols[0].path, a.symbols[0].line_start)
+                .cmp(&(&b.symbols[0].path, b.symbols[0].line_start))
+        })
+    });
+    duplicates
+}
+
+/// IDs of the `symbols` whose span lies inside another of `symbols`
+///
+/// Sorted by file, start and then end descending, every symbol containing
+/// another comes before it, so one pass tracking the furthest end so far
+/// finds them. Symbols with the same span contain each other.
+fn nested_symbols(mut symbols: Vec<&Symbol>) -> HashSet<&SymbolId> {
+    symbols.sort_by(|a, b| {
+        (&a.path, a.byte_start, b.byte_end).cmp(&(&b.path, b.byte_start, a.byte_end))
+    });
+
+    let mut nested = HashSet::new();
+    let mut furthest: Option<&Symbol> = None;
+    for symbol in symbols {
+        match furthest {
+            Some(outer) if outer.path == symbol.path && outer.byte_end >= symbol.byte_end => {
+                nested.insert(&symbol.id);
+                if (outer.byte_start, outer.byte_end) == (symbol.byte_start, symbol.byte_end) {
+                    nested.insert(&outer.id);
+                }
+            }
+            _ => furthest = Some(symbol),
+        }
+    }
+    nested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_graph::SymbolGraphBuilder;
+    use std::path::PathBuf;
+
+    const HELPER: &str = "export function formatPrice(cents: number) {\n    const units = Math.floor(cents / 100);\n    const rest = cents % 100;\n    return `${units}.${rest}`;\n}\n";
+
+    fn graph(files: &[(&str, &str)]) -> SymbolGraph {
+        let sources: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|(path, source)| (PathBuf::from(path), source.to_string()))
+            .collect();
+        SymbolGraphBuilder::new().build_graph(sources).unwrap()
+    }
+
+    #[test]
+    fn test_identical_helper_in_two_files() {
+        let graph = graph(&[
+            (
+                "src/cart.ts",
+                &format!("{}\nexport const total = () => 1;\n", HELPER),
+            ),
+            // Indented differently, still the same code
+            ("src/invoice.ts", &HELPER.replace("    ", "  ")),
+            (
+                "src/other.ts",
+                "export function formatDate(d: Date) {\n    return d.toISOString();\n}\n",
+            ),
+        ]);
+
+        let groups = find_duplicates(&graph, 3);
+        assert_eq!(groups.len(), 1);
+        let paths: Vec<&PathBuf> = groups[0].symbols.iter().map(|s| &s.path).collect();
+        assert_eq!(
+            paths,
+            [
+                &PathBuf::from("src/cart.ts"),
+                &PathBuf::from("src/invoice.ts")
+            ]
+        );
+        assert_eq!(groups[0].symbols[0].symbol, "formatPrice");
+        assert_eq!(groups[0].loc, 5);
+    }
+
+    #[test]
+    fn test_renamed_copies_are_grouped() {
+        let renamed = HELPER.replace("formatPrice", "priceLabel");
+        let graph = graph(&[("src/cart.ts", HELPER), ("src/invoice.ts", &renamed)]);
+
+        let groups = find_duplicates(&graph, 3);
+        assert_eq!(groups.len(), 1);
+        let names: Vec<&str> = groups[0]
+            .symbols
+            .iter()
+            .map(|s| s.symbol.as_str())
+            .collect();
+        assert_eq!(names, ["formatPrice", "priceLabel"]);
+    }
+
+    #[test]
+    fn test_min_loc_skips_trivial_bodies() {
+        let getter = "export function one() { return 1; }\n";
+        let graph = graph(&[("a.ts", getter), ("b.ts", getter)]);
+
+        assert_eq!(find_duplicates(&graph, 1).len(), 1);
+        assert!(find_duplicates(&graph, 2).is_empty());
+    }
+
+    #[test]
+    fn test_methods_of_duplicated_class_are_not_reported_separately() {
+        let class = "export class Money {\n    add(a: number, b: number) {\n        const sum = a + b;\n        return sum;\n    }\n}\n";
+        let graph = graph(&[("a.ts", class), ("b.ts", class)]);
+
+        let groups = find_duplicates(&graph, 3);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].symbols[0].kind, SymbolKind::Class);
+    }
+}
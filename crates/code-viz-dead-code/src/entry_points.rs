@@ -7,11 +7,20 @@
 //! - Main entry files (main.ts, index.ts, lib.rs)
 //! - Functions named "main"
 //! - All symbols in test files
-//! - Exported symbols in entry files
-
-use crate::models::{Symbol, SymbolId};
+//! - Exported symbols in entry files (re-exports excluded, so unused barrel
+//!   entries are still reported)
+//! - Optionally, exported symbols of the files `package.json` declares in
+//!   `main`, `module`, `bin` and `exports`, re-exports included since such a
+//!   barrel is the package's public API (enabled by listing
+//!   [`PACKAGE_ENTRY_POINTS`] among the entry point patterns)
+
+use crate::models::{Symbol, SymbolId, SymbolKind};
 use crate::symbol_graph::SymbolGraph;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
+
+/// Entry point pattern that selects the files `package.json` declares,
+/// rather than files matching a glob
+pub const PACKAGE_ENTRY_POINTS: &str = "package.json";
 
 /// Detect entry points in the symbol graph
 ///
@@ -39,7 +48,11 @@ pub fn detect_entry_points(graph: &SymbolGraph) -> Vec<SymbolId> {
     for (file_path, exported_symbols) in &graph.exports {
         if is_entry_file(file_path) {
             for symbol_id in exported_symbols {
-                if !entry_points.contains(symbol_id) {
+                let is_reexport = graph
+                    .symbols
+                    .get(symbol_id)
+                    .is_some_and(|s| s.kind == SymbolKind::ReExport);
+                if !is_reexport && !entry_points.contains(symbol_id) {
                     entry_points.push(symbol_id.clone());
                 }
             }
@@ -49,6 +62,92 @@ pub fn detect_entry_points(graph: &SymbolGraph) -> Vec<SymbolId> {
     entry_points
 }
 
+/// Detect entry points declared by the nearest `package.json`
+///
+/// Looks for `package.json` in `root` and then its ancestors, and resolves
+/// every path in its `main`, `module`, `bin` and `exports` fields to a file
+/// of the graph, trying the common extensions and `index` files when a path
+/// names no file directly. The exported symbols of those files, re-exports
+/// included, are returned sorted, so the code a barrel `main` forwards stays
+/// live. Paths that resolve to no analyzed file, e.g. build output, are
+/// ignored.
+pub fn detect_package_entry_points(graph: &SymbolGraph, root: &Path) -> Vec<SymbolId> {
+    let Some((package_dir, manifest)) = nearest_package_manifest(root) else {
+        return Vec::new();
+    };
+
+    let mut targets = Vec::new();
+    for field in ["main", "module", "bin", "exports"] {
+        collect_strings(&manifest[field], &mut targets);
+    }
+
+    let mut entry_points: Vec<SymbolId> = targets
+        .iter()
+        .filter_map(|target| resolve_package_target(graph, &package_dir.join(target)))
+        .flat_map(|file| graph.exports.get(&file).into_iter().flatten())
+        .cloned()
+        .collect();
+    entry_points.sort();
+    entry_points.dedup();
+    entry_points
+}
+
+/// Directory and parsed contents of the first `package.json` in `dir` or its ancestors
+fn nearest_package_manifest(dir: &Path) -> Option<(PathBuf, serde_json::Value)> {
+    dir.ancestors().find_map(|dir| {
+        let content = std::fs::read_to_string(dir.join("package.json")).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(manifest) => Some((dir.to_path_buf(), manifest)),
+            Err(e) => {
+                tracing::warn!(dir = %dir.display(), error = %e, "Invalid package.json");
+                None
+            }
+        }
+    })
+}
+
+/// Every string in a `package.json` field, e.g. the values of a `bin` map or
+/// of nested conditional `exports`
+fn collect_strings(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(values) => values.iter().for_each(|v| collect_strings(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+/// The graph file a `package.json` path refers to
+///
+/// Both sides are compared without `.` components, since graph paths look
+/// like `./src/cli.ts` when the analyzed path is `.`.
+fn resolve_package_target(graph: &SymbolGraph, target: &Path) -> Option<PathBuf> {
+    let target = without_cur_dir(target);
+    let candidates = [
+        target.clone(),
+        target.with_extension("ts"),
+        target.with_extension("tsx"),
+        target.with_extension("js"),
+        target.with_extension("jsx"),
+        target.join("index.ts"),
+        target.join("index.js"),
+    ];
+    candidates.into_iter().find_map(|candidate| {
+        graph
+            .exports
+            .keys()
+            .find(|file| without_cur_dir(file) == candidate)
+            .cloned()
+    })
+}
+
+/// `path` without its `.` components ("./src/cli.ts" names "src/cli.ts")
+fn without_cur_dir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| *c != Component::CurDir)
+        .collect()
+}
+
 /// Check if a symbol is an entry point based on heuristics
 ///
 /// # Arguments
@@ -68,8 +167,9 @@ fn is_entry_point(symbol: &Symbol, path: &Path) -> bool {
         return true;
     }
 
-    // Heuristic 3: Exported symbols in entry files are entry points
-    if symbol.is_exported && is_entry_file(path) {
+    // Heuristic 3: Exported symbols in entry files are entry points, except
+    // re-exports which must be imported somewhere to be considered live
+    if symbol.is_exported && symbol.kind != SymbolKind::ReExport && is_entry_file(path) {
         return true;
     }
 
@@ -142,17 +242,91 @@ mod tests {
             path,
             line_start: 1,
             line_end: 10,
+            byte_start: 0,
+            byte_end: 0,
             is_exported,
             is_test: false,
+            complexity: 0,
+            fingerprint: String::new(),
+            body_fingerprint: String::new(),
+            accessibility: None,
         }
     }
 
+    #[test]
+    fn test_detect_package_entry_points() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{
+                "main": "./dist/index.js",
+                "bin": { "tool": "./src/cli.ts" },
+                "exports": { ".": { "import": "./src/api", "types": "./dist/api.d.ts" } }
+            }"#,
+        )
+        .unwrap();
+
+        let mut graph = SymbolGraph {
+            symbols: ahash::AHashMap::new(),
+            imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
+            exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
+        };
+        for (name, file, is_exported) in [
+            ("run", "src/cli.ts", true),
+            ("helper", "src/cli.ts", false),
+            ("query", "src/api.ts", true),
+            ("other", "src/other.ts", true),
+        ] {
+            let symbol = create_test_symbol(name, root.join(file), is_exported);
+            if is_exported {
+                graph
+                    .exports
+                    .entry(symbol.path.clone())
+                    .or_default()
+                    .push(symbol.id.clone());
+            }
+            graph.symbols.insert(symbol.id.clone(), symbol);
+        }
+        // api.ts is a barrel: its re-exports are public API too
+        let forwarded = Symbol {
+            kind: SymbolKind::ReExport,
+            ..create_test_symbol("forwarded", root.join("src/api.ts"), true)
+        };
+        graph
+            .exports
+            .entry(forwarded.path.clone())
+            .or_default()
+            .push(forwarded.id.clone());
+        graph.symbols.insert(forwarded.id.clone(), forwarded);
+
+        // Found from a subdirectory too, through the nearest package.json
+        let names = |root: &Path| -> Vec<String> {
+            detect_package_entry_points(&graph, root)
+                .iter()
+                .map(|id| graph.symbols[id].name.clone())
+                .collect()
+        };
+        assert_eq!(names(root), vec!["forwarded", "query", "run"]);
+        assert_eq!(names(&root.join("src")), vec!["forwarded", "query", "run"]);
+    }
+
     #[test]
     fn test_detect_main_ts() {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let main_path = PathBuf::from("src/main.ts");
@@ -168,7 +342,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let index_path = PathBuf::from("src/index.ts");
@@ -184,7 +363,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         // Add symbol in test file
@@ -206,7 +390,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let index_path = PathBuf::from("src/index.ts");
@@ -234,7 +423,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         // Add a regular, unexported symbol in a non-entry file
@@ -272,7 +466,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let regular_path = PathBuf::from("src/app.ts");
@@ -295,7 +494,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let lib_path = PathBuf::from("src/lib.rs");
@@ -314,7 +518,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let index_path = PathBuf::from("src/index.ts");
@@ -333,7 +542,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let utils_path = PathBuf::from("src/utils.ts");
@@ -352,7 +566,12 @@ mod tests {
         let mut graph = SymbolGraph {
             symbols: ahash::AHashMap::new(),
             imports: ahash::AHashMap::new(),
+            type_imports: ahash::AHashMap::new(),
             exports: ahash::AHashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let main_path = PathBuf::from("src/main.ts");
@@ -0,0 +1,87 @@
+//! Sample dead code results for tests of report consumers.
+//!
+//! The CLI formatters and the PR comment build their test input from these
+//! helpers, so a new field on [`DeadSymbol`] or [`DeadCodeResult`] only
+//! needs a default instead of an edit to every fixture.
+
+use crate::models::{
+    DeadCodeResult, DeadCodeSummary, DeadSymbol, FileDeadCode, KindCounts, SymbolKind,
+};
+use std::path::PathBuf;
+
+/// Dead function `name` spanning `loc` lines from `line_start`
+pub fn dead_symbol(name: &str, line_start: usize, loc: usize, confidence: u8) -> DeadSymbol {
+    DeadSymbol {
+        symbol: name.to_string(),
+        line_start,
+        line_end: line_start + loc.saturating_sub(1),
+        loc,
+        confidence,
+        reason: "Unreachable from entry points".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Result listing `files` as path and dead symbols, with a summary counting
+/// them as the only analyzed files
+pub fn dead_code_result(files: Vec<(&str, Vec<DeadSymbol>)>) -> DeadCodeResult {
+    let files: Vec<FileDeadCode> = files
+        .into_iter()
+        .map(|(path, dead_code)| FileDeadCode {
+            path: PathBuf::from(path),
+            dead_code,
+        })
+        .collect();
+    let kinds: KindCounts = files
+        .iter()
+        .flat_map(|file| &file.dead_code)
+        .map(|symbol| symbol.kind)
+        .collect();
+
+    DeadCodeResult {
+        summary: DeadCodeSummary {
+            total_files: files.len(),
+            files_with_dead_code: files.iter().filter(|f| !f.dead_code.is_empty()).count(),
+            dead_functions: kinds.functions,
+            dead_classes: kinds.classes,
+            dead_other: kinds.other,
+            total_dead_loc: files.iter().map(FileDeadCode::dead_loc).sum(),
+            ..Default::default()
+        },
+        files,
+        ..Default::default()
+    }
+}
+
+/// Two files with dead code out of ten: two dead functions in
+/// `src/utils.ts` and a dead class in `src/legacy.ts`
+pub fn sample_result() -> DeadCodeResult {
+    let mut result = dead_code_result(vec![
+        (
+            "src/utils.ts",
+            vec![
+                DeadSymbol {
+                    reason: "Not imported or called anywhere".to_string(),
+                    ..dead_symbol("unusedFunction", 10, 10, 95)
+                },
+                DeadSymbol {
+                    kind: SymbolKind::ArrowFunction,
+                    reason: "Exported but never used".to_string(),
+                    ..dead_symbol("oldHelper", 25, 5, 85)
+                },
+            ],
+        ),
+        (
+            "src/legacy.ts",
+            vec![DeadSymbol {
+                kind: SymbolKind::Class,
+                reason: "Exported and recently modified".to_string(),
+                ..dead_symbol("LegacyClass", 1, 100, 65)
+            }],
+        ),
+    ]);
+    result.summary.total_files = 10;
+    result.summary.total_loc = 1000;
+    result.summary.dead_code_ratio = 0.115;
+    result
+}
@@ -0,0 +1,273 @@
+//! Last commit time of every file in a git repository.
+//!
+//! Confidence scoring penalizes symbols in recently modified files. Walking
+//! the history once per file is slow on large repositories, so the recent
+//! history is walked once per analysis and every file changed by a walked
+//! commit is recorded with the time of the newest commit changing it.
+//!
+//! The age of a dead symbol needs the last change to its own lines instead,
+//! so files are also blamed on demand, each at most once per analysis.
+
+use ahash::AHashMap as HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of commits walked back from HEAD
+const MAX_COMMITS: usize = 100;
+
+#[cfg(test)]
+thread_local! {
+    /// Revwalks started on this thread, so tests can check they are shared
+    pub(crate) static REVWALKS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+
+    /// Builds on this thread still to fail as if another process held the
+    /// index lock, so tests can check the failure is retried
+    pub(crate) static LOCKED_BUILDS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Commit time of each line of a blamed file, `None` for uncommitted lines
+type LineTimes = Arc<[Option<SystemTime>]>;
+
+/// Time of the newest commit changing each file, within the recent history
+#[derive(Debug, Default)]
+pub struct GitHistoryCache {
+    /// Repository root that recorded paths are relative to
+    repo_root: PathBuf,
+
+    /// Commit time per path relative to `repo_root`
+    last_commit: HashMap<PathBuf, SystemTime>,
+
+    /// Line commit times per blamed file (`None` if it cannot be blamed)
+    blamed: Mutex<HashMap<PathBuf, Option<LineTimes>>>,
+}
+
+impl GitHistoryCache {
+    /// Walk the last commits of the repository at `repo_root` once
+    ///
+    /// Each commit is compared with its first parent, so merges count as
+    /// changing what they brought into the mainline. Files not changed in the
+    /// walked commits have no entry.
+    ///
+    /// # Arguments
+    /// * `repo_root` - Root of the working tree
+    pub fn build(repo_root: &Path) -> Result<Self, git2::Error> {
+        let repo = git2::Repository::open(repo_root)?;
+
+        #[cfg(test)]
+        if LOCKED_BUILDS.with(|count| count.replace(count.get().saturating_sub(1))) > 0 {
+            return Err(git2::Error::new(
+                git2::ErrorCode::Locked,
+                git2::ErrorClass::Index,
+                "index is locked",
+            ));
+        }
+        #[cfg(test)]
+        REVWALKS.with(|count| count.set(count.get() + 1));
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+        revwalk.push_head()?;
+
+        let mut last_commit = HashMap::new();
+        for oid in revwalk.take(MAX_COMMITS) {
+            let commit = repo.find_commit(oid?)?;
+            let parent_tree = match commit.parents().next() {
+                Some(parent) => Some(parent.tree()?),
+                None => None,
+            };
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+            let time = UNIX_EPOCH + Duration::from_secs(commit.time().seconds().max(0) as u64);
+            for delta in diff.deltas() {
+                if let Some(path) = delta.new_file().path() {
+                    // Newest first, so the first commit seen is the last change
+                    last_commit.entry(path.to_path_buf()).or_insert(time);
+                }
+            }
+        }
+
+        tracing::debug!(
+            repo_root = %repo_root.display(),
+            files = last_commit.len(),
+            "Git history cached"
+        );
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            last_commit,
+            blamed: Mutex::default(),
+        })
+    }
+
+    /// Time of the newest walked commit changing `path`
+    ///
+    /// # Arguments
+    /// * `path` - File path, absolute or relative to the repository root
+    pub fn last_commit_time(&self, path: &Path) -> Option<SystemTime> {
+        let relative = path.strip_prefix(&self.repo_root).unwrap_or(path);
+        self.last_commit.get(relative).copied()
+    }
+
+    /// Time of the newest change to lines `line_start..=line_end` of `path`
+    ///
+    /// Unlike `last_commit_time` this covers the whole history, and lines
+    /// changed in the working tree but not committed yet count as changed
+    /// now. `None` if the file is not tracked or cannot be blamed.
+    ///
+    /// # Arguments
+    /// * `path` - File path, absolute or relative to the repository root
+    /// * `line_start` - First line, 1-indexed
+    /// * `line_end` - Last line, inclusive
+    pub fn last_line_change(
+        &self,
+        path: &Path,
+        line_start: usize,
+        line_end: usize,
+    ) -> Option<SystemTime> {
+        let relative = path.strip_prefix(&self.repo_root).unwrap_or(path);
+        let lines = self.line_times(relative)?;
+
+        let start = line_start.max(1) - 1;
+        let end = line_end.min(lines.len());
+        if start >= end {
+            return None;
+        }
+        let now = SystemTime::now();
+        lines[start..end]
+            .iter()
+            .map(|time| time.unwrap_or(now))
+            .max()
+    }
+
+    /// Line commit times of a file, blamed on first use
+    ///
+    /// The lock is not held while blaming, so other files are blamed in
+    /// parallel; a file blamed twice at once keeps the first result.
+    fn line_times(&self, relative: &Path) -> Option<LineTimes> {
+        let blamed = || self.blamed.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(lines) = blamed().get(relative) {
+            return lines.clone();
+        }
+
+        let lines = blame_lines(&self.repo_root, relative)
+            .map_err(|e| {
+                tracing::debug!(path = %relative.display(), error = %e, "Failed to blame file");
+            })
+            .ok();
+        blamed()
+            .entry(relative.to_path_buf())
+            .or_insert(lines)
+            .clone()
+    }
+}
+
+/// Blame the working tree copy of a file, line by line
+fn blame_lines(repo_root: &Path, relative: &Path) -> Result<LineTimes, git2::Error> {
+    let repo = git2::Repository::open(repo_root)?;
+    let committed = repo.blame_file(relative, None)?;
+    let contents = std::fs::read(repo_root.join(relative))
+        .map_err(|e| git2::Error::from_str(&e.to_string()))?;
+    let blame = committed.blame_buffer(&contents)?;
+
+    let mut commit_times = HashMap::new();
+    let mut lines = Vec::new();
+    for hunk in blame.iter() {
+        let oid = hunk.final_commit_id();
+        let time = if oid.is_zero() {
+            None
+        } else {
+            let seconds = match commit_times.get(&oid) {
+                Some(&seconds) => seconds,
+                None => {
+                    let seconds = repo.find_commit(oid)?.time().seconds().max(0) as u64;
+                    commit_times.insert(oid, seconds);
+                    seconds
+                }
+            };
+            Some(UNIX_EPOCH + Duration::from_secs(seconds))
+        };
+        let start = hunk.final_start_line().saturating_sub(1);
+        let end = start + hunk.lines_in_hunk();
+        if lines.len() < end {
+            lines.resize(end, None);
+        }
+        lines[start..end].fill(time);
+    }
+    Ok(lines.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Commit the given file contents at `seconds` after the epoch
+    fn commit(repo: &git2::Repository, files: &[(&str, &str)], seconds: i64) {
+        let root = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            fs::write(root.join(path), content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        let signature =
+            git2::Signature::new("Dev", "dev@example.com", &git2::Time::new(seconds, 0)).unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "change",
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    /// Last change of one file found by its own revwalk, comparing blob IDs
+    fn per_file_last_commit(repo: &git2::Repository, path: &Path) -> Option<SystemTime> {
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.set_sorting(git2::Sort::TIME).unwrap();
+        revwalk.push_head().unwrap();
+        revwalk.take(MAX_COMMITS).find_map(|oid| {
+            let commit = repo.find_commit(oid.unwrap()).unwrap();
+            let blob =
+                |commit: &git2::Commit| commit.tree().unwrap().get_path(path).ok().map(|e| e.id());
+            let own = blob(&commit)?;
+            let parent = commit.parents().next().and_then(|parent| blob(&parent));
+            (parent != Some(own))
+                .then(|| UNIX_EPOCH + Duration::from_secs(commit.time().seconds() as u64))
+        })
+    }
+
+    #[test]
+    fn test_one_revwalk_matches_per_file_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        commit(&repo, &[("a.ts", "a"), ("b.ts", "b"), ("c.ts", "c")], 1_000);
+        commit(&repo, &[("b.ts", "b2")], 2_000);
+        commit(&repo, &[("d.ts", "d")], 3_000);
+
+        REVWALKS.with(|count| count.set(0));
+        let cache = GitHistoryCache::build(temp_dir.path()).unwrap();
+        for name in ["a.ts", "b.ts", "c.ts", "d.ts"] {
+            let path = temp_dir.path().join(name);
+            assert_eq!(
+                cache.last_commit_time(&path),
+                per_file_last_commit(&repo, Path::new(name)),
+                "{}",
+                name
+            );
+        }
+        assert_eq!(REVWALKS.with(|count| count.get()), 1);
+
+        let at = |seconds| Some(UNIX_EPOCH + Duration::from_secs(seconds));
+        assert_eq!(cache.last_commit_time(Path::new("a.ts")), at(1_000));
+        assert_eq!(cache.last_commit_time(Path::new("b.ts")), at(2_000));
+        assert_eq!(cache.last_commit_time(Path::new("d.ts")), at(3_000));
+        assert_eq!(cache.last_commit_time(Path::new("missing.ts")), None);
+    }
+}
@@ -14,7 +14,8 @@
 //! 2. **Graph Building**: Construct a bidirectional dependency graph from symbols
 //! 3. **Entry Point Detection**: Identify entry points (main files, exports, tests)
 //! 4. **Reachability Analysis**: DFS traversal from entry points to mark reachable symbols
-//! 5. **Dead Code Identification**: Symbols not reached = dead code
+//! 5. **Dead Code Identification**: Symbols not reached = dead code, plus enum and
+//!    const-object members that are never accessed
 //! 6. **Confidence Scoring**: Calculate deletion confidence based on heuristics
 //!
 //! # Example
@@ -31,24 +32,59 @@
 
 #![allow(dead_code)]
 
+// Parsing needs the C grammars and caching needs sled, so browser (wasm32)
+// builds only analyze graphs that were built elsewhere, see the README
+#[cfg(not(target_arch = "wasm32"))]
+pub mod archive;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod call_graph;
 pub mod confidence;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dead_branches;
+pub mod duplicates;
 pub mod entry_points;
+pub mod fixtures;
+#[cfg(feature = "git-integration")]
+pub mod git_history;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod members;
 pub mod models;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod private_members;
 pub mod reachability;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod session;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod string_references;
 pub mod symbol_graph;
 
 // Re-export main types for convenience
-pub use models::{DeadCodeResult, DeadCodeSummary, DeadSymbol, FileDeadCode};
+pub use models::{
+    AnalysisWarning, AnalysisWarningKind, ConfidencePenalty, DeadCodeDiff, DeadCodeResult,
+    DeadCodeSummary, DeadSymbol, DirectoryDeadCode, DuplicateGroup, DuplicateSymbol, FileDeadCode,
+    FileTiming, FileTotals, PublicApiReport, PublicExport, RawSymbolIds, RootReport,
+};
 
-pub use cache::{CacheError, SymbolGraphCache};
-pub use confidence::ConfidenceCalculator;
+#[cfg(not(target_arch = "wasm32"))]
+pub use cache::{CacheError, MemoryGraphCache, SymbolGraphCache};
+pub use confidence::{ConfidenceBreakdown, ConfidenceCalculator, ConfidenceWeights};
+pub use duplicates::find_duplicates;
 pub use entry_points::detect_entry_points;
 pub use reachability::{ReachabilityAnalyzer, ReachabilityError};
-pub use symbol_graph::{GraphError, SymbolGraph, SymbolGraphBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+pub use session::AnalysisSession;
+#[cfg(not(target_arch = "wasm32"))]
+pub use symbol_graph::SymbolGraphBuilder;
+pub use symbol_graph::{GraphError, SymbolGraph};
 
 use ahash::AHashMap as HashMap;
+use code_viz_core::scanner::ScanResult;
+use code_viz_core::sfc;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Configuration options for dead code analysis
@@ -57,11 +93,99 @@ pub struct AnalysisConfig {
     /// Patterns to exclude from analysis (glob patterns)
     pub exclude_patterns: Vec<String>,
 
-    /// Enable caching for incremental analysis
-    pub enable_cache: bool,
+    /// How cached symbol graphs are used for incremental analysis
+    pub cache_mode: CacheMode,
 
-    /// Cache directory path (defaults to .code-viz/cache)
+    /// Cache directory path (defaults to .code-viz/cache in the analyzed path,
+    /// or in the first root if there are roots); analysis goes on without the
+    /// cache if the directory cannot be created or written
     pub cache_dir: Option<PathBuf>,
+
+    /// Symbol graphs kept in memory across analyses in this process (0 disables)
+    pub memory_cache_capacity: usize,
+
+    /// How long a graph stays in the in-memory cache
+    pub memory_cache_ttl: Duration,
+
+    /// Only analyze files matching one of these glob patterns (all files if empty)
+    pub include_patterns: Vec<String>,
+
+    /// Only analyze files under these directories, relative to the analyzed
+    /// path or absolute (all of it if empty). Each root is scanned on its own,
+    /// and files of every root share one symbol graph, so imports between
+    /// roots keep their targets alive.
+    pub roots: Vec<PathBuf>,
+
+    /// Only scan this many directory levels deep (unlimited if `None`)
+    pub max_depth: Option<usize>,
+
+    /// Fail with `AnalysisError::TooManyFiles` when the scan selects more
+    /// source files than this, before any is parsed (unlimited if `None`)
+    pub max_files: Option<usize>,
+
+    /// Only analyze these languages, e.g. "typescript" (all supported if empty)
+    pub languages: Vec<String>,
+
+    /// Extra extensions to analyze, mapped to the language they are parsed
+    /// as, e.g. `"ts.tmpl" => "typescript"`; "typescript" and "tsx" select
+    /// the TypeScript parser, anything else JavaScript
+    pub extension_overrides: std::collections::HashMap<String, String>,
+
+    /// Treat every symbol in files matching these glob patterns as an entry
+    /// point; the pattern "package.json" instead selects the exports of the
+    /// files the nearest `package.json` declares in `main`, `module`, `bin`
+    /// and `exports`
+    pub entry_point_patterns: Vec<String>,
+
+    /// Penalty weights used for confidence scoring
+    pub confidence_weights: ConfidenceWeights,
+
+    /// Extra confidence penalty for symbols in files matching a glob, e.g.
+    /// `("src/api/**", 20)`; negative values raise the score. Only the first
+    /// matching glob applies.
+    pub confidence_overrides: Vec<(String, i16)>,
+
+    /// Never report symbols whose name matches one of these regexes, e.g. `^route_`
+    pub keep_patterns: Vec<String>,
+
+    /// Whether type-only imports (`import type`) keep their targets alive;
+    /// disable to find code that is dead at runtime
+    pub follow_type_imports: bool,
+
+    /// Leave out dead symbols shorter than this many lines (0 keeps all)
+    pub min_loc: usize,
+
+    /// Dead code ratio (0.0 to 1.0) above which a result with few entry
+    /// points gets a `SuspiciousDeadCodeRatio` warning
+    pub suspicious_dead_ratio: f64,
+
+    /// Also report symbols referenced only inside always-false `if`
+    /// branches, with low confidence (see [`dead_branches`])
+    pub detect_dead_branches: bool,
+
+    /// Time parsing per file and report this many of the slowest files in
+    /// `DeadCodeResult::profile` (off if `None`). Bypasses the graph cache.
+    pub profile: Option<usize>,
+
+    /// Leave out dead symbols of files whose first lines match one of
+    /// `generated_markers`; their references still keep other code alive
+    pub skip_generated: bool,
+
+    /// Regexes marking a file as generated when they match one of its first
+    /// [`GENERATED_HEADER_LINES`] lines
+    pub generated_markers: Vec<String>,
+
+    /// Only report dead exported symbols and members of exported enums and
+    /// objects, i.e. unused public API; summary totals cover them alone
+    pub exports_only: bool,
+
+    /// Add edges from each symbol to the same-file functions it calls, so
+    /// local helpers of live code stay alive (see [`call_graph`])
+    pub call_graph: bool,
+
+    /// Fill `DeadSymbol::age_days` from `git blame`. Every file with dead
+    /// code is blamed, which is slow on large repositories.
+    pub symbol_ages: bool,
 }
 
 impl Default for AnalysisConfig {
@@ -73,12 +197,62 @@ impl Default for AnalysisConfig {
                 "build/**".to_string(),
                 ".git/**".to_string(),
             ],
-            enable_cache: true,
+            cache_mode: CacheMode::ReadWrite,
             cache_dir: None,
+            memory_cache_capacity: 8,
+            memory_cache_ttl: Duration::from_secs(300),
+            include_patterns: Vec::new(),
+            roots: Vec::new(),
+            max_depth: None,
+            max_files: None,
+            languages: Vec::new(),
+            extension_overrides: std::collections::HashMap::new(),
+            entry_point_patterns: Vec::new(),
+            confidence_weights: ConfidenceWeights::default(),
+            confidence_overrides: Vec::new(),
+            keep_patterns: Vec::new(),
+            follow_type_imports: true,
+            min_loc: 0,
+            suspicious_dead_ratio: 0.8,
+            detect_dead_branches: false,
+            profile: None,
+            skip_generated: false,
+            generated_markers: vec![
+                "@generated".to_string(),
+                r"\bDO NOT EDIT\b".to_string(),
+                "<auto-generated".to_string(),
+                r"^/\* eslint-disable \*/$".to_string(),
+            ],
+            exports_only: false,
+            call_graph: false,
+            symbol_ages: false,
         }
     }
 }
 
+/// How an analysis uses the symbol graph caches
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheMode {
+    /// Load cached graphs, and save or invalidate them as files change
+    #[default]
+    ReadWrite,
+    /// Load cached graphs but never save or invalidate them, so a run can
+    /// check whether the cache would have hit without changing it. Reads
+    /// from a temporary copy of the whole on-disk cache.
+    ReadOnly,
+    /// Always build the graph from the files
+    Off,
+}
+
+/// Number of leading lines searched for `generated_markers`
+pub const GENERATED_HEADER_LINES: usize = 5;
+
+/// At most this many entry points counts as "few" for the suspicion check
+const FEW_ENTRY_POINTS: usize = 3;
+
 /// Error type for analysis operations
 #[derive(Debug, Error)]
 pub enum AnalysisError {
@@ -95,6 +269,7 @@ pub enum AnalysisError {
     ReachabilityError(#[from] reachability::ReachabilityError),
 
     /// Cache operation failed
+    #[cfg(not(target_arch = "wasm32"))]
     #[error("Cache operation failed: {0}")]
     CacheError(#[from] cache::CacheError),
 
@@ -105,6 +280,68 @@ pub enum AnalysisError {
     /// No entry points found
     #[error("No entry points found in the codebase")]
     NoEntryPoints,
+
+    /// The directory has files, but none in a supported language
+    #[error(
+        "No supported source files in {} ({unsupported} files of other types)",
+        path.display()
+    )]
+    NoSupportedFiles {
+        /// Analyzed directory
+        path: PathBuf,
+        /// Files left out for their extension
+        unsupported: usize,
+    },
+
+    /// The scan selected more source files than `AnalysisConfig::max_files`
+    #[error(
+        "Found {found} source files, more than the limit of {limit}; \
+         narrow the path or exclude patterns, or raise the limit"
+    )]
+    TooManyFiles {
+        /// Source files selected by the scan
+        found: usize,
+        /// Configured `max_files`
+        limit: usize,
+    },
+
+    /// Analysis was cancelled via the cancellation flag
+    #[error("Analysis cancelled")]
+    Cancelled,
+
+    /// Invalid glob or regex pattern in the configuration
+    #[error("Invalid pattern: {0}")]
+    InvalidPattern(String),
+
+    /// Requested symbol does not exist in the symbol graph
+    #[error("Symbol not found: {0}")]
+    SymbolNotFound(String),
+}
+
+impl AnalysisError {
+    /// Collapse stage-level cancellation errors into `AnalysisError::Cancelled`
+    fn from_graph(err: symbol_graph::GraphError) -> Self {
+        match err {
+            symbol_graph::GraphError::Cancelled => AnalysisError::Cancelled,
+            err => AnalysisError::GraphError(err),
+        }
+    }
+
+    fn from_reachability(err: reachability::ReachabilityError) -> Self {
+        match err {
+            reachability::ReachabilityError::Cancelled => AnalysisError::Cancelled,
+            err => AnalysisError::ReachabilityError(err),
+        }
+    }
+}
+
+/// Return `AnalysisError::Cancelled` if the cancellation flag is set
+fn check_cancelled(cancel: Option<&AtomicBool>) -> Result<(), AnalysisError> {
+    if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+        tracing::info!("Dead code analysis cancelled");
+        return Err(AnalysisError::Cancelled);
+    }
+    Ok(())
 }
 
 /// Main entry point for dead code analysis
@@ -136,41 +373,133 @@ pub enum AnalysisError {
 /// println!("Found {} dead symbols", result.summary.dead_functions);
 /// # Ok::<(), code_viz_dead_code::AnalysisError>(())
 /// ```
-#[tracing::instrument(skip(config), fields(path = %path.display()))]
+#[cfg(not(target_arch = "wasm32"))]
 pub fn analyze_dead_code(
     path: &Path,
     config: Option<AnalysisConfig>,
+) -> Result<DeadCodeResult, AnalysisError> {
+    analyze_dead_code_with_cancel(path, config, None)
+}
+
+/// Dead code analysis that can be cancelled from another thread
+///
+/// Behaves like [`analyze_dead_code`], but checks `cancel` between pipeline
+/// stages, between files while building the symbol graph, and between entry
+/// points during reachability analysis. Once the flag is set the analysis
+/// returns `AnalysisError::Cancelled` without writing to the cache.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::{analyze_dead_code_with_cancel, AnalysisError};
+/// use std::path::Path;
+/// use std::sync::atomic::AtomicBool;
+///
+/// let cancel = AtomicBool::new(false);
+/// match analyze_dead_code_with_cancel(Path::new("./src"), None, Some(&cancel)) {
+///     Err(AnalysisError::Cancelled) => println!("Cancelled"),
+///     other => println!("{:?}", other.map(|r| r.summary.dead_functions)),
+/// }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_dead_code_with_cancel(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+    cancel: Option<&AtomicBool>,
+) -> Result<DeadCodeResult, AnalysisError> {
+    analyze_dead_code_with_progress(path, config, cancel, &|_, _| {})
+}
+
+/// Dead code analysis reporting progress of each pipeline stage
+///
+/// Behaves like [`analyze_dead_code_with_cancel`], calling `progress` with the
+/// completed fraction (0.0 to 1.0) and a message as each stage starts.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::analyze_dead_code_with_progress;
+/// use std::path::Path;
+///
+/// let result = analyze_dead_code_with_progress(Path::new("./src"), None, None, &|fraction, message| {
+///     eprintln!("{:.0}% - {}", fraction * 100.0, message);
+/// })?;
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_dead_code_with_progress(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+    cancel: Option<&AtomicBool>,
+    progress: &dyn Fn(f32, &str),
+) -> Result<DeadCodeResult, AnalysisError> {
+    analyze_dead_code_with_graph_cache(
+        path,
+        config,
+        cache::MemoryGraphCache::global(),
+        cancel,
+        progress,
+    )
+}
+
+/// Dead code analysis keeping symbol graphs in `graphs` instead of the
+/// process-wide [`cache::MemoryGraphCache::global`]
+///
+/// Behaves like [`analyze_dead_code_with_progress`]; `graphs` is only used
+/// when `cache_mode` is not `CacheMode::Off`.
+#[cfg(not(target_arch = "wasm32"))]
+#[tracing::instrument(skip(config, graphs, cancel, progress), fields(path = %path.display()))]
+pub fn analyze_dead_code_with_graph_cache(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+    graphs: &cache::MemoryGraphCache,
+    cancel: Option<&AtomicBool>,
+    progress: &dyn Fn(f32, &str),
 ) -> Result<DeadCodeResult, AnalysisError> {
     let config = config.unwrap_or_default();
 
     tracing::info!("Starting dead code analysis");
+    // Fail on invalid patterns before the expensive stages
+    build_keep_patterns(&config.keep_patterns)?;
+    build_confidence_overrides(&config.confidence_overrides)?;
+    if archive::is_archive(path) {
+        return analyze_archive(path, &config, cancel, progress);
+    }
 
     // Step 1: Scan directory for source files
     tracing::info!("Scanning directory for source files");
-    let files = code_viz_core::scanner::scan_directory(path, &config.exclude_patterns)?;
-
-    if files.is_empty() {
+    progress(0.0, "Scanning directory...");
+    let scan = scan_files(path, &config)?;
+    if scan.files.is_empty() {
         tracing::warn!("No source files found in directory");
-        return Ok(DeadCodeResult {
-            summary: DeadCodeSummary {
-                total_files: 0,
-                files_with_dead_code: 0,
-                dead_functions: 0,
-                dead_classes: 0,
-                total_dead_loc: 0,
-                dead_code_ratio: 0.0,
-            },
-            files: vec![],
-        });
+        progress(1.0, "No source files found");
+        return no_files_result(path, &scan);
     }
+    let ScanResult { files, skipped, .. } = scan;
+    let skipped_files = skipped.len();
 
     tracing::info!(file_count = files.len(), "Found source files");
+    check_cancelled(cancel)?;
+    progress(
+        0.1,
+        &format!("Building symbol graph for {} files...", files.len()),
+    );
 
     // Step 2: Build or load cached symbol graph
-    let graph = if config.enable_cache {
-        load_or_build_graph(&files, &config, path)?
+    let mut profile = None;
+    let mut cache_hit = false;
+    let graph = if let Some(top) = config.profile {
+        // A cached graph was not parsed in this run, so there is nothing to time
+        let (graph, mut timings) = profile_graph_build(&files, &config, cancel)?;
+        timings.truncate(top);
+        profile = Some(timings);
+        graph
+    } else if config.cache_mode != CacheMode::Off {
+        let (graph, hit) = load_or_build_graph(&files, &config, path, cancel, graphs)?;
+        cache_hit = hit;
+        graph
     } else {
-        build_graph_from_files(&files)?
+        build_graph_from_files(&files, &config, cancel)?
     };
 
     tracing::info!(
@@ -178,24 +507,479 @@ pub fn analyze_dead_code(
         "Symbol graph constructed"
     );
 
-    // Step 3: Detect entry points
-    tracing::info!("Detecting entry points");
-    let entry_points = entry_points::detect_entry_points(&graph);
+    let entry_points = collect_entry_points(&graph, &config, path, &read_templates(&files))?;
+    let mut result = analyze_graph(
+        &graph,
+        path,
+        entry_points,
+        SourceFindings::scan(&read_sources(&files), &graph, &config)?,
+        &config,
+        cancel,
+        progress,
+    )?;
+    record_skipped_files(&mut result, skipped_files);
+    result.profile = profile;
+    // Only reported where the cache is being measured, see `DeadCodeResult::cache_hit`
+    result.cache_hit = (config.cache_mode == CacheMode::ReadOnly).then_some(cache_hit);
+    Ok(result)
+}
+
+/// Dead code analysis of the source entries of an archive, read in memory
+///
+/// Entries are analyzed under virtual paths inside `path`. The caches are
+/// keyed by files on disk, so they are not used.
+#[cfg(not(target_arch = "wasm32"))]
+fn analyze_archive(
+    path: &Path,
+    config: &AnalysisConfig,
+    cancel: Option<&AtomicBool>,
+    progress: &dyn Fn(f32, &str),
+) -> Result<DeadCodeResult, AnalysisError> {
+    progress(0.0, "Reading archive...");
+    let selected = archive::read_archive(path, config)?;
+    if selected.sources.is_empty() {
+        tracing::warn!("No source files found in archive");
+        progress(1.0, "No source files found");
+        return no_files_result(path, &selected.scan_result());
+    }
+    let archive::ArchiveSources {
+        sources,
+        templates,
+        skipped,
+        ..
+    } = selected;
+
+    tracing::info!(file_count = sources.len(), "Found source files in archive");
+    check_cancelled(cancel)?;
+    progress(
+        0.1,
+        &format!("Building symbol graph for {} files...", sources.len()),
+    );
+
+    let mut profile = None;
+    let graph = if let Some(top) = config.profile {
+        let (graph, mut timings) =
+            profile_graph_build_from_sources(sources.clone(), config, cancel)?;
+        timings.truncate(top);
+        profile = Some(timings);
+        graph
+    } else {
+        build_graph_from_sources(sources.clone(), config, cancel)?
+    };
+
+    let entry_points = collect_entry_points(&graph, config, path, &templates)?;
+    let mut result = analyze_graph(
+        &graph,
+        path,
+        entry_points,
+        SourceFindings::scan(&sources, &graph, config)?,
+        config,
+        cancel,
+        progress,
+    )?;
+    record_skipped_files(&mut result, skipped.len());
+    result.profile = profile;
+    Ok(result)
+}
+
+/// Best-effort dead code analysis of a single file's source, e.g. piped from stdin
+///
+/// `path` does not need to exist; its extension selects the parser. The file
+/// is treated as a module whose exported symbols are used elsewhere, so its
+/// exports and entry points count as live and everything else is reported.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::analyze_source;
+/// use std::path::Path;
+///
+/// let source = "export function used() {}\nfunction unused() {}\n";
+/// let result = analyze_source(Path::new("<stdin>.ts"), source, None)?;
+/// assert_eq!(result.summary.dead_functions, 1);
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_source(
+    path: &Path,
+    source: &str,
+    config: Option<AnalysisConfig>,
+) -> Result<DeadCodeResult, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let templates = component_templates([(path, source)]);
+    let sources = vec![(
+        path.to_path_buf(),
+        component_script(path, normalize_source(source.to_string())),
+    )];
+
+    let graph = symbol_graph::SymbolGraphBuilder::new()
+        .build_graph(sources.clone())
+        .map_err(AnalysisError::from_graph)?;
+
+    let root = path.parent().unwrap_or(Path::new(""));
+    let mut entry_points = collect_entry_points(&graph, &config, root, &templates)?;
+    for symbol_id in graph.exports.values().flatten() {
+        if !entry_points.contains(symbol_id) {
+            entry_points.push(symbol_id.clone());
+        }
+    }
+
+    analyze_graph(
+        &graph,
+        root,
+        entry_points,
+        SourceFindings::scan(&sources, &graph, &config)?,
+        &config,
+        None,
+        &|_, _| {},
+    )
+}
+
+/// Dead code analysis of a project given as in-memory `(path, source)` pairs
+///
+/// Nothing is scanned, read or cached: `sources` are the whole project and
+/// paths only need to live under `root`, which entry point and override globs
+/// are relative to. Exclude, include and language settings do not apply, but
+/// `max_files` does. Parsing needs the Tree-sitter grammars, so browser
+/// builds use [`analyze_symbol_graph`] instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::analyze_sources;
+/// use std::path::{Path, PathBuf};
+///
+/// let sources = vec![
+///     (PathBuf::from("/demo/main.ts"), "function main() {}\nmain();\n".to_string()),
+///     (PathBuf::from("/demo/util.ts"), "export function unused() {}\n".to_string()),
+/// ];
+/// let result = analyze_sources(Path::new("/demo"), sources, None)?;
+/// assert_eq!(result.summary.dead_functions, 1);
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+///
+/// # Errors
+/// Returns `AnalysisError::NoEntryPoints` if no source has an entry point
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_sources(
+    root: &Path,
+    sources: Vec<(PathBuf, String)>,
+    config: Option<AnalysisConfig>,
+) -> Result<DeadCodeResult, AnalysisError> {
+    let config = config.unwrap_or_default();
+    build_keep_patterns(&config.keep_patterns)?;
+    build_confidence_overrides(&config.confidence_overrides)?;
+    if sources.is_empty() {
+        return Ok(empty_result());
+    }
+    check_file_count(sources.len(), &config)?;
+
+    let templates = component_templates(
+        sources
+            .iter()
+            .map(|(path, source)| (path.as_path(), source.as_str())),
+    );
+    let sources: Vec<(PathBuf, String)> = sources
+        .into_iter()
+        .map(|(path, source)| {
+            let source = component_script(&path, normalize_source(source));
+            (path, source)
+        })
+        .collect();
+    let graph = build_graph_from_sources(sources.clone(), &config, None)?;
+    let entry_points = collect_entry_points(&graph, &config, root, &templates)?;
+    analyze_graph(
+        &graph,
+        root,
+        entry_points,
+        SourceFindings::scan(&sources, &graph, &config)?,
+        &config,
+        None,
+        &|_, _| {},
+    )
+}
+
+/// Dead code analysis of an already built symbol graph
+///
+/// Runs entry point detection, reachability and confidence scoring on
+/// `graph` alone, skipping the passes that need the sources (unused members
+/// and private methods, dead branches, string references and call edges).
+/// The graph may come from [`SymbolGraph::from_symbols`] or a deserialized
+/// [`export_symbol_graph`]; `root` is what entry point and override globs are
+/// relative to. This is the analysis available in wasm32 builds.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::{analyze_symbol_graph, SymbolGraph};
+/// use std::path::Path;
+///
+/// # let json = "{}";
+/// let graph: SymbolGraph = serde_json::from_str(json).unwrap();
+/// let result = analyze_symbol_graph(&graph, Path::new(""), None)?;
+/// println!("Found {} dead symbols", result.summary.dead_functions);
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+///
+/// # Errors
+/// Returns `AnalysisError::NoEntryPoints` if the graph has no entry point
+pub fn analyze_symbol_graph(
+    graph: &SymbolGraph,
+    root: &Path,
+    config: Option<AnalysisConfig>,
+) -> Result<DeadCodeResult, AnalysisError> {
+    let config = config.unwrap_or_default();
+    build_keep_patterns(&config.keep_patterns)?;
+    build_confidence_overrides(&config.confidence_overrides)?;
+    if graph.symbols.is_empty() {
+        return Ok(empty_result());
+    }
+
+    // Only the symbols are known, not the component markup
+    let entry_points = collect_entry_points(graph, &config, root, &Templates::default())?;
+    analyze_graph(
+        graph,
+        root,
+        entry_points,
+        SourceFindings::default(),
+        &config,
+        None,
+        &|_, _| {},
+    )
+}
+
+/// Replace the re-exports synthesized for each `export * from` with one
+/// symbol named `*` spanning the statement
+///
+/// A wildcard cannot drop single names, so it is only reported when every
+/// name it forwards is dead; otherwise its dead names are left out.
+fn collapse_wildcard_reexports(
+    graph: &SymbolGraph,
+    dead_symbols: Vec<models::Symbol>,
+) -> Vec<models::Symbol> {
+    let (wildcards, mut dead_symbols): (Vec<_>, Vec<_>) = dead_symbols
+        .into_iter()
+        .partition(|symbol| graph.is_wildcard_reexport(&symbol.id));
+    if wildcards.is_empty() {
+        return dead_symbols;
+    }
+
+    let statement = |symbol: &models::Symbol| (symbol.path.clone(), symbol.byte_start);
+    let mut forwarded: HashMap<(PathBuf, usize), usize> = HashMap::new();
+    for id in &graph.wildcard_reexports {
+        if let Some(symbol) = graph.symbols.get(id) {
+            *forwarded.entry(statement(symbol)).or_default() += 1;
+        }
+    }
+    let mut dead: HashMap<(PathBuf, usize), Vec<models::Symbol>> = HashMap::new();
+    for symbol in wildcards {
+        dead.entry(statement(&symbol)).or_default().push(symbol);
+    }
+
+    let mut collapsed: Vec<models::Symbol> = dead
+        .into_iter()
+        .filter(|(key, names)| forwarded.get(key) == Some(&names.len()))
+        .filter_map(|(_, names)| names.into_iter().next())
+        .map(|symbol| models::Symbol {
+            id: format!("{}:{}:*", symbol.path.display(), symbol.line_start),
+            name: "*".to_string(),
+            fingerprint: models::fingerprint("*"),
+            ..symbol
+        })
+        .collect();
+    collapsed.sort_by(|a, b| a.id.cmp(&b.id));
+    dead_symbols.extend(collapsed);
+    dead_symbols
+}
+
+/// Outcome of a scan that found no source files
+///
+/// A directory holding only files of other types is an error, so that a
+/// mistyped path does not pass as a clean result; an empty one is not.
+fn no_files_result(path: &Path, scan: &ScanResult) -> Result<DeadCodeResult, AnalysisError> {
+    if scan.unsupported_files > 0 {
+        return Err(AnalysisError::NoSupportedFiles {
+            path: path.to_path_buf(),
+            unsupported: scan.unsupported_files,
+        });
+    }
+    let mut result = empty_result();
+    record_skipped_files(&mut result, scan.skipped.len());
+    Ok(result)
+}
+
+/// Count the files left out of the scan in `result`, with a warning if any were
+pub(crate) fn record_skipped_files(result: &mut DeadCodeResult, skipped: usize) {
+    result.summary.skipped_files = skipped;
+    if skipped > 0 {
+        result.warnings.push(AnalysisWarning {
+            kind: AnalysisWarningKind::SkippedFiles,
+            message: format!(
+                "{} source file(s) were skipped as too large or unreadable; code only they \
+                 use may be reported as dead",
+                skipped
+            ),
+        });
+    }
+}
+
+/// Result for a directory without source files
+fn empty_result() -> DeadCodeResult {
+    DeadCodeResult {
+        schema_version: code_viz_core::report::current_schema_version(),
+        summary: DeadCodeSummary {
+            total_files: 0,
+            files_with_dead_code: 0,
+            dead_functions: 0,
+            dead_classes: 0,
+            dead_other: 0,
+            total_dead_loc: 0,
+            total_loc: 0,
+            total_complexity: 0,
+            dead_code_ratio: 0.0,
+            dead_complexity_ratio: 0.0,
+            kept_by_pattern: 0,
+            filtered_small: 0,
+            skipped_generated: 0,
+            skipped_files: 0,
+        },
+        files: vec![],
+        by_directory: vec![],
+        file_totals: vec![],
+        dead_clusters: vec![],
+        warnings: vec![],
+        profile: None,
+        cache_hit: None,
+    }
+}
+
+/// Dead code found by scanning sources rather than following the graph
+#[derive(Default)]
+pub(crate) struct SourceFindings {
+    /// Enum and const-object members that are never accessed
+    unused_members: Vec<models::UnusedMember>,
+
+    /// `private` methods their class never refers to
+    unused_private_methods: Vec<models::UnusedPrivateMethod>,
+
+    /// Symbols only referenced inside always-false branches, if enabled
+    dead_branch_only: ahash::AHashSet<models::SymbolId>,
+
+    /// Files carrying a generated-code marker, if skipping them is enabled
+    generated_files: ahash::AHashSet<PathBuf>,
+
+    /// Same-file call edges to add to the graph, if call-graph mode is enabled
+    call_edges: HashMap<models::SymbolId, Vec<models::SymbolId>>,
+}
+
+impl SourceFindings {
+    /// Run the source-level passes enabled in `config` over `sources`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn scan(
+        sources: &[(PathBuf, String)],
+        graph: &symbol_graph::SymbolGraph,
+        config: &AnalysisConfig,
+    ) -> Result<Self, AnalysisError> {
+        let dead_branch_only = if config.detect_dead_branches {
+            dead_branches::find_dead_branch_only(sources, graph.symbols.values())
+        } else {
+            ahash::AHashSet::new()
+        };
+        let generated_files = if config.skip_generated {
+            find_generated_files(sources, &config.generated_markers)?
+        } else {
+            ahash::AHashSet::new()
+        };
+        let call_edges = if config.call_graph {
+            call_graph::find_call_edges(sources, graph.symbols.values())
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            unused_members: members::find_unused_members_in_sources(sources),
+            unused_private_methods: private_members::find_unused_private_methods(
+                sources,
+                graph.symbols.values(),
+            ),
+            dead_branch_only,
+            generated_files,
+            call_edges,
+        })
+    }
+}
+
+/// Files with a line matching one of `markers` among their first
+/// [`GENERATED_HEADER_LINES`] lines
+fn find_generated_files(
+    sources: &[(PathBuf, String)],
+    markers: &[String],
+) -> Result<ahash::AHashSet<PathBuf>, AnalysisError> {
+    let markers =
+        regex::RegexSet::new(markers).map_err(|e| AnalysisError::InvalidPattern(e.to_string()))?;
+    Ok(sources
+        .iter()
+        .filter(|(_, source)| {
+            source
+                .lines()
+                .take(GENERATED_HEADER_LINES)
+                .any(|line| markers.is_match(line.trim()))
+        })
+        .map(|(path, _)| path.clone())
+        .collect())
+}
+
+/// Find and score dead code in an already built symbol graph, reached from
+/// `entry_points`, along with the dead code found in its sources
+///
+/// `root` is the directory that confidence override globs are relative to.
+/// The summary's `skipped_files` is left for the caller to fill in.
+fn analyze_graph(
+    graph: &symbol_graph::SymbolGraph,
+    root: &Path,
+    entry_points: Vec<models::SymbolId>,
+    findings: SourceFindings,
+    config: &AnalysisConfig,
+    cancel: Option<&AtomicBool>,
+    progress: &dyn Fn(f32, &str),
+) -> Result<DeadCodeResult, AnalysisError> {
+    let keep_patterns = build_keep_patterns(&config.keep_patterns)?;
 
     if entry_points.is_empty() {
         tracing::error!("No entry points found in codebase");
         return Err(AnalysisError::NoEntryPoints);
     }
 
-    tracing::info!(
-        entry_point_count = entry_points.len(),
-        "Entry points detected"
-    );
+    let entry_point_count = entry_points.len();
+    tracing::info!(entry_point_count, "Entry points detected");
+
+    // Entry points are used from outside, wherever else they are referenced
+    let SourceFindings {
+        unused_members,
+        unused_private_methods,
+        mut dead_branch_only,
+        generated_files,
+        call_edges,
+    } = findings;
+    for entry_point in &entry_points {
+        dead_branch_only.remove(entry_point);
+    }
 
     // Step 4: Perform reachability analysis
     tracing::info!("Performing reachability analysis");
-    let mut analyzer = reachability::ReachabilityAnalyzer::new(graph.clone());
-    let reachable = analyzer.analyze(entry_points)?;
+    progress(0.6, "Analyzing reachability...");
+    let mut reachability_graph = graph.clone();
+    for (caller, callees) in call_edges {
+        reachability_graph
+            .imports
+            .entry(caller)
+            .or_default()
+            .extend(callees);
+    }
+    let mut analyzer = reachability::ReachabilityAnalyzer::new(reachability_graph)
+        .with_type_imports(config.follow_type_imports);
+    let reachable = analyzer
+        .analyze_with_cancel(entry_points, cancel)
+        .map_err(AnalysisError::from_reachability)?;
 
     tracing::info!(
         reachable_count = reachable.len(),
@@ -203,50 +987,157 @@ pub fn analyze_dead_code(
         "Reachability analysis complete"
     );
 
-    // Step 5: Identify dead code
-    let dead_symbols = reachability::identify_dead_code(&graph, &reachable);
+    // Step 5: Identify dead code, including live symbols only referenced in
+    // always-false branches and private methods of live classes that the
+    // class never calls
+    let branch_only = dead_branch_only
+        .iter()
+        .filter(|id| reachable.contains(*id))
+        .filter_map(|id| graph.symbols.get(id).cloned());
+    let unused_private: ahash::AHashSet<models::SymbolId> = unused_private_methods
+        .into_iter()
+        .filter(|m| reachable.contains(&m.class) && reachable.contains(&m.method))
+        .filter(|m| !dead_branch_only.contains(&m.method))
+        .map(|m| m.method)
+        .collect();
+    let private_only = unused_private
+        .iter()
+        .filter_map(|id| graph.symbols.get(id).cloned());
+    let (generated, dead_symbols): (Vec<_>, Vec<_>) =
+        reachability::identify_dead_code(graph, &reachable)
+            .into_iter()
+            .chain(branch_only)
+            .chain(private_only)
+            .filter(|symbol| {
+                !config.exports_only || (symbol.is_exported && !unused_private.contains(&symbol.id))
+            })
+            .partition(|symbol| generated_files.contains(&symbol.path));
+    let dead_symbols = collapse_wildcard_reexports(graph, dead_symbols);
+    let (kept, dead_symbols): (Vec<_>, Vec<_>) = dead_symbols
+        .into_iter()
+        .partition(|symbol| keep_patterns.is_match(&symbol.name));
+    let kept_by_pattern = kept.len();
+
+    // Members of enums and const objects that are never accessed; members
+    // of a container that is dead itself are covered by its finding
+    let dead_containers: ahash::AHashSet<(&Path, &str)> = graph
+        .symbols
+        .values()
+        .filter(|symbol| !reachable.contains(&symbol.id))
+        .map(|symbol| (symbol.path.as_path(), symbol.name.as_str()))
+        .collect();
+    let (generated_members, unused_members): (Vec<_>, Vec<_>) = unused_members
+        .into_iter()
+        .filter(|m| {
+            !dead_containers.contains(&(m.container.path.as_path(), m.container.name.as_str()))
+        })
+        .filter(|m| !config.exports_only || m.container.is_exported)
+        .partition(|m| generated_files.contains(&m.member.path));
+    let skipped_generated = generated.len() + generated_members.len();
+    let (kept_members, unused_members): (Vec<_>, Vec<_>) = unused_members
+        .into_iter()
+        .partition(|m| keep_patterns.is_match(&m.member.name));
+    let kept_by_pattern = kept_by_pattern + kept_members.len();
+
+    // Drop symbols shorter than the configured minimum size
+    let (small, dead_symbols): (Vec<_>, Vec<_>) = dead_symbols
+        .into_iter()
+        .partition(|symbol| symbol_loc(symbol) < config.min_loc);
+    let (small_members, unused_members): (Vec<_>, Vec<_>) = unused_members
+        .into_iter()
+        .partition(|m| symbol_loc(&m.member) < config.min_loc);
+    let filtered_small = small.len() + small_members.len();
+
+    let dead_clusters = reachability::find_dead_clusters(graph, &dead_symbols);
 
     tracing::info!(
         dead_symbol_count = dead_symbols.len(),
+        unused_member_count = unused_members.len(),
+        kept_by_pattern,
+        filtered_small,
+        skipped_generated,
         "Dead code identified"
     );
 
+    check_cancelled(cancel)?;
+
     // Step 6: Calculate confidence scores
     tracing::info!("Calculating confidence scores");
-    let calculator = confidence::ConfidenceCalculator::new(graph.clone());
+    progress(0.8, "Scoring confidence...");
+    let calculator =
+        confidence::ConfidenceCalculator::with_weights(graph.clone(), config.confidence_weights)
+            .with_path_overrides(
+                root,
+                build_confidence_overrides(&config.confidence_overrides)?,
+            )
+            .with_string_literals(graph.string_literals.iter().cloned().collect());
 
     // Group dead symbols by file and calculate confidence
     let mut files_map: HashMap<PathBuf, Vec<DeadSymbol>> = HashMap::new();
-    let mut total_dead_loc = 0;
-    let mut dead_functions = 0;
-    let mut dead_classes = 0;
-
-    for symbol in dead_symbols {
-        let confidence = calculator.calculate(&symbol);
-        let loc = symbol.line_end.saturating_sub(symbol.line_start) + 1;
-        total_dead_loc += loc;
-
-        match symbol.kind {
-            models::SymbolKind::Function
-            | models::SymbolKind::ArrowFunction
-            | models::SymbolKind::Method => {
-                dead_functions += 1;
+    let mut total_dead_complexity = 0;
+    let mut kinds = models::KindCounts::default();
+
+    // Unused members are scored like the enum or object declaring them
+    let mut findings: Vec<_> = dead_symbols
+        .into_iter()
+        .map(|symbol| {
+            if unused_private.contains(&symbol.id) {
+                // Private methods are not part of the exported class's API
+                let breakdown = calculator.calculate_detailed(&models::Symbol {
+                    is_exported: false,
+                    ..symbol.clone()
+                });
+                return (
+                    symbol,
+                    breakdown,
+                    "Private method is never used by its class",
+                );
             }
-            models::SymbolKind::Class => {
-                dead_classes += 1;
+            let mut breakdown = calculator.calculate_detailed(&symbol);
+            if !reachable.contains(&symbol.id) {
+                return (symbol, breakdown, "Unreachable from entry points");
             }
-            _ => {}
-        }
+            breakdown.penalties.push(ConfidencePenalty::new(
+                "dead branch only",
+                confidence::DEAD_BRANCH_PENALTY,
+            ));
+            (
+                symbol,
+                breakdown,
+                "Only referenced in always-false branches",
+            )
+        })
+        .collect();
+    findings.extend(unused_members.into_iter().map(|unused| {
+        let breakdown = calculator.calculate_detailed(&unused.container);
+        (unused.member, breakdown, "Member is never accessed")
+    }));
+
+    for (symbol, breakdown, reason) in findings {
+        let confidence = breakdown.score();
+        let loc = symbol_loc(&symbol);
+        total_dead_complexity += symbol.complexity;
+
+        kinds.add(symbol.kind);
 
         let dead_symbol = DeadSymbol {
             symbol: symbol.name.clone(),
             kind: symbol.kind,
             line_start: symbol.line_start,
             line_end: symbol.line_end,
+            byte_start: symbol.byte_start,
+            byte_end: symbol.byte_end,
             loc,
+            complexity: symbol.complexity,
             confidence,
-            reason: "Unreachable from entry points".to_string(),
+            reason: reason.to_string(),
             last_modified: None,
+            age_days: config
+                .symbol_ages
+                .then(|| calculator.age_days(&symbol))
+                .flatten(),
+            confidence_penalties: Some(breakdown.penalties),
+            fingerprint: symbol.fingerprint.clone(),
         };
 
         files_map
@@ -261,15 +1152,18 @@ pub fn analyze_dead_code(
         .map(|(path, dead_code)| FileDeadCode { path, dead_code })
         .collect();
 
-    // Sort by path for consistent output
+    // Sort by path, and symbols by position, for consistent output
     files.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in &mut files {
+        file.dead_code
+            .sort_by(|a, b| (a.line_start, &a.symbol).cmp(&(b.line_start, &b.symbol)));
+    }
 
-    // Calculate total LOC (approximate by counting lines in all symbols)
-    let total_loc: usize = graph
-        .symbols
-        .values()
-        .map(|s| s.line_end.saturating_sub(s.line_start) + 1)
-        .sum();
+    // Total LOC counts each line covered by a symbol once, so a class and its
+    // methods do not add up to more lines than the file has; dead LOC alike
+    let file_loc = symbol_lines_per_file(graph);
+    let total_loc: usize = file_loc.values().sum();
+    let total_dead_loc: usize = files.iter().map(FileDeadCode::dead_loc).sum();
 
     let dead_code_ratio = if total_loc > 0 {
         total_dead_loc as f64 / total_loc as f64
@@ -277,220 +1171,3067 @@ pub fn analyze_dead_code(
         0.0
     };
 
-    let files_with_dead_code = files.len();
+    // Weighted by complexity, where classes and their methods both count
+    let total_complexity: usize = graph.symbols.values().map(|s| s.complexity).sum();
+    let dead_complexity_ratio = if total_complexity > 0 {
+        total_dead_complexity as f64 / total_complexity as f64
+    } else {
+        0.0
+    };
 
-    tracing::info!(
-        dead_functions,
-        dead_classes,
-        total_dead_loc,
-        dead_code_ratio = format!("{:.2}%", dead_code_ratio * 100.0),
-        "Analysis complete"
-    );
+    let mut file_complexity: HashMap<&Path, usize> = HashMap::new();
+    let mut file_exports: HashMap<&Path, usize> = HashMap::new();
+    for symbol in graph.symbols.values() {
+        *file_complexity.entry(symbol.path.as_path()).or_default() += symbol.complexity;
+        // Class and enum members are exported with their parent; `export *`
+        // is not counted per forwarded name, matching the count without
+        // dead code analysis
+        if symbol.is_exported
+            && !matches!(
+                symbol.kind,
+                models::SymbolKind::Method | models::SymbolKind::Member
+            )
+            && !graph.is_wildcard_reexport(&symbol.id)
+        {
+            *file_exports.entry(symbol.path.as_path()).or_default() += 1;
+        }
+    }
+    let mut file_totals: Vec<FileTotals> = file_loc
+        .into_iter()
+        .map(|(path, loc)| {
+            let complexity = file_complexity.get(path.as_path()).copied().unwrap_or(0);
+            let export_count = file_exports.get(path.as_path()).copied().unwrap_or(0);
+            FileTotals {
+                path,
+                loc,
+                complexity,
+                export_count,
+            }
+        })
+        .collect();
+    file_totals.sort_by(|a, b| a.path.cmp(&b.path));
 
-    Ok(DeadCodeResult {
-        summary: DeadCodeSummary {
-            total_files: files.len(),
-            files_with_dead_code,
-            dead_functions,
-            dead_classes,
-            total_dead_loc,
-            dead_code_ratio,
-        },
-        files,
+    // Per-directory totals (same line counting as total_loc) for directory ratios
+    let dir_loc = FileTotals::loc_per_directory(&file_totals);
+
+    let by_directory = DirectoryDeadCode::rollup(&files, |dir, dead_loc| {
+        match dir_loc.get(dir) {
+            Some(&loc) if loc > 0 => dead_loc as f64 / loc as f64,
+            _ => 0.0,
+        }
+    });
+
+    let files_with_dead_code = files.len();
+
+    let mut warnings: Vec<AnalysisWarning> = graph
+        .manifest_warnings
+        .iter()
+        .map(|message| AnalysisWarning {
+            kind: AnalysisWarningKind::InvalidManifest,
+            message: format!("{}; imports were resolved without it", message),
+        })
+        .collect();
+    if let Some((importer, source)) = graph.unresolved_imports.first() {
+        tracing::warn!(
+            count = graph.unresolved_imports.len(),
+            "Relative imports match no analyzed file"
+        );
+        warnings.push(AnalysisWarning {
+            kind: AnalysisWarningKind::UnresolvedImport,
+            message: format!(
+                "{} relative import(s) match no analyzed file, e.g. '{}' in {}; check the \
+                 import paths and exclude patterns",
+                graph.unresolved_imports.len(),
+                source,
+                importer.strip_prefix(root).unwrap_or(importer).display()
+            ),
+        });
+    }
+    if dead_code_ratio > config.suspicious_dead_ratio && entry_point_count <= FEW_ENTRY_POINTS {
+        tracing::warn!(
+            entry_point_count,
+            dead_code_ratio,
+            "Suspiciously high dead code ratio"
+        );
+        warnings.push(AnalysisWarning {
+            kind: AnalysisWarningKind::SuspiciousDeadCodeRatio,
+            message: format!(
+                "{:.0}% of the code is unreachable from only {} entry point(s); entry point \
+                 detection probably missed the real entry files. Configure them with entry \
+                 point patterns (`entry_points` under `[dead_code]` in .code-viz.toml).",
+                dead_code_ratio * 100.0,
+                entry_point_count
+            ),
+        });
+    }
+
+    tracing::info!(
+        dead_functions = kinds.functions,
+        dead_classes = kinds.classes,
+        dead_other = kinds.other,
+        total_dead_loc,
+        dead_code_ratio = format!("{:.2}%", dead_code_ratio * 100.0),
+        "Analysis complete"
+    );
+    progress(1.0, "Analysis complete");
+
+    Ok(DeadCodeResult {
+        schema_version: code_viz_core::report::current_schema_version(),
+        summary: DeadCodeSummary {
+            total_files: file_totals.len(),
+            files_with_dead_code,
+            dead_functions: kinds.functions,
+            dead_classes: kinds.classes,
+            dead_other: kinds.other,
+            total_dead_loc,
+            total_loc,
+            total_complexity,
+            dead_code_ratio,
+            dead_complexity_ratio,
+            kept_by_pattern,
+            filtered_small,
+            skipped_generated,
+            skipped_files: 0,
+        },
+        files,
+        by_directory,
+        file_totals,
+        dead_clusters,
+        warnings,
+        profile: None,
+        cache_hit: None,
+    })
+}
+
+/// Lines spanned by a symbol, both ends included
+fn symbol_loc(symbol: &models::Symbol) -> usize {
+    symbol.line_end.saturating_sub(symbol.line_start) + 1
+}
+
+/// Number of lines covered by at least one symbol, per file
+///
+/// Nested symbols, such as methods inside their class, overlap the lines of
+/// the enclosing symbol and are only counted once.
+fn symbol_lines_per_file(graph: &symbol_graph::SymbolGraph) -> HashMap<PathBuf, usize> {
+    let mut spans: HashMap<&Path, Vec<(usize, usize)>> = HashMap::new();
+    for symbol in graph.symbols.values() {
+        spans
+            .entry(symbol.path.as_path())
+            .or_default()
+            .push((symbol.line_start, symbol.line_end));
+    }
+
+    spans
+        .into_iter()
+        .map(|(path, spans)| (path.to_path_buf(), models::covered_lines(spans)))
+        .collect()
+}
+
+/// Graph-level outcome of dead code analysis, before confidence scoring,
+/// keep patterns and summaries
+#[derive(Debug, Clone)]
+pub struct RawAnalysis {
+    /// Symbol graph of every analyzed file
+    pub graph: symbol_graph::SymbolGraph,
+
+    /// Symbols reachable from an entry point
+    pub reachable: ahash::AHashSet<models::SymbolId>,
+
+    /// Entry points the reachability analysis started from
+    pub entry_points: Vec<models::SymbolId>,
+}
+
+impl RawAnalysis {
+    /// Sorted IDs of the reachable, dead and entry point symbols
+    pub fn symbol_ids(&self) -> RawSymbolIds {
+        let sorted = |ids: Vec<&models::SymbolId>| {
+            let mut ids: Vec<models::SymbolId> = ids.into_iter().cloned().collect();
+            ids.sort();
+            ids
+        };
+        let (reachable, dead) = self
+            .graph
+            .symbols
+            .keys()
+            .partition(|id| self.reachable.contains(*id));
+
+        RawSymbolIds {
+            reachable: sorted(reachable),
+            dead: sorted(dead),
+            entry_points: sorted(self.entry_points.iter().collect()),
+        }
+    }
+}
+
+/// Run only the graph stages of dead code analysis
+///
+/// Scans, builds the symbol graph, detects entry points and computes
+/// reachability, skipping the member, dead branch and confidence passes. Every
+/// symbol outside `reachable` is dead.
+///
+/// # Errors
+/// Returns `AnalysisError::NoEntryPoints` if the graph has no entry points
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_dead_code_raw(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+) -> Result<RawAnalysis, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let (graph, templates) = load_graph(path, &config)?;
+
+    let entry_points = collect_entry_points(&graph, &config, path, &templates)?;
+    if entry_points.is_empty() {
+        return Err(AnalysisError::NoEntryPoints);
+    }
+    let reachable = reachability::ReachabilityAnalyzer::new(graph.clone())
+        .with_type_imports(config.follow_type_imports)
+        .analyze(entry_points.clone())
+        .map_err(AnalysisError::from_reachability)?;
+
+    Ok(RawAnalysis {
+        graph,
+        reachable,
+        entry_points,
+    })
+}
+
+/// List every exported symbol with whether other analyzed files import it
+/// and whether it is an entry point
+///
+/// This is the public surface of a library rather than a dead code report:
+/// an export no file imports may still be used by the library's consumers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::analyze_public_api;
+/// use std::path::Path;
+///
+/// let report = analyze_public_api(Path::new("./src"), None)?;
+/// for export in report.exports.iter().filter(|e| !e.used_internally) {
+///     println!("{} is only used by consumers", export.symbol);
+/// }
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_public_api(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+) -> Result<PublicApiReport, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let (graph, templates) = load_graph(path, &config)?;
+    let entry_points: ahash::AHashSet<models::SymbolId> =
+        collect_entry_points(&graph, &config, path, &templates)?
+            .into_iter()
+            .collect();
+    let used = reachability::find_internally_used(&graph);
+
+    let mut exports: Vec<PublicExport> = graph
+        .symbols
+        .values()
+        .filter(|s| s.is_exported)
+        .map(|s| PublicExport {
+            id: s.id.clone(),
+            symbol: s.name.clone(),
+            kind: s.kind,
+            path: s.path.clone(),
+            line_start: s.line_start,
+            used_internally: used.contains(&s.id),
+            is_entry: entry_points.contains(&s.id),
+        })
+        .collect();
+    exports.sort_by(|a, b| {
+        (&a.path, a.line_start, &a.symbol).cmp(&(&b.path, b.line_start, &b.symbol))
+    });
+
+    Ok(PublicApiReport { exports })
+}
+
+/// Build the symbol graph of the codebase at `path` for external tools
+///
+/// Only the symbols and their import and export edges are computed, without
+/// entry points, reachability or confidence. Symbol IDs, paths and export
+/// keys are relative to `path`, so graphs from different checkouts compare.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::export_symbol_graph;
+/// use std::path::Path;
+///
+/// let graph = export_symbol_graph(Path::new("./src"), None)?;
+/// println!("{}", serde_json::to_string(&graph).unwrap());
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_symbol_graph(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+) -> Result<SymbolGraph, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let (mut graph, _) = load_graph(path, &config)?;
+    graph.strip_root(path);
+    Ok(graph)
+}
+
+/// Find symbols duplicated across the codebase at `path`
+///
+/// Symbols shorter than `min_loc` lines are left out, so one-line helpers
+/// such as getters are not reported. See [`find_duplicates`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use code_viz_dead_code::analyze_duplicates;
+/// use std::path::Path;
+///
+/// for group in analyze_duplicates(Path::new("./src"), None, 5)? {
+///     println!("{} copies of {}", group.symbols.len(), group.symbols[0].symbol);
+/// }
+/// # Ok::<(), code_viz_dead_code::AnalysisError>(())
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn analyze_duplicates(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+    min_loc: usize,
+) -> Result<Vec<DuplicateGroup>, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let (graph, _) = load_graph(path, &config)?;
+    Ok(find_duplicates(&graph, min_loc))
+}
+
+/// Explain why a symbol is considered alive
+///
+/// Finds the symbol named `symbol` in a file whose path ends with `file` and
+/// returns the shortest chain of symbols from an entry point to it, or `None`
+/// if the symbol is unreachable (dead).
+///
+/// # Errors
+/// Returns `AnalysisError::SymbolNotFound` if no matching symbol exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn reachability_path(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+    file: &Path,
+    symbol: &str,
+) -> Result<Option<Vec<models::Symbol>>, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let (graph, templates) = load_graph(path, &config)?;
+
+    let target = graph
+        .symbols
+        .values()
+        .filter(|s| s.name == symbol && s.path.ends_with(file))
+        .min_by(|a, b| (&a.path, a.line_start).cmp(&(&b.path, b.line_start)))
+        .ok_or_else(|| {
+            AnalysisError::SymbolNotFound(format!("{}::{}", file.display(), symbol))
+        })?;
+
+    let entry_points = collect_entry_points(&graph, &config, path, &templates)?;
+    let mut analyzer = reachability::ReachabilityAnalyzer::new(graph.clone())
+        .with_type_imports(config.follow_type_imports);
+    analyzer
+        .analyze_with_paths(entry_points)
+        .map_err(AnalysisError::from_reachability)?;
+
+    Ok(analyzer.path_to(&target.id).map(|ids| {
+        ids.iter()
+            .filter_map(|id| graph.symbols.get(id).cloned())
+            .collect()
+    }))
+}
+
+/// Liveness of a single symbol, see [`explain_symbol`]
+#[derive(Debug, Clone)]
+pub enum SymbolLiveness {
+    /// Reported as dead code, with its confidence penalties
+    Dead(DeadSymbol),
+
+    /// Reachable along this shortest chain of symbols from an entry point
+    Reachable(Vec<models::Symbol>),
+
+    /// Unreachable, but left out of the report by a keep pattern, the
+    /// minimum size or generated-file skipping
+    Unreported,
+}
+
+/// Explain whether a symbol is dead, and why
+///
+/// Finds the symbol like [`reachability_path`]. A symbol reported by the full
+/// analysis is returned as [`SymbolLiveness::Dead`] with its confidence
+/// penalties, so private methods and symbols only used in dead branches are
+/// explained as well.
+///
+/// # Errors
+/// Returns `AnalysisError::SymbolNotFound` if no matching symbol exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn explain_symbol(
+    path: &Path,
+    config: Option<AnalysisConfig>,
+    file: &Path,
+    symbol: &str,
+) -> Result<SymbolLiveness, AnalysisError> {
+    let config = config.unwrap_or_default();
+    let result = analyze_dead_code(path, Some(config.clone()))?;
+
+    // Files are sorted by path, symbols by position
+    let dead = result
+        .files
+        .into_iter()
+        .filter(|f| f.path.ends_with(file))
+        .find_map(|f| f.dead_code.into_iter().find(|d| d.symbol == symbol));
+    if let Some(dead) = dead {
+        return Ok(SymbolLiveness::Dead(dead));
+    }
+
+    Ok(match reachability_path(path, Some(config), file, symbol)? {
+        Some(chain) => SymbolLiveness::Reachable(chain),
+        None => SymbolLiveness::Unreported,
+    })
+}
+
+/// Scan `path` and build its symbol graph, using the caches if enabled,
+/// along with the markup identifiers of its components
+#[cfg(not(target_arch = "wasm32"))]
+fn load_graph(
+    path: &Path,
+    config: &AnalysisConfig,
+) -> Result<(symbol_graph::SymbolGraph, Templates), AnalysisError> {
+    if archive::is_archive(path) {
+        let selected = archive::read_archive(path, config)?;
+        if selected.sources.is_empty() {
+            no_files_result(path, &selected.scan_result())?;
+        }
+        let graph = build_graph_from_sources(selected.sources, config, None)?;
+        return Ok((graph, selected.templates));
+    }
+    let scan = scan_files(path, config)?;
+    if scan.files.is_empty() {
+        no_files_result(path, &scan)?;
+    }
+    let files = scan.files;
+    let graph = if config.cache_mode != CacheMode::Off {
+        load_or_build_graph(
+            &files,
+            config,
+            path,
+            None,
+            cache::MemoryGraphCache::global(),
+        )?
+        .0
+    } else {
+        build_graph_from_files(&files, config, None)?
+    };
+    Ok((graph, read_templates(&files)))
+}
+
+/// Return `AnalysisError::TooManyFiles` if `found` exceeds `config.max_files`
+pub(crate) fn check_file_count(found: usize, config: &AnalysisConfig) -> Result<(), AnalysisError> {
+    match config.max_files {
+        Some(limit) if found > limit => {
+            tracing::error!(found, limit, "Too many source files to analyze");
+            Err(AnalysisError::TooManyFiles { found, limit })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Scan settings of `config`
+fn scan_options(config: &AnalysisConfig) -> code_viz_core::scanner::ScanOptions {
+    code_viz_core::scanner::ScanOptions {
+        exclude_patterns: config.exclude_patterns.clone(),
+        max_depth: config.max_depth,
+        extension_overrides: config.extension_overrides.clone(),
+        // Symbols are only found in files with a grammar
+        plain_text_extensions: Vec::new(),
+        include_patterns: config.include_patterns.clone(),
+        languages: config.languages.clone(),
+    }
+}
+
+/// Files under `root` selected by the exclude, include and language settings
+/// of `options`
+fn file_selection(
+    root: &Path,
+    options: &code_viz_core::scanner::ScanOptions,
+) -> Result<code_viz_core::scanner::FileSelection, AnalysisError> {
+    code_viz_core::scanner::FileSelection::new(root, options).map_err(|e| match e {
+        code_viz_core::scanner::ScanError::InvalidPattern(e) => AnalysisError::InvalidPattern(e),
+        e => e.into(),
+    })
+}
+
+/// Scan for source files, keeping only those selected by the roots, include
+/// and language settings
+fn scan_files(path: &Path, config: &AnalysisConfig) -> Result<ScanResult, AnalysisError> {
+    let options = scan_options(config);
+    // Include patterns are relative to `path`, so the files of each root are
+    // matched against them here rather than by the root's own scan
+    let selection = file_selection(path, &options)?;
+    let roots: Vec<PathBuf> = config.roots.iter().map(|root| path.join(root)).collect();
+    let in_roots =
+        |file: &Path| roots.is_empty() || roots.iter().any(|root| file.starts_with(root));
+
+    // Each root is walked on its own, so unrelated roots do not walk
+    // everything under their common ancestor
+    let scan = if roots.is_empty() {
+        code_viz_core::scanner::scan_with_options(path, &options)?
+    } else {
+        let options = code_viz_core::scanner::ScanOptions {
+            include_patterns: Vec::new(),
+            ..options
+        };
+        let mut combined = ScanResult::default();
+        for root in &roots {
+            let dir = if root.is_file() {
+                root.parent().unwrap_or(path)
+            } else {
+                root.as_path()
+            };
+            let scan = code_viz_core::scanner::scan_with_options(dir, &options)?;
+            combined
+                .files
+                .extend(scan.files.into_iter().filter(|file| selection.is_selected(file)));
+            combined.skipped.extend(scan.skipped);
+            combined.unsupported_files += scan.unsupported_files;
+        }
+        // Nested roots find the same files twice
+        combined.files.sort();
+        combined.files.dedup();
+        combined.skipped.sort_by(|a, b| a.path.cmp(&b.path));
+        combined.skipped.dedup_by(|a, b| a.path == b.path);
+        combined
+    };
+
+    let files = scan
+        .files
+        .into_iter()
+        .filter(|file| in_roots(file))
+        .collect::<Vec<_>>();
+    check_file_count(files.len(), config)?;
+
+    Ok(ScanResult {
+        files,
+        skipped: scan
+            .skipped
+            .into_iter()
+            .filter(|skipped| in_roots(&skipped.path))
+            .collect(),
+        unsupported_files: scan.unsupported_files,
+        plain_text_files: Vec::new(),
     })
 }
 
-/// Load graph from cache or build it from files
-#[tracing::instrument(skip(files, config))]
-fn load_or_build_graph(
-    files: &[PathBuf],
-    config: &AnalysisConfig,
-    root_path: &Path,
-) -> Result<symbol_graph::SymbolGraph, AnalysisError> {
-    let cache_dir = config
-        .cache_dir
-        .clone()
-        .unwrap_or_else(|| root_path.join(".code-viz").join("cache"));
+/// Detect entry points, adding every symbol in files matching the configured patterns
+fn collect_entry_points(
+    graph: &symbol_graph::SymbolGraph,
+    config: &AnalysisConfig,
+    root: &Path,
+    templates: &Templates,
+) -> Result<Vec<models::SymbolId>, AnalysisError> {
+    tracing::info!("Detecting entry points");
+    let mut entry_points = entry_points::detect_entry_points(graph);
+    let template_references = template_references(graph, &entry_points, templates);
+    entry_points.extend(template_references);
+    let (package, globs): (Vec<String>, Vec<String>) = config
+        .entry_point_patterns
+        .iter()
+        .cloned()
+        .partition(|pattern| pattern == entry_points::PACKAGE_ENTRY_POINTS);
+    if !package.is_empty() {
+        for symbol_id in entry_points::detect_package_entry_points(graph, root) {
+            if !entry_points.contains(&symbol_id) {
+                entry_points.push(symbol_id);
+            }
+        }
+    }
+    if globs.is_empty() {
+        return Ok(entry_points);
+    }
+
+    let patterns = build_glob_set(&globs)?;
+    let detected: ahash::AHashSet<&models::SymbolId> = entry_points.iter().collect();
+    let mut configured: Vec<models::SymbolId> = graph
+        .symbols
+        .values()
+        .filter(|s| !detected.contains(&s.id))
+        .filter(|s| patterns.is_match(s.path.strip_prefix(root).unwrap_or(&s.path)))
+        .map(|s| s.id.clone())
+        .collect();
+    configured.sort();
+    entry_points.extend(configured);
+
+    Ok(entry_points)
+}
+
+/// Symbols of Vue and Svelte components whose names appear in the component markup
+///
+/// Script bindings used only by the template are otherwise unreferenced.
+fn template_references(
+    graph: &symbol_graph::SymbolGraph,
+    detected: &[models::SymbolId],
+    templates: &Templates,
+) -> Vec<models::SymbolId> {
+    let detected: ahash::AHashSet<&models::SymbolId> = detected.iter().collect();
+    let mut referenced: Vec<models::SymbolId> = graph
+        .symbols
+        .values()
+        .filter(|s| sfc::is_component(&s.path) && !detected.contains(&s.id))
+        .filter(|s| {
+            templates
+                .get(&s.path)
+                .is_some_and(|identifiers| identifiers.contains(&s.name))
+        })
+        .map(|s| s.id.clone())
+        .collect();
+    referenced.sort();
+    referenced
+}
+
+/// Identifier-like words in the markup of each Vue and Svelte component
+///
+/// Parsed sources only keep the component scripts, so the markup is taken
+/// from the raw text wherever it is read.
+pub(crate) type Templates = HashMap<PathBuf, ahash::AHashSet<String>>;
+
+/// Markup identifiers of the components among raw `(path, source)` pairs
+pub(crate) fn component_templates<'a>(
+    sources: impl IntoIterator<Item = (&'a Path, &'a str)>,
+) -> Templates {
+    sources
+        .into_iter()
+        .filter(|(path, _)| sfc::is_component(path))
+        .map(|(path, source)| (path.to_path_buf(), template_identifiers(source)))
+        .collect()
+}
+
+/// Markup identifiers of the components among `files` on disk
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_templates(files: &[PathBuf]) -> Templates {
+    files
+        .iter()
+        .filter(|path| sfc::is_component(path))
+        .map(|path| {
+            let source = std::fs::read(path)
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default();
+            (path.clone(), template_identifiers(&source))
+        })
+        .collect()
+}
+
+/// Identifier-like words in the markup of a component's source
+pub(crate) fn template_identifiers(source: &str) -> ahash::AHashSet<String> {
+    sfc::template_source(source)
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<globset::GlobSet, AnalysisError> {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = globset::Glob::new(pattern)
+            .map_err(|e| AnalysisError::InvalidPattern(e.to_string()))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| AnalysisError::InvalidPattern(e.to_string()))
+}
+
+fn build_confidence_overrides(
+    overrides: &[(String, i16)],
+) -> Result<Vec<(globset::GlobMatcher, i16)>, AnalysisError> {
+    overrides
+        .iter()
+        .map(|(pattern, amount)| {
+            let glob = globset::Glob::new(pattern)
+                .map_err(|e| AnalysisError::InvalidPattern(e.to_string()))?;
+            Ok((glob.compile_matcher(), *amount))
+        })
+        .collect()
+}
+
+fn build_keep_patterns(patterns: &[String]) -> Result<regex::RegexSet, AnalysisError> {
+    regex::RegexSet::new(patterns).map_err(|e| AnalysisError::InvalidPattern(e.to_string()))
+}
+
+/// `.code-viz/cache` of the analyzed directory, or of the first root when the
+/// analysis is limited to roots, so their common ancestor is left alone
+#[cfg(not(target_arch = "wasm32"))]
+fn default_cache_dir(root_path: &Path, config: &AnalysisConfig) -> PathBuf {
+    let dir = match config.roots.first().map(|root| root_path.join(root)) {
+        Some(root) if root.is_file() => root.parent().unwrap_or(root_path).to_path_buf(),
+        Some(root) => root,
+        None => root_path.to_path_buf(),
+    };
+    dir.join(".code-viz").join("cache")
+}
+
+/// Load graph from the in-memory or on-disk cache, or build it from files
+///
+/// Returns the graph and whether it came from a cache. In
+/// `CacheMode::ReadOnly` neither cache is written or invalidated.
+#[cfg(not(target_arch = "wasm32"))]
+#[tracing::instrument(skip(files, config, cancel, memory))]
+fn load_or_build_graph(
+    files: &[PathBuf],
+    config: &AnalysisConfig,
+    root_path: &Path,
+    cancel: Option<&AtomicBool>,
+    memory: &cache::MemoryGraphCache,
+) -> Result<(symbol_graph::SymbolGraph, bool), AnalysisError> {
+    let cache_dir = config
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| default_cache_dir(root_path, config));
+    let key = cache::GraphCacheKey {
+        root: root_path.to_path_buf(),
+        config_hash: graph_config_hash(config, &cache_dir),
+    };
+
+    if let Some(graph) = memory.get(&key, files, config.memory_cache_ttl) {
+        tracing::info!("Loaded symbol graph from memory");
+        return Ok((graph, true));
+    }
+
+    if config.cache_mode == CacheMode::ReadOnly {
+        let cached = match cache::SymbolGraphCache::open_read_only(&cache_dir) {
+            Ok(cache) => cache.and_then(|cache| cache.peek(files)),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to open symbol graph cache");
+                None
+            }
+        };
+        return match cached {
+            Some(graph) => {
+                tracing::info!("Loaded symbol graph from cache (read-only)");
+                Ok((graph, true))
+            }
+            None => {
+                tracing::info!("No current cached symbol graph (read-only), building");
+                Ok((build_graph_from_files(files, config, cancel)?, false))
+            }
+        };
+    }
+
+    // The on-disk cache only saves time, so a cache directory that cannot be
+    // created or written (e.g. on a read-only file system) is skipped
+    let cache = match cache::SymbolGraphCache::new(&cache_dir) {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            tracing::warn!(
+                cache_dir = %cache_dir.display(),
+                error = %e,
+                "Symbol graph cache unavailable, analyzing without it"
+            );
+            None
+        }
+    };
+
+    if let Some(cache) = &cache {
+        // Check if cache is stale
+        match cache.invalidate_if_stale(files) {
+            Ok(true) => memory.remove(&key),
+            Ok(false) => match cache.load() {
+                Ok(Some(graph)) => {
+                    tracing::info!("Loaded symbol graph from cache");
+                    memory.insert(key, graph.clone(), files, config.memory_cache_capacity);
+                    return Ok((graph, true));
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(error = %e, "Failed to load cached symbol graph"),
+            },
+            Err(e) => tracing::warn!(error = %e, "Failed to check symbol graph cache"),
+        }
+    }
+
+    tracing::info!("Building fresh symbol graph");
+    let graph = build_graph_from_files(files, config, cancel)?;
+
+    if let Some(cache) = &cache {
+        match cache.save(&graph, files) {
+            Ok(()) => tracing::info!("Saved symbol graph to cache"),
+            Err(e) => tracing::warn!(error = %e, "Failed to save symbol graph to cache"),
+        }
+    }
+    memory.insert(key, graph.clone(), files, config.memory_cache_capacity);
+
+    Ok((graph, false))
+}
+
+/// Hash of the settings that decide which files end up in the symbol graph
+fn graph_config_hash(config: &AnalysisConfig, cache_dir: &Path) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    cache_dir.hash(&mut hasher);
+    config.exclude_patterns.hash(&mut hasher);
+    config.include_patterns.hash(&mut hasher);
+    config.roots.hash(&mut hasher);
+    config.max_depth.hash(&mut hasher);
+    config.languages.hash(&mut hasher);
+    let mut overrides: Vec<_> = config.extension_overrides.iter().collect();
+    overrides.sort();
+    overrides.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Read `files` for source-level passes, skipping unreadable ones
+pub(crate) fn read_sources(files: &[PathBuf]) -> Vec<(PathBuf, String)> {
+    files
+        .iter()
+        .filter_map(|path| match read_source(path) {
+            Ok(source) => Some((path.clone(), source)),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to read file");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Read a source file for parsing
+///
+/// Invalid UTF-8 is decoded lossily rather than failing the file; the scanner
+/// already skips binary files.
+pub(crate) fn read_source(path: &Path) -> std::io::Result<String> {
+    Ok(decode_source(path, std::fs::read(path)?))
+}
+
+/// Decode the contents of the source file at `path` for parsing
+pub(crate) fn decode_source(path: &Path, bytes: Vec<u8>) -> String {
+    let source = match String::from_utf8(bytes) {
+        Ok(source) => source,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), "File is not valid UTF-8, decoding lossily");
+            String::from_utf8_lossy(e.as_bytes()).into_owned()
+        }
+    };
+    component_script(path, normalize_source(source))
+}
+
+/// Blank out a UTF-8 BOM and a `#!` shebang line with spaces
+///
+/// Both keep their length and the shebang's newline is kept, so reported
+/// line numbers and byte offsets stay those of the file on disk.
+pub(crate) fn normalize_source(source: String) -> String {
+    let source = match source.strip_prefix('\u{feff}') {
+        Some(stripped) => format!("{:width$}{}", "", stripped, width = '\u{feff}'.len_utf8()),
+        None => source,
+    };
+    let shebang_start = source.len() - source.trim_start_matches(' ').len();
+    if !source[shebang_start..].starts_with("#!") {
+        return source;
+    }
+    let shebang_end = source.find('\n').unwrap_or(source.len());
+    format!(
+        "{:width$}{}",
+        "",
+        &source[shebang_end..],
+        width = shebang_end
+    )
+}
+
+/// Only the scripts of a Vue or Svelte component, at their original lines
+pub(crate) fn component_script(path: &Path, source: String) -> String {
+    if sfc::is_component(path) {
+        sfc::script_source(&source)
+    } else {
+        source
+    }
+}
+
+/// Build symbol graph from files using parallel processing
+#[cfg(not(target_arch = "wasm32"))]
+#[tracing::instrument(skip(files, config, cancel))]
+fn build_graph_from_files(
+    files: &[PathBuf],
+    config: &AnalysisConfig,
+    cancel: Option<&AtomicBool>,
+) -> Result<symbol_graph::SymbolGraph, AnalysisError> {
+    build_graph_from_sources(read_all_sources(files, cancel)?, config, cancel)
+}
+
+/// Build symbol graph from already read sources
+#[cfg(not(target_arch = "wasm32"))]
+fn build_graph_from_sources(
+    sources: Vec<(PathBuf, String)>,
+    config: &AnalysisConfig,
+    cancel: Option<&AtomicBool>,
+) -> Result<symbol_graph::SymbolGraph, AnalysisError> {
+    symbol_graph::SymbolGraphBuilder::new()
+        .with_extension_overrides(config.extension_overrides.clone())
+        .build_graph_with_cancel(sources, cancel)
+        .map_err(AnalysisError::from_graph)
+}
+
+/// Build symbol graph from files, timing each file
+///
+/// # Returns
+/// The graph and per-file parse times, slowest first
+#[cfg(not(target_arch = "wasm32"))]
+fn profile_graph_build(
+    files: &[PathBuf],
+    config: &AnalysisConfig,
+    cancel: Option<&AtomicBool>,
+) -> Result<(symbol_graph::SymbolGraph, Vec<models::FileTiming>), AnalysisError> {
+    profile_graph_build_from_sources(read_all_sources(files, cancel)?, config, cancel)
+}
+
+/// Build symbol graph from already read sources, timing each file
+#[cfg(not(target_arch = "wasm32"))]
+fn profile_graph_build_from_sources(
+    sources: Vec<(PathBuf, String)>,
+    config: &AnalysisConfig,
+    cancel: Option<&AtomicBool>,
+) -> Result<(symbol_graph::SymbolGraph, Vec<models::FileTiming>), AnalysisError> {
+    let mut builder = symbol_graph::SymbolGraphBuilder::new()
+        .with_profiling()
+        .with_extension_overrides(config.extension_overrides.clone());
+    let graph = builder
+        .build_graph_with_cancel(sources, cancel)
+        .map_err(AnalysisError::from_graph)?;
+    Ok((graph, builder.take_profile().unwrap_or_default()))
+}
+
+/// Read every file in parallel, failing on the first unreadable one
+#[cfg(not(target_arch = "wasm32"))]
+fn read_all_sources(
+    files: &[PathBuf],
+    cancel: Option<&AtomicBool>,
+) -> Result<Vec<(PathBuf, String)>, AnalysisError> {
+    use rayon::prelude::*;
+
+    // Read all files in parallel
+    tracing::info!(file_count = files.len(), "Reading source files");
+
+    let file_contents: Result<Vec<_>, _> = files
+        .par_iter()
+        .map(|path| {
+            read_source(path)
+                .map(|content| (path.clone(), content))
+                .map_err(|e| {
+                    tracing::error!(path = %path.display(), error = %e, "Failed to read file");
+                    e
+                })
+        })
+        .collect();
+
+    let file_contents = file_contents?;
+    check_cancelled(cancel)?;
+    Ok(file_contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_analyze_dead_code_integration() {
+        // Create temporary directory with test files
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+
+        // Create main.ts (entry point)
+        fs::write(
+            src_dir.join("main.ts"),
+            r#"
+import { usedFunction } from './used';
+
+function main() {
+    usedFunction();
+}
+
+main();
+"#,
+        )
+        .unwrap();
+
+        // Create used.ts (partially used)
+        fs::write(
+            src_dir.join("used.ts"),
+            r#"
+export function usedFunction() {
+    console.log('I am used');
+}
+
+export function deadFunction() {
+    console.log('I am never called');
+}
+"#,
+        )
+        .unwrap();
+
+        // Create dead.ts (completely unused)
+        fs::write(
+            src_dir.join("dead.ts"),
+            r#"
+export function completelyUnused() {
+    console.log('Nobody uses me');
+}
+
+export class UnusedClass {
+    method() {
+        console.log('Never called');
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        // Run analysis
+        let result = analyze_dead_code(&src_dir, None).unwrap();
+
+        // Verify results
+        assert!(result.summary.total_files > 0, "Should analyze files");
+        assert!(
+            result.summary.files_with_dead_code > 0,
+            "Should find dead code"
+        );
+        assert!(
+            result.summary.dead_functions >= 2,
+            "Should find at least 2 dead functions"
+        );
+
+        // Print results for debugging
+        eprintln!("Dead functions: {}", result.summary.dead_functions);
+        eprintln!("Dead classes: {}", result.summary.dead_classes);
+        eprintln!(
+            "Files with dead code: {}",
+            result.summary.files_with_dead_code
+        );
+    }
+
+    #[test]
+    fn test_analyze_dead_code_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("main.ts"),
+            "import { helper } from './helper';\n\nfunction main() {\n    helper();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("helper.ts"),
+            "export function helper() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+
+        let cache_dir = temp_dir.path().join("cache");
+        let config = AnalysisConfig {
+            cache_dir: Some(cache_dir.clone()),
+            ..Default::default()
+        };
+
+        let cancel = AtomicBool::new(true);
+        let result = analyze_dead_code_with_cancel(temp_dir.path(), Some(config.clone()), Some(&cancel));
+        assert!(matches!(result, Err(AnalysisError::Cancelled)));
+
+        // A cancelled run must not leave a graph behind in the cache
+        let cache = cache::SymbolGraphCache::new(&cache_dir).unwrap();
+        assert!(cache.load().unwrap().is_none());
+
+        // The same analysis completes once the flag is cleared
+        cancel.store(false, Ordering::Relaxed);
+        assert!(analyze_dead_code_with_cancel(temp_dir.path(), Some(config), Some(&cancel)).is_ok());
+    }
+
+    #[test]
+    fn test_analyze_sources_without_filesystem() {
+        // Nothing exists under this root; every source is in memory
+        let root = Path::new("/nonexistent/code-viz-demo");
+        let sources = vec![
+            (
+                root.join("main.ts"),
+                "import { used } from './util';\n\nfunction main() {\n    return used();\n}\n\nmain();\n"
+                    .to_string(),
+            ),
+            (
+                root.join("util.ts"),
+                "export function used() {\n    return 1;\n}\n\nfunction unused() {\n    return 2;\n}\n"
+                    .to_string(),
+            ),
+        ];
+
+        let result = analyze_sources(root, sources, None).unwrap();
+
+        assert_eq!(result.summary.dead_functions, 1);
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, root.join("util.ts"));
+        assert_eq!(result.files[0].dead_code[0].symbol, "unused");
+        // Not on disk, so no recent-change penalty applies
+        assert_eq!(result.files[0].dead_code[0].confidence, 100);
+    }
+
+    #[test]
+    fn test_analyze_symbol_graph_from_json() {
+        let root = Path::new("/nonexistent/code-viz-demo");
+        let sources = vec![
+            (
+                root.join("main.ts"),
+                "import { used } from './util';\n\nfunction main() {\n    return used();\n}\n\nmain();\n"
+                    .to_string(),
+            ),
+            (
+                root.join("util.ts"),
+                "export function used() {\n    return 1;\n}\n\nfunction unused() {\n    return 2;\n}\n"
+                    .to_string(),
+            ),
+        ];
+        let config = AnalysisConfig::default();
+        let mut graph = build_graph_from_sources(sources, &config, None).unwrap();
+        graph.strip_root(root);
+
+        // Built here, analyzed from its JSON as a browser build would
+        let json = serde_json::to_string(&graph).unwrap();
+        let graph: SymbolGraph = serde_json::from_str(&json).unwrap();
+        let result = analyze_symbol_graph(&graph, Path::new(""), None).unwrap();
+
+        assert_eq!(result.summary.dead_functions, 1);
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, PathBuf::from("util.ts"));
+        assert_eq!(result.files[0].dead_code[0].symbol, "unused");
+        assert_eq!(result.summary.total_files, 2);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_max_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("main.ts"),
+            "function main() {}\n\nmain();\n",
+        )
+        .unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(
+                temp_dir.path().join(format!("{}.ts", name)),
+                "export function f() {}\n",
+            )
+            .unwrap();
+        }
+
+        let analyze = |max_files| {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                max_files,
+                ..Default::default()
+            };
+            analyze_dead_code(temp_dir.path(), Some(config))
+        };
+
+        let err = analyze(Some(2)).unwrap_err();
+        assert!(matches!(
+            err,
+            AnalysisError::TooManyFiles { found: 4, limit: 2 }
+        ));
+        assert!(err.to_string().contains("4 source files"));
+        assert!(analyze(Some(4)).is_ok());
+    }
+
+    #[test]
+    fn test_load_or_build_graph_reuses_memory_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let helper = temp_dir.path().join("helper.ts");
+        fs::write(
+            temp_dir.path().join("main.ts"),
+            "import { helper } from './helper';\n\nfunction main() {\n    helper();\n}\n",
+        )
+        .unwrap();
+        fs::write(&helper, "export function helper() {\n    return 1;\n}\n").unwrap();
+
+        let cache_dir = temp_dir.path().join("cache");
+        let config = AnalysisConfig {
+            cache_dir: Some(cache_dir.clone()),
+            ..Default::default()
+        };
+        let memory = cache::MemoryGraphCache::new();
+        let files = scan_files(temp_dir.path(), &config).unwrap().files;
+
+        let (first, hit) =
+            load_or_build_graph(&files, &config, temp_dir.path(), None, &memory).unwrap();
+        assert!(!hit);
+        assert_eq!(memory.hits(), 0);
+        assert_eq!(memory.len(), 1);
+
+        // The second load is answered from memory without opening the sled cache
+        fs::remove_dir_all(&cache_dir).unwrap();
+        let (second, hit) =
+            load_or_build_graph(&files, &config, temp_dir.path(), None, &memory).unwrap();
+        assert!(hit);
+        assert_eq!(memory.hits(), 1);
+        assert!(!cache_dir.exists());
+        assert_eq!(first.symbols.len(), second.symbols.len());
+
+        // Changing a file drops the in-memory graph
+        fs::write(
+            &helper,
+            "export function helper() {\n    return 1;\n}\n\nfunction extra() {}\n",
+        )
+        .unwrap();
+        let (third, _) =
+            load_or_build_graph(&files, &config, temp_dir.path(), None, &memory).unwrap();
+        assert_eq!(memory.hits(), 1);
+        assert_eq!(third.symbols.len(), first.symbols.len() + 1);
+    }
+
+    #[test]
+    fn test_read_only_cache_reports_hits_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let helper = src_dir.join("helper.ts");
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { helper } from './helper';\n\nfunction main() {\n    helper();\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(&helper, "export function helper() {\n    return 1;\n}\n").unwrap();
+
+        let cache_dir = temp_dir.path().join("cache");
+        let analyze = |cache_mode| {
+            let config = AnalysisConfig {
+                cache_mode,
+                cache_dir: Some(cache_dir.clone()),
+                ..Default::default()
+            };
+            // A fresh memory cache per run, so only the database can hit
+            let memory = cache::MemoryGraphCache::new();
+            analyze_dead_code_with_graph_cache(&src_dir, Some(config), &memory, None, &|_, _| {})
+                .unwrap()
+        };
+        let db_mtimes = || -> Vec<(PathBuf, std::time::SystemTime)> {
+            let mut mtimes: Vec<_> = fs::read_dir(cache_dir.join("symbols.db"))
+                .unwrap()
+                .map(|entry| {
+                    let entry = entry.unwrap();
+                    let modified = entry.metadata().unwrap().modified().unwrap();
+                    (entry.path(), modified)
+                })
+                .collect();
+            mtimes.sort();
+            mtimes
+        };
+
+        // Nothing cached yet, and a read-only run does not create the database
+        assert_eq!(analyze(CacheMode::ReadOnly).cache_hit, Some(false));
+        assert!(!cache_dir.exists());
+
+        // Hits are only reported in read-only mode
+        assert_eq!(analyze(CacheMode::ReadWrite).cache_hit, None);
+        let saved = db_mtimes();
+        assert_eq!(analyze(CacheMode::ReadOnly).cache_hit, Some(true));
+        assert_eq!(db_mtimes(), saved);
+
+        // A stale graph is neither invalidated nor replaced
+        let later = std::time::SystemTime::now() + Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&helper)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(analyze(CacheMode::ReadOnly).cache_hit, Some(false));
+        assert_eq!(analyze(CacheMode::ReadOnly).cache_hit, Some(false));
+        assert_eq!(db_mtimes(), saved);
+    }
+
+    #[test]
+    fn test_serialized_result_and_graph_are_byte_identical() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { used, type Shape } from './shapes';\n\nexport function main(s: Shape) {\n    used(s);\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("shapes.ts"),
+            "export type Shape = { w: number };\nexport function used(s: Shape) {\n    return s.w;\n}\nexport function area() {}\nexport function perimeter() {}\nexport class Circle {}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let serialize = || {
+            let result = analyze_dead_code_with_graph_cache(
+                &src_dir,
+                Some(config.clone()),
+                &cache::MemoryGraphCache::new(),
+                None,
+                &|_, _| {},
+            )
+            .unwrap();
+            serde_json::to_string(&result).unwrap()
+        };
+        let first = serialize();
+        assert_eq!(first, serialize());
+        let version = code_viz_core::report::current_schema_version();
+        assert!(
+            first.starts_with(&format!("{{\"schemaVersion\":{}", version)),
+            "{}",
+            first
+        );
+
+        let graph = || {
+            serde_json::to_string(&export_symbol_graph(&src_dir, Some(config.clone())).unwrap())
+                .unwrap()
+        };
+        assert_eq!(graph(), graph());
+    }
+
+    #[test]
+    fn test_analyze_dead_code_with_unwritable_cache_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(
+            src_dir.join("index.ts"),
+            "export function main() {}\n\nfunction unused() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+
+        // A directory below a regular file cannot be created, even as root
+        let blocker = temp_dir.path().join("blocker");
+        fs::write(&blocker, "").unwrap();
+        let config = AnalysisConfig {
+            cache_dir: Some(blocker.join("cache")),
+            ..Default::default()
+        };
+        assert!(cache::SymbolGraphCache::new(&blocker.join("cache")).is_err());
+
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].dead_code[0].symbol, "unused");
+    }
+
+    #[test]
+    fn test_partition_by_roots_per_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str, source: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        };
+        write(
+            "packages/a/main.ts",
+            "function main() {}\n\nmain();\n\nfunction unusedA() {\n    return 1;\n}\n",
+        );
+        write("packages/b/main.ts", "function main() {}\n\nmain();\n");
+        write(
+            "packages/b/lib/util.ts",
+            "function unusedB() {\n    return 1;\n}\n",
+        );
+        write("tools/orphan.ts", "function orphan() {}\n");
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let mut result = analyze_dead_code(root, Some(config)).unwrap();
+        result.strip_root(root);
+
+        let reports =
+            result.partition_by_roots(&[PathBuf::from("packages/a"), PathBuf::from("packages/b")]);
+
+        let summary = |report: &RootReport| -> (Option<PathBuf>, Vec<(PathBuf, String)>) {
+            let symbols = report
+                .result
+                .files
+                .iter()
+                .flat_map(|f| {
+                    f.dead_code
+                        .iter()
+                        .map(|d| (f.path.clone(), d.symbol.clone()))
+                })
+                .collect();
+            (report.root.clone(), symbols)
+        };
+        assert_eq!(
+            reports.iter().map(summary).collect::<Vec<_>>(),
+            vec![
+                (
+                    Some(PathBuf::from("packages/a")),
+                    vec![(PathBuf::from("main.ts"), "unusedA".to_string())]
+                ),
+                (
+                    Some(PathBuf::from("packages/b")),
+                    vec![(PathBuf::from("lib/util.ts"), "unusedB".to_string())]
+                ),
+                (
+                    None,
+                    vec![(PathBuf::from("tools/orphan.ts"), "orphan".to_string())]
+                ),
+            ]
+        );
+        let b = &reports[1].result.summary;
+        assert_eq!(b.dead_functions, 1);
+        assert_eq!(reports[1].result.by_directory[0].dir, PathBuf::from("lib"));
+        assert_eq!(reports[1].result.by_directory[0].dead_ratio, 1.0);
+        // util.ts (3 lines) is all dead code, main.ts (1 line) is live
+        assert_eq!(b.total_files, 2);
+        assert_eq!(b.files_with_dead_code, 1);
+        assert_eq!(b.total_loc, 4);
+        assert_eq!(b.dead_code_ratio, 0.75);
+        assert!(reports[0].result.summary.dead_code_ratio < 1.0);
+    }
+
+    #[test]
+    fn test_filter_by_confidence() {
+        let result = DeadCodeResult {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: DeadCodeSummary {
+                total_files: 2,
+                files_with_dead_code: 2,
+                dead_functions: 3,
+                dead_classes: 0,
+                dead_other: 0,
+                total_dead_loc: 30,
+                total_loc: 60,
+                total_complexity: 0,
+                dead_code_ratio: 0.5,
+                dead_complexity_ratio: 0.5,
+                kept_by_pattern: 0,
+                filtered_small: 0,
+                skipped_generated: 0,
+                skipped_files: 0,
+            },
+            files: vec![FileDeadCode {
+                path: PathBuf::from("test.ts"),
+                dead_code: vec![
+                    DeadSymbol {
+                        symbol: "highConfidence".to_string(),
+                        kind: models::SymbolKind::Function,
+                        line_start: 1,
+                        line_end: 10,
+                        byte_start: 0,
+                        byte_end: 0,
+                        loc: 10,
+                        complexity: 0,
+                        confidence: 95,
+                        reason: "Test".to_string(),
+                        last_modified: None,
+                        age_days: None,
+                        confidence_penalties: None,
+                        fingerprint: String::new(),
+                    },
+                    DeadSymbol {
+                        symbol: "lowConfidence".to_string(),
+                        kind: models::SymbolKind::Function,
+                        line_start: 11,
+                        line_end: 20,
+                        byte_start: 0,
+                        byte_end: 0,
+                        loc: 10,
+                        complexity: 0,
+                        confidence: 50,
+                        reason: "Test".to_string(),
+                        last_modified: None,
+                        age_days: None,
+                        confidence_penalties: None,
+                        fingerprint: String::new(),
+                    },
+                ],
+            }],
+            by_directory: vec![],
+            file_totals: vec![],
+            dead_clusters: vec![],
+            warnings: vec![],
+            profile: None,
+            cache_hit: None,
+        };
+
+        let filtered = result.filter_by_confidence(80);
+
+        assert_eq!(filtered.summary.dead_functions, 1);
+        assert_eq!(filtered.summary.total_dead_loc, 10);
+        assert_eq!(filtered.files.len(), 1);
+        assert_eq!(filtered.files[0].dead_code.len(), 1);
+        assert_eq!(filtered.files[0].dead_code[0].symbol, "highConfidence");
+
+        let low = result.filter_by_confidence_range(0..=59);
+        assert_eq!(low.summary.dead_functions, 1);
+        assert_eq!(low.files[0].dead_code[0].symbol, "lowConfidence");
+        assert!(result.filter_by_confidence_range(60..=79).files.is_empty());
+    }
+
+    #[test]
+    fn test_dead_kind_counts_reconcile() {
+        let kinds = [
+            models::SymbolKind::Function,
+            models::SymbolKind::Method,
+            models::SymbolKind::Class,
+            models::SymbolKind::Variable,
+            models::SymbolKind::Member,
+        ];
+        let result = DeadCodeResult {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: DeadCodeSummary {
+                total_files: 1,
+                files_with_dead_code: 1,
+                dead_functions: 0,
+                dead_classes: 0,
+                dead_other: 0,
+                total_dead_loc: 10,
+                total_loc: 20,
+                total_complexity: 0,
+                dead_code_ratio: 0.5,
+                dead_complexity_ratio: 0.0,
+                kept_by_pattern: 0,
+                filtered_small: 0,
+                skipped_generated: 0,
+                skipped_files: 0,
+            },
+            files: vec![FileDeadCode {
+                path: PathBuf::from("test.ts"),
+                dead_code: kinds
+                    .iter()
+                    .map(|&kind| DeadSymbol {
+                        kind,
+                        ..dead_symbol(&format!("{:?}", kind), 2, 90)
+                    })
+                    .collect(),
+            }],
+            by_directory: vec![],
+            file_totals: vec![],
+            dead_clusters: vec![],
+            warnings: vec![],
+            profile: None,
+            cache_hit: None,
+        };
+
+        // The summary is recomputed from the listed symbols
+        let summary = result.filter_by_confidence(0).summary;
+
+        assert_eq!(summary.dead_functions, 2);
+        assert_eq!(summary.dead_classes, 1);
+        // The dead variable and the member fall outside functions and classes
+        assert_eq!(summary.dead_other, 2);
+        assert_eq!(
+            summary.dead_functions + summary.dead_classes + summary.dead_other,
+            kinds.len()
+        );
+    }
+
+    fn dead_symbol(name: &str, loc: usize, confidence: u8) -> DeadSymbol {
+        DeadSymbol {
+            symbol: name.to_string(),
+            kind: models::SymbolKind::Function,
+            line_start: 1,
+            line_end: loc,
+            byte_start: 0,
+            byte_end: 0,
+            loc,
+            complexity: 0,
+            confidence,
+            reason: "Test".to_string(),
+            last_modified: None,
+            age_days: None,
+            confidence_penalties: None,
+            fingerprint: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_rollup_by_directory() {
+        let files = vec![
+            FileDeadCode {
+                path: PathBuf::from("src/a/one.ts"),
+                dead_code: vec![
+                    dead_symbol("a1", 5, 90),
+                    // Below a1, so their lines do not overlap
+                    DeadSymbol {
+                        line_start: 6,
+                        line_end: 8,
+                        ..dead_symbol("a2", 3, 50)
+                    },
+                ],
+            },
+            FileDeadCode {
+                path: PathBuf::from("src/a/two.ts"),
+                dead_code: vec![dead_symbol("a3", 2, 90)],
+            },
+            FileDeadCode {
+                path: PathBuf::from("src/b/three.ts"),
+                dead_code: vec![dead_symbol("b1", 20, 90)],
+            },
+        ];
+
+        let dirs = DirectoryDeadCode::rollup(&files, |_, dead_loc| dead_loc as f64 / 40.0);
+
+        assert_eq!(dirs.len(), 2);
+        // Sorted by dead LOC, largest first
+        assert_eq!(dirs[0].dir, PathBuf::from("src/b"));
+        assert_eq!(dirs[0].dead_symbols, 1);
+        assert_eq!(dirs[0].dead_loc, 20);
+        assert_eq!(dirs[0].dead_ratio, 0.5);
+        assert_eq!(dirs[1].dir, PathBuf::from("src/a"));
+        assert_eq!(dirs[1].dead_symbols, 3);
+        assert_eq!(dirs[1].dead_loc, 10);
+        assert_eq!(dirs[1].dead_ratio, 0.25);
+
+        // Filtering keeps directory totals in sync with the remaining symbols
+        let result = DeadCodeResult {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: DeadCodeSummary {
+                total_files: 3,
+                files_with_dead_code: 3,
+                dead_functions: 4,
+                dead_classes: 0,
+                dead_other: 0,
+                total_dead_loc: 30,
+                total_loc: 40,
+                total_complexity: 0,
+                dead_code_ratio: 0.75,
+                dead_complexity_ratio: 0.75,
+                kept_by_pattern: 0,
+                filtered_small: 0,
+                skipped_generated: 0,
+                skipped_files: 0,
+            },
+            files,
+            by_directory: dirs,
+            file_totals: vec![],
+            dead_clusters: vec![],
+            warnings: vec![],
+            profile: None,
+            cache_hit: None,
+        };
+        let filtered = result.filter_by_confidence(80);
+        let a = filtered
+            .by_directory
+            .iter()
+            .find(|d| d.dir == Path::new("src/a"))
+            .unwrap();
+        assert_eq!(a.dead_symbols, 2);
+        assert_eq!(a.dead_loc, 7);
+        assert!((a.dead_ratio - 0.175).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_by_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(src_dir.join("lib")).unwrap();
+        fs::create_dir_all(src_dir.join("unused")).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { helper } from './lib/helper';\n\nfunction main() {\n    helper();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("lib/helper.ts"),
+            "export function helper() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("unused/dead.ts"),
+            "export function deadOne() {\n    return 1;\n}\n\nexport function deadTwo() {\n    return 2;\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let unused = result
+            .by_directory
+            .iter()
+            .find(|d| d.dir.ends_with("unused"))
+            .expect("unused/ should have dead code");
+        assert_eq!(unused.dead_symbols, 2);
+        assert_eq!(unused.dead_loc, 6);
+        assert_eq!(unused.dead_ratio, 1.0);
+        assert!(!result.by_directory.iter().any(|d| d.dir.ends_with("lib")));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_unused_reexport() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(src_dir.join("utils")).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { used } from './utils';\n\nfunction main() {\n    used();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("utils/index.ts"),
+            "export { used } from './used';\nexport { unused } from './unused';\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("utils/used.ts"),
+            "export function used() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("utils/unused.ts"),
+            "export function unused() {\n    return 2;\n}\n",
+        )
+        .unwrap();
+        // Keeps the origin of the unused re-export alive through another path
+        fs::write(
+            src_dir.join("unused.test.ts"),
+            "import { unused } from './utils/unused';\n\nfunction testUnused() {\n    unused();\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: Vec<&DeadSymbol> = result.files.iter().flat_map(|f| &f.dead_code).collect();
+        assert_eq!(dead.len(), 1, "only the unused re-export should be dead: {:?}", dead);
+        let reexport = dead[0];
+        assert_eq!(reexport.kind, models::SymbolKind::ReExport);
+        assert_eq!(reexport.symbol, "unused");
+        assert_eq!(reexport.line_start, 2);
+        assert!(result.files[0].path.ends_with("utils/index.ts"));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_config_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(src_dir.join("routes")).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "function main() {\n    return 0;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("routes/home.ts"),
+            "function renderHome() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("legacy.js"),
+            "function oldCode() {\n    return 2;\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            languages: vec!["typescript".to_string()],
+            entry_point_patterns: vec!["routes/**".to_string()],
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        // routes/ is configured as entry points and legacy.js is not analyzed
+        assert_eq!(result.summary.dead_functions, 0);
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            entry_point_patterns: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            analyze_dead_code(&src_dir, Some(config)),
+            Err(AnalysisError::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_keep_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "function main() {\n    return 0;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("pages.ts"),
+            "function route_home() {\n    return 1;\n}\n\nfunction homePage() {\n    return 2;\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            keep_patterns: vec!["^route_".to_string(), "Command$".to_string()],
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead_names: Vec<&str> = result
+            .files
+            .iter()
+            .flat_map(|f| f.dead_code.iter().map(|s| s.symbol.as_str()))
+            .collect();
+        assert_eq!(dead_names, vec!["homePage"]);
+        assert_eq!(result.summary.kept_by_pattern, 1);
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            keep_patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            analyze_dead_code(&src_dir, Some(config)),
+            Err(AnalysisError::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_confidence_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(src_dir.join("api")).unwrap();
+        fs::create_dir_all(src_dir.join("utils")).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "function main() {\n    return 0;\n}\n",
+        )
+        .unwrap();
+        let unused = "function formatDate() {\n    return 1;\n}\n";
+        fs::write(src_dir.join("api").join("dates.ts"), unused).unwrap();
+        fs::write(src_dir.join("utils").join("dates.ts"), unused).unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            confidence_overrides: vec![("src/api/**".to_string(), 30), ("src/**".to_string(), 10)],
+            ..Default::default()
+        };
+        let result = analyze_dead_code(temp_dir.path(), Some(config)).unwrap();
+
+        let symbol_in = |dir: &str| {
+            result
+                .files
+                .iter()
+                .find(|f| f.path.parent().unwrap().ends_with(dir))
+                .map(|f| f.dead_code[0].clone())
+                .unwrap()
+        };
+        let api = symbol_in("api");
+        let utils = symbol_in("utils");
+
+        // First match wins: the api file only gets the api penalty
+        assert_eq!(api.confidence, utils.confidence - 20);
+        let names: Vec<&str> = api
+            .confidence_penalties
+            .iter()
+            .flatten()
+            .map(|p| p.name.as_str())
+            .filter(|name| name.starts_with("path "))
+            .collect();
+        assert_eq!(names, vec!["path src/api/**"]);
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            confidence_overrides: vec![("[".to_string(), 10)],
+            ..Default::default()
+        };
+        assert!(matches!(
+            analyze_dead_code(temp_dir.path(), Some(config)),
+            Err(AnalysisError::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_package_entry_points() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let src_dir = root.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "tool", "main": "./src/lib.ts", "bin": { "tool": "./src/cli.ts" } }"#,
+        )
+        .unwrap();
+        fs::write(src_dir.join("index.ts"), "export function main() {}\n").unwrap();
+        // A barrel main keeps the code it forwards alive
+        fs::write(src_dir.join("lib.ts"), "export { format } from './format';\n").unwrap();
+        fs::write(
+            src_dir.join("format.ts"),
+            "export function format() {\n    return '';\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("cli.ts"),
+            "import { parseArgs } from './args';\n\nexport function runCli() {\n    return parseArgs();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("args.ts"),
+            "export function parseArgs() {\n    return [];\n}\n",
+        )
+        .unwrap();
+
+        let dead_names = |entry_point_patterns: &[&str]| {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                entry_point_patterns: entry_point_patterns.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            };
+            let result = analyze_dead_code(root, Some(config)).unwrap();
+            let mut names: Vec<String> = result
+                .files
+                .iter()
+                .flat_map(|f| &f.dead_code)
+                .map(|s| s.symbol.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            dead_names(&[]),
+            vec!["format", "format", "parseArgs", "runCli"]
+        );
+        assert!(dead_names(&["package.json"]).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_dead_code_dead_branches() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { legacyInit } from './legacy';\n\nfunction main() {\n    if (false) {\n        legacyInit();\n    }\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("legacy.ts"),
+            "export function legacyInit() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+
+        let dead_in_legacy = |detect_dead_branches| {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                detect_dead_branches,
+                ..Default::default()
+            };
+            let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+            result
+                .files
+                .into_iter()
+                .find(|f| f.path.ends_with("legacy.ts"))
+                .map(|f| f.dead_code)
+                .unwrap_or_default()
+        };
+
+        // Import edges keep it alive by default
+        assert!(dead_in_legacy(false).is_empty());
+
+        let dead = dead_in_legacy(true);
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].symbol, "legacyInit");
+        assert_eq!(dead[0].reason, "Only referenced in always-false branches");
+        assert!(dead[0].confidence < 60);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_no_supported_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = || AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+
+        // A genuinely empty directory is a clean, empty result
+        let result = analyze_dead_code(temp_dir.path(), Some(config())).unwrap();
+        assert_eq!(result.summary.total_files, 0);
+
+        fs::write(temp_dir.path().join("notes.txt"), "not code\n").unwrap();
+        fs::write(temp_dir.path().join("todo.txt"), "nor this\n").unwrap();
+        match analyze_dead_code(temp_dir.path(), Some(config())) {
+            Err(AnalysisError::NoSupportedFiles { path, unsupported }) => {
+                assert_eq!(path, temp_dir.path());
+                assert_eq!(unsupported, 2);
+            }
+            other => panic!(
+                "expected NoSupportedFiles, got {:?}",
+                other.map(|r| r.summary)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_diff_matches_moved_symbols_by_fingerprint() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let config = || AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let unused = "function unusedHelper() {\n    return 1;\n}\n\nfunction staleHelper() {\n    return 2;\n}\n";
+
+        fs::write(src_dir.join("index.ts"), "export function main() {}\n").unwrap();
+        fs::write(src_dir.join("utils.ts"), unused).unwrap();
+        let old = analyze_dead_code(&src_dir, Some(config())).unwrap();
+
+        // Both helpers shift down, and one of them also changes its body
+        fs::write(
+            src_dir.join("utils.ts"),
+            format!("// Helpers\n\n{}", unused.replace("return 2;", "return 3;")),
+        )
+        .unwrap();
+        let new = analyze_dead_code(&src_dir, Some(config())).unwrap();
+
+        let diff = DeadCodeResult::diff(&old, &new);
+        assert_eq!(diff.unchanged, 1);
+        let names = |files: &[FileDeadCode]| -> Vec<String> {
+            files
+                .iter()
+                .flat_map(|f| f.dead_code.iter().map(|s| s.symbol.clone()))
+                .collect()
+        };
+        assert_eq!(names(&diff.added), vec!["staleHelper"]);
+        assert_eq!(names(&diff.removed), vec!["staleHelper"]);
+        assert_eq!(diff.added[0].dead_code[0].line_start, 7);
+
+        // Comparing a result with itself matches everything in place
+        let diff = DeadCodeResult::diff(&new, &new);
+        assert_eq!(diff.unchanged, 2);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_dead_code_tar_archive() {
+        let files: [(&str, &str); 2] = [
+            (
+                "src/index.ts",
+                "import { helper } from './utils';\nexport function main() {\n    return helper();\n}\n",
+            ),
+            (
+                "src/utils.ts",
+                "export function helper() {\n    return 1;\n}\nfunction unusedHelper() {\n    return 2;\n}\n",
+            ),
+        ];
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, source) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(source.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, source.as_bytes())
+                .unwrap();
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("app.tar");
+        fs::write(&archive, builder.into_inner().unwrap()).unwrap();
+
+        let mut result = analyze_dead_code(&archive, None).unwrap();
+        result.strip_root(&archive);
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, Path::new("src/utils.ts"));
+        let dead: Vec<&str> = result.files[0]
+            .dead_code
+            .iter()
+            .map(|s| s.symbol.as_str())
+            .collect();
+        assert_eq!(dead, vec!["unusedHelper"]);
+
+        // Nothing is cached next to the archive
+        assert!(!temp_dir.path().join(".code-viz").exists());
+    }
+
+    #[test]
+    fn test_analyze_dead_code_extension_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(src_dir.join("index.ts"), "export function main() {}\n").unwrap();
+        fs::write(
+            src_dir.join("legacy.es"),
+            "function unusedLegacy() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(temp_dir.path(), Some(config.clone())).unwrap();
+        assert!(result.files.is_empty());
+
+        let config = AnalysisConfig {
+            extension_overrides: std::collections::HashMap::from([(
+                "es".to_string(),
+                "javascript".to_string(),
+            )]),
+            ..config
+        };
+        let mut result = analyze_dead_code(temp_dir.path(), Some(config)).unwrap();
+        result.strip_root(temp_dir.path());
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, Path::new("src/legacy.es"));
+        assert_eq!(result.files[0].dead_code[0].symbol, "unusedLegacy");
+    }
+
+    #[test]
+    fn test_analyze_dead_code_anonymous_default_exports() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("index.ts"),
+            "import render from './used';\nexport function main() {\n    return render();\n}\n",
+        )
+        .unwrap();
+        fs::write(src_dir.join("used.ts"), "export default () => 1;\n").unwrap();
+        fs::write(
+            src_dir.join("unused.ts"),
+            "export default function () {\n    return 2;\n}\n",
+        )
+        .unwrap();
+
+        let mut result = analyze_dead_code(temp_dir.path(), None).unwrap();
+        result.strip_root(temp_dir.path());
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, Path::new("src/unused.ts"));
+        let dead = &result.files[0].dead_code;
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].symbol, "default");
+        assert_eq!(dead[0].kind, models::SymbolKind::Function);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_bom_shebang_and_binary_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(src_dir.join("index.ts"), "export function main() {}\n").unwrap();
+        fs::write(
+            src_dir.join("cli.ts"),
+            "\u{feff}#!/usr/bin/env node\n\nfunction unusedCli() {\n    return 1;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("latin1.ts"),
+            b"// caf\xe9\nfunction unusedLatin1() {\n    return 2;\n}\n",
+        )
+        .unwrap();
+        fs::write(src_dir.join("compiled.js"), b"\x00asm\x01\x00\x00\x00").unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+        let dead = |file: &str| {
+            result
+                .files
+                .iter()
+                .find(|f| f.path.ends_with(file))
+                .map(|f| f.dead_code.clone())
+                .unwrap_or_default()
+        };
+
+        // Lines count from the shebang, which the BOM does not shift
+        let cli = dead("cli.ts");
+        assert_eq!(cli.len(), 1);
+        assert_eq!(cli[0].symbol, "unusedCli");
+        assert_eq!((cli[0].line_start, cli[0].line_end), (3, 5));
+        let source = fs::read_to_string(src_dir.join("cli.ts")).unwrap();
+        assert_eq!(
+            &source[cli[0].byte_start..cli[0].byte_end],
+            "function unusedCli() {\n    return 1;\n}"
+        );
+
+        // Invalid UTF-8 is decoded lossily instead of failing the run
+        let latin1 = dead("latin1.ts");
+        assert_eq!(latin1.len(), 1);
+        assert_eq!((latin1[0].line_start, latin1[0].line_end), (2, 4));
+
+        // The binary file is left out with a count
+        assert!(dead("compiled.js").is_empty());
+        assert_eq!(result.summary.skipped_files, 1);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let big: String = (0..300)
+            .map(|i| {
+                format!(
+                    "export function f{}(a: number) {{ return a + {}; }}\n",
+                    i, i
+                )
+            })
+            .collect();
+        fs::write(src_dir.join("big.ts"), big).unwrap();
+        fs::write(src_dir.join("index.ts"), "export function main() {}\n").unwrap();
+        fs::write(src_dir.join("b.ts"), "export function b() {}\n").unwrap();
+
+        let config = |profile| AnalysisConfig {
+            cache_dir: Some(temp_dir.path().join("cache")),
+            profile,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config(None))).unwrap();
+        assert!(result.profile.is_none());
+
+        // Profiling rebuilds even though the graph is now cached
+        let result = analyze_dead_code(&src_dir, Some(config(Some(2)))).unwrap();
+        let profile = result.profile.expect("profile should be populated");
+        assert_eq!(profile.len(), 2);
+        assert!(profile.windows(2).all(|pair| pair[0].ms >= pair[1].ms));
+        assert!(profile.iter().all(|timing| timing.ms >= 0.0));
+        assert_ne!(profile[0].path, profile[1].path);
+        for timing in &profile {
+            assert!(
+                ["big.ts", "index.ts", "b.ts"]
+                    .iter()
+                    .any(|name| timing.path.ends_with(name)),
+                "unexpected profiled file {}",
+                timing.path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_analyze_dead_code_unused_const_object_member() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { Colors } from './colors';\n\nfunction main() {\n    return Colors.Red;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("colors.ts"),
+            "export const Colors = {\n    Red: 'red',\n    Blue: 'blue',\n} as const;\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: Vec<&DeadSymbol> = result.files.iter().flat_map(|f| &f.dead_code).collect();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].symbol, "Colors.Blue");
+        assert_eq!(dead[0].kind, models::SymbolKind::Member);
+        assert_eq!(dead[0].line_start, 3);
+        assert_eq!(result.summary.dead_other, 1);
+        assert_eq!(result.summary.dead_functions, 0);
+        // Scored like the exported `Colors` object
+        assert!(dead[0]
+            .confidence_penalties
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|p| p.name == "exported"));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_skips_members_of_dead_container() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        // Only the dead `legacy` function accesses `Util`
+        fs::write(src_dir.join("main.ts"), "function main() {}\n").unwrap();
+        fs::write(
+            src_dir.join("legacy.ts"),
+            "import { Util } from './util';\n\nexport function legacy() {\n    return Util.used();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("util.ts"),
+            "export namespace Util {\n    export function used() {}\n    export function unused() {}\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: Vec<&DeadSymbol> = result.files.iter().flat_map(|f| &f.dead_code).collect();
+        assert!(dead.iter().any(|d| d.symbol == "Util"));
+        assert!(dead.iter().any(|d| d.symbol == "legacy"));
+        assert!(!dead.iter().any(|d| d.kind == models::SymbolKind::Member));
+    }
+
+    #[test]
+    fn test_total_loc_counts_nested_symbols_once() {
+        let source = "export class Cart {\n    add() {\n        return 1;\n    }\n\n    remove() {\n        return 2;\n    }\n}\n\nfunction unused() {\n    return 3;\n}\n";
+        let graph = symbol_graph::SymbolGraphBuilder::new()
+            .build_graph(vec![(PathBuf::from("cart.ts"), source.to_string())])
+            .unwrap();
+
+        let file_loc = symbol_lines_per_file(&graph);
+        // Class lines 1-9 and function lines 11-13, methods inside the class
+        assert_eq!(file_loc[Path::new("cart.ts")], 12);
+        assert!(file_loc[Path::new("cart.ts")] <= source.lines().count());
+        let summed: usize = graph.symbols.values().map(symbol_loc).sum();
+        assert!(summed > source.lines().count());
+    }
+
+    #[test]
+    fn test_dead_loc_counts_nested_symbols_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("main.ts"), "function main() {}\n\nmain();\n").unwrap();
+        fs::write(
+            src_dir.join("cart.ts"),
+            "class Cart {\n    add() {\n        return 1;\n    }\n\n    remove() {\n        return 2;\n    }\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        // The class and both of its methods are dead, over the class's 9 lines
+        let cart = result.files.iter().find(|f| f.path.ends_with("cart.ts")).unwrap();
+        assert_eq!(cart.dead_code.len(), 3);
+        assert_eq!(cart.dead_loc(), 9);
+        assert_eq!(result.summary.total_dead_loc, 9);
+        assert!(result.summary.dead_code_ratio <= 1.0);
+        assert!(result.by_directory.iter().all(|dir| dir.dead_ratio <= 1.0));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_unused_private_method() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { Service } from './service';\n\nfunction main() {\n    return new Service().run();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("service.ts"),
+            "export class Service {\n    run() {\n        return this.used();\n    }\n\n    private used() {\n        return 1;\n    }\n\n    private helper() {\n        return 2;\n    }\n\n    describe() {\n        return 'public, never called';\n    }\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: Vec<&DeadSymbol> = result.files.iter().flat_map(|f| &f.dead_code).collect();
+        assert_eq!(dead.len(), 1, "{:?}", dead);
+        assert_eq!(dead[0].symbol, "helper");
+        assert_eq!(dead[0].kind, models::SymbolKind::Method);
+        assert_eq!(dead[0].reason, "Private method is never used by its class");
+        // Not part of the API, even though the class is exported
+        assert!(!dead[0]
+            .confidence_penalties
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|p| p.name == "exported"));
+    }
+
+    #[test]
+    fn test_analyze_dead_code_export_assignment_and_namespace() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import Greeter = require('./greeter');\nimport { Util } from './util';\n\nfunction main() {\n    return new Greeter().greet() + Util.used();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("greeter.ts"),
+            "class Greeter {\n    greet() {\n        return 'hi';\n    }\n}\n\nexport = Greeter;\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("util.ts"),
+            "export namespace Util {\n    export function used() {\n        return 1;\n    }\n\n    export function unused() {\n        return 2;\n    }\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: Vec<(&str, models::SymbolKind)> = result
+            .files
+            .iter()
+            .flat_map(|f| &f.dead_code)
+            .map(|d| (d.symbol.as_str(), d.kind))
+            .collect();
+        assert_eq!(dead, vec![("Util.unused", models::SymbolKind::Member)]);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_commonjs_exports() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.js"),
+            "const lib = require('./lib');\nconst { format } = require('./format');\n\nfunction main() {\n    return format(lib.used());\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("lib.js"),
+            "function used() {\n    return 1;\n}\n\nfunction unused() {\n    return 2;\n}\n\nmodule.exports = { used };\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("format.js"),
+            "function formatValue(value) {\n    return String(value);\n}\n\nfunction unusedFormat() {\n    return '';\n}\n\nexports.format = formatValue;\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let mut dead: Vec<&str> = result
+            .files
+            .iter()
+            .flat_map(|f| &f.dead_code)
+            .map(|d| d.symbol.as_str())
+            .collect();
+        dead.sort();
+        assert_eq!(dead, vec!["unused", "unusedFormat"]);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_vue_component() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import App from './App.vue';\n\nfunction main() {\n    return App;\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("App.vue"),
+            "<template>\n  <button @click=\"increment\">{{ count }}</button>\n</template>\n\n\
+             <script setup lang=\"ts\">\nimport { ref } from 'vue';\n\nconst count = ref(0);\n\n\
+             function increment(): void {\n  count.value++;\n}\n\n\
+             function formatCount(): string {\n  return `${count.value}`;\n}\n</script>\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        assert_eq!(result.files.len(), 1);
+        assert!(result.files[0].path.ends_with("App.vue"));
+        let dead = &result.files[0].dead_code;
+        assert_eq!(dead.len(), 1, "{:?}", dead);
+        assert_eq!(dead[0].symbol, "formatCount");
+        // Line in the component file, not in the extracted script
+        assert_eq!(dead[0].line_start, 14);
+    }
+
+    #[test]
+    fn test_analyze_sources_reports_each_dead_wildcard_once() {
+        let source = |path: &str, text: &str| (PathBuf::from(path), text.to_string());
+        let sources = vec![
+            source(
+                "/demo/main.ts",
+                "import { used } from './lib';\n\nfunction main() {\n    used();\n}\n\nmain();\n",
+            ),
+            source(
+                "/demo/lib.ts",
+                "export * from './util';\nexport * from './legacy';\n",
+            ),
+            source(
+                "/demo/util.ts",
+                "export function used() {}\nexport function unused() {}\nexport default function () {}\n",
+            ),
+            source(
+                "/demo/legacy.ts",
+                "export function old() {}\nexport function older() {}\n",
+            ),
+        ];
+
+        let result = analyze_sources(Path::new("/demo"), sources, None).unwrap();
+        let dead = |file: &str| -> Vec<(String, usize)> {
+            result
+                .files
+                .iter()
+                .filter(|f| f.path.ends_with(file))
+                .flat_map(|f| &f.dead_code)
+                .map(|d| (d.symbol.clone(), d.line_start))
+                .collect()
+        };
+
+        // The wildcard forwarding a used name stays, the unused one is one finding
+        assert_eq!(dead("lib.ts"), vec![("*".to_string(), 2)]);
+        assert_eq!(
+            dead("util.ts"),
+            vec![("unused".to_string(), 2), ("default".to_string(), 3)]
+        );
+        assert_eq!(
+            dead("legacy.ts"),
+            vec![("old".to_string(), 1), ("older".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_analyze_sources_vue_component_template() {
+        // Nothing is on disk, so the markup must come from the given source
+        let sources = vec![
+            (
+                PathBuf::from("/virtual/src/main.ts"),
+                "import App from './App.vue';\n\nfunction main() {\n    return App;\n}\n\nmain();\n"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("/virtual/src/App.vue"),
+                "<template>\n  <button @click=\"increment\">{{ count }}</button>\n</template>\n\n\
+                 <script setup lang=\"ts\">\nimport { ref } from 'vue';\n\nconst count = ref(0);\n\n\
+                 function increment(): void {\n  count.value++;\n}\n\n\
+                 function formatCount(): string {\n  return `${count.value}`;\n}\n</script>\n"
+                    .to_string(),
+            ),
+        ];
+
+        let result = analyze_sources(Path::new("/virtual/src"), sources, None).unwrap();
+
+        assert_eq!(result.files.len(), 1);
+        let dead: Vec<&str> = result.files[0]
+            .dead_code
+            .iter()
+            .map(|d| d.symbol.as_str())
+            .collect();
+        assert_eq!(dead, vec!["formatCount"]);
+    }
+
+    #[test]
+    fn test_analyze_sources_jsx_in_ts_file() {
+        let sources = vec![
+            (
+                PathBuf::from("/virtual/src/main.ts"),
+                "import { App } from './app';\n\nfunction main() {\n    return App();\n}\n\nmain();\n"
+                    .to_string(),
+            ),
+            (
+                PathBuf::from("/virtual/src/app.ts"),
+                "export const App = () => <main><h1>Hello</h1></main>;\n\n\
+                 function unusedHelper(): void {\n  console.log('</p>');\n}\n"
+                    .to_string(),
+            ),
+        ];
+
+        let result = analyze_sources(Path::new("/virtual/src"), sources, None).unwrap();
+
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].path, PathBuf::from("/virtual/src/app.ts"));
+        let dead: Vec<&str> = result.files[0]
+            .dead_code
+            .iter()
+            .map(|d| d.symbol.as_str())
+            .collect();
+        assert_eq!(dead, vec!["unusedHelper"]);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_ignoring_type_imports() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
 
-    let cache = cache::SymbolGraphCache::new(&cache_dir)?;
+        fs::write(
+            src_dir.join("main.ts"),
+            "import type { Config } from './config';\n\nfunction main(config?: Config) {\n    return config;\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("config.ts"),
+            "export class Config {\n    debug = false;\n}\n",
+        )
+        .unwrap();
 
-    // Check if cache is stale
-    let is_stale = cache.invalidate_if_stale(files)?;
+        let dead_names = |follow_type_imports: bool| -> Vec<String> {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                follow_type_imports,
+                ..Default::default()
+            };
+            let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+            result
+                .files
+                .iter()
+                .flat_map(|f| &f.dead_code)
+                .map(|d| d.symbol.clone())
+                .collect()
+        };
 
-    if !is_stale {
-        // Try to load from cache
-        if let Some(graph) = cache.load()? {
-            tracing::info!("Loaded symbol graph from cache");
-            return Ok(graph);
-        }
+        // The class is only used as a type, so nothing keeps it alive at runtime
+        assert!(dead_names(true).is_empty());
+        assert_eq!(dead_names(false), vec!["Config"]);
     }
 
-    tracing::info!("Building fresh symbol graph");
-    let graph = build_graph_from_files(files)?;
+    #[test]
+    fn test_analyze_source_without_file_on_disk() {
+        let source = "export function used() {\n    return 1;\n}\n\nfunction unused() {}\n";
+        let result = analyze_source(Path::new("<stdin>.ts"), source, None).unwrap();
 
-    // Save to cache
-    cache.save(&graph)?;
-    tracing::info!("Saved symbol graph to cache");
+        let dead: Vec<&str> = result
+            .files
+            .iter()
+            .flat_map(|f| &f.dead_code)
+            .map(|d| d.symbol.as_str())
+            .collect();
+        assert_eq!(dead, vec!["unused"]);
+        assert_eq!(result.files[0].path, PathBuf::from("<stdin>.ts"));
+    }
 
-    Ok(graph)
-}
+    #[test]
+    fn test_export_symbol_graph_round_trips_with_relative_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("index.ts"),
+            "import { used } from './util';\n\nexport function main() {\n    return used();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("util.ts"),
+            "export function used() {\n    return 1;\n}\n",
+        )
+        .unwrap();
 
-/// Build symbol graph from files using parallel processing
-#[tracing::instrument(skip(files))]
-fn build_graph_from_files(files: &[PathBuf]) -> Result<symbol_graph::SymbolGraph, AnalysisError> {
-    use rayon::prelude::*;
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let graph = export_symbol_graph(temp_dir.path(), Some(config)).unwrap();
+        assert!(graph.symbols.contains_key("util.ts:1:used"));
+        assert!(graph.exports.contains_key(Path::new("util.ts")));
+        assert!(graph.imports["index.ts:3:main"].contains(&"util.ts:1:used".to_string()));
 
-    // Read all files in parallel
-    tracing::info!(file_count = files.len(), "Reading source files");
+        let json = serde_json::to_string(&graph).unwrap();
+        let parsed: SymbolGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.symbols.len(), graph.symbols.len());
+        assert_eq!(
+            parsed.symbols["util.ts:1:used"].path,
+            PathBuf::from("util.ts")
+        );
+    }
 
-    let file_contents: Result<Vec<_>, _> = files
-        .par_iter()
-        .map(|path| {
-            std::fs::read_to_string(path)
-                .map(|content| (path.clone(), content))
-                .map_err(|e| {
-                    tracing::error!(path = %path.display(), error = %e, "Failed to read file");
-                    e
-                })
-        })
-        .collect();
+    #[test]
+    fn test_dead_complexity_ratio_weighs_branches() {
+        let live = "export function used() {\n    return 1;\n}\n\n";
+        let flat = "function unused(x: number) {\n    console.log(x);\n    console.log(x);\n    console.log(x);\n}\n";
+        let branching = "function unused(x: number) {\n    if (x > 0) { console.log(x); }\n    if (x < 0) { console.log(x); }\n    while (x > 9) { x--; }\n}\n";
 
-    let file_contents = file_contents?;
+        let path = Path::new("app.ts");
+        let flat = analyze_source(path, &format!("{}{}", live, flat), None).unwrap();
+        let branching = analyze_source(path, &format!("{}{}", live, branching), None).unwrap();
 
-    // Build the graph
-    let mut builder = symbol_graph::SymbolGraphBuilder::new();
-    let graph = builder.build_graph(file_contents)?;
+        assert_eq!(
+            flat.summary.total_dead_loc,
+            branching.summary.total_dead_loc
+        );
+        assert_eq!(
+            flat.summary.dead_code_ratio,
+            branching.summary.dead_code_ratio
+        );
+        assert_eq!(flat.files[0].dead_code[0].complexity, 1);
+        assert_eq!(branching.files[0].dead_code[0].complexity, 4);
+        assert!((flat.summary.dead_complexity_ratio - 0.5).abs() < 1e-9);
+        assert!((branching.summary.dead_complexity_ratio - 0.8).abs() < 1e-9);
+    }
 
-    Ok(graph)
-}
+    #[test]
+    fn test_analyze_dead_code_string_reference_penalty() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use tempfile::TempDir;
+        fs::write(
+            src_dir.join("main.ts"),
+            "function main() {\n    return container.get('handleRefund');\n}\n\nfunction handleRefund() {}\n\nfunction handleCharge() {}\n\nmain();\n",
+        )
+        .unwrap();
+
+        // The files were just written, so leave out the recent-change penalty
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            confidence_weights: ConfidenceWeights {
+                recently_modified: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: HashMap<&str, &DeadSymbol> = result
+            .files
+            .iter()
+            .flat_map(|f| &f.dead_code)
+            .map(|d| (d.symbol.as_str(), d))
+            .collect();
+        assert_eq!(dead["handleCharge"].confidence, 100);
+        assert_eq!(dead["handleRefund"].confidence, 80);
+        assert!(dead["handleRefund"]
+            .confidence_penalties
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|p| p.name == "string reference"));
+    }
 
     #[test]
-    fn test_analyze_dead_code_integration() {
-        // Create temporary directory with test files
+    fn test_analyze_dead_code_exports_only() {
         let temp_dir = TempDir::new().unwrap();
         let src_dir = temp_dir.path().join("src");
-        fs::create_dir(&src_dir).unwrap();
+        fs::create_dir_all(&src_dir).unwrap();
 
-        // Create main.ts (entry point)
         fs::write(
             src_dir.join("main.ts"),
-            r#"
-import { usedFunction } from './used';
+            "import { format } from './strings';\n\nfunction main() {\n    return format('a');\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("strings.ts"),
+            "export function format(s: string) {\n    return s.trim();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("unused_api.ts"),
+            "export function capitalize(s: string) {\n    return s.toUpperCase();\n}\n\nfunction internalHelper() {\n    return 1;\n}\n",
+        )
+        .unwrap();
 
-function main() {
-    usedFunction();
-}
+        let analyze = |exports_only| {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                exports_only,
+                ..Default::default()
+            };
+            analyze_dead_code(&src_dir, Some(config)).unwrap()
+        };
+        let names = |result: &DeadCodeResult| -> Vec<String> {
+            result
+                .files
+                .iter()
+                .flat_map(|f| &f.dead_code)
+                .map(|d| d.symbol.clone())
+                .collect()
+        };
 
-main();
-"#,
+        let all = analyze(false);
+        assert_eq!(names(&all), vec!["capitalize", "internalHelper"]);
+
+        let exports = analyze(true);
+        assert_eq!(names(&exports), vec!["capitalize"]);
+        assert_eq!(exports.summary.dead_functions, 1);
+        assert_eq!(exports.summary.total_dead_loc, 3);
+        assert!(exports.summary.dead_code_ratio < all.summary.dead_code_ratio);
+    }
+
+    #[test]
+    fn test_analyze_dead_code_call_graph_optional_chaining() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { used } from './util';\n\nfunction main() {\n    return used();\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("util.ts"),
+            "export function used() {\n    return helper() + maybeFn?.();\n}\n\nfunction helper() {\n    return 1;\n}\n\nfunction maybeFn() {\n    return 2;\n}\n\nfunction nobody() {\n    return 3;\n}\n",
         )
         .unwrap();
 
-        // Create used.ts (partially used)
+        let dead_names = |call_graph| -> Vec<String> {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                call_graph,
+                ..Default::default()
+            };
+            let mut names: Vec<String> = analyze_dead_code(&src_dir, Some(config))
+                .unwrap()
+                .files
+                .iter()
+                .flat_map(|f| &f.dead_code)
+                .map(|d| d.symbol.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(dead_names(false), vec!["helper", "maybeFn", "nobody"]);
+        // Only called through `maybeFn?.()`, yet still alive in call-graph mode
+        assert_eq!(dead_names(true), vec!["nobody"]);
+    }
+
+    #[cfg(feature = "git-integration")]
+    #[test]
+    fn test_analyze_dead_code_age_days_per_symbol() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        let main_path = src_dir.join("main.ts");
+
+        let days_ago = |days: i64| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            git2::Time::new(now - days * 24 * 60 * 60, 0)
+        };
+        let commit = |content: &str, time: git2::Time| {
+            fs::write(&main_path, content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("src/main.ts")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let signature = git2::Signature::new("Dev", "dev@example.com", &time).unwrap();
+            let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "change",
+                &tree,
+                &parents,
+            )
+            .unwrap();
+        };
+
+        let stale = "function main() {}\n\nfunction stale() {\n    return 1;\n}\n\nmain();\n";
+        commit(stale, days_ago(400));
+        let fresh = format!("{}\nfunction fresh() {{\n    return 2;\n}}\n", stale);
+        commit(&fresh, days_ago(10));
+        // Not committed yet, so changed just now
         fs::write(
-            src_dir.join("used.ts"),
-            r#"
-export function usedFunction() {
-    console.log('I am used');
-}
+            &main_path,
+            format!("{}\nfunction draft() {{\n    return 3;\n}}\n", fresh),
+        )
+        .unwrap();
 
-export function deadFunction() {
-    console.log('I am never called');
-}
-"#,
+        let result = analyze_dead_code(
+            &src_dir,
+            Some(AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                ..Default::default()
+            }),
         )
         .unwrap();
+        // Only blamed when asked for
+        assert!(result.files[0].dead_code.iter().all(|d| d.age_days.is_none()));
 
-        // Create dead.ts (completely unused)
-        fs::write(
-            src_dir.join("dead.ts"),
-            r#"
-export function completelyUnused() {
-    console.log('Nobody uses me');
-}
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            symbol_ages: true,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+        let age = |name: &str| {
+            result.files[0]
+                .dead_code
+                .iter()
+                .find(|d| d.symbol == name)
+                .unwrap()
+                .age_days
+        };
 
-export class UnusedClass {
-    method() {
-        console.log('Never called');
+        assert_eq!(age("stale"), Some(400));
+        assert_eq!(age("fresh"), Some(10));
+        assert_eq!(age("draft"), Some(0));
     }
-}
-"#,
+
+    #[test]
+    fn test_analyze_dead_code_skip_generated() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "import { used } from './api';\n\nfunction main() {\n    return used();\n}\n\nfunction orphan() {}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("api.ts"),
+            "// @generated by the API client generator\nexport function used() {}\nfunction helper() {}\n",
         )
         .unwrap();
 
-        // Run analysis
-        let result = analyze_dead_code(&src_dir, None).unwrap();
+        let dead_names = |skip_generated| {
+            let config = AnalysisConfig {
+                cache_mode: CacheMode::Off,
+                skip_generated,
+                ..Default::default()
+            };
+            let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+            let names: Vec<String> = result
+                .files
+                .iter()
+                .flat_map(|f| &f.dead_code)
+                .map(|d| d.symbol.clone())
+                .collect();
+            (names, result.summary.skipped_generated)
+        };
 
-        // Verify results
-        assert!(result.summary.total_files > 0, "Should analyze files");
-        assert!(
-            result.summary.files_with_dead_code > 0,
-            "Should find dead code"
-        );
-        assert!(
-            result.summary.dead_functions >= 2,
-            "Should find at least 2 dead functions"
+        assert_eq!(
+            dead_names(false),
+            (vec!["helper".to_string(), "orphan".to_string()], 0)
         );
+        // `used` stays live through main.ts either way
+        assert_eq!(dead_names(true), (vec!["orphan".to_string()], 1));
+    }
 
-        // Print results for debugging
-        eprintln!("Dead functions: {}", result.summary.dead_functions);
-        eprintln!("Dead classes: {}", result.summary.dead_classes);
-        eprintln!(
-            "Files with dead code: {}",
-            result.summary.files_with_dead_code
-        );
+    #[test]
+    fn test_analyze_dead_code_min_loc() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        fs::write(
+            src_dir.join("main.ts"),
+            "function main() {}\n\nfunction tiny() {\n}\n\nfunction large() {\n    const a = 1;\n    const b = 2;\n    const c = 3;\n    const d = 4;\n    const e = 5;\n    const f = 6;\n    const g = 7;\n    return a + b + c + d + e + f + g;\n}\n\nmain();\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            min_loc: 5,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+
+        let dead: Vec<&str> = result
+            .files
+            .iter()
+            .flat_map(|f| &f.dead_code)
+            .map(|d| d.symbol.as_str())
+            .collect();
+        assert_eq!(dead, vec!["large"]);
+        assert_eq!(result.summary.filtered_small, 1);
+        assert_eq!(result.summary.dead_functions, 1);
+        assert_eq!(result.summary.total_dead_loc, 10);
     }
 
     #[test]
-    fn test_filter_by_confidence() {
-        let result = DeadCodeResult {
-            summary: DeadCodeSummary {
-                total_files: 2,
-                files_with_dead_code: 2,
-                dead_functions: 3,
-                dead_classes: 0,
-                total_dead_loc: 30,
-                dead_code_ratio: 0.5,
-            },
-            files: vec![FileDeadCode {
-                path: PathBuf::from("test.ts"),
-                dead_code: vec![
-                    DeadSymbol {
-                        symbol: "highConfidence".to_string(),
-                        kind: models::SymbolKind::Function,
-                        line_start: 1,
-                        line_end: 10,
-                        loc: 10,
-                        confidence: 95,
-                        reason: "Test".to_string(),
-                        last_modified: None,
-                    },
-                    DeadSymbol {
-                        symbol: "lowConfidence".to_string(),
-                        kind: models::SymbolKind::Function,
-                        line_start: 11,
-                        line_end: 20,
-                        loc: 10,
-                        confidence: 50,
-                        reason: "Test".to_string(),
-                        last_modified: None,
-                    },
-                ],
-            }],
+    fn test_analyze_dead_code_warns_on_missed_entry_points() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        // The real entry is server.ts, which no heuristic recognizes
+        fs::write(
+            src_dir.join("main.ts"),
+            "export function version() {\n    return '1.0';\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src_dir.join("server.ts"),
+            "function start() {\n    listen();\n    route();\n}\n\nfunction listen() {\n    const host = '0.0.0.0';\n    const port = 8080;\n    return `${host}:${port}`;\n}\n\nfunction route() {\n    const method = 'GET';\n    const path = '/';\n    return `${method} ${path}`;\n}\n\nstart();\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
         };
+        let result = analyze_dead_code(&src_dir, Some(config.clone())).unwrap();
+        assert!(result.summary.dead_code_ratio > 0.8);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            result.warnings[0].kind,
+            AnalysisWarningKind::SuspiciousDeadCodeRatio
+        );
+        assert!(result.warnings[0].message.contains("1 entry point"));
 
-        let filtered = result.filter_by_confidence(80);
+        // Raising the threshold silences the warning
+        let config = AnalysisConfig {
+            suspicious_dead_ratio: 1.0,
+            ..config
+        };
+        let result = analyze_dead_code(&src_dir, Some(config)).unwrap();
+        assert!(result.warnings.is_empty());
+    }
 
-        assert_eq!(filtered.summary.dead_functions, 1);
-        assert_eq!(filtered.summary.total_dead_loc, 10);
-        assert_eq!(filtered.files.len(), 1);
-        assert_eq!(filtered.files[0].dead_code.len(), 1);
-        assert_eq!(filtered.files[0].dead_code[0].symbol, "highConfidence");
+    #[test]
+    fn test_analyze_dead_code_warns_on_broken_tsconfig() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("tsconfig.json"),
+            "{ \"compilerOptions\": ",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("index.ts"),
+            "import { format } from 'date-fns';\nimport { used } from './util';\n\nexport function main() {\n    return used();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("util.ts"),
+            "export function used() {\n    return 1;\n}\n\nexport function unused() {\n    return 2;\n}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: CacheMode::Off,
+            ..Default::default()
+        };
+        let result = analyze_dead_code(temp_dir.path(), Some(config)).unwrap();
+        // util.ts is still reached through the relative import
+        assert!(result.files.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            result.warnings[0].kind,
+            AnalysisWarningKind::InvalidManifest
+        );
+        assert!(result.warnings[0].message.contains("tsconfig.json"));
     }
 }
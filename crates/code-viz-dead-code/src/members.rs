@@ -0,0 +1,592 @@
+//! Member-level usage of enums, const objects and namespaces.
+//!
+//! TypeScript enums, object literals used as pseudo-enums
+//! (`const Colors = { Red: "red" } as const`) and namespaces are accessed
+//! member by member (`Colors.Red`). This module records which members are
+//! accessed anywhere in the codebase so the unused ones can be reported
+//! individually.
+//!
+//! The analysis is name-based and conservative: once a container escapes
+//! (passed as a value, spread, iterated, indexed with a computed key, used in
+//! `typeof`), all of its members count as used. Containers that are never
+//! accessed by member are left alone. Accesses through an aliased import
+//! (`import { Colors as Palette }`) count for the imported container.
+
+pub use crate::models::UnusedMember;
+
+use crate::models::{Symbol, SymbolKind};
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use code_viz_core::parser::LanguageParser;
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+/// A declared enum, const object or namespace and its members
+struct Container {
+    symbol: Symbol,
+    members: Vec<Symbol>,
+}
+
+/// How a container name is used across the codebase
+#[derive(Default)]
+struct Usage {
+    accessed: HashSet<String>,
+    escaped: bool,
+}
+
+/// Find enum, const-object and namespace members never accessed in `files`
+///
+/// Files that cannot be read or parsed are skipped.
+pub fn find_unused_members(files: &[PathBuf]) -> Vec<UnusedMember> {
+    find_unused_members_in_sources(&crate::read_sources(files))
+}
+
+/// Find unused members in already loaded `(path, source)` pairs
+pub fn find_unused_members_in_sources(sources: &[(PathBuf, String)]) -> Vec<UnusedMember> {
+    let trees: Vec<_> = sources
+        .iter()
+        .filter_map(|(path, source)| {
+            let parser = parser_for(path, source);
+            match parser.parse(source) {
+                Ok(tree) => Some((path, source, tree)),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to parse file");
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let containers: Vec<Container> = trees
+        .iter()
+        .flat_map(|(path, source, tree)| collect_containers(path, source, tree.root_node()))
+        .collect();
+    if containers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut usages: HashMap<String, Usage> = containers
+        .iter()
+        .map(|c| (c.symbol.name.clone(), Usage::default()))
+        .collect();
+    for (_, source, tree) in &trees {
+        let aliases = import_aliases(tree.root_node(), source, &usages);
+        record_usages(tree.root_node(), source, &aliases, &mut usages);
+    }
+
+    let mut unused = Vec::new();
+    for container in containers {
+        let usage = &usages[&container.symbol.name];
+        if usage.escaped || usage.accessed.is_empty() {
+            continue;
+        }
+        let prefix_len = container.symbol.name.len() + 1;
+        for member in &container.members {
+            if !usage.accessed.contains(&member.name[prefix_len..]) {
+                unused.push(UnusedMember {
+                    container: container.symbol.clone(),
+                    member: member.clone(),
+                });
+            }
+        }
+    }
+
+    unused
+}
+
+/// Parser for `path`, honouring `AnalysisConfig::extension_overrides`
+pub(crate) fn parser_for_file(
+    path: &Path,
+    source: &str,
+    extension_overrides: &std::collections::HashMap<String, String>,
+) -> Box<dyn LanguageParser> {
+    match code_viz_core::scanner::override_language(path, extension_overrides) {
+        Some(language @ ("typescript" | "tsx")) => typescript_parser(language, source),
+        Some(_) => Box::new(code_viz_core::parser::JavaScriptParser),
+        None => parser_for(path, source),
+    }
+}
+
+/// Parser for `path`, with `.ts` files containing JSX parsed as TSX
+pub(crate) fn parser_for(path: &Path, source: &str) -> Box<dyn LanguageParser> {
+    match path.extension().and_then(|s| s.to_str()) {
+        Some("tsx") => typescript_parser("tsx", source),
+        Some("ts" | "vue" | "svelte") => typescript_parser("typescript", source),
+        _ => Box::new(code_viz_core::parser::JavaScriptParser),
+    }
+}
+
+fn typescript_parser(language: &str, source: &str) -> Box<dyn LanguageParser> {
+    match code_viz_core::parser::detect_dialect(language, source) {
+        "tsx" => Box::new(code_viz_core::parser::TsxParser),
+        _ => Box::new(code_viz_core::parser::TypeScriptParser),
+    }
+}
+
+fn text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+/// Collect top-level enums, const objects and namespaces declared in a file
+fn collect_containers(path: &Path, source: &str, root: Node) -> Vec<Container> {
+    let mut containers = Vec::new();
+    let mut cursor = root.walk();
+
+    for statement in root.named_children(&mut cursor) {
+        let (declaration, is_exported) = if statement.kind() == "export_statement" {
+            match statement.child_by_field_name("declaration") {
+                Some(declaration) => (declaration, true),
+                None => continue,
+            }
+        } else {
+            (statement, false)
+        };
+
+        // `namespace Foo {}` parses as an expression statement
+        let declaration = match declaration.kind() {
+            "expression_statement" => match declaration.named_child(0) {
+                Some(inner) => inner,
+                None => continue,
+            },
+            _ => declaration,
+        };
+
+        let container = match declaration.kind() {
+            "enum_declaration" => enum_container(path, source, declaration, is_exported),
+            "internal_module" | "module" => {
+                namespace_container(path, source, declaration, is_exported)
+            }
+            "lexical_declaration" if text(declaration, source).starts_with("const") => {
+                const_object_container(path, source, declaration, is_exported)
+            }
+            _ => None,
+        };
+        containers.extend(container);
+    }
+
+    containers
+}
+
+fn enum_container(path: &Path, source: &str, node: Node, is_exported: bool) -> Option<Container> {
+    let name = text(node.child_by_field_name("name")?, source);
+    let body = node.child_by_field_name("body")?;
+
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        let name_node = match member.kind() {
+            "enum_assignment" => member.named_child(0),
+            "property_identifier" | "string" | "number" => Some(member),
+            _ => None,
+        };
+        if let Some(name_node) = name_node {
+            members.push((member, property_name(name_node, source)));
+        }
+    }
+
+    Some(container(path, source, name, node, is_exported, members))
+}
+
+fn const_object_container(
+    path: &Path,
+    source: &str,
+    node: Node,
+    is_exported: bool,
+) -> Option<Container> {
+    // Only single-declarator statements: `const Colors = { ... }`
+    if node.named_child_count() != 1 {
+        return None;
+    }
+    let declarator = node.named_child(0)?;
+    let name_node = declarator.child_by_field_name("name")?;
+    if name_node.kind() != "identifier" {
+        return None;
+    }
+
+    // Unwrap `{ ... } as const` and `{ ... } satisfies T`
+    let mut value = declarator.child_by_field_name("value")?;
+    while matches!(value.kind(), "as_expression" | "satisfies_expression") {
+        value = value.named_child(0)?;
+    }
+    if value.kind() != "object" {
+        return None;
+    }
+
+    let mut members = Vec::new();
+    let mut cursor = value.walk();
+    for member in value.named_children(&mut cursor) {
+        let name_node = match member.kind() {
+            "pair" => member.child_by_field_name("key"),
+            "method_definition" => member.child_by_field_name("name"),
+            "shorthand_property_identifier" => Some(member),
+            // Spread members are unknown, so nothing can be reported
+            "spread_element" => return None,
+            _ => None,
+        };
+        if let Some(name_node) = name_node {
+            members.push((member, property_name(name_node, source)));
+        }
+    }
+
+    Some(container(
+        path,
+        source,
+        text(name_node, source),
+        node,
+        is_exported,
+        members,
+    ))
+}
+
+/// Members exported from a namespace body
+///
+/// Members referenced by name inside the namespace itself are left out, since
+/// those uses do not go through `Namespace.member`.
+fn namespace_container(
+    path: &Path,
+    source: &str,
+    node: Node,
+    is_exported: bool,
+) -> Option<Container> {
+    let name_node = node.child_by_field_name("name")?;
+    if name_node.kind() != "identifier" {
+        return None;
+    }
+    let body = node.child_by_field_name("body")?;
+
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        let Some(declaration) = statement
+            .child_by_field_name("declaration")
+            .filter(|_| statement.kind() == "export_statement")
+        else {
+            continue;
+        };
+        let name_nodes: Vec<Node> = match declaration.kind() {
+            "lexical_declaration" | "variable_declaration" => {
+                let mut cursor = declaration.walk();
+                declaration
+                    .named_children(&mut cursor)
+                    .filter_map(|declarator| declarator.child_by_field_name("name"))
+                    .filter(|name| name.kind() == "identifier")
+                    .collect()
+            }
+            _ => declaration
+                .child_by_field_name("name")
+                .into_iter()
+                .collect(),
+        };
+        for name_node in name_nodes {
+            let member_name = text(name_node, source);
+            if identifier_count(body, source, member_name) == 1 {
+                members.push((declaration, member_name.to_string()));
+            }
+        }
+    }
+
+    Some(container(
+        path,
+        source,
+        text(name_node, source),
+        node,
+        is_exported,
+        members,
+    ))
+}
+
+/// Number of identifiers named `name` inside `node`
+fn identifier_count(node: Node, source: &str, name: &str) -> usize {
+    let own = usize::from(
+        matches!(node.kind(), "identifier" | "type_identifier") && text(node, source) == name,
+    );
+    let mut cursor = node.walk();
+    let children: usize = node
+        .children(&mut cursor)
+        .map(|child| identifier_count(child, source, name))
+        .sum();
+    own + children
+}
+
+/// Name of a property key, without quotes for string keys
+fn property_name(node: Node, source: &str) -> String {
+    let name = text(node, source);
+    if node.kind() == "string" {
+        name.trim_matches(|c| c == '"' || c == '\'' || c == '`')
+            .to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn container(
+    path: &Path,
+    source: &str,
+    name: &str,
+    node: Node,
+    is_exported: bool,
+    members: Vec<(Node, String)>,
+) -> Container {
+    let is_test = crate::symbol_graph::is_test_file(path);
+    let symbol = |name: String, kind: SymbolKind, node: Node| {
+        let line_start = node.start_position().row + 1;
+        Symbol {
+            id: format!("{}:{}:{}", path.display(), line_start, name),
+            name,
+            kind,
+            path: path.to_path_buf(),
+            line_start,
+            line_end: node.end_position().row + 1,
+            byte_start: node.start_byte(),
+            byte_end: node.end_byte(),
+            is_exported,
+            is_test,
+            complexity: 0,
+            fingerprint: crate::models::fingerprint(text(node, source)),
+            body_fingerprint: String::new(),
+            accessibility: None,
+        }
+    };
+
+    Container {
+        symbol: symbol(name.to_string(), SymbolKind::Variable, node),
+        members: members
+            .into_iter()
+            .map(|(member, member_name)| {
+                symbol(
+                    format!("{}.{}", name, member_name),
+                    SymbolKind::Member,
+                    member,
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Local names bound by `import { Container as Local }` in a file, mapped
+/// to the tracked container they import
+fn import_aliases(
+    root: Node,
+    source: &str,
+    usages: &HashMap<String, Usage>,
+) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+    let mut cursor = root.walk();
+    for statement in root.named_children(&mut cursor) {
+        if statement.kind() == "import_statement" {
+            collect_import_aliases(statement, source, usages, &mut aliases);
+        }
+    }
+    aliases
+}
+
+fn collect_import_aliases(
+    node: Node,
+    source: &str,
+    usages: &HashMap<String, Usage>,
+    aliases: &mut HashMap<String, String>,
+) {
+    if node.kind() == "import_specifier" {
+        if let (Some(name), Some(alias)) = (
+            node.child_by_field_name("name"),
+            node.child_by_field_name("alias"),
+        ) {
+            let name = text(name, source);
+            if usages.contains_key(name) {
+                aliases.insert(text(alias, source).to_string(), name.to_string());
+            }
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_import_aliases(child, source, usages, aliases);
+    }
+}
+
+/// Record member accesses and escapes of every tracked container name,
+/// resolving the local names in `aliases` to the container they import
+fn record_usages(
+    node: Node,
+    source: &str,
+    aliases: &HashMap<String, String>,
+    usages: &mut HashMap<String, Usage>,
+) {
+    if matches!(node.kind(), "identifier" | "shorthand_property_identifier") {
+        let name = text(node, source);
+        let name = aliases.get(name).map_or(name, String::as_str);
+        if let Some(usage) = usages.get_mut(name) {
+            match classify_reference(node, source) {
+                Reference::Member(member) => {
+                    usage.accessed.insert(member);
+                }
+                Reference::Escape => usage.escaped = true,
+                Reference::Ignored => {}
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        record_usages(child, source, aliases, usages);
+    }
+}
+
+enum Reference {
+    /// `Container.member`, `Container["member"]` or the type `Container.member`
+    Member(String),
+    /// Any use that may touch every member
+    Escape,
+    /// Declarations, imports and exports of the name
+    Ignored,
+}
+
+fn classify_reference(node: Node, source: &str) -> Reference {
+    if node.kind() == "shorthand_property_identifier" {
+        return Reference::Escape;
+    }
+    let Some(parent) = node.parent() else {
+        return Reference::Escape;
+    };
+    let is_field = |field: &str| parent.child_by_field_name(field) == Some(node);
+
+    match parent.kind() {
+        "member_expression" if is_field("object") => parent
+            .child_by_field_name("property")
+            .map(|p| Reference::Member(text(p, source).to_string()))
+            .unwrap_or(Reference::Escape),
+        "subscript_expression" if is_field("object") => match parent.child_by_field_name("index") {
+            Some(index) if index.kind() == "string" => {
+                Reference::Member(property_name(index, source))
+            }
+            _ => Reference::Escape,
+        },
+        "nested_type_identifier" if is_field("module") => parent
+            .child_by_field_name("name")
+            .map(|n| Reference::Member(text(n, source).to_string()))
+            .unwrap_or(Reference::Escape),
+        "variable_declarator" | "enum_declaration" | "internal_module" | "module"
+            if is_field("name") =>
+        {
+            Reference::Ignored
+        }
+        "import_specifier" | "export_specifier" | "import_clause" | "namespace_import" => {
+            Reference::Ignored
+        }
+        _ => Reference::Escape,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unused(files: &[(&str, &str)]) -> Vec<String> {
+        let sources: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|(path, source)| (PathBuf::from(path), source.to_string()))
+            .collect();
+        let mut names: Vec<String> = find_unused_members_in_sources(&sources)
+            .into_iter()
+            .map(|m| m.member.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_const_object_members() {
+        let names = unused(&[
+            (
+                "colors.ts",
+                "export const Colors = {\n    Red: 'red',\n    Blue: 'blue',\n} as const;\n",
+            ),
+            (
+                "main.ts",
+                "import { Colors } from './colors';\n\nfunction main() {\n    return Colors.Red;\n}\n",
+            ),
+        ]);
+        assert_eq!(names, vec!["Colors.Blue"]);
+    }
+
+    #[test]
+    fn test_jsx_in_ts_file_is_parsed_as_tsx() {
+        let badge = "import { Colors } from './colors';\n\nexport const Badge = () => <span className={Colors.Red}>new</span>;\n";
+        assert_eq!(parser_for(Path::new("badge.ts"), badge).language(), "tsx");
+        assert_eq!(
+            parser_for(Path::new("plain.ts"), "let n = 1;\n").language(),
+            "typescript"
+        );
+
+        let names = unused(&[
+            (
+                "colors.ts",
+                "export const Colors = {\n    Red: 'red',\n    Blue: 'blue',\n} as const;\n",
+            ),
+            ("badge.ts", badge),
+        ]);
+        assert_eq!(names, vec!["Colors.Blue"]);
+    }
+
+    #[test]
+    fn test_enum_members() {
+        let names = unused(&[(
+            "status.ts",
+            "const enum Status {\n    Active = 1,\n    Idle,\n    'Gone',\n}\n\nlet s: Status.Idle = Status['Active'];\n",
+        )]);
+        assert_eq!(names, vec!["Status.Gone"]);
+    }
+
+    #[test]
+    fn test_namespace_members() {
+        let names = unused(&[
+            (
+                "util.ts",
+                "export namespace Util {\n    export function used() {}\n    export function unused() {}\n    export const helper = () => used();\n}\n",
+            ),
+            (
+                "main.ts",
+                "import { Util } from './util';\n\nUtil.helper();\n",
+            ),
+        ]);
+        // `used` is called inside the namespace, so only `unused` is reported
+        assert_eq!(names, vec!["Util.unused"]);
+    }
+
+    #[test]
+    fn test_aliased_import_counts_for_container() {
+        let names = unused(&[
+            (
+                "colors.ts",
+                "export const Colors = {\n    Red: 'red',\n    Blue: 'blue',\n    Green: 'green',\n} as const;\n",
+            ),
+            (
+                "main.ts",
+                "import { Colors as Palette } from './colors';\n\nfunction main() {\n    return Palette.Red;\n}\n",
+            ),
+            (
+                "theme.ts",
+                "import { Colors } from './colors';\n\nexport const accent = Colors.Green;\n",
+            ),
+        ]);
+        assert_eq!(names, vec!["Colors.Blue"]);
+    }
+
+    #[test]
+    fn test_escaped_container_keeps_all_members() {
+        let names = unused(&[(
+            "colors.ts",
+            "const Colors = { Red: 'red', Blue: 'blue' };\n\nconsole.log(Colors.Red, Object.values(Colors));\n",
+        )]);
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn test_unaccessed_container_is_not_reported() {
+        let names = unused(&[(
+            "colors.ts",
+            "const Colors = { Red: 'red', Blue: 'blue' };\n",
+        )]);
+        assert!(names.is_empty());
+    }
+}
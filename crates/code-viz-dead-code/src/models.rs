@@ -4,8 +4,12 @@
 //! detection pipeline, including symbol representations, analysis results,
 //! and summary statistics.
 
+pub use code_viz_core::models::{AnalysisWarning, AnalysisWarningKind};
+use code_viz_core::models::relative_path;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 /// Unique identifier for a symbol (typically file path + line number)
@@ -32,18 +36,86 @@ pub struct Symbol {
     /// Ending line number (1-indexed)
     pub line_end: usize,
 
+    /// Byte offset of the symbol's first byte in the analyzed source
+    #[serde(default)]
+    pub byte_start: usize,
+
+    /// Byte offset just past the symbol's last byte
+    #[serde(default)]
+    pub byte_end: usize,
+
     /// Whether symbol is exported from its module
     pub is_exported: bool,
 
     /// Whether symbol is in a test file
     pub is_test: bool,
+
+    /// Summed cyclomatic complexity of the functions inside the symbol
+    /// (0 for symbols without a body, such as re-exports and enum members)
+    #[serde(default)]
+    pub complexity: usize,
+
+    /// Hash of the symbol's source (see [`fingerprint`]), which stays the
+    /// same when the symbol moves
+    #[serde(default)]
+    pub fingerprint: String,
+
+    /// Hash of the symbol's source with its own name masked (see
+    /// [`body_fingerprint`]), shared by copies that were renamed
+    #[serde(default)]
+    pub body_fingerprint: String,
+
+    /// TypeScript accessibility modifier of a class method (`private`,
+    /// `protected` or `public`), if it was written
+    #[serde(default)]
+    pub accessibility: Option<String>,
+}
+
+/// Content hash of a symbol's source, stable across runs
+///
+/// Runs of whitespace count as a single space, so reindenting a symbol or
+/// moving it to other lines keeps its fingerprint. FNV-1a is used since the
+/// std hashers may change between Rust releases.
+pub fn fingerprint(source: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for (i, word) in source.split_whitespace().enumerate() {
+        let separator = if i > 0 { Some(b' ') } else { None };
+        for byte in separator.into_iter().chain(word.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// [`fingerprint`] of a symbol's source with every occurrence of its name
+/// replaced, so copies that only differ in their name share it
+///
+/// Recursive calls are masked too, since they use the name as well.
+pub fn body_fingerprint(source: &str, name: &str) -> String {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut masked = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find(is_identifier) {
+        masked.push_str(&rest[..start]);
+        let word = &rest[start..];
+        let word = &word[..word.find(|c| !is_identifier(c)).unwrap_or(word.len())];
+        masked.push_str(if word == name { "_" } else { word });
+        rest = &rest[start + word.len()..];
+    }
+    masked.push_str(rest);
+    fingerprint(&masked)
 }
 
 /// Type of symbol
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
 pub enum SymbolKind {
     /// Regular function declaration
+    #[default]
     Function,
 
     /// Arrow function or function expression
@@ -57,18 +129,138 @@ pub enum SymbolKind {
 
     /// Variable or constant
     Variable,
+
+    /// Re-export binding in a barrel file (`export { foo } from "./foo"`, or
+    /// one name forwarded by `export * from "./foo"`)
+    ReExport,
+
+    /// Member of an enum, const object or namespace (`Colors.Blue`)
+    Member,
+
+    /// TypeScript `namespace` or `module` declaration
+    Namespace,
+}
+
+/// Dead symbols tallied into the `DeadCodeSummary` kind counters
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KindCounts {
+    pub functions: usize,
+    pub classes: usize,
+    pub other: usize,
+}
+
+impl KindCounts {
+    pub fn add(&mut self, kind: SymbolKind) {
+        match kind {
+            SymbolKind::Function | SymbolKind::ArrowFunction | SymbolKind::Method => {
+                self.functions += 1
+            }
+            SymbolKind::Class => self.classes += 1,
+            SymbolKind::Variable
+            | SymbolKind::ReExport
+            | SymbolKind::Member
+            | SymbolKind::Namespace => self.other += 1,
+        }
+    }
+}
+
+impl FromIterator<SymbolKind> for KindCounts {
+    fn from_iter<I: IntoIterator<Item = SymbolKind>>(kinds: I) -> Self {
+        let mut counts = KindCounts::default();
+        for kind in kinds {
+            counts.add(kind);
+        }
+        counts
+    }
+}
+
+/// A member of an enum, const object or namespace that is never accessed
+///
+/// Found by [`crate::members`]; defined here so browser builds, which leave
+/// out the source passes, still share the analysis types.
+#[derive(Debug, Clone)]
+pub struct UnusedMember {
+    /// The enum, const object or namespace declaring the member
+    pub container: Symbol,
+
+    /// The member itself, named `Container.member`
+    pub member: Symbol,
+}
+
+/// A `private` method that its class never refers to, found by
+/// [`crate::private_members`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedPrivateMethod {
+    /// Symbol ID of the class declaring the method
+    pub class: SymbolId,
+
+    /// Symbol ID of the method itself
+    pub method: SymbolId,
 }
 
 /// Complete result of dead code analysis
+///
+/// Serialized with camelCase keys by every consumer: the CLI, the web routes
+/// and the Tauri commands.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct DeadCodeResult {
+    /// Version of this JSON layout, serialized first (see `code_viz_core::report`)
+    #[serde(default = "code_viz_core::report::current_schema_version")]
+    pub schema_version: u32,
+
     /// Aggregated summary statistics
     pub summary: DeadCodeSummary,
 
     /// Dead code grouped by file
     pub files: Vec<FileDeadCode>,
+
+    /// Dead code aggregated per parent directory, sorted by dead LOC (descending)
+    #[serde(default)]
+    pub by_directory: Vec<DirectoryDeadCode>,
+
+    /// Size of every analyzed file, dead code or not, sorted by path, so
+    /// results can be merged and split with the right totals
+    #[serde(default)]
+    pub file_totals: Vec<FileTotals>,
+
+    /// Groups of dead symbols that only reference each other (e.g. an unused
+    /// circular import), as symbol IDs, so they can be deleted together
+    #[serde(default)]
+    pub dead_clusters: Vec<Vec<SymbolId>>,
+
+    /// Signs that the result itself is unreliable, e.g. missed entry points
+    #[serde(default)]
+    pub warnings: Vec<AnalysisWarning>,
+
+    /// Slowest files to parse with their wall time, slowest first; only set
+    /// when `AnalysisConfig::profile` is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Vec<FileTiming>>,
+
+    /// Whether the symbol graph was loaded from a cache instead of built;
+    /// only set in `CacheMode::ReadOnly`, so that other runs over the same
+    /// tree serialize identically whether or not the cache hit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_hit: Option<bool>,
+}
+
+/// An empty result of the current schema version
+impl Default for DeadCodeResult {
+    fn default() -> Self {
+        Self {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: DeadCodeSummary::default(),
+            files: Vec::new(),
+            by_directory: Vec::new(),
+            file_totals: Vec::new(),
+            dead_clusters: Vec::new(),
+            warnings: Vec::new(),
+            profile: None,
+            cache_hit: None,
+        }
+    }
 }
 
 impl DeadCodeResult {
@@ -90,38 +282,72 @@ impl DeadCodeResult {
     /// let high_confidence = result.filter_by_confidence(90);
     /// ```
     pub fn filter_by_confidence(&self, min_confidence: u8) -> Self {
+        self.filter_by_confidence_range(min_confidence..=u8::MAX)
+    }
+
+    /// Filter dead code to symbols whose confidence lies in `range`
+    ///
+    /// Summaries are recomputed the same way as `filter_by_confidence`, e.g.
+    /// `filter_by_confidence_range(60..=79)` yields the medium-confidence tier.
+    pub fn filter_by_confidence_range(&self, range: RangeInclusive<u8>) -> Self {
+        self.filter_symbols(|_, symbol| range.contains(&symbol.confidence))
+    }
+
+    /// Hide the dead symbols already reported in `baseline`, so only newly
+    /// introduced dead code remains
+    ///
+    /// Symbols are matched by file and fingerprint, so a baselined symbol
+    /// that moved within its file stays hidden while one whose code changed
+    /// is reported again. Paths are compared relative to the analysis `root`,
+    /// so baselines with absolute and with relative paths both match. The
+    /// summary is recomputed like `filter_by_confidence` does.
+    pub fn suppress_baseline(&self, baseline: &DeadCodeResult, root: &Path) -> Self {
+        let accepted: std::collections::HashSet<(PathBuf, &str)> = baseline
+            .files
+            .iter()
+            .flat_map(|file| {
+                let path = relative_path(&file.path, root);
+                file.dead_code
+                    .iter()
+                    .filter(|symbol| !symbol.fingerprint.is_empty())
+                    .map(move |symbol| (path.clone(), symbol.fingerprint.as_str()))
+            })
+            .collect();
+
+        self.filter_symbols(|path, symbol| {
+            !accepted.contains(&(relative_path(path, root), symbol.fingerprint.as_str()))
+        })
+    }
+
+    /// Keep the dead symbols for which `keep` returns true, given the
+    /// symbol's file path, and recompute the summary from them
+    fn filter_symbols(&self, keep: impl Fn(&Path, &DeadSymbol) -> bool) -> Self {
         let mut filtered_files = Vec::new();
-        let mut dead_functions = 0;
-        let mut dead_classes = 0;
+        let mut kinds = KindCounts::default();
         let mut total_dead_loc = 0;
+        let mut total_dead_complexity = 0;
 
         for file in &self.files {
             let filtered_symbols: Vec<DeadSymbol> = file
                 .dead_code
                 .iter()
-                .filter(|symbol| symbol.confidence >= min_confidence)
+                .filter(|symbol| keep(&file.path, symbol))
                 .cloned()
                 .collect();
 
             if !filtered_symbols.is_empty() {
                 // Update counters
                 for symbol in &filtered_symbols {
-                    total_dead_loc += symbol.loc;
-                    match symbol.kind {
-                        SymbolKind::Function | SymbolKind::ArrowFunction | SymbolKind::Method => {
-                            dead_functions += 1;
-                        }
-                        SymbolKind::Class => {
-                            dead_classes += 1;
-                        }
-                        _ => {}
-                    }
+                    total_dead_complexity += symbol.complexity;
+                    kinds.add(symbol.kind);
                 }
 
-                filtered_files.push(FileDeadCode {
+                let filtered = FileDeadCode {
                     path: file.path.clone(),
                     dead_code: filtered_symbols,
-                });
+                };
+                total_dead_loc += filtered.dead_loc();
+                filtered_files.push(filtered);
             }
         }
 
@@ -134,23 +360,569 @@ impl DeadCodeResult {
         } else {
             0.0
         };
+        let original_dead_complexity: usize = self
+            .files
+            .iter()
+            .flat_map(|file| &file.dead_code)
+            .map(|symbol| symbol.complexity)
+            .sum();
+        let dead_complexity_ratio = if original_dead_complexity > 0 {
+            total_dead_complexity as f64 / original_dead_complexity as f64
+                * self.summary.dead_complexity_ratio
+        } else {
+            0.0
+        };
+
+        // Rescale directory ratios the same way as the summary ratio
+        let by_directory = DirectoryDeadCode::rollup(&filtered_files, |dir, dead_loc| {
+            self.by_directory
+                .iter()
+                .find(|d| d.dir == dir)
+                .filter(|d| d.dead_loc > 0)
+                .map(|d| dead_loc as f64 / d.dead_loc as f64 * d.dead_ratio)
+                .unwrap_or(0.0)
+        });
+
+        // Clusters are only kept while every member passes the filter
+        let remaining: std::collections::HashSet<SymbolId> = filtered_files
+            .iter()
+            .flat_map(|file| {
+                file.dead_code
+                    .iter()
+                    .map(|symbol| symbol_id(&file.path, symbol))
+            })
+            .collect();
+        let dead_clusters = self
+            .dead_clusters
+            .iter()
+            .filter(|cluster| cluster.iter().all(|id| remaining.contains(id)))
+            .cloned()
+            .collect();
 
         DeadCodeResult {
+            schema_version: self.schema_version,
             summary: DeadCodeSummary {
                 total_files: self.summary.total_files,
                 files_with_dead_code,
-                dead_functions,
-                dead_classes,
+                dead_functions: kinds.functions,
+                dead_classes: kinds.classes,
+                dead_other: kinds.other,
                 total_dead_loc,
+                total_loc: self.summary.total_loc,
+                total_complexity: self.summary.total_complexity,
                 dead_code_ratio,
+                dead_complexity_ratio,
+                kept_by_pattern: self.summary.kept_by_pattern,
+                filtered_small: self.summary.filtered_small,
+                skipped_generated: self.summary.skipped_generated,
+                skipped_files: self.summary.skipped_files,
             },
             files: filtered_files,
+            by_directory,
+            file_totals: self.file_totals.clone(),
+            dead_clusters,
+            warnings: self.warnings.clone(),
+            profile: self.profile.clone(),
+            cache_hit: self.cache_hit,
         }
     }
+
+    /// Make every path, including those inside cluster symbol IDs, relative
+    /// to the analysis `root`
+    ///
+    /// Paths outside `root` are left unchanged.
+    pub fn strip_root(&mut self, root: &Path) {
+        for file in &mut self.files {
+            file.path = relative_path(&file.path, root);
+        }
+        for dir in &mut self.by_directory {
+            dir.dir = match dir.dir.strip_prefix(root) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => dir.dir.clone(),
+            };
+        }
+        for totals in &mut self.file_totals {
+            totals.path = relative_path(&totals.path, root);
+        }
+        for id in self.dead_clusters.iter_mut().flatten() {
+            *id = relative_id(id, root);
+        }
+        for timing in self.profile.iter_mut().flatten() {
+            timing.path = relative_path(&timing.path, root);
+        }
+    }
+
+    /// Combine the results of analyses run on separate shards of a codebase
+    ///
+    /// File lists are concatenated and the summary recomputed from the merged
+    /// symbols. Ratios are taken over the summed `total_loc` and
+    /// `total_complexity` of the shards, so a shard without dead code lowers
+    /// them. A file reported by several shards is kept from the first one
+    /// only, with a logged warning, and its size is counted once.
+    ///
+    /// Reachability is not recomputed: a symbol that is only used from another
+    /// shard is still reported as dead. Shard along boundaries that nothing
+    /// imports across, such as independent packages.
+    pub fn merge(results: Vec<DeadCodeResult>) -> DeadCodeResult {
+        let mut seen: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+        let mut seen_totals: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+        let mut files = Vec::new();
+        let mut file_totals: Vec<FileTotals> = Vec::new();
+        let mut dead_clusters = Vec::new();
+        let mut warnings: Vec<AnalysisWarning> = Vec::new();
+        let mut total_loc = 0;
+        let mut total_complexity = 0;
+        let mut total_files = 0;
+        let mut kept_by_pattern = 0;
+        let mut filtered_small = 0;
+        let mut skipped_generated = 0;
+        let mut skipped_files = 0;
+        let mut profile: Option<Vec<FileTiming>> = None;
+        // Only a hit if no shard had to be built
+        let cache_hit = if results.is_empty() {
+            None
+        } else {
+            results
+                .iter()
+                .try_fold(true, |all, result| result.cache_hit.map(|hit| all && hit))
+        };
+
+        for result in results {
+            let summary = &result.summary;
+            total_loc += summary.total_loc;
+            total_complexity += summary.total_complexity;
+            total_files += summary.total_files;
+            kept_by_pattern += summary.kept_by_pattern;
+            filtered_small += summary.filtered_small;
+            skipped_generated += summary.skipped_generated;
+            skipped_files += summary.skipped_files;
+
+            for totals in result.file_totals {
+                if seen_totals.contains(&totals.path) {
+                    // Already counted by an earlier shard
+                    total_loc = total_loc.saturating_sub(totals.loc);
+                    total_complexity = total_complexity.saturating_sub(totals.complexity);
+                    total_files = total_files.saturating_sub(1);
+                } else {
+                    seen_totals.insert(totals.path.clone());
+                    file_totals.push(totals);
+                }
+            }
+            for file in result.files {
+                if seen.insert(file.path.clone()) {
+                    files.push(file);
+                } else {
+                    tracing::warn!(
+                        path = %file.path.display(),
+                        "File reported by several shards, keeping the first"
+                    );
+                }
+            }
+            for cluster in result.dead_clusters {
+                if !dead_clusters.contains(&cluster) {
+                    dead_clusters.push(cluster);
+                }
+            }
+            for warning in result.warnings {
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+            if let Some(timings) = result.profile {
+                profile.get_or_insert_with(Vec::new).extend(timings);
+            }
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        file_totals.sort_by(|a, b| a.path.cmp(&b.path));
+        if let Some(profile) = &mut profile {
+            profile.sort_by(|a, b| b.ms.total_cmp(&a.ms));
+        }
+
+        let symbols = || files.iter().flat_map(|file| &file.dead_code);
+        let total_dead_loc: usize = files.iter().map(FileDeadCode::dead_loc).sum();
+        let dead_complexity: usize = symbols().map(|symbol| symbol.complexity).sum();
+        let kinds: KindCounts = symbols().map(|symbol| symbol.kind).collect();
+
+        let dir_loc = FileTotals::loc_per_directory(&file_totals);
+        let by_directory = DirectoryDeadCode::rollup(&files, |dir, dead_loc| {
+            ratio(dead_loc, dir_loc.get(dir).copied().unwrap_or(0))
+        });
+
+        DeadCodeResult {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: DeadCodeSummary {
+                total_files,
+                files_with_dead_code: files.len(),
+                dead_functions: kinds.functions,
+                dead_classes: kinds.classes,
+                dead_other: kinds.other,
+                total_dead_loc,
+                total_loc,
+                total_complexity,
+                dead_code_ratio: ratio(total_dead_loc, total_loc),
+                dead_complexity_ratio: ratio(dead_complexity, total_complexity),
+                kept_by_pattern,
+                filtered_small,
+                skipped_generated,
+                skipped_files,
+            },
+            files,
+            by_directory,
+            file_totals,
+            dead_clusters,
+            warnings,
+            profile,
+            cache_hit,
+        }
+    }
+
+    /// Split the result into one report per root, e.g. per package of a
+    /// monorepo, with paths relative to that root
+    ///
+    /// Each file goes to the deepest root containing it; roots are matched
+    /// against file paths as they are, so pass them in the same form
+    /// (relative or absolute). Every root gets a report, even an empty one,
+    /// in the order given. Files outside all roots go into a last report with
+    /// no root, which is only added if there are any.
+    ///
+    /// Totals and ratios come from the `file_totals` under each root, so live
+    /// files count too. Clusters spanning several reports and whole-analysis
+    /// counters such as `kept_by_pattern` are left out.
+    pub fn partition_by_roots(&self, roots: &[PathBuf]) -> Vec<RootReport> {
+        let root_index = |path: &Path| {
+            roots
+                .iter()
+                .enumerate()
+                .filter(|(_, root)| path.starts_with(root))
+                .max_by_key(|(_, root)| root.components().count())
+                .map_or(roots.len(), |(index, _)| index)
+        };
+        let mut groups: Vec<Vec<FileDeadCode>> = vec![Vec::new(); roots.len() + 1];
+        for file in &self.files {
+            groups[root_index(&file.path)].push(file.clone());
+        }
+        let mut totals_groups: Vec<Vec<FileTotals>> = vec![Vec::new(); roots.len() + 1];
+        for totals in &self.file_totals {
+            totals_groups[root_index(&totals.path)].push(totals.clone());
+        }
+
+        let mut reports = Vec::new();
+        for (index, (files, file_totals)) in groups.into_iter().zip(totals_groups).enumerate() {
+            let root = roots.get(index);
+            if root.is_none() && files.is_empty() && file_totals.is_empty() {
+                continue;
+            }
+
+            let symbols = || files.iter().flat_map(|file| &file.dead_code);
+            let total_dead_loc: usize = files.iter().map(FileDeadCode::dead_loc).sum();
+            let dead_complexity: usize = symbols().map(|symbol| symbol.complexity).sum();
+            let kinds: KindCounts = symbols().map(|symbol| symbol.kind).collect();
+            let total_loc: usize = file_totals.iter().map(|totals| totals.loc).sum();
+            let total_complexity: usize = file_totals.iter().map(|totals| totals.complexity).sum();
+
+            let dir_loc = FileTotals::loc_per_directory(&file_totals);
+            let by_directory = DirectoryDeadCode::rollup(&files, |dir, dead_loc| {
+                ratio(dead_loc, dir_loc.get(dir).copied().unwrap_or(0))
+            });
+
+            let ids: std::collections::HashSet<SymbolId> = files
+                .iter()
+                .flat_map(|file| {
+                    file.dead_code
+                        .iter()
+                        .map(|symbol| symbol_id(&file.path, symbol))
+                })
+                .collect();
+            let dead_clusters = self
+                .dead_clusters
+                .iter()
+                .filter(|cluster| cluster.iter().all(|id| ids.contains(id)))
+                .cloned()
+                .collect();
+
+            let mut result = DeadCodeResult {
+                schema_version: self.schema_version,
+                summary: DeadCodeSummary {
+                    total_files: file_totals.len(),
+                    files_with_dead_code: files.len(),
+                    dead_functions: kinds.functions,
+                    dead_classes: kinds.classes,
+                    dead_other: kinds.other,
+                    total_dead_loc,
+                    total_loc,
+                    total_complexity,
+                    dead_code_ratio: ratio(total_dead_loc, total_loc),
+                    dead_complexity_ratio: ratio(dead_complexity, total_complexity),
+                    kept_by_pattern: 0,
+                    filtered_small: 0,
+                    skipped_generated: 0,
+                    skipped_files: 0,
+                },
+                files,
+                by_directory,
+                file_totals,
+                dead_clusters,
+                warnings: self.warnings.clone(),
+                profile: None,
+                cache_hit: self.cache_hit,
+            };
+            if let Some(root) = root {
+                result.strip_root(root);
+            }
+            reports.push(RootReport {
+                root: root.cloned(),
+                result,
+            });
+        }
+        reports
+    }
+
+    /// Dead symbols that appeared or disappeared between `old` and `new`
+    ///
+    /// Symbols are matched by file, name and start line first, then by name
+    /// and fingerprint, so a symbol that only moved (even to another file)
+    /// counts as unchanged rather than removed and added. Symbols without a
+    /// fingerprint, from reports of older versions, only match in place.
+    pub fn diff(old: &DeadCodeResult, new: &DeadCodeResult) -> DeadCodeDiff {
+        fn symbols(result: &DeadCodeResult) -> Vec<(&Path, &DeadSymbol)> {
+            result
+                .files
+                .iter()
+                .flat_map(|file| file.dead_code.iter().map(|s| (file.path.as_path(), s)))
+                .collect()
+        }
+        let old_symbols = symbols(old);
+        let new_symbols = symbols(new);
+        let mut old_matched = vec![false; old_symbols.len()];
+        let mut new_matched = vec![false; new_symbols.len()];
+
+        let mut by_location: HashMap<(&Path, &str, usize), Vec<usize>> = HashMap::new();
+        for (i, (path, symbol)) in old_symbols.iter().enumerate().rev() {
+            by_location
+                .entry((path, &symbol.symbol, symbol.line_start))
+                .or_default()
+                .push(i);
+        }
+        for (j, (path, symbol)) in new_symbols.iter().enumerate() {
+            let key = (*path, symbol.symbol.as_str(), symbol.line_start);
+            if let Some(i) = by_location.get_mut(&key).and_then(Vec::pop) {
+                old_matched[i] = true;
+                new_matched[j] = true;
+            }
+        }
+
+        let mut by_content: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+        for (i, (_, symbol)) in old_symbols.iter().enumerate().rev() {
+            if !old_matched[i] && !symbol.fingerprint.is_empty() {
+                by_content
+                    .entry((&symbol.symbol, &symbol.fingerprint))
+                    .or_default()
+                    .push(i);
+            }
+        }
+        for (j, (_, symbol)) in new_symbols.iter().enumerate() {
+            if new_matched[j] {
+                continue;
+            }
+            let key = (symbol.symbol.as_str(), symbol.fingerprint.as_str());
+            if let Some(i) = by_content.get_mut(&key).and_then(Vec::pop) {
+                old_matched[i] = true;
+                new_matched[j] = true;
+            }
+        }
+
+        let unmatched = |symbols: &[(&Path, &DeadSymbol)], matched: &[bool]| {
+            let mut files: Vec<FileDeadCode> = Vec::new();
+            for ((path, symbol), _) in symbols.iter().zip(matched).filter(|(_, m)| !**m) {
+                match files.last_mut() {
+                    Some(file) if file.path == *path => file.dead_code.push((*symbol).clone()),
+                    _ => files.push(FileDeadCode {
+                        path: path.to_path_buf(),
+                        dead_code: vec![(*symbol).clone()],
+                    }),
+                }
+            }
+            files
+        };
+
+        DeadCodeDiff {
+            added: unmatched(&new_symbols, &new_matched),
+            removed: unmatched(&old_symbols, &old_matched),
+            unchanged: new_matched.iter().filter(|m| **m).count(),
+        }
+    }
+}
+
+/// Dead code that appeared or disappeared between two results, from
+/// [`DeadCodeResult::diff`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadCodeDiff {
+    /// Dead symbols only in the new result, by file
+    pub added: Vec<FileDeadCode>,
+
+    /// Dead symbols only in the old result, by file
+    pub removed: Vec<FileDeadCode>,
+
+    /// Dead symbols in both results, including moved ones
+    pub unchanged: usize,
+}
+
+/// A symbol ID with its file path made relative to `root`
+pub(crate) fn relative_id(id: &str, root: &Path) -> SymbolId {
+    // IDs are `path:line:name`, and only the path may contain ':'
+    let mut parts = id.rsplitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(name), Some(line), Some(path)) => format!(
+            "{}:{}:{}",
+            relative_path(Path::new(path), root).display(),
+            line,
+            name
+        ),
+        _ => id.to_string(),
+    }
+}
+
+/// Raw symbol IDs from reachability analysis, for tooling that wants the
+/// full sets rather than scored `DeadSymbol`s
+///
+/// Each list is sorted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawSymbolIds {
+    /// Symbols reachable from an entry point
+    pub reachable: Vec<SymbolId>,
+
+    /// Symbols no entry point reaches
+    pub dead: Vec<SymbolId>,
+
+    /// Symbols the analysis started from
+    pub entry_points: Vec<SymbolId>,
+}
+
+impl RawSymbolIds {
+    /// Rewrite every ID's file path relative to `root`
+    pub fn strip_root(&mut self, root: &Path) {
+        for list in [&mut self.reachable, &mut self.dead, &mut self.entry_points] {
+            for id in list.iter_mut() {
+                *id = relative_id(id, root);
+            }
+        }
+    }
+}
+
+/// Exported symbols of the analyzed code, for reviewing a library's public
+/// surface
+///
+/// Unlike dead code, an export that nothing imports may well be intended
+/// for outside consumers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicApiReport {
+    /// Every exported symbol, sorted by path and line
+    pub exports: Vec<PublicExport>,
+}
+
+/// An exported symbol and how the analyzed code uses it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicExport {
+    /// Unique symbol ID (`path:line:name`)
+    pub id: SymbolId,
+
+    /// Symbol name
+    pub symbol: String,
+
+    /// Type of symbol
+    pub kind: SymbolKind,
+
+    /// Declaring file
+    pub path: PathBuf,
+
+    /// Starting line number
+    pub line_start: usize,
+
+    /// Imported by another analyzed file, directly or through re-exports
+    pub used_internally: bool,
+
+    /// Detected or configured as an entry point
+    pub is_entry: bool,
+}
+
+impl PublicApiReport {
+    /// Rewrite every path and ID relative to `root`
+    pub fn strip_root(&mut self, root: &Path) {
+        for export in &mut self.exports {
+            export.id = relative_id(&export.id, root);
+            export.path = relative_path(&export.path, root);
+        }
+    }
+}
+
+/// Symbols whose source is identical up to whitespace and their names
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    /// Body fingerprint shared by every symbol of the group
+    pub fingerprint: String,
+
+    /// Lines of code of each copy
+    pub loc: usize,
+
+    /// The copies, sorted by path and line (at least two)
+    pub symbols: Vec<DuplicateSymbol>,
+}
+
+/// One copy of a duplicated symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateSymbol {
+    /// Unique symbol ID (`path:line:name`)
+    pub id: SymbolId,
+
+    /// Symbol name
+    pub symbol: String,
+
+    /// Type of symbol
+    pub kind: SymbolKind,
+
+    /// Declaring file
+    pub path: PathBuf,
+
+    /// Starting line number
+    pub line_start: usize,
+
+    /// Ending line number
+    pub line_end: usize,
+}
+
+impl DuplicateGroup {
+    /// Rewrite every path and ID relative to `root`
+    pub fn strip_root(&mut self, root: &Path) {
+        for symbol in &mut self.symbols {
+            symbol.id = relative_id(&symbol.id, root);
+            symbol.path = relative_path(&symbol.path, root);
+        }
+    }
+}
+
+/// Share of `dead` in `total`, capped at 1 (0 for an empty total)
+fn ratio(dead: usize, total: usize) -> f64 {
+    if total > 0 {
+        (dead as f64 / total as f64).min(1.0)
+    } else {
+        0.0
+    }
+}
+
+/// ID of the graph symbol a dead symbol in `path` was reported for
+fn symbol_id(path: &Path, symbol: &DeadSymbol) -> SymbolId {
+    format!("{}:{}:{}", path.display(), symbol.line_start, symbol.symbol)
 }
 
 /// Summary statistics for dead code analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct DeadCodeSummary {
@@ -160,17 +932,66 @@ pub struct DeadCodeSummary {
     /// Number of files containing dead code
     pub files_with_dead_code: usize,
 
-    /// Total number of dead functions
+    /// Dead functions, arrow functions and methods
     pub dead_functions: usize,
 
-    /// Total number of dead classes
+    /// Dead classes
     pub dead_classes: usize,
 
-    /// Total lines of dead code
+    /// Every other dead symbol: variables, namespaces, enum or object members
+    /// and re-exports. The three kind counters add up to the number of dead
+    /// symbols listed in `files`.
+    #[serde(default)]
+    pub dead_other: usize,
+
+    /// Total lines of dead code, each line counted once like `total_loc`
     pub total_dead_loc: usize,
 
+    /// Lines covered by any symbol across the analyzed files, the total that
+    /// `dead_code_ratio` is relative to
+    #[serde(default)]
+    pub total_loc: usize,
+
+    /// Summed complexity of every analyzed symbol, the total that
+    /// `dead_complexity_ratio` is relative to
+    #[serde(default)]
+    pub total_complexity: usize,
+
     /// Ratio of dead code to total code (0.0 to 1.0)
     pub dead_code_ratio: f64,
+
+    /// Ratio of dead complexity to total complexity (0.0 to 1.0), so one long
+    /// but branchless dead function weighs less than many branching ones
+    #[serde(default)]
+    pub dead_complexity_ratio: f64,
+
+    /// Unreachable symbols left out because they match a keep pattern
+    #[serde(default)]
+    pub kept_by_pattern: usize,
+
+    /// Dead symbols left out because they are shorter than the minimum LOC
+    #[serde(default)]
+    pub filtered_small: usize,
+
+    /// Dead symbols left out because their file is marked as generated
+    #[serde(default)]
+    pub skipped_generated: usize,
+
+    /// Source files left out of the scan (too large or unreadable)
+    #[serde(default)]
+    pub skipped_files: usize,
+}
+
+/// Dead code under one report root, see [`DeadCodeResult::partition_by_roots`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct RootReport {
+    /// Report root, or `None` for the files outside every root
+    pub root: Option<PathBuf>,
+
+    /// Dead code of the files under `root`, with paths relative to it
+    pub result: DeadCodeResult,
 }
 
 /// Dead code found in a single file
@@ -185,10 +1006,150 @@ pub struct FileDeadCode {
     pub dead_code: Vec<DeadSymbol>,
 }
 
-/// A dead (unreachable) symbol with metadata
+impl FileDeadCode {
+    /// Lines covered by at least one dead symbol, so a dead class and its
+    /// dead methods count their lines once
+    pub fn dead_loc(&self) -> usize {
+        covered_lines(
+            self.dead_code
+                .iter()
+                .map(|symbol| (symbol.line_start, symbol.line_end)),
+        )
+    }
+}
+
+/// Number of lines covered by at least one of the inclusive `(start, end)`
+/// line spans
+pub(crate) fn covered_lines(spans: impl IntoIterator<Item = (usize, usize)>) -> usize {
+    let mut spans: Vec<(usize, usize)> = spans
+        .into_iter()
+        .map(|(start, end)| (start, end.max(start)))
+        .collect();
+    spans.sort_unstable();
+
+    let mut loc = 0;
+    let mut covered_until = 0;
+    for (start, end) in spans {
+        let start = start.max(covered_until + 1);
+        if end >= start {
+            loc += end - start + 1;
+            covered_until = end;
+        }
+    }
+    loc
+}
+
+/// Wall time spent parsing one file, see `AnalysisConfig::profile`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct FileTiming {
+    /// File path
+    pub path: PathBuf,
+
+    /// Milliseconds spent extracting the file's symbols and imports
+    pub ms: f64,
+}
+
+/// Size of one analyzed file, counted like the summary totals
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct FileTotals {
+    /// File path
+    pub path: PathBuf,
+
+    /// Lines covered by at least one symbol
+    pub loc: usize,
+
+    /// Summed complexity of the file's symbols
+    pub complexity: usize,
+
+    /// Symbols the file exports, from the graph's `is_exported`; names
+    /// forwarded by `export *` are not counted
+    #[serde(default)]
+    pub export_count: usize,
+}
+
+impl FileTotals {
+    /// Summed LOC of `totals` per parent directory
+    pub fn loc_per_directory(totals: &[FileTotals]) -> HashMap<PathBuf, usize> {
+        let mut dir_loc: HashMap<PathBuf, usize> = HashMap::new();
+        for file in totals {
+            let dir = file
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+            *dir_loc.entry(dir).or_default() += file.loc;
+        }
+        dir_loc
+    }
+}
+
+/// Dead code aggregated for a single directory
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct DirectoryDeadCode {
+    /// Directory path (parent of the files it aggregates)
+    pub dir: PathBuf,
+
+    /// Number of dead symbols in files directly inside this directory
+    pub dead_symbols: usize,
+
+    /// Lines of dead code in files directly inside this directory
+    pub dead_loc: usize,
+
+    /// Ratio of dead code to total code in this directory (0.0 to 1.0)
+    pub dead_ratio: f64,
+}
+
+impl DirectoryDeadCode {
+    /// Group per-file dead code by parent directory
+    ///
+    /// `ratio` receives each directory and its dead LOC and returns the
+    /// directory's dead code ratio. Results are sorted by dead LOC
+    /// (descending), then by directory path.
+    pub fn rollup<F>(files: &[FileDeadCode], ratio: F) -> Vec<DirectoryDeadCode>
+    where
+        F: Fn(&Path, usize) -> f64,
+    {
+        let mut totals: BTreeMap<PathBuf, (usize, usize)> = BTreeMap::new();
+
+        for file in files {
+            let dir = file
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+            let entry = totals.entry(dir).or_default();
+            entry.0 += file.dead_code.len();
+            entry.1 += file.dead_loc();
+        }
+
+        let mut directories: Vec<DirectoryDeadCode> = totals
+            .into_iter()
+            .map(|(dir, (dead_symbols, dead_loc))| {
+                let dead_ratio = ratio(&dir, dead_loc);
+                DirectoryDeadCode {
+                    dir,
+                    dead_symbols,
+                    dead_loc,
+                    dead_ratio,
+                }
+            })
+            .collect();
+
+        directories.sort_by(|a, b| b.dead_loc.cmp(&a.dead_loc).then_with(|| a.dir.cmp(&b.dir)));
+        directories
+    }
+}
+
+/// A dead (unreachable) symbol with metadata
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct DeadSymbol {
     /// Symbol name
     pub symbol: String,
@@ -202,17 +1163,300 @@ pub struct DeadSymbol {
     /// Ending line number
     pub line_end: usize,
 
+    /// Byte offset of the symbol's first byte in the file
+    ///
+    /// Offsets are into the UTF-8 source, so they only differ from the file
+    /// on disk for files decoded lossily and for Vue/Svelte components, whose
+    /// scripts are analyzed on their own.
+    #[serde(default)]
+    pub byte_start: usize,
+
+    /// Byte offset just past the symbol's last byte, so
+    /// `source[byte_start..byte_end]` is the symbol's text
+    #[serde(default)]
+    pub byte_end: usize,
+
     /// Lines of code in this symbol
     pub loc: usize,
 
+    /// Summed cyclomatic complexity of the functions in this symbol
+    #[serde(default)]
+    pub complexity: usize,
+
     /// Deletion confidence score (0-100)
     pub confidence: u8,
 
     /// Reason why this symbol is marked as dead
     pub reason: String,
 
-    /// Last modification time (if available)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Last modification time (if available), as an ISO 8601 string
+    #[serde(default, with = "iso8601", skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "specta", specta(type = Option<String>))]
     pub last_modified: Option<SystemTime>,
+
+    /// Whole days since the last commit changing the symbol's lines (if
+    /// inside a git repository), so the stalest code can be deleted first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<u64>,
+
+    /// Penalties that lowered the confidence score (if requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence_penalties: Option<Vec<ConfidencePenalty>>,
+
+    /// Hash of the symbol's source, matching it across runs after it moved
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+impl DeadSymbol {
+    /// Human-readable explanation of the confidence score
+    ///
+    /// Formatted as `confidence 45 (exported -30, handler pattern -25)`.
+    pub fn explain_confidence(&self) -> String {
+        match &self.confidence_penalties {
+            Some(penalties) if !penalties.is_empty() => {
+                let reasons: Vec<String> = penalties
+                    .iter()
+                    .map(|p| format!("{} {:+}", p.name, -p.amount))
+                    .collect();
+                format!("confidence {} ({})", self.confidence, reasons.join(", "))
+            }
+            _ => format!("confidence {}", self.confidence),
+        }
+    }
+}
+
+/// A single penalty applied to a confidence score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct ConfidencePenalty {
+    /// Short name of the heuristic (e.g., "exported", "handler pattern")
+    pub name: String,
+
+    /// Points subtracted from the score (negative for a bonus)
+    pub amount: i16,
+}
+
+impl ConfidencePenalty {
+    /// Create a penalty with the given name and amount
+    pub fn new(name: impl Into<String>, amount: i16) -> Self {
+        Self {
+            name: name.into(),
+            amount,
+        }
+    }
+}
+
+/// `Option<SystemTime>` as an ISO 8601 string, like `TreeNode::last_modified`
+/// in the API, so timestamps never leak as raw `secs_since_epoch` objects.
+/// Reports written before still load: the `{secs_since_epoch, ...}` form is
+/// accepted on deserialize.
+mod iso8601 {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::SystemTime;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<SystemTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => {
+                let datetime: chrono::DateTime<chrono::Utc> = (*time).into();
+                serializer
+                    .serialize_str(&datetime.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// A timestamp as written now, or as serde's own `SystemTime` form
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Iso8601(String),
+        Legacy(SystemTime),
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<SystemTime>, D::Error> {
+        match Option::<Timestamp>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(Timestamp::Legacy(time)) => Ok(Some(time)),
+            Some(Timestamp::Iso8601(s)) => {
+                let datetime =
+                    chrono::DateTime::parse_from_rfc3339(&s).map_err(serde::de::Error::custom)?;
+                Ok(Some(datetime.with_timezone(&chrono::Utc).into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn shard(path: &str, loc: usize, kind: SymbolKind, dead_code_ratio: f64) -> DeadCodeResult {
+        let files = vec![FileDeadCode {
+            path: PathBuf::from(path),
+            dead_code: vec![DeadSymbol {
+                symbol: "unused".to_string(),
+                kind,
+                line_start: 1,
+                line_end: loc,
+                byte_start: 0,
+                byte_end: 0,
+                loc,
+                complexity: 1,
+                confidence: 90,
+                reason: "Unreachable".to_string(),
+                last_modified: None,
+                age_days: None,
+                confidence_penalties: None,
+                fingerprint: String::new(),
+            }],
+        }];
+        let by_directory = DirectoryDeadCode::rollup(&files, |_, _| dead_code_ratio);
+        let total_loc = (loc as f64 / dead_code_ratio).round() as usize;
+        DeadCodeResult {
+            schema_version: code_viz_core::report::current_schema_version(),
+            summary: DeadCodeSummary {
+                total_files: 4,
+                files_with_dead_code: 1,
+                dead_functions: usize::from(kind == SymbolKind::Function),
+                dead_classes: usize::from(kind == SymbolKind::Class),
+                dead_other: 0,
+                total_dead_loc: loc,
+                total_loc,
+                total_complexity: 2,
+                dead_code_ratio,
+                dead_complexity_ratio: 0.5,
+                kept_by_pattern: 1,
+                filtered_small: 2,
+                skipped_generated: 0,
+                skipped_files: 0,
+            },
+            files,
+            by_directory,
+            file_totals: vec![FileTotals {
+                path: PathBuf::from(path),
+                loc: total_loc,
+                complexity: 2,
+                export_count: 0,
+            }],
+            dead_clusters: vec![],
+            warnings: vec![],
+            profile: None,
+            cache_hit: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_disjoint_shards() {
+        // 10 of 100 LOC and 30 of 100 LOC dead
+        let merged = DeadCodeResult::merge(vec![
+            shard("b/lib.ts", 30, SymbolKind::Class, 0.3),
+            shard("a/app.ts", 10, SymbolKind::Function, 0.1),
+        ]);
+
+        assert_eq!(merged.summary.total_files, 8);
+        assert_eq!(merged.summary.files_with_dead_code, 2);
+        assert_eq!(merged.summary.dead_functions, 1);
+        assert_eq!(merged.summary.dead_classes, 1);
+        assert_eq!(merged.summary.total_dead_loc, 40);
+        assert!((merged.summary.dead_code_ratio - 0.2).abs() < 1e-9);
+        assert!((merged.summary.dead_complexity_ratio - 0.5).abs() < 1e-9);
+        assert_eq!(merged.summary.kept_by_pattern, 2);
+        assert_eq!(merged.summary.filtered_small, 4);
+        assert_eq!(merged.files[0].path, PathBuf::from("a/app.ts"));
+        assert_eq!(merged.by_directory[0].dir, PathBuf::from("b"));
+        assert!((merged.by_directory[0].dead_ratio - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_merge_keeps_first_duplicate_file() {
+        let merged = DeadCodeResult::merge(vec![
+            shard("a/app.ts", 10, SymbolKind::Function, 0.1),
+            shard("a/app.ts", 30, SymbolKind::Function, 0.3),
+        ]);
+
+        assert_eq!(merged.files.len(), 1);
+        assert_eq!(merged.summary.total_dead_loc, 10);
+        // The dropped copy's code is not counted either
+        assert_eq!(merged.summary.total_loc, 100);
+        assert_eq!(merged.summary.total_files, 7);
+        assert!((merged.summary.dead_code_ratio - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_merge_counts_shard_without_dead_code() {
+        let clean = DeadCodeResult {
+            summary: DeadCodeSummary {
+                files_with_dead_code: 0,
+                dead_functions: 0,
+                total_dead_loc: 0,
+                total_loc: 1000,
+                total_complexity: 20,
+                dead_code_ratio: 0.0,
+                dead_complexity_ratio: 0.0,
+                ..shard("clean/main.ts", 1, SymbolKind::Function, 0.1).summary
+            },
+            files: vec![],
+            by_directory: vec![],
+            file_totals: vec![FileTotals {
+                path: PathBuf::from("clean/main.ts"),
+                loc: 1000,
+                complexity: 20,
+                export_count: 0,
+            }],
+            ..shard("clean/main.ts", 1, SymbolKind::Function, 0.1)
+        };
+
+        // 10 dead LOC of 100, next to 1000 live LOC
+        let merged = DeadCodeResult::merge(vec![
+            clean,
+            shard("a/app.ts", 10, SymbolKind::Function, 0.1),
+        ]);
+
+        assert_eq!(merged.summary.total_loc, 1100);
+        assert_eq!(merged.summary.total_complexity, 22);
+        assert!((merged.summary.dead_code_ratio - 10.0 / 1100.0).abs() < 1e-9);
+        assert!((merged.summary.dead_complexity_ratio - 1.0 / 22.0).abs() < 1e-9);
+        assert_eq!(merged.file_totals.len(), 2);
+    }
+
+    #[test]
+    fn test_dead_symbol_last_modified_is_iso_8601() {
+        let symbol = DeadSymbol {
+            symbol: "unused".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 1,
+            line_end: 3,
+            byte_start: 0,
+            byte_end: 0,
+            loc: 3,
+            complexity: 0,
+            confidence: 90,
+            reason: "Unreachable".to_string(),
+            last_modified: Some(UNIX_EPOCH + Duration::from_secs(1234567890)),
+            age_days: None,
+            confidence_penalties: None,
+            fingerprint: String::new(),
+        };
+
+        let json = serde_json::to_value(&symbol).unwrap();
+        assert_eq!(json["lastModified"], "2009-02-13T23:31:30.000Z");
+
+        let roundtrip: DeadSymbol = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(roundtrip.last_modified, symbol.last_modified);
+
+        // Baselines written before ISO 8601 carry serde's `SystemTime` form
+        let mut legacy = json;
+        legacy["lastModified"] =
+            serde_json::json!({ "secs_since_epoch": 1234567890, "nanos_since_epoch": 0 });
+        let loaded: DeadSymbol = serde_json::from_value(legacy).unwrap();
+        assert_eq!(loaded.last_modified, symbol.last_modified);
+    }
 }
@@ -0,0 +1,149 @@
+//! Unused `private` methods of classes.
+//!
+//! The symbol graph keeps every method of a reachable class alive, since a
+//! public method may be called by anyone holding an instance. TypeScript
+//! `private` methods can only be called from inside their class, so one the
+//! class body never refers to is dead even though the class is live.
+//!
+//! Public and protected methods are never reported here: they may be part of
+//! the class's API. The check is name-based and conservative, so any mention
+//! of the method's name inside the class, such as a property access, a string
+//! key or a comment, counts as a use.
+
+pub use crate::models::UnusedPrivateMethod;
+
+use crate::models::{Symbol, SymbolKind};
+use crate::symbol_graph::references::find_name_occurrences;
+use ahash::AHashMap as HashMap;
+use std::path::{Path, PathBuf};
+
+/// Find `private` methods never referred to inside their class
+///
+/// Classes and methods are taken from the graph's `symbols`, so methods are
+/// only `private` if the extractor saw the modifier.
+pub fn find_unused_private_methods<'a>(
+    sources: &[(PathBuf, String)],
+    symbols: impl IntoIterator<Item = &'a Symbol>,
+) -> Vec<UnusedPrivateMethod> {
+    let mut by_file: HashMap<&Path, Vec<&Symbol>> = HashMap::new();
+    for symbol in symbols {
+        if matches!(symbol.kind, SymbolKind::Class | SymbolKind::Method) {
+            by_file
+                .entry(symbol.path.as_path())
+                .or_default()
+                .push(symbol);
+        }
+    }
+
+    let mut unused = Vec::new();
+    for (path, source) in sources {
+        let Some(file_symbols) = by_file.get(path.as_path()) else {
+            continue;
+        };
+        for method in file_symbols
+            .iter()
+            .filter(|s| s.accessibility.as_deref() == Some("private"))
+        {
+            let Some(class) = enclosing_class(file_symbols, method) else {
+                continue;
+            };
+            if reference_count(source, file_symbols, class, &method.name) == 0 {
+                unused.push(UnusedPrivateMethod {
+                    class: class.id.clone(),
+                    method: method.id.clone(),
+                });
+            }
+        }
+    }
+    unused.sort_by(|a, b| a.method.cmp(&b.method));
+    unused
+}
+
+/// Innermost class whose span contains `method`
+fn enclosing_class<'a>(file_symbols: &[&'a Symbol], method: &Symbol) -> Option<&'a Symbol> {
+    file_symbols
+        .iter()
+        .filter(|s| s.kind == SymbolKind::Class)
+        .filter(|s| s.byte_start <= method.byte_start && method.byte_end <= s.byte_end)
+        .min_by_key(|s| s.byte_end - s.byte_start)
+        .copied()
+}
+
+/// Number of times `name` occurs inside `class`, other than as the name of
+/// one of its method declarations
+fn reference_count(source: &str, file_symbols: &[&Symbol], class: &Symbol, name: &str) -> usize {
+    let Some(body) = source.get(class.byte_start..class.byte_end) else {
+        return 0;
+    };
+    // A method's name is the first occurrence in its own text
+    let declarations: Vec<usize> = file_symbols
+        .iter()
+        .filter(|s| s.kind == SymbolKind::Method && s.name == name)
+        .filter(|s| class.byte_start <= s.byte_start && s.byte_end <= class.byte_end)
+        .filter_map(|s| {
+            let text = source.get(s.byte_start..s.byte_end)?;
+            let first = find_name_occurrences(text, name).into_iter().next()?;
+            Some(s.byte_start + first.start)
+        })
+        .collect();
+    find_name_occurrences(body, name)
+        .into_iter()
+        .filter(|occurrence| !declarations.contains(&(class.byte_start + occurrence.start)))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_graph::SymbolGraphBuilder;
+
+    fn unused(source: &str) -> Vec<String> {
+        let sources = vec![(PathBuf::from("service.ts"), source.to_string())];
+        let graph = SymbolGraphBuilder::new()
+            .build_graph(sources.clone())
+            .unwrap();
+        find_unused_private_methods(&sources, graph.symbols.values())
+            .into_iter()
+            .map(|m| m.method)
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_private_methods() {
+        let source = r#"export class Service {
+    run() {
+        return this.used() + this["indexed"]();
+    }
+
+    private used() { return 1; }
+    private indexed() { return 2; }
+    private helper() { return 3; }
+    protected hook() {}
+    unusedPublic() {}
+}
+"#;
+        assert_eq!(unused(source), vec!["service.ts:8:helper"]);
+    }
+
+    #[test]
+    fn test_private_method_used_by_another_private_method() {
+        let source = "class A {\n    private a() { this.b(); }\n    private b() {}\n}\n";
+        // `b` is referred to; `a` is not, however much it calls
+        assert_eq!(unused(source), vec!["service.ts:2:a"]);
+    }
+
+    #[test]
+    fn test_private_methods_follow_extension_overrides() {
+        let source = "export class A {\n    private unused() {}\n}\n";
+        let sources = vec![(PathBuf::from("a.ts.tmpl"), source.to_string())];
+        let overrides = [("ts.tmpl".to_string(), "typescript".to_string())].into();
+        let graph = SymbolGraphBuilder::new()
+            .with_extension_overrides(overrides)
+            .build_graph(sources.clone())
+            .unwrap();
+
+        let unused = find_unused_private_methods(&sources, graph.symbols.values());
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].method, "a.ts.tmpl:2:unused");
+    }
+}
@@ -4,9 +4,11 @@
 //! all reachable symbols in the codebase. Unreachable symbols are
 //! considered dead code.
 
-use crate::models::{Symbol, SymbolId};
+use crate::models::{Symbol, SymbolId, SymbolKind};
 use crate::symbol_graph::SymbolGraph;
-use ahash::AHashSet as HashSet;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
 /// Error type for reachability analysis
@@ -19,6 +21,10 @@ pub enum ReachabilityError {
     /// No entry points provided
     #[error("No entry points provided for analysis")]
     NoEntryPoints,
+
+    /// Analysis was cancelled via the cancellation flag
+    #[error("Reachability analysis cancelled")]
+    Cancelled,
 }
 
 /// Reachability analyzer that performs DFS from entry points
@@ -28,6 +34,15 @@ pub struct ReachabilityAnalyzer {
 
     /// Set of visited symbols during DFS
     visited: HashSet<SymbolId>,
+
+    /// Predecessor of each reached symbol, recorded by `analyze_with_paths`
+    predecessors: HashMap<SymbolId, SymbolId>,
+
+    /// Entry points of the last analysis, reused by `update`
+    entry_points: Vec<SymbolId>,
+
+    /// Whether type-only imports keep their targets alive
+    follow_type_imports: bool,
 }
 
 impl ReachabilityAnalyzer {
@@ -39,9 +54,21 @@ impl ReachabilityAnalyzer {
         Self {
             graph,
             visited: HashSet::new(),
+            predecessors: HashMap::new(),
+            entry_points: Vec::new(),
+            follow_type_imports: true,
         }
     }
 
+    /// Set whether type-only imports (`import type`) are followed
+    ///
+    /// They are by default. Ignoring them finds code that is dead at runtime
+    /// even though types from it are still referenced.
+    pub fn with_type_imports(mut self, follow: bool) -> Self {
+        self.follow_type_imports = follow;
+        self
+    }
+
     /// Perform reachability analysis from given entry points
     ///
     /// # Arguments
@@ -55,6 +82,18 @@ impl ReachabilityAnalyzer {
     pub fn analyze(
         &mut self,
         entry_points: Vec<SymbolId>,
+    ) -> Result<HashSet<SymbolId>, ReachabilityError> {
+        self.analyze_with_cancel(entry_points, None)
+    }
+
+    /// Perform reachability analysis, checking `cancel` between entry points
+    ///
+    /// # Errors
+    /// Returns `ReachabilityError::Cancelled` as soon as the flag is observed set
+    pub fn analyze_with_cancel(
+        &mut self,
+        entry_points: Vec<SymbolId>,
+        cancel: Option<&AtomicBool>,
     ) -> Result<HashSet<SymbolId>, ReachabilityError> {
         if entry_points.is_empty() {
             return Err(ReachabilityError::NoEntryPoints);
@@ -62,11 +101,16 @@ impl ReachabilityAnalyzer {
 
         // Clear visited set for fresh analysis
         self.visited.clear();
+        self.predecessors.clear();
 
         // Perform DFS from each entry point
-        for entry_point in entry_points {
-            self.dfs(&entry_point);
+        for entry_point in &entry_points {
+            if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                return Err(ReachabilityError::Cancelled);
+            }
+            self.dfs(entry_point);
         }
+        self.entry_points = entry_points;
 
         tracing::info!(
             "Reachability analysis complete: {} reachable symbols out of {} total",
@@ -77,6 +121,137 @@ impl ReachabilityAnalyzer {
         Ok(self.visited.clone())
     }
 
+    /// Perform reachability analysis, recording how each symbol was reached
+    ///
+    /// Traverses breadth-first from all entry points at once, so the recorded
+    /// predecessors form shortest entry-point paths. Use [`Self::path_to`] to
+    /// reconstruct the path that keeps a symbol alive.
+    ///
+    /// # Errors
+    /// Returns error if no entry points provided
+    pub fn analyze_with_paths(
+        &mut self,
+        entry_points: Vec<SymbolId>,
+    ) -> Result<HashSet<SymbolId>, ReachabilityError> {
+        if entry_points.is_empty() {
+            return Err(ReachabilityError::NoEntryPoints);
+        }
+
+        self.visited.clear();
+        self.predecessors.clear();
+
+        let mut queue = VecDeque::new();
+        for entry_point in &entry_points {
+            if self.graph.symbols.contains_key(entry_point)
+                && self.visited.insert(entry_point.clone())
+            {
+                queue.push_back(entry_point.clone());
+            }
+        }
+
+        while let Some(current_id) = queue.pop_front() {
+            for dep_id in dependencies(&self.graph, self.follow_type_imports, &current_id) {
+                if self.visited.insert(dep_id.clone()) {
+                    self.predecessors.insert(dep_id.clone(), current_id.clone());
+                    queue.push_back(dep_id.clone());
+                }
+            }
+        }
+        self.entry_points = entry_points;
+
+        Ok(self.visited.clone())
+    }
+
+    /// Re-run the last analysis against an updated graph, reusing its result
+    ///
+    /// `changed_symbols` must list every symbol that was added, removed, or
+    /// whose imports differ between the current and the new graph (see
+    /// [`changed_symbols`]). Only symbols that were reached through a changed
+    /// symbol are invalidated; traversal restarts from the reachable symbols
+    /// bordering them, so the result equals a full [`Self::analyze`] with the
+    /// same entry points. Recorded paths are cleared.
+    ///
+    /// # Errors
+    /// Returns `ReachabilityError::NoEntryPoints` if nothing was analyzed yet
+    pub fn update(
+        &mut self,
+        graph: SymbolGraph,
+        changed_symbols: &[SymbolId],
+    ) -> Result<HashSet<SymbolId>, ReachabilityError> {
+        if self.entry_points.is_empty() {
+            return Err(ReachabilityError::NoEntryPoints);
+        }
+
+        // Everything previously reached through a changed symbol may have
+        // lost its only path, so forget it
+        let mut invalidated: HashSet<SymbolId> = HashSet::new();
+        let mut stack: Vec<&SymbolId> = changed_symbols
+            .iter()
+            .filter(|id| self.visited.contains(*id))
+            .collect();
+        while let Some(current_id) = stack.pop() {
+            if !invalidated.insert(current_id.clone()) {
+                continue;
+            }
+            stack.extend(
+                dependencies(&self.graph, self.follow_type_imports, current_id)
+                    .filter(|dep_id| self.visited.contains(*dep_id)),
+            );
+        }
+        self.visited.retain(|id| !invalidated.contains(id));
+        self.predecessors.clear();
+        self.graph = graph;
+
+        // Restart from entry points and still-reached symbols that depend
+        // on an invalidated or changed symbol
+        let changed: HashSet<&SymbolId> = changed_symbols.iter().collect();
+        let mut boundary: Vec<SymbolId> = self
+            .entry_points
+            .iter()
+            .filter(|id| !self.visited.contains(*id))
+            .cloned()
+            .collect();
+        for id in &self.visited {
+            if dependencies(&self.graph, self.follow_type_imports, id)
+                .any(|dep_id| invalidated.contains(dep_id) || changed.contains(dep_id))
+            {
+                boundary.push(id.clone());
+            }
+        }
+        for id in &boundary {
+            self.visited.remove(id);
+        }
+        for id in &boundary {
+            self.dfs(id);
+        }
+
+        tracing::debug!(
+            invalidated = invalidated.len(),
+            boundary = boundary.len(),
+            reachable = self.visited.len(),
+            "Incremental reachability update complete"
+        );
+
+        Ok(self.visited.clone())
+    }
+
+    /// Shortest path from an entry point to `symbol_id`, inclusive
+    ///
+    /// Only available after [`Self::analyze_with_paths`]; returns `None` if
+    /// the symbol was not reached.
+    pub fn path_to(&self, symbol_id: &str) -> Option<Vec<SymbolId>> {
+        let mut current = self.visited.get(symbol_id)?;
+        let mut path = vec![current.clone()];
+
+        while let Some(predecessor) = self.predecessors.get(current) {
+            path.push(predecessor.clone());
+            current = predecessor;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
     /// Perform depth-first search from a symbol
     ///
     /// # Arguments
@@ -104,17 +279,58 @@ impl ReachabilityAnalyzer {
             self.visited.insert(current_id.clone());
 
             // Add all dependencies to the stack
-            if let Some(dependencies) = self.graph.imports.get(&current_id) {
-                for dep_id in dependencies {
-                    if !self.visited.contains(dep_id) {
-                        stack.push(dep_id.clone());
-                    }
+            for dep_id in dependencies(&self.graph, self.follow_type_imports, &current_id) {
+                if !self.visited.contains(dep_id) {
+                    stack.push(dep_id.clone());
                 }
             }
         }
     }
 }
 
+/// Symbols `symbol_id` depends on, including type-only imports if `follow_type_imports`
+fn dependencies<'a>(
+    graph: &'a SymbolGraph,
+    follow_type_imports: bool,
+    symbol_id: &SymbolId,
+) -> impl Iterator<Item = &'a SymbolId> {
+    let type_imports = if follow_type_imports {
+        graph.type_imports.get(symbol_id)
+    } else {
+        None
+    };
+    graph
+        .imports
+        .get(symbol_id)
+        .into_iter()
+        .chain(type_imports)
+        .flatten()
+}
+
+/// Symbols that were added, removed, or whose imports differ between graphs
+///
+/// This is the `changed_symbols` argument of [`ReachabilityAnalyzer::update`]
+/// when going from `old` to `new`. The result is sorted.
+pub fn changed_symbols(old: &SymbolGraph, new: &SymbolGraph) -> Vec<SymbolId> {
+    let edges_differ = |id: &SymbolId| {
+        old.imports.get(id) != new.imports.get(id)
+            || old.type_imports.get(id) != new.type_imports.get(id)
+    };
+    let mut changed: Vec<SymbolId> = old
+        .symbols
+        .keys()
+        .filter(|id| !new.symbols.contains_key(*id) || edges_differ(id))
+        .chain(
+            new.symbols
+                .keys()
+                .filter(|id| !old.symbols.contains_key(*id)),
+        )
+        .cloned()
+        .collect();
+    changed.sort();
+    changed
+}
+
 /// Identify dead code (unreachable symbols) in the symbol graph
 ///
 /// # Arguments
@@ -142,6 +358,95 @@ pub fn identify_dead_code(graph: &SymbolGraph, reachable: &HashSet<SymbolId>) ->
     dead_symbols
 }
 
+/// Group dead symbols that reference each other into clusters
+///
+/// Clusters are the connected components of the `imports` and `type_imports`
+/// edges between dead symbols, such as the two halves of an unused circular
+/// import. Symbols that are not linked to another dead symbol form no
+/// cluster. Each cluster is sorted, and clusters are ordered by their first ID.
+pub fn find_dead_clusters(graph: &SymbolGraph, dead_symbols: &[Symbol]) -> Vec<Vec<SymbolId>> {
+    let dead: HashSet<&SymbolId> = dead_symbols.iter().map(|s| &s.id).collect();
+
+    // Undirected adjacency restricted to dead symbols, over runtime and
+    // type-only imports
+    let mut neighbors: HashMap<&SymbolId, Vec<&SymbolId>> = HashMap::new();
+    for (from, dependencies) in graph.imports.iter().chain(&graph.type_imports) {
+        if !dead.contains(from) {
+            continue;
+        }
+        for to in dependencies
+            .iter()
+            .filter(|to| *to != from && dead.contains(to))
+        {
+            neighbors.entry(from).or_default().push(to);
+            neighbors.entry(to).or_default().push(from);
+        }
+    }
+
+    let mut seen: HashSet<&SymbolId> = HashSet::new();
+    let mut clusters = Vec::new();
+    for &start in neighbors.keys() {
+        if !seen.insert(start) {
+            continue;
+        }
+
+        let mut cluster = Vec::new();
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            cluster.push(id.clone());
+            for &next in &neighbors[id] {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        cluster.sort();
+        clusters.push(cluster);
+    }
+
+    clusters.sort();
+    clusters
+}
+
+/// Symbols imported by code in another file, whether or not that code is live
+///
+/// Both runtime and type-only imports count. Re-exports only forward use: a
+/// symbol re-exported by a barrel counts once something imports it through
+/// the barrel.
+pub fn find_internally_used(graph: &SymbolGraph) -> HashSet<SymbolId> {
+    let is_reexport = |id: &SymbolId| {
+        graph
+            .symbols
+            .get(id)
+            .is_some_and(|s| s.kind == SymbolKind::ReExport)
+    };
+
+    let mut stack: Vec<&SymbolId> = Vec::new();
+    for (from, dependencies) in graph.imports.iter().chain(&graph.type_imports) {
+        let Some(importer) = graph.symbols.get(from) else {
+            continue;
+        };
+        if importer.kind == SymbolKind::ReExport {
+            continue;
+        }
+        stack.extend(dependencies.iter().filter(|to| {
+            graph
+                .symbols
+                .get(*to)
+                .is_some_and(|s| s.path != importer.path)
+        }));
+    }
+
+    let mut used = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if used.insert(id.clone()) && is_reexport(id) {
+            stack.extend(graph.imports.get(id).into_iter().flatten());
+        }
+    }
+    used
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,8 +463,14 @@ mod tests {
             path: PathBuf::from(path),
             line_start: 1,
             line_end: 5,
+            byte_start: 0,
+            byte_end: 0,
             is_exported: false,
             is_test: false,
+            complexity: 0,
+            fingerprint: String::new(),
+            body_fingerprint: String::new(),
+            accessibility: None,
         }
     }
 
@@ -182,7 +493,12 @@ mod tests {
         SymbolGraph {
             symbols,
             imports,
+            type_imports: HashMap::new(),
             exports,
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         }
     }
 
@@ -230,7 +546,12 @@ mod tests {
         let graph = SymbolGraph {
             symbols,
             imports,
+            type_imports: HashMap::new(),
             exports,
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let mut analyzer = ReachabilityAnalyzer::new(graph);
@@ -304,7 +625,12 @@ mod tests {
         let graph = SymbolGraph {
             symbols,
             imports,
+            type_imports: HashMap::new(),
             exports,
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let mut analyzer = ReachabilityAnalyzer::new(graph.clone());
@@ -352,6 +678,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_analyze_cancelled() {
+        let graph = create_test_graph();
+        let mut analyzer = ReachabilityAnalyzer::new(graph);
+        let cancel = AtomicBool::new(true);
+
+        let result = analyzer.analyze_with_cancel(vec!["A".to_string()], Some(&cancel));
+        assert!(matches!(result, Err(ReachabilityError::Cancelled)));
+
+        // Unset flag behaves like a plain analyze
+        cancel.store(false, Ordering::Relaxed);
+        let reachable = analyzer
+            .analyze_with_cancel(vec!["A".to_string()], Some(&cancel))
+            .unwrap();
+        assert_eq!(reachable.len(), 3);
+    }
+
+    #[test]
+    fn test_path_to_chain() {
+        let graph = create_test_graph();
+        let mut analyzer = ReachabilityAnalyzer::new(graph);
+
+        analyzer
+            .analyze_with_paths(vec!["A".to_string()])
+            .expect("Analysis should succeed");
+
+        assert_eq!(
+            analyzer.path_to("C"),
+            Some(vec!["A".to_string(), "B".to_string(), "C".to_string()])
+        );
+        assert_eq!(analyzer.path_to("A"), Some(vec!["A".to_string()]));
+        assert_eq!(analyzer.path_to("D"), None, "D is unreachable");
+    }
+
+    #[test]
+    fn test_path_to_prefers_shortest() {
+        let mut graph = create_test_graph();
+        // A -> C shortcut alongside A -> B -> C
+        graph
+            .imports
+            .insert("A".to_string(), vec!["B".to_string(), "C".to_string()]);
+        let mut analyzer = ReachabilityAnalyzer::new(graph);
+
+        analyzer.analyze_with_paths(vec!["A".to_string()]).unwrap();
+
+        assert_eq!(
+            analyzer.path_to("C"),
+            Some(vec!["A".to_string(), "C".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_update_matches_full_analysis() {
+        let graph = create_test_graph();
+        let entry_points = vec!["A".to_string()];
+        let mut analyzer = ReachabilityAnalyzer::new(graph.clone());
+        analyzer.analyze(entry_points.clone()).unwrap();
+
+        // Move B's import from C to D
+        let mut moved = graph.clone();
+        moved.imports.insert("B".to_string(), vec!["D".to_string()]);
+        let changed = changed_symbols(&graph, &moved);
+        assert_eq!(changed, vec!["B".to_string()]);
+
+        let updated = analyzer.update(moved.clone(), &changed).unwrap();
+        let full = ReachabilityAnalyzer::new(moved.clone())
+            .analyze(entry_points.clone())
+            .unwrap();
+        assert_eq!(updated, full);
+        assert!(updated.contains("D"));
+        assert!(!updated.contains("C"));
+
+        // Cutting A off from a B <-> D cycle leaves neither reachable
+        let mut cut = moved.clone();
+        cut.imports.insert("D".to_string(), vec!["B".to_string()]);
+        cut.imports.remove("A");
+        let changed = changed_symbols(&moved, &cut);
+        assert_eq!(changed, vec!["A".to_string(), "D".to_string()]);
+
+        let updated = analyzer.update(cut.clone(), &changed).unwrap();
+        let full = ReachabilityAnalyzer::new(cut)
+            .analyze(entry_points)
+            .unwrap();
+        assert_eq!(updated, full);
+        assert_eq!(updated.len(), 1);
+    }
+
+    #[test]
+    fn test_update_requires_prior_analysis() {
+        let mut analyzer = ReachabilityAnalyzer::new(create_test_graph());
+        let result = analyzer.update(create_test_graph(), &[]);
+        assert!(matches!(result, Err(ReachabilityError::NoEntryPoints)));
+    }
+
     #[test]
     fn test_multiple_calls_to_analyze() {
         let graph = create_test_graph();
@@ -380,7 +800,12 @@ mod tests {
         let graph = SymbolGraph {
             symbols: HashMap::new(),
             imports: HashMap::new(),
+            type_imports: HashMap::new(),
             exports: HashMap::new(),
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let mut analyzer = ReachabilityAnalyzer::new(graph.clone());
@@ -418,7 +843,12 @@ mod tests {
         let graph = SymbolGraph {
             symbols,
             imports,
+            type_imports: HashMap::new(),
             exports,
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
         };
 
         let mut analyzer = ReachabilityAnalyzer::new(graph.clone());
@@ -436,4 +866,35 @@ mod tests {
         let dead = identify_dead_code(&graph, &reachable);
         assert_eq!(dead.len(), 0);
     }
+
+    #[test]
+    fn test_find_dead_clusters() {
+        let mut graph = create_test_graph();
+        // E and F import each other, B also imports E; D stays isolated
+        for (id, name) in [("E", "funcE"), ("F", "funcF")] {
+            graph
+                .symbols
+                .insert(id.to_string(), create_symbol(id, name, "cycle.ts"));
+        }
+        graph.imports.insert("E".to_string(), vec!["F".to_string()]);
+        graph.imports.insert("F".to_string(), vec!["E".to_string()]);
+        graph.imports.get_mut("B").unwrap().push("E".to_string());
+
+        let mut analyzer = ReachabilityAnalyzer::new(graph.clone());
+        let reachable = analyzer.analyze(vec!["C".to_string()]).unwrap();
+        let dead = identify_dead_code(&graph, &reachable);
+
+        // A -> B -> E <-> F are linked; the live C is not part of any cluster
+        assert_eq!(
+            find_dead_clusters(&graph, &dead),
+            vec![vec!["A", "B", "E", "F"]]
+        );
+
+        // A type-only import links D as well
+        graph.type_imports.insert("D".to_string(), vec!["A".to_string()]);
+        assert_eq!(
+            find_dead_clusters(&graph, &dead),
+            vec![vec!["A", "B", "D", "E", "F"]]
+        );
+    }
 }
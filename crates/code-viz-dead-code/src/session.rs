@@ -0,0 +1,208 @@
+//! Long-lived analysis sessions for servers and editor integrations.
+//!
+//! An `AnalysisSession` keeps the symbol graph of each directory it analyzed
+//! in memory between analyses. Each call rescans the directory and only
+//! rebuilds its graph when a source file was added, removed or modified
+//! since the last call, so repeated requests on an unchanged tree skip
+//! parsing entirely.
+
+use crate::symbol_graph::SymbolGraph;
+use crate::{
+    analyze_archive, analyze_graph, archive, build_confidence_overrides, build_graph_from_files,
+    build_keep_patterns, check_cancelled, collect_entry_points, no_files_result, read_sources,
+    read_templates, record_skipped_files, scan_files, AnalysisConfig, AnalysisError,
+    DeadCodeResult, SourceFindings,
+};
+use ahash::AHashMap as HashMap;
+use code_viz_core::scanner::ScanResult;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Modification time and size of a source file, used to detect changes
+pub(crate) type Fingerprint = (Option<SystemTime>, u64);
+
+/// Symbol graph of one directory and the files it was built from
+struct SessionGraph {
+    graph: Arc<SymbolGraph>,
+    fingerprints: HashMap<PathBuf, Fingerprint>,
+}
+
+/// Dead code analysis with symbol graphs cached in memory
+///
+/// Cloning is cheap and clones share the graphs, so a server can keep one
+/// session in its state and have every request reuse them. Graphs are held
+/// for as long as the session, regardless of `cache_mode`; the on-disk cache
+/// is not used.
+#[derive(Clone)]
+pub struct AnalysisSession {
+    config: AnalysisConfig,
+    graphs: Arc<Mutex<HashMap<PathBuf, SessionGraph>>>,
+    rebuilds: Arc<AtomicUsize>,
+}
+
+impl AnalysisSession {
+    /// Create a session analyzing with `config`; nothing is analyzed until
+    /// [`Self::analyze`]
+    pub fn new(config: AnalysisConfig) -> Self {
+        Self {
+            config,
+            graphs: Arc::default(),
+            rebuilds: Arc::default(),
+        }
+    }
+
+    /// Settings used by every analysis of this session
+    pub fn config(&self) -> &AnalysisConfig {
+        &self.config
+    }
+
+    /// Number of symbol graphs built so far, as opposed to reused
+    pub fn rebuilds(&self) -> usize {
+        self.rebuilds.load(Ordering::Relaxed)
+    }
+
+    /// Analyze `root`, reusing its symbol graph if no file changed
+    pub fn analyze(&self, root: &Path) -> Result<DeadCodeResult, AnalysisError> {
+        self.analyze_with_progress(root, None, &|_, _| {})
+    }
+
+    /// Like [`Self::analyze`], checking `cancel` and reporting progress as
+    /// [`crate::analyze_dead_code_with_progress`] does
+    pub fn analyze_with_progress(
+        &self,
+        root: &Path,
+        cancel: Option<&AtomicBool>,
+        progress: &dyn Fn(f32, &str),
+    ) -> Result<DeadCodeResult, AnalysisError> {
+        build_keep_patterns(&self.config.keep_patterns)?;
+        build_confidence_overrides(&self.config.confidence_overrides)?;
+        // Archive entries are not files that could be checked for changes
+        if archive::is_archive(root) {
+            return analyze_archive(root, &self.config, cancel, progress);
+        }
+
+        progress(0.0, "Scanning directory...");
+        let scan = scan_files(root, &self.config)?;
+        if scan.files.is_empty() {
+            self.graphs.lock().unwrap().remove(root);
+            progress(1.0, "No source files found");
+            return no_files_result(root, &scan);
+        }
+        let ScanResult { files, skipped, .. } = scan;
+        check_cancelled(cancel)?;
+        progress(
+            0.1,
+            &format!("Building symbol graph for {} files...", files.len()),
+        );
+
+        let fingerprints = fingerprint_files(&files);
+        let cached = self
+            .graphs
+            .lock()
+            .unwrap()
+            .get(root)
+            .filter(|cached| cached.fingerprints == fingerprints)
+            .map(|cached| Arc::clone(&cached.graph));
+        let graph = match cached {
+            Some(graph) => graph,
+            None => {
+                tracing::info!(file_count = files.len(), "Rebuilding symbol graph");
+                self.rebuilds.fetch_add(1, Ordering::Relaxed);
+                let graph = Arc::new(build_graph_from_files(&files, &self.config, cancel)?);
+                self.graphs.lock().unwrap().insert(
+                    root.to_path_buf(),
+                    SessionGraph {
+                        graph: Arc::clone(&graph),
+                        fingerprints,
+                    },
+                );
+                graph
+            }
+        };
+
+        let entry_points =
+            collect_entry_points(&graph, &self.config, root, &read_templates(&files))?;
+        let mut result = analyze_graph(
+            &graph,
+            root,
+            entry_points,
+            SourceFindings::scan(&read_sources(&files), &graph, &self.config)?,
+            &self.config,
+            cancel,
+            progress,
+        )?;
+        record_skipped_files(&mut result, skipped.len());
+        Ok(result)
+    }
+}
+
+pub(crate) fn fingerprint_files(files: &[PathBuf]) -> HashMap<PathBuf, Fingerprint> {
+    files
+        .iter()
+        .map(|path| {
+            let fingerprint = std::fs::metadata(path)
+                .map(|m| (m.modified().ok(), m.len()))
+                .unwrap_or((None, 0));
+            (path.clone(), fingerprint)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_session_reuses_graph_until_a_file_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("main.ts"),
+            "import { used } from './lib';\n\nfunction main() {\n    used();\n}\n\nmain();\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("lib.ts"),
+            "export function used() {}\n\nfunction unused() {}\n",
+        )
+        .unwrap();
+
+        let config = AnalysisConfig {
+            cache_mode: crate::CacheMode::Off,
+            ..Default::default()
+        };
+        let session = AnalysisSession::new(config);
+
+        let first = session.analyze(temp_dir.path()).unwrap();
+        // Clones share the graph, as the requests of a server would
+        let second = session.clone().analyze(temp_dir.path()).unwrap();
+        assert_eq!(session.rebuilds(), 1);
+        assert_eq!(first.summary.dead_functions, second.summary.dead_functions);
+
+        fs::write(
+            temp_dir.path().join("lib.ts"),
+            "export function used() {}\n\nfunction unused() {}\n\nfunction another() {}\n",
+        )
+        .unwrap();
+        let third = session.analyze(temp_dir.path()).unwrap();
+        assert_eq!(session.rebuilds(), 2);
+        assert_eq!(
+            third.summary.dead_functions,
+            first.summary.dead_functions + 1
+        );
+
+        // Each directory keeps its own graph
+        let other = TempDir::new().unwrap();
+        fs::write(
+            other.path().join("main.ts"),
+            "function main() {}\n\nmain();\n",
+        )
+        .unwrap();
+        session.analyze(other.path()).unwrap();
+        session.analyze(temp_dir.path()).unwrap();
+        assert_eq!(session.rebuilds(), 3);
+    }
+}
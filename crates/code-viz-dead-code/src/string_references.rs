@@ -0,0 +1,61 @@
+//! Symbol names spelled out in string literals.
+//!
+//! Dependency injection containers, routers and other reflection patterns look
+//! symbols up by name (`container.get('UserService')`), which the symbol graph
+//! cannot follow. A dead symbol whose exact name appears in a string literal
+//! anywhere in the codebase is therefore less certain to be dead. The symbol
+//! graph builder gathers these names into `SymbolGraph::string_literals`.
+
+use ahash::AHashSet as HashSet;
+use tree_sitter::Node;
+
+/// Add the contents of every string literal under `node` that could be a
+/// symbol name to `literals`
+///
+/// Only literals made of identifier characters are kept, so module paths and
+/// messages are left out.
+pub(crate) fn collect_string_literals(node: Node, source: &str, literals: &mut HashSet<String>) {
+    if node.kind() == "string" {
+        let content = node
+            .utf8_text(source.as_bytes())
+            .unwrap_or("")
+            .trim_matches(|c| c == '"' || c == '\'');
+        if is_identifier(content) {
+            literals.insert(content.to_string());
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_string_literals(child, source, literals);
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::symbol_graph::SymbolGraphBuilder;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_string_literals_keep_identifiers() {
+        let source = r#"import { x } from './payments';
+container.get('UserService');
+router.on("handleRefund", x);
+console.log('Refund failed: retry later');
+"#;
+        let graph = SymbolGraphBuilder::new()
+            .build_graph(vec![(PathBuf::from("app.ts"), source.to_string())])
+            .unwrap();
+
+        assert_eq!(graph.string_literals, vec!["UserService", "handleRefund"]);
+    }
+}
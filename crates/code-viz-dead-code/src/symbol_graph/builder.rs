@@ -1,21 +1,45 @@
 //! Symbol graph builder implementation.
 
-use super::extractors::{extract_symbol_name, is_symbol_exported, is_test_file};
-use super::queries::{get_import_query, get_symbol_query};
-use super::resolver::resolve_import_path;
+use super::extractors::{
+    accessibility_modifier, export_assigned_names, extract_symbol_name, is_symbol_exported,
+    is_test_file,
+};
+use super::queries::{
+    get_binding_query, get_import_query, get_require_binding_query, get_symbol_query,
+};
+use super::resolver::{is_unresolved_source_import, resolve_import_path, Manifests};
 use super::{GraphError, SymbolGraph};
-use crate::models::{Symbol, SymbolId, SymbolKind};
-use ahash::AHashMap as HashMap;
+use crate::members::parser_for_file;
+use crate::models::{FileTiming, Symbol, SymbolId, SymbolKind};
+use crate::string_references::collect_string_literals;
+use ahash::{AHashMap as HashMap, AHashSet as HashSet};
 use code_viz_core::parser::LanguageParser;
 use rayon::prelude::*;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tree_sitter::QueryCursor;
 
+/// A single `export { name as alias } from "./source"` binding
+pub(crate) struct ReExport {
+    /// Import source string of the export statement
+    pub source: String,
+
+    /// Name of the binding in the origin module
+    pub imported_name: String,
+
+    /// Graph node for the re-export, located in the barrel file
+    pub symbol: Symbol,
+}
+
 /// Builder for constructing symbol graphs
 pub struct SymbolGraphBuilder {
     graph: HashMap<SymbolId, Symbol>,
     dependencies: HashMap<SymbolId, Vec<SymbolId>>,
+    profile: Option<Vec<(PathBuf, Duration)>>,
+    extension_overrides: std::collections::HashMap<String, String>,
 }
 
 impl SymbolGraphBuilder {
@@ -24,9 +48,86 @@ impl SymbolGraphBuilder {
         Self {
             graph: HashMap::new(),
             dependencies: HashMap::new(),
+            profile: None,
+            extension_overrides: std::collections::HashMap::new(),
         }
     }
 
+    /// Parse files with these extensions as the mapped language, see
+    /// `AnalysisConfig::extension_overrides`
+    pub fn with_extension_overrides(
+        mut self,
+        extension_overrides: std::collections::HashMap<String, String>,
+    ) -> Self {
+        self.extension_overrides = extension_overrides;
+        self
+    }
+
+    /// Record how long each file takes to parse during [`Self::build_graph`]
+    pub fn with_profiling(mut self) -> Self {
+        self.profile = Some(Vec::new());
+        self
+    }
+
+    /// Per-file wall time of the last graph build, slowest first
+    ///
+    /// Returns `None` unless profiling was enabled with [`Self::with_profiling`].
+    pub fn take_profile(&mut self) -> Option<Vec<FileTiming>> {
+        let profile = std::mem::take(self.profile.as_mut()?);
+        Some(
+            profile
+                .into_iter()
+                .map(|(path, elapsed)| FileTiming {
+                    path,
+                    ms: elapsed.as_secs_f64() * 1000.0,
+                })
+                .collect(),
+        )
+    }
+
+    /// Add a symbol parsed by another frontend, replacing any with the same ID
+    pub fn add_symbol(&mut self, symbol: Symbol) -> &mut Self {
+        self.graph.insert(symbol.id.clone(), symbol);
+        self
+    }
+
+    /// Record that `from` depends on `to`
+    ///
+    /// Either end may be added later; edges are validated by [`Self::build`].
+    pub fn add_edge(&mut self, from: impl Into<SymbolId>, to: impl Into<SymbolId>) -> &mut Self {
+        self.dependencies
+            .entry(from.into())
+            .or_default()
+            .push(to.into());
+        self
+    }
+
+    /// Build a graph from the symbols and edges added so far, leaving the
+    /// builder empty
+    ///
+    /// Each file exports its symbols that have `is_exported` set.
+    ///
+    /// # Errors
+    /// Returns `GraphError::DanglingEdge` if an edge names a symbol that was
+    /// never added
+    pub fn build(&mut self) -> Result<SymbolGraph, GraphError> {
+        let symbols = std::mem::take(&mut self.graph);
+        let imports = std::mem::take(&mut self.dependencies);
+
+        let mut exports: HashMap<PathBuf, Vec<SymbolId>> = HashMap::new();
+        for symbol in symbols.values().filter(|s| s.is_exported) {
+            exports
+                .entry(symbol.path.clone())
+                .or_default()
+                .push(symbol.id.clone());
+        }
+        for ids in exports.values_mut() {
+            ids.sort();
+        }
+
+        SymbolGraph::from_symbols(symbols.into_values(), imports, exports)
+    }
+
     /// Extract symbols from a single file using Tree-sitter
     ///
     /// # Arguments
@@ -50,6 +151,8 @@ impl SymbolGraphBuilder {
 
         let mut symbols = Vec::new();
         let is_test = is_test_file(path);
+        let functions = parser.find_functions(&tree, source);
+        let assigned = export_assigned_names(tree.root_node(), source);
 
         // Get the appropriate query based on language
         let query = get_symbol_query(parser.language())?;
@@ -70,6 +173,12 @@ impl SymbolGraphBuilder {
                     "class" => SymbolKind::Class,
                     "method" => SymbolKind::Method,
                     "variable" => SymbolKind::Variable,
+                    "namespace" => SymbolKind::Namespace,
+                    "default" => match node.kind() {
+                        "arrow_function" => SymbolKind::ArrowFunction,
+                        "class" => SymbolKind::Class,
+                        _ => SymbolKind::Function,
+                    },
                     _ => continue,
                 };
 
@@ -80,7 +189,7 @@ impl SymbolGraphBuilder {
                 }
 
                 // Check if symbol is exported
-                let is_exported = is_symbol_exported(&node, source);
+                let is_exported = is_symbol_exported(&node, source, &assigned);
 
                 // Get line range
                 let start_point = node.start_position();
@@ -88,8 +197,22 @@ impl SymbolGraphBuilder {
                 let line_start = start_point.row + 1; // Convert to 1-indexed
                 let line_end = end_point.row + 1;
 
+                // Functions inside the symbol, so a class sums its methods
+                let complexity = functions
+                    .iter()
+                    .filter(|f| f.start_row >= start_point.row && f.end_row <= end_point.row)
+                    .map(|f| f.complexity)
+                    .sum();
+
                 // Create unique symbol ID
                 let id = format!("{}:{}:{}", path.display(), line_start, name);
+                let text = node.utf8_text(source.as_bytes()).unwrap_or("");
+                let fingerprint = crate::models::fingerprint(text);
+                let body_fingerprint = crate::models::body_fingerprint(text, &name);
+                let accessibility = match kind {
+                    SymbolKind::Method => accessibility_modifier(&node, source),
+                    _ => None,
+                };
 
                 symbols.push(Symbol {
                     id,
@@ -98,8 +221,14 @@ impl SymbolGraphBuilder {
                     path: path.to_path_buf(),
                     line_start,
                     line_end,
+                    byte_start: node.start_byte(),
+                    byte_end: node.end_byte(),
                     is_exported,
                     is_test,
+                    complexity,
+                    fingerprint,
+                    body_fingerprint,
+                    accessibility,
                 });
             }
         }
@@ -139,6 +268,10 @@ impl SymbolGraphBuilder {
 
         for m in matches {
             for capture in m.captures {
+                // Skip helper captures such as the `require` callee
+                if query.capture_names()[capture.index as usize] != "import_source" {
+                    continue;
+                }
                 let node = capture.node;
                 let import_source = node.utf8_text(source.as_bytes()).unwrap_or("");
                 if !import_source.is_empty() {
@@ -150,6 +283,361 @@ impl SymbolGraphBuilder {
         Ok(imports)
     }
 
+    /// Extract names bound by destructured CommonJS requires
+    ///
+    /// For `const { foo, bar: baz } = require("./x")` this returns
+    /// `("\"./x\"", ["foo", "bar"])`. Requires using a rest pattern are
+    /// skipped since they can reach any export.
+    ///
+    /// # Returns
+    /// List of (import source, required names) pairs
+    pub(crate) fn extract_require_bindings(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<Vec<(String, Vec<String>)>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut bindings = Vec::new();
+
+        let query = get_require_binding_query(parser.language())?;
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+
+        for m in matches {
+            let mut require_source = None;
+            let mut names = Vec::new();
+            let mut has_rest = false;
+
+            for capture in m.captures {
+                let node = capture.node;
+                match query.capture_names()[capture.index as usize].as_str() {
+                    "require_source" => {
+                        require_source = node.utf8_text(source.as_bytes()).ok();
+                    }
+                    "require_names" => {
+                        let mut pattern_cursor = node.walk();
+                        for child in node.named_children(&mut pattern_cursor) {
+                            let name_node = match child.kind() {
+                                "shorthand_property_identifier_pattern" => Some(child),
+                                "pair_pattern" => child.child_by_field_name("key"),
+                                "rest_pattern" => {
+                                    has_rest = true;
+                                    None
+                                }
+                                _ => None,
+                            };
+                            if let Some(text) =
+                                name_node.and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                            {
+                                names.push(text.to_string());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(require_source) = require_source {
+                if !has_rest && !names.is_empty() {
+                    bindings.push((require_source.to_string(), names));
+                }
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// Extract names bound by ES named imports
+    ///
+    /// For `import { foo, bar as baz } from "./x"` this returns
+    /// `("\"./x\"", ["foo", "bar"])`. Namespace and default imports are not
+    /// included since they do not name individual bindings.
+    ///
+    /// # Returns
+    /// List of (import source, imported names) pairs
+    pub(crate) fn extract_import_names(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<Vec<(String, Vec<String>)>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut bindings = Vec::new();
+
+        let query = get_binding_query(parser.language())?;
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+
+        for m in matches {
+            let mut import_source = None;
+            let mut names = Vec::new();
+
+            for capture in m.captures {
+                let node = capture.node;
+                match query.capture_names()[capture.index as usize].as_str() {
+                    "binding_source" => {
+                        import_source = node.utf8_text(source.as_bytes()).ok();
+                    }
+                    "import_names" => {
+                        let mut specifier_cursor = node.walk();
+                        for specifier in node.named_children(&mut specifier_cursor) {
+                            if let Some(text) = specifier
+                                .child_by_field_name("name")
+                                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                            {
+                                names.push(text.to_string());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(import_source) = import_source {
+                if !names.is_empty() {
+                    bindings.push((import_source.to_string(), names));
+                }
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// Extract import sources that are only imported for their types
+    ///
+    /// A source is type-only when every import of it is `import type { .. }`
+    /// or names only `type`-qualified specifiers (`import { type Foo }`).
+    /// Such imports are erased at runtime.
+    ///
+    /// # Returns
+    /// Import source strings, quotes included as in [`Self::extract_imports`]
+    pub(crate) fn extract_type_only_sources(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<HashSet<String>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut type_only = HashSet::new();
+        let mut value = HashSet::new();
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        for statement in root.named_children(&mut cursor) {
+            if statement.kind() != "import_statement" {
+                continue;
+            }
+            let Some(import_source) = statement
+                .child_by_field_name("source")
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+            else {
+                continue;
+            };
+
+            if is_type_only_import(statement) {
+                type_only.insert(import_source.to_string());
+            } else {
+                value.insert(import_source.to_string());
+            }
+        }
+
+        type_only.retain(|import_source| !value.contains(import_source));
+        Ok(type_only)
+    }
+
+    /// Extract re-export bindings (`export { foo as bar } from "./x"`)
+    ///
+    /// Each binding becomes its own `SymbolKind::ReExport` node, named after
+    /// the exported alias and located on the specifier's line, so an unused
+    /// re-export can be reported independently of the origin symbol.
+    ///
+    /// # Returns
+    /// List of re-exports found in the file
+    pub(crate) fn extract_reexports(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<Vec<ReExport>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut reexports = Vec::new();
+        let is_test = is_test_file(path);
+
+        let query = get_binding_query(parser.language())?;
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+
+        for m in matches {
+            let mut export_source = None;
+            let mut specifier = None;
+
+            for capture in m.captures {
+                match query.capture_names()[capture.index as usize].as_str() {
+                    "binding_source" => {
+                        export_source = capture.node.utf8_text(source.as_bytes()).ok();
+                    }
+                    "reexport" => specifier = Some(capture.node),
+                    _ => {}
+                }
+            }
+
+            let (Some(export_source), Some(specifier)) = (export_source, specifier) else {
+                continue;
+            };
+
+            let field_text = |field: &str| {
+                specifier
+                    .child_by_field_name(field)
+                    .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                    .map(str::to_string)
+            };
+            let Some(imported_name) = field_text("name") else {
+                continue;
+            };
+            let name = field_text("alias").unwrap_or_else(|| imported_name.clone());
+
+            let line = specifier.start_position().row + 1;
+            let id = format!("{}:{}:{}", path.display(), line, name);
+            let fingerprint = crate::models::fingerprint(&format!(
+                "{} from {}",
+                specifier.utf8_text(source.as_bytes()).unwrap_or(""),
+                export_source
+            ));
+
+            reexports.push(ReExport {
+                source: export_source.to_string(),
+                imported_name,
+                symbol: Symbol {
+                    id,
+                    name,
+                    kind: SymbolKind::ReExport,
+                    path: path.to_path_buf(),
+                    line_start: line,
+                    line_end: line,
+                    byte_start: specifier.start_byte(),
+                    byte_end: specifier.end_byte(),
+                    is_exported: true,
+                    is_test,
+                    complexity: 0,
+                    fingerprint,
+                    body_fingerprint: String::new(),
+                    accessibility: None,
+                },
+            });
+        }
+
+        Ok(reexports)
+    }
+
+    /// Extract wildcard re-exports (`export * from "./x"`)
+    ///
+    /// Namespace re-exports (`export * as ns from "./x"`) are not included.
+    ///
+    /// # Returns
+    /// List of (export source, line, byte range of the statement) tuples
+    pub(crate) fn extract_wildcard_reexports(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<Vec<(String, usize, Range<usize>)>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut wildcards = Vec::new();
+
+        let query = get_binding_query(parser.language())?;
+        let mut cursor = QueryCursor::new();
+        let matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+
+        for m in matches {
+            let mut export_source = None;
+            let mut statement = None;
+
+            for capture in m.captures {
+                match query.capture_names()[capture.index as usize].as_str() {
+                    "binding_source" => {
+                        export_source = capture.node.utf8_text(source.as_bytes()).ok();
+                    }
+                    "wildcard" => statement = capture.node.parent(),
+                    _ => {}
+                }
+            }
+
+            if let (Some(export_source), Some(statement)) = (export_source, statement) {
+                wildcards.push((
+                    export_source.to_string(),
+                    statement.start_position().row + 1,
+                    statement.byte_range(),
+                ));
+            }
+        }
+
+        Ok(wildcards)
+    }
+
+    /// Extract string literals that could name a symbol looked up by
+    /// reflection (see [`crate::string_references`])
+    pub(crate) fn extract_string_literals(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<HashSet<String>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut literals = HashSet::new();
+        collect_string_literals(tree.root_node(), source, &mut literals);
+        Ok(literals)
+    }
+
+    /// Extract top-level declarations exported under another name
+    ///
+    /// `export { formatValue as format }`, `exports.format = formatValue` and
+    /// `module.exports = { format: formatValue }` each yield
+    /// `("formatValue", "format")`, so importers of `format` reach
+    /// `formatValue`.
+    ///
+    /// # Returns
+    /// Local binding and exported name pairs
+    pub(crate) fn extract_export_aliases(
+        &self,
+        path: &Path,
+        source: &str,
+        parser: &dyn LanguageParser,
+    ) -> Result<Vec<(String, String)>, GraphError> {
+        let tree = parser.parse(source).map_err(|e| GraphError::ParseError {
+            file: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+
+        let mut aliases = export_assigned_names(tree.root_node(), source);
+        aliases.retain(|(local, exported)| local != exported);
+        Ok(aliases)
+    }
+
     /// Build complete symbol graph from multiple files
     ///
     /// # Arguments
@@ -161,6 +649,20 @@ impl SymbolGraphBuilder {
         &mut self,
         files: Vec<(PathBuf, String)>,
     ) -> Result<SymbolGraph, GraphError> {
+        self.build_graph_with_cancel(files, None)
+    }
+
+    /// Build symbol graph, checking `cancel` before each file in both passes
+    ///
+    /// # Errors
+    /// Returns `GraphError::Cancelled` once the flag is observed set
+    pub fn build_graph_with_cancel(
+        &mut self,
+        files: Vec<(PathBuf, String)>,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<SymbolGraph, GraphError> {
+        let is_cancelled = || cancel.is_some_and(|c| c.load(Ordering::Relaxed));
+
         // Pre-allocate capacity more accurately (estimate 20 symbols per file)
         let file_count = files.len();
         let estimated_symbols = file_count * 20;
@@ -168,6 +670,8 @@ impl SymbolGraphBuilder {
         // Build a map of available files for import resolution
         let available_files: HashMap<PathBuf, bool> =
             files.iter().map(|(path, _)| (path.clone(), true)).collect();
+        let manifests = Manifests::default();
+        let extension_overrides = &self.extension_overrides;
 
         // Use thread-safe containers for parallel processing
         let all_symbols = Mutex::new(HashMap::with_capacity(estimated_symbols));
@@ -177,21 +681,30 @@ impl SymbolGraphBuilder {
         let symbol_results: Vec<Result<_, GraphError>> = files
             .par_iter()
             .map(|(file_path, source)| {
+                if is_cancelled() {
+                    return Err(GraphError::Cancelled);
+                }
+                let started = Instant::now();
+
                 // Determine the parser based on file extension
-                let parser: Box<dyn LanguageParser> = if file_path
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|s| s == "ts" || s == "tsx")
-                    .unwrap_or(false)
-                {
-                    Box::new(code_viz_core::parser::TypeScriptParser)
-                } else {
-                    Box::new(code_viz_core::parser::JavaScriptParser)
-                };
+                let parser = parser_for_file(file_path, source, extension_overrides);
 
                 // Extract symbols (each thread gets its own builder)
                 let mut builder = SymbolGraphBuilder::new();
-                let symbols = builder.extract_symbols(file_path, source, parser.as_ref())?;
+                let mut symbols = builder.extract_symbols(file_path, source, parser.as_ref())?;
+                symbols.extend(
+                    builder
+                        .extract_reexports(file_path, source, parser.as_ref())?
+                        .into_iter()
+                        .map(|reexport| reexport.symbol),
+                );
+
+                let file_wildcards =
+                    builder.extract_wildcard_reexports(file_path, source, parser.as_ref())?;
+                let file_literals =
+                    builder.extract_string_literals(file_path, source, parser.as_ref())?;
+                let file_aliases =
+                    builder.extract_export_aliases(file_path, source, parser.as_ref())?;
 
                 // Track exported symbols per file
                 let mut file_exports = Vec::new();
@@ -201,13 +714,48 @@ impl SymbolGraphBuilder {
                     }
                 }
 
-                Ok((file_path.clone(), symbols, file_exports))
+                Ok((
+                    file_path.clone(),
+                    symbols,
+                    file_exports,
+                    file_wildcards,
+                    file_literals,
+                    file_aliases,
+                    started.elapsed(),
+                ))
             })
             .collect();
 
         // Collect results and handle errors
+        let mut wildcards = Vec::new();
+        let mut string_literals = HashSet::new();
+        let mut export_aliases: HashMap<PathBuf, Vec<(String, String)>> = HashMap::new();
+        let mut timings: HashMap<PathBuf, Duration> = HashMap::new();
         for result in symbol_results {
-            let (file_path, symbols, file_exports) = result?;
+            let (
+                file_path,
+                symbols,
+                file_exports,
+                file_wildcards,
+                file_literals,
+                file_aliases,
+                elapsed,
+            ) = result?;
+            string_literals.extend(file_literals);
+            if !file_aliases.is_empty() {
+                export_aliases.insert(file_path.clone(), file_aliases);
+            }
+            if self.profile.is_some() {
+                timings.insert(file_path.clone(), elapsed);
+            }
+
+            for (export_source, line, bytes) in file_wildcards {
+                if let Some(target) =
+                    resolve_import_path(&file_path, &export_source, &available_files, &manifests)
+                {
+                    wildcards.push((file_path.clone(), target, line, bytes));
+                }
+            }
 
             let mut all_symbols_guard = all_symbols.lock().unwrap();
             for symbol in symbols {
@@ -221,8 +769,30 @@ impl SymbolGraphBuilder {
         }
 
         // Unwrap the Mutex to get the final HashMaps
-        let all_symbols = all_symbols.into_inner().unwrap();
-        let exports = exports.into_inner().unwrap();
+        let mut all_symbols = all_symbols.into_inner().unwrap();
+        let mut exports = exports.into_inner().unwrap();
+
+        let wildcard_imports =
+            expand_wildcard_reexports(&wildcards, &mut all_symbols, &mut exports);
+
+        // Index symbols by file so resolving an import does not scan every symbol
+        let mut symbols_by_path: HashMap<&Path, Vec<&Symbol>> = HashMap::with_capacity(file_count);
+        for symbol in all_symbols.values() {
+            symbols_by_path
+                .entry(symbol.path.as_path())
+                .or_default()
+                .push(symbol);
+        }
+        let symbols_in = |path: &Path| symbols_by_path.get(path).into_iter().flatten().copied();
+        // Whether `symbol` can be imported as `name`, directly or by an alias
+        let exported_as = |symbol: &Symbol, name: &str| {
+            symbol.name == name
+                || export_aliases
+                    .get(&symbol.path)
+                    .into_iter()
+                    .flatten()
+                    .any(|(local, exported)| *local == symbol.name && exported == name)
+        };
 
         // Second pass: Build import relationships IN PARALLEL
         let imports = Mutex::new(HashMap::with_capacity(estimated_symbols));
@@ -230,54 +800,143 @@ impl SymbolGraphBuilder {
         let import_results: Vec<Result<_, GraphError>> = files
             .par_iter()
             .map(|(file_path, source)| {
-                let parser: Box<dyn LanguageParser> = if file_path
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|s| s == "ts" || s == "tsx")
-                    .unwrap_or(false)
-                {
-                    Box::new(code_viz_core::parser::TypeScriptParser)
-                } else {
-                    Box::new(code_viz_core::parser::JavaScriptParser)
-                };
+                if is_cancelled() {
+                    return Err(GraphError::Cancelled);
+                }
+                let started = Instant::now();
+
+                let parser = parser_for_file(file_path, source, extension_overrides);
 
                 // Extract imports
                 let builder = SymbolGraphBuilder::new();
                 let import_sources = builder.extract_imports(file_path, source, parser.as_ref())?;
+                let type_only_sources =
+                    builder.extract_type_only_sources(file_path, source, parser.as_ref())?;
+                let require_bindings: HashMap<String, Vec<String>> = builder
+                    .extract_require_bindings(file_path, source, parser.as_ref())?
+                    .into_iter()
+                    .collect();
+                let mut import_names: HashMap<String, Vec<String>> = HashMap::new();
+                for (import_source, names) in
+                    builder.extract_import_names(file_path, source, parser.as_ref())?
+                {
+                    import_names.entry(import_source).or_default().extend(names);
+                }
 
                 // Collect import relationships for this file
                 let mut file_imports: Vec<(SymbolId, Vec<SymbolId>)> = Vec::new();
+                let mut file_type_imports: Vec<(SymbolId, Vec<SymbolId>)> = Vec::new();
+                let mut file_unresolved: Vec<String> = Vec::new();
+
+                // Each re-export depends on the symbol it forwards
+                for reexport in builder.extract_reexports(file_path, source, parser.as_ref())? {
+                    if let Some(resolved_path) = resolve_import_path(
+                        file_path,
+                        &reexport.source,
+                        &available_files,
+                        &manifests,
+                    ) {
+                        let origins: Vec<SymbolId> = symbols_in(&resolved_path)
+                            .filter(|s| exported_as(s, &reexport.imported_name))
+                            .map(|s| s.id.clone())
+                            .collect();
+                        if !origins.is_empty() {
+                            file_imports.push((reexport.symbol.id, origins));
+                        }
+                    }
+                }
 
                 // Resolve import paths to actual files
                 for import_source in import_sources {
-                    if let Some(resolved_path) =
-                        resolve_import_path(file_path, &import_source, &available_files)
-                    {
-                        // Find exported symbols from the imported file
-                        if let Some(exported_symbols) = exports.get(&resolved_path) {
+                    let resolved = resolve_import_path(
+                        file_path,
+                        &import_source,
+                        &available_files,
+                        &manifests,
+                    );
+                    if resolved.is_none() && is_unresolved_source_import(&import_source) {
+                        file_unresolved.push(
+                            import_source
+                                .trim_matches(|c| c == '"' || c == '\'')
+                                .to_string(),
+                        );
+                    }
+                    if let Some(resolved_path) = resolved {
+                        // Destructured requires depend on the named symbols only, since
+                        // CommonJS modules rarely mark their symbols with `export`
+                        let named_symbols: Vec<SymbolId> = require_bindings
+                            .get(&import_source)
+                            .map(|names| {
+                                symbols_in(&resolved_path)
+                                    .filter(|s| names.iter().any(|name| exported_as(s, name)))
+                                    .map(|s| s.id.clone())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+
+                        // Otherwise, find exported symbols from the imported file. Re-exports
+                        // are only reached when named by the import, so unused barrel entries
+                        // stay unreachable.
+                        let target_symbols = if !named_symbols.is_empty() {
+                            Some(named_symbols)
+                        } else {
+                            let names = import_names.get(&import_source);
+                            exports.get(&resolved_path).map(|ids| {
+                                ids.iter()
+                                    .filter(|id| {
+                                        all_symbols.get(*id).is_none_or(|s| {
+                                            s.kind != SymbolKind::ReExport
+                                                || names.is_none_or(|n| n.contains(&s.name))
+                                        })
+                                    })
+                                    .cloned()
+                                    .collect()
+                            })
+                        };
+
+                        if let Some(target_symbols) = target_symbols {
                             // Get all symbols in the current file that could depend on these imports
-                            let file_symbols: Vec<SymbolId> = all_symbols
-                                .values()
-                                .filter(|s| s.path == *file_path)
+                            let file_symbols: Vec<SymbolId> = symbols_in(file_path)
+                                .filter(|s| s.kind != SymbolKind::ReExport)
                                 .map(|s| s.id.clone())
                                 .collect();
 
+                            // Type-only imports are erased at runtime, so they get their own edges
+                            let edges = if type_only_sources.contains(&import_source) {
+                                &mut file_type_imports
+                            } else {
+                                &mut file_imports
+                            };
+
                             // For simplicity, mark all symbols in the importing file as depending
-                            // on all exported symbols from the imported file
+                            // on all target symbols from the imported file
                             for symbol_id in file_symbols {
-                                file_imports.push((symbol_id, exported_symbols.clone()));
+                                edges.push((symbol_id, target_symbols.clone()));
                             }
                         }
                     }
                 }
 
-                Ok(file_imports)
+                Ok((
+                    file_imports,
+                    file_type_imports,
+                    file_unresolved,
+                    started.elapsed(),
+                ))
             })
             .collect();
 
         // Collect import results
-        for result in import_results {
-            let file_imports = result?;
+        let mut type_imports: HashMap<SymbolId, Vec<SymbolId>> = HashMap::new();
+        let mut unresolved_imports = Vec::new();
+        for ((file_path, _), result) in files.iter().zip(import_results) {
+            let (file_imports, file_type_imports, file_unresolved, elapsed) = result?;
+            for import_source in file_unresolved {
+                unresolved_imports.push((file_path.clone(), import_source));
+            }
+            if self.profile.is_some() {
+                *timings.entry(file_path.clone()).or_default() += elapsed;
+            }
             let mut imports_guard = imports.lock().unwrap();
             for (symbol_id, deps) in file_imports {
                 imports_guard
@@ -285,18 +944,160 @@ impl SymbolGraphBuilder {
                     .or_insert_with(Vec::new)
                     .extend(deps);
             }
+            for (symbol_id, deps) in file_type_imports {
+                type_imports.entry(symbol_id).or_default().extend(deps);
+            }
         }
 
-        let imports = imports.into_inner().unwrap();
+        let mut wildcard_reexports: Vec<SymbolId> =
+            wildcard_imports.iter().map(|(id, _)| id.clone()).collect();
+        wildcard_reexports.sort();
+        let mut imports = imports.into_inner().unwrap();
+        for (symbol_id, deps) in wildcard_imports {
+            imports.entry(symbol_id).or_default().extend(deps);
+        }
+
+        let mut string_literals: Vec<String> = string_literals.into_iter().collect();
+        string_literals.sort();
+
+        if let Some(profile) = &mut self.profile {
+            *profile = timings.into_iter().collect();
+            profile.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        }
 
         Ok(SymbolGraph {
             symbols: all_symbols,
             imports,
+            type_imports,
             exports,
+            manifest_warnings: manifests
+                .take_errors()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            unresolved_imports,
+            wildcard_reexports,
+            string_literals,
         })
     }
 }
 
+/// Expand each `export * from` into one re-export per name the target exports
+///
+/// The synthesized `SymbolKind::ReExport` nodes span the wildcard statement in
+/// the barrel and are added to its export surface, so importers naming them
+/// resolve exactly as they would for `export { name } from`. Names the barrel
+/// already exports take precedence, and expansion repeats until no barrel
+/// gains a name, so chained barrels forward everything they reach. The
+/// default export is not forwarded, as in JavaScript.
+///
+/// # Returns
+/// Edges from each synthesized re-export to the symbol it forwards
+fn expand_wildcard_reexports(
+    wildcards: &[(PathBuf, PathBuf, usize, Range<usize>)],
+    symbols: &mut HashMap<SymbolId, Symbol>,
+    exports: &mut HashMap<PathBuf, Vec<SymbolId>>,
+) -> Vec<(SymbolId, Vec<SymbolId>)> {
+    let mut edges = Vec::new();
+
+    loop {
+        let mut expanded = false;
+
+        for (barrel, target, line, bytes) in wildcards {
+            let Some(target_exports) = exports.get(target).cloned() else {
+                continue;
+            };
+            let mut exported_names: HashSet<String> = exports
+                .get(barrel)
+                .into_iter()
+                .flatten()
+                .filter_map(|id| symbols.get(id).map(|s| s.name.clone()))
+                .collect();
+
+            for origin_id in target_exports {
+                // Class and enum members are exported with their parent, not by name
+                let Some(name) = symbols
+                    .get(&origin_id)
+                    .filter(|s| !matches!(s.kind, SymbolKind::Method | SymbolKind::Member))
+                    .map(|s| s.name.clone())
+                else {
+                    continue;
+                };
+                if name == "default" || !exported_names.insert(name.clone()) {
+                    continue;
+                }
+
+                let id = format!("{}:{}:{}", barrel.display(), line, name);
+                let fingerprint = crate::models::fingerprint(&name);
+                symbols.insert(
+                    id.clone(),
+                    Symbol {
+                        id: id.clone(),
+                        name,
+                        kind: SymbolKind::ReExport,
+                        path: barrel.clone(),
+                        line_start: *line,
+                        line_end: *line,
+                        byte_start: bytes.start,
+                        byte_end: bytes.end,
+                        is_exported: true,
+                        is_test: is_test_file(barrel),
+                        complexity: 0,
+                        fingerprint,
+                        body_fingerprint: String::new(),
+                        accessibility: None,
+                    },
+                );
+                exports.entry(barrel.clone()).or_default().push(id.clone());
+                edges.push((id, vec![origin_id]));
+                expanded = true;
+            }
+        }
+
+        if !expanded {
+            return edges;
+        }
+    }
+}
+
+/// Whether an import statement only brings in types
+///
+/// True for `import type { .. }` and for named imports whose specifiers are
+/// all `type`-qualified; default and namespace imports are values.
+fn is_type_only_import(statement: tree_sitter::Node) -> bool {
+    fn is_type_keyword(node: tree_sitter::Node) -> bool {
+        !node.is_named() && node.kind() == "type"
+    }
+
+    let mut cursor = statement.walk();
+    let children: Vec<_> = statement.children(&mut cursor).collect();
+    if children.iter().any(|&child| is_type_keyword(child)) {
+        return true;
+    }
+
+    let Some(clause) = children.iter().find(|c| c.kind() == "import_clause") else {
+        return false;
+    };
+    let mut clause_cursor = clause.walk();
+    let clause_children: Vec<_> = clause.named_children(&mut clause_cursor).collect();
+    let [named_imports] = clause_children.as_slice() else {
+        return false;
+    };
+    if named_imports.kind() != "named_imports" {
+        return false;
+    }
+
+    let mut specifier_cursor = named_imports.walk();
+    let specifiers: Vec<_> = named_imports
+        .named_children(&mut specifier_cursor)
+        .filter(|n| n.kind() == "import_specifier")
+        .collect();
+    !specifiers.is_empty()
+        && specifiers
+            .iter()
+            .all(|specifier| specifier.child(0).is_some_and(is_type_keyword))
+}
+
 impl Default for SymbolGraphBuilder {
     fn default() -> Self {
         Self::new()
@@ -48,41 +48,229 @@ pub(super) fn extract_symbol_name(node: &tree_sitter::Node, source: &str, kind:
                 }
             }
         }
+        "namespace" => {
+            // For internal_module and module, the query only matches identifier names
+            if let Some(name) = node.child_by_field_name("name") {
+                return name.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+            }
+        }
+        "default" => {
+            // Anonymous `export default` values are imported as `default`
+            return "default".to_string();
+        }
         _ => {}
     }
 
     String::new()
 }
 
+/// Accessibility modifier (`private`, `protected` or `public`) written on a
+/// class member
+pub(super) fn accessibility_modifier(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let modifier = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "accessibility_modifier")?;
+    modifier
+        .utf8_text(source.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
+
 /// Check if a symbol is exported by examining parent nodes
-pub(super) fn is_symbol_exported(node: &tree_sitter::Node, source: &str) -> bool {
+///
+/// A symbol inside a namespace is only exported if it and every enclosing
+/// namespace are. Top-level declarations bound in `assigned` (see
+/// [`export_assigned_names`]) count as exported.
+pub(super) fn is_symbol_exported(
+    node: &tree_sitter::Node,
+    source: &str,
+    assigned: &[(String, String)],
+) -> bool {
     let mut current = *node;
+    let mut exported = false;
 
     // Walk up the tree to find export declarations
     while let Some(parent) = current.parent() {
-        let kind = parent.kind();
-
-        // Check for export declarations
-        if kind == "export_statement" {
-            return true;
-        }
-
-        // Check for "export default"
-        if kind == "export_statement" {
-            let text = parent.utf8_text(source.as_bytes()).unwrap_or("");
-            if text.starts_with("export default") || text.starts_with("export {") {
-                return true;
+        match parent.kind() {
+            "export_statement" => exported = true,
+            "internal_module" | "module" => {
+                if !exported {
+                    return false;
+                }
+                // The namespace itself must be exported too
+                exported = false;
+            }
+            "program" if !exported => {
+                exported = declared_name(current, source)
+                    .is_some_and(|name| assigned.iter().any(|(local, _)| local == name));
             }
+            _ => {}
         }
 
         current = parent;
     }
 
-    false
+    exported
+}
+
+/// Top-level declarations made public by assignment or an export clause
+/// rather than an `export` declaration
+///
+/// Returns each local binding with the name it is exported as. Covers
+/// `export { foo as bar }`, `export = Foo` and CommonJS exports. Assigning an
+/// identifier as the whole module (`module.exports = Foo`) exports it under
+/// its own name; `module.exports = { foo, bar: baz }` exports `foo` as foo and
+/// `baz` as bar; a named assignment (`exports.foo = bar` or
+/// `module.exports.foo = bar`) exports `bar` as foo. Only identifiers count,
+/// so inline functions such as `exports.foo = () => 1` add nothing.
+pub(super) fn export_assigned_names(
+    root: tree_sitter::Node,
+    source: &str,
+) -> Vec<(String, String)> {
+    let mut names = Vec::new();
+    let mut cursor = root.walk();
+    for statement in root.named_children(&mut cursor) {
+        match statement.kind() {
+            // `export { foo } from "./x"` re-exports another module's binding
+            "export_statement" if statement.child_by_field_name("source").is_none() => {
+                let mut cursor = statement.walk();
+                let is_assignment = statement.children(&mut cursor).any(|c| c.kind() == "=");
+                let Some(value) = statement.named_child(0) else {
+                    continue;
+                };
+                if is_assignment {
+                    names.extend(identifier_text(value, source).map(|name| (name.clone(), name)));
+                } else if value.kind() == "export_clause" {
+                    names.extend(export_clause_bindings(value, source));
+                }
+            }
+            "expression_statement" => {
+                let Some(assignment) = statement
+                    .named_child(0)
+                    .filter(|e| e.kind() == "assignment_expression")
+                else {
+                    continue;
+                };
+                let (Some(left), Some(right)) = (
+                    assignment.child_by_field_name("left"),
+                    assignment.child_by_field_name("right"),
+                ) else {
+                    continue;
+                };
+
+                if is_module_exports(left, source) {
+                    if right.kind() == "object" {
+                        names.extend(object_bindings(right, source));
+                    } else {
+                        names.extend(
+                            identifier_text(right, source).map(|name| (name.clone(), name)),
+                        );
+                    }
+                } else if left.kind() == "member_expression"
+                    && left.child_by_field_name("object").is_some_and(|object| {
+                        is_module_exports(object, source)
+                            || identifier_text(object, source).as_deref() == Some("exports")
+                    })
+                {
+                    let property = left
+                        .child_by_field_name("property")
+                        .and_then(|property| property.utf8_text(source.as_bytes()).ok());
+                    if let (Some(local), Some(exported)) =
+                        (identifier_text(right, source), property)
+                    {
+                        names.push((local, exported.to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Whether `node` is the `module.exports` member expression
+fn is_module_exports(node: tree_sitter::Node, source: &str) -> bool {
+    node.kind() == "member_expression"
+        && node
+            .child_by_field_name("object")
+            .is_some_and(|object| identifier_text(object, source).as_deref() == Some("module"))
+        && node
+            .child_by_field_name("property")
+            .and_then(|property| property.utf8_text(source.as_bytes()).ok())
+            == Some("exports")
+}
+
+/// Identifiers bound by an object literal with their keys: `{ foo, bar: baz }`
+/// yields foo as foo and baz as bar
+fn object_bindings(object: tree_sitter::Node, source: &str) -> Vec<(String, String)> {
+    let mut cursor = object.walk();
+    object
+        .named_children(&mut cursor)
+        .filter_map(|property| match property.kind() {
+            "shorthand_property_identifier" => {
+                let name = property.utf8_text(source.as_bytes()).ok()?.to_string();
+                Some((name.clone(), name))
+            }
+            "pair" => {
+                let local = identifier_text(property.child_by_field_name("value")?, source)?;
+                let key = property.child_by_field_name("key")?;
+                let key = key
+                    .utf8_text(source.as_bytes())
+                    .ok()?
+                    .trim_matches(|c| c == '"' || c == '\'');
+                Some((local, key.to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Local names of `export { foo, bar as baz }` with the names they are
+/// exported as
+fn export_clause_bindings(clause: tree_sitter::Node, source: &str) -> Vec<(String, String)> {
+    let mut cursor = clause.walk();
+    clause
+        .named_children(&mut cursor)
+        .filter(|specifier| specifier.kind() == "export_specifier")
+        .filter_map(|specifier| {
+            let local = specifier
+                .child_by_field_name("name")?
+                .utf8_text(source.as_bytes())
+                .ok()?;
+            let exported = specifier
+                .child_by_field_name("alias")
+                .and_then(|alias| alias.utf8_text(source.as_bytes()).ok())
+                .unwrap_or(local);
+            Some((local.to_string(), exported.to_string()))
+        })
+        .collect()
+}
+
+fn identifier_text(node: tree_sitter::Node, source: &str) -> Option<String> {
+    (node.kind() == "identifier")
+        .then(|| node.utf8_text(source.as_bytes()).ok())
+        .flatten()
+        .map(str::to_string)
+}
+
+/// Name declared by a top-level statement, if it declares a single one
+fn declared_name<'a>(statement: tree_sitter::Node, source: &'a str) -> Option<&'a str> {
+    let declaration = match statement.kind() {
+        // `namespace Foo {}` parses as an expression statement
+        "expression_statement" | "lexical_declaration" | "variable_declaration" => {
+            statement.named_child(0)?
+        }
+        _ => statement,
+    };
+    declaration
+        .child_by_field_name("name")?
+        .utf8_text(source.as_bytes())
+        .ok()
 }
 
 /// Check if a file is a test file based on its path
-pub(super) fn is_test_file(path: &Path) -> bool {
+pub(crate) fn is_test_file(path: &Path) -> bool {
     let path_str = path.to_string_lossy();
     path_str.contains(".test.")
         || path_str.contains(".spec.")
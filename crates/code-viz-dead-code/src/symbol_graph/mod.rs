@@ -4,19 +4,29 @@
 //! from source files using Tree-sitter and building a dependency graph showing
 //! import/export relationships.
 
+#[cfg(not(target_arch = "wasm32"))]
 mod builder;
+#[cfg(not(target_arch = "wasm32"))]
 mod extractors;
+#[cfg(not(target_arch = "wasm32"))]
 mod queries;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod references;
 mod resolver;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(not(target_arch = "wasm32"))]
 pub use builder::SymbolGraphBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use extractors::is_test_file;
+pub use resolver::ManifestError;
 
-use crate::models::{Symbol, SymbolId};
+use crate::models::{relative_id, Symbol, SymbolId};
 use ahash::AHashMap as HashMap;
-use std::path::PathBuf;
+use code_viz_core::models::relative_path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Error type for symbol graph operations
@@ -38,17 +48,177 @@ pub enum GraphError {
     /// I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// Graph construction was cancelled via the cancellation flag
+    #[error("Symbol graph construction cancelled")]
+    Cancelled,
+
+    /// An import edge names a symbol that is not in the graph
+    #[error("Dangling edge from {from} to unknown symbol {to}")]
+    DanglingEdge {
+        /// Importing symbol
+        from: SymbolId,
+        /// Missing symbol
+        to: SymbolId,
+    },
+
+    /// An export list names a symbol that is not in the graph
+    #[error("Unknown symbol {symbol} exported from {}", file.display())]
+    UnknownExport {
+        /// Exporting file
+        file: PathBuf,
+        /// Missing symbol
+        symbol: SymbolId,
+    },
 }
 
 /// Symbol graph containing all symbols and their relationships
+///
+/// Maps serialize with their keys in order, so the same graph always yields
+/// the same JSON.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SymbolGraph {
     /// All symbols indexed by their ID
+    #[serde(serialize_with = "sorted_map")]
     pub symbols: HashMap<SymbolId, Symbol>,
 
     /// Import relationships: symbol -> list of symbols it imports/depends on
+    #[serde(serialize_with = "sorted_map")]
     pub imports: HashMap<SymbolId, Vec<SymbolId>>,
 
+    /// Type-only import relationships (`import type`), erased at runtime
+    #[serde(default, serialize_with = "sorted_map")]
+    pub type_imports: HashMap<SymbolId, Vec<SymbolId>>,
+
     /// Exported symbols per file: file path -> list of exported symbol IDs
+    #[serde(serialize_with = "sorted_map")]
     pub exports: HashMap<PathBuf, Vec<SymbolId>>,
+
+    /// `tsconfig.json` and `package.json` files skipped during import
+    /// resolution because they could not be read or parsed
+    #[serde(default)]
+    pub manifest_warnings: Vec<String>,
+
+    /// Relative imports of source modules that match no analyzed file, as
+    /// importing file and import source, in file order
+    #[serde(default)]
+    pub unresolved_imports: Vec<(PathBuf, String)>,
+
+    /// Re-exports synthesized for the names an `export * from` forwards,
+    /// sorted; they are not written in the barrel by name
+    #[serde(default)]
+    pub wildcard_reexports: Vec<SymbolId>,
+
+    /// String literals that could name a symbol looked up by reflection,
+    /// sorted (see [`crate::string_references`])
+    #[serde(default)]
+    pub string_literals: Vec<String>,
+}
+
+/// Serialize a hash map as a map with sorted keys
+fn sorted_map<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord + serde::Serialize,
+    V: serde::Serialize,
+    S: serde::Serializer,
+{
+    serializer.collect_map(map.iter().collect::<std::collections::BTreeMap<_, _>>())
+}
+
+impl SymbolGraph {
+    /// Build a graph from symbols parsed outside of Tree-sitter
+    ///
+    /// Lets other frontends reuse reachability and dead code scoring for
+    /// languages this crate does not parse. All import edges are runtime
+    /// edges; `type_imports` is left empty.
+    ///
+    /// # Errors
+    /// Returns `GraphError::DanglingEdge` if an import names an unknown
+    /// symbol on either end, or `GraphError::UnknownExport` if an export list
+    /// does.
+    pub fn from_symbols(
+        symbols: impl IntoIterator<Item = Symbol>,
+        imports: HashMap<SymbolId, Vec<SymbolId>>,
+        exports: HashMap<PathBuf, Vec<SymbolId>>,
+    ) -> Result<Self, GraphError> {
+        let symbols: HashMap<SymbolId, Symbol> = symbols
+            .into_iter()
+            .map(|symbol| (symbol.id.clone(), symbol))
+            .collect();
+
+        // Sorted so the reported edge does not depend on hash order
+        let mut edges: Vec<(&SymbolId, &Vec<SymbolId>)> = imports.iter().collect();
+        edges.sort_by(|a, b| a.0.cmp(b.0));
+        for (from, deps) in edges {
+            let missing = std::iter::once(from)
+                .chain(deps)
+                .find(|id| !symbols.contains_key(*id));
+            if let Some(to) = missing {
+                return Err(GraphError::DanglingEdge {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        let mut files: Vec<(&PathBuf, &Vec<SymbolId>)> = exports.iter().collect();
+        files.sort_by(|a, b| a.0.cmp(b.0));
+        for (file, ids) in files {
+            if let Some(symbol) = ids.iter().find(|id| !symbols.contains_key(*id)) {
+                return Err(GraphError::UnknownExport {
+                    file: file.clone(),
+                    symbol: symbol.clone(),
+                });
+            }
+        }
+
+        Ok(Self {
+            symbols,
+            imports,
+            type_imports: HashMap::new(),
+            exports,
+            manifest_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            wildcard_reexports: Vec::new(),
+            string_literals: Vec::new(),
+        })
+    }
+
+    /// Whether `id` is a re-export synthesized from an `export * from`
+    pub fn is_wildcard_reexport(&self, id: &SymbolId) -> bool {
+        self.wildcard_reexports.binary_search(id).is_ok()
+    }
+
+    /// Rewrite every symbol ID, symbol path and export key relative to `root`
+    ///
+    /// Paths outside `root` are left unchanged.
+    pub fn strip_root(&mut self, root: &Path) {
+        let relative = |ids: Vec<SymbolId>| -> Vec<SymbolId> {
+            ids.iter().map(|id| relative_id(id, root)).collect()
+        };
+
+        self.symbols = std::mem::take(&mut self.symbols)
+            .into_values()
+            .map(|mut symbol| {
+                symbol.id = relative_id(&symbol.id, root);
+                symbol.path = relative_path(&symbol.path, root);
+                (symbol.id.clone(), symbol)
+            })
+            .collect();
+        for edges in [&mut self.imports, &mut self.type_imports] {
+            *edges = std::mem::take(edges)
+                .into_iter()
+                .map(|(from, deps)| (relative_id(&from, root), relative(deps)))
+                .collect();
+        }
+        self.exports = std::mem::take(&mut self.exports)
+            .into_iter()
+            .map(|(file, ids)| (relative_path(&file, root), relative(ids)))
+            .collect();
+        for (importer, _) in &mut self.unresolved_imports {
+            *importer = relative_path(importer, root);
+        }
+        self.wildcard_reexports = relative(std::mem::take(&mut self.wildcard_reexports));
+        self.wildcard_reexports.sort();
+    }
 }
@@ -7,45 +7,65 @@ use tree_sitter::Query;
 
 /// Get the Tree-sitter query for extracting symbols from a specific language
 pub(super) fn get_symbol_query(language: &str) -> Result<&'static Query, GraphError> {
+    const TS_PATTERN: &str = r#"
+        (function_declaration) @function
+        (lexical_declaration
+            (variable_declarator
+                value: (arrow_function))) @arrow
+        (variable_declaration
+            (variable_declarator
+                value: (arrow_function))) @arrow
+        (class_declaration) @class
+        (method_definition) @method
+        (export_statement
+            value: [
+                (function_expression)
+                (generator_function)
+                (arrow_function)
+                (class)
+            ] @default)
+        (internal_module name: (identifier)) @namespace
+        (module name: (identifier)) @namespace
+    "#;
+    const JS_PATTERN: &str = r#"
+        (function_declaration) @function
+        (lexical_declaration
+            (variable_declarator
+                value: (arrow_function))) @arrow
+        (variable_declaration
+            (variable_declarator
+                value: (arrow_function))) @arrow
+        (class_declaration) @class
+        (method_definition) @method
+        (export_statement
+            value: [
+                (function_expression)
+                (generator_function)
+                (arrow_function)
+                (class)
+            ] @default)
+    "#;
+
     match language {
-        "typescript" | "tsx" => {
+        "typescript" => {
             static TS_QUERY: OnceLock<Query> = OnceLock::new();
             Ok(TS_QUERY.get_or_init(|| {
-                Query::new(
-                    tree_sitter_typescript::language_typescript(),
-                    r#"
-                    (function_declaration) @function
-                    (lexical_declaration
-                        (variable_declarator
-                            value: (arrow_function))) @arrow
-                    (variable_declaration
-                        (variable_declarator
-                            value: (arrow_function))) @arrow
-                    (class_declaration) @class
-                    (method_definition) @method
-                    "#,
-                )
-                .expect("Invalid TypeScript symbol query")
+                Query::new(tree_sitter_typescript::language_typescript(), TS_PATTERN)
+                    .expect("Invalid TypeScript symbol query")
+            }))
+        }
+        "tsx" => {
+            static TSX_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(TSX_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_typescript::language_tsx(), TS_PATTERN)
+                    .expect("Invalid TSX symbol query")
             }))
         }
         "javascript" | "jsx" => {
             static JS_QUERY: OnceLock<Query> = OnceLock::new();
             Ok(JS_QUERY.get_or_init(|| {
-                Query::new(
-                    tree_sitter_javascript::language(),
-                    r#"
-                    (function_declaration) @function
-                    (lexical_declaration
-                        (variable_declarator
-                            value: (arrow_function))) @arrow
-                    (variable_declaration
-                        (variable_declarator
-                            value: (arrow_function))) @arrow
-                    (class_declaration) @class
-                    (method_definition) @method
-                    "#,
-                )
-                .expect("Invalid JavaScript symbol query")
+                Query::new(tree_sitter_javascript::language(), JS_PATTERN)
+                    .expect("Invalid JavaScript symbol query")
             }))
         }
         _ => Err(GraphError::ParseError {
@@ -57,31 +77,136 @@ pub(super) fn get_symbol_query(language: &str) -> Result<&'static Query, GraphEr
 
 /// Get the Tree-sitter query for extracting imports from a specific language
 pub(super) fn get_import_query(language: &str) -> Result<&'static Query, GraphError> {
+    const TS_PATTERN: &str = r#"
+        (import_statement
+            source: (string) @import_source)
+        (import_require_clause
+            source: (string) @import_source)
+        (call_expression
+            function: (identifier) @require_fn
+            arguments: (arguments . (string) @import_source)
+            (#eq? @require_fn "require"))
+    "#;
+    const JS_PATTERN: &str = r#"
+        (import_statement
+            source: (string) @import_source)
+        (call_expression
+            function: (identifier) @require_fn
+            arguments: (arguments . (string) @import_source)
+            (#eq? @require_fn "require"))
+    "#;
+
+    match language {
+        "typescript" => {
+            static TS_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(TS_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_typescript::language_typescript(), TS_PATTERN)
+                    .expect("Invalid TypeScript import query")
+            }))
+        }
+        "tsx" => {
+            static TSX_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(TSX_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_typescript::language_tsx(), TS_PATTERN)
+                    .expect("Invalid TSX import query")
+            }))
+        }
+        "javascript" | "jsx" => {
+            static JS_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(JS_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_javascript::language(), JS_PATTERN)
+                    .expect("Invalid JavaScript import query")
+            }))
+        }
+        _ => Err(GraphError::ParseError {
+            file: PathBuf::new(),
+            message: format!("Unsupported language for imports: {}", language),
+        }),
+    }
+}
+
+/// Get the Tree-sitter query for destructured CommonJS requires
+///
+/// Matches `const { foo, bar: baz } = require("./x")`, capturing the object
+/// pattern so the required names can be resolved individually.
+pub(super) fn get_require_binding_query(language: &str) -> Result<&'static Query, GraphError> {
+    const PATTERN: &str = r#"
+        (variable_declarator
+            name: (object_pattern) @require_names
+            value: (call_expression
+                function: (identifier) @require_fn
+                arguments: (arguments . (string) @require_source))
+            (#eq? @require_fn "require"))
+    "#;
+
     match language {
-        "typescript" | "tsx" => {
+        "typescript" => {
             static TS_QUERY: OnceLock<Query> = OnceLock::new();
             Ok(TS_QUERY.get_or_init(|| {
-                Query::new(
-                    tree_sitter_typescript::language_typescript(),
-                    r#"
-                    (import_statement
-                        source: (string) @import_source)
-                    "#,
-                )
-                .expect("Invalid TypeScript import query")
+                Query::new(tree_sitter_typescript::language_typescript(), PATTERN)
+                    .expect("Invalid TypeScript require query")
+            }))
+        }
+        "tsx" => {
+            static TSX_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(TSX_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_typescript::language_tsx(), PATTERN)
+                    .expect("Invalid TSX require query")
+            }))
+        }
+        "javascript" | "jsx" => {
+            static JS_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(JS_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_javascript::language(), PATTERN)
+                    .expect("Invalid JavaScript require query")
+            }))
+        }
+        _ => Err(GraphError::ParseError {
+            file: PathBuf::new(),
+            message: format!("Unsupported language for imports: {}", language),
+        }),
+    }
+}
+
+/// Get the Tree-sitter query for named imports and barrel re-exports
+///
+/// Matches `import { foo } from "./x"` (capturing `@import_names`),
+/// `export { foo as bar } from "./x"` (capturing each `@reexport`) and
+/// `export * from "./x"` (capturing `@wildcard`), so both sides of a barrel
+/// can be resolved per binding.
+pub(super) fn get_binding_query(language: &str) -> Result<&'static Query, GraphError> {
+    const PATTERN: &str = r#"
+        (import_statement
+            (import_clause (named_imports) @import_names)
+            source: (string) @binding_source)
+        (export_statement
+            (export_clause (export_specifier) @reexport)
+            source: (string) @binding_source)
+        (export_statement
+            "*" @wildcard
+            source: (string) @binding_source)
+    "#;
+
+    match language {
+        "typescript" => {
+            static TS_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(TS_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_typescript::language_typescript(), PATTERN)
+                    .expect("Invalid TypeScript binding query")
+            }))
+        }
+        "tsx" => {
+            static TSX_QUERY: OnceLock<Query> = OnceLock::new();
+            Ok(TSX_QUERY.get_or_init(|| {
+                Query::new(tree_sitter_typescript::language_tsx(), PATTERN)
+                    .expect("Invalid TSX binding query")
             }))
         }
         "javascript" | "jsx" => {
             static JS_QUERY: OnceLock<Query> = OnceLock::new();
             Ok(JS_QUERY.get_or_init(|| {
-                Query::new(
-                    tree_sitter_javascript::language(),
-                    r#"
-                    (import_statement
-                        source: (string) @import_source)
-                    "#,
-                )
-                .expect("Invalid JavaScript import query")
+                Query::new(tree_sitter_javascript::language(), PATTERN)
+                    .expect("Invalid JavaScript binding query")
             }))
         }
         _ => Err(GraphError::ParseError {
@@ -0,0 +1,55 @@
+//! Text-level reference candidates and comment filtering.
+//!
+//! Edges in the symbol graph must come from real code references. Any
+//! name-based lookup (call-graph edges, textual heuristics) should scan
+//! candidates with [`find_name_occurrences`] and then drop the ones that sit
+//! inside comments or doc comments (e.g. JSDoc `@see` tags) with
+//! [`exclude_comment_references`] before creating edges.
+
+use std::ops::Range;
+
+/// Find byte ranges where `name` occurs as a whole identifier in `source`
+///
+/// An occurrence only counts when it is not part of a longer identifier,
+/// so `foo` does not match inside `fooBar` or `_foo`.
+pub(crate) fn find_name_occurrences(source: &str, name: &str) -> Vec<Range<usize>> {
+    if name.is_empty() {
+        return Vec::new();
+    }
+
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut occurrences = Vec::new();
+
+    for (start, _) in source.match_indices(name) {
+        let end = start + name.len();
+        let before_ok = source[..start].chars().next_back().is_none_or(|c| !is_ident(c));
+        let after_ok = source[end..].chars().next().is_none_or(|c| !is_ident(c));
+        if before_ok && after_ok {
+            occurrences.push(start..end);
+        }
+    }
+
+    occurrences
+}
+
+/// Drop candidate reference positions that overlap any comment range
+///
+/// # Arguments
+/// * `candidates` - Byte ranges of candidate references
+/// * `comment_ranges` - Comment ranges from `LanguageParser::find_comment_ranges`
+///
+/// # Returns
+/// Candidates that lie entirely outside comments
+pub(crate) fn exclude_comment_references(
+    candidates: Vec<Range<usize>>,
+    comment_ranges: &[tree_sitter::Range],
+) -> Vec<Range<usize>> {
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            !comment_ranges
+                .iter()
+                .any(|c| candidate.start < c.end_byte && c.start_byte < candidate.end)
+        })
+        .collect()
+}
@@ -1,60 +1,333 @@
 //! Import path resolution for symbol graph construction.
 
 use ahash::AHashMap as HashMap;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Why a `package.json` or `tsconfig.json` could not be used for resolution
+///
+/// These never fail the analysis: resolution falls back to the heuristics
+/// used without the file, and the problem is reported as a warning.
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    /// The file exists but could not be read
+    #[error("Failed to read {}: {source}", path.display())]
+    Read {
+        /// Manifest path
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+
+    /// The file is not valid JSON (comments and trailing commas are allowed
+    /// in `tsconfig.json`)
+    #[error("Invalid JSON in {}: {source}", path.display())]
+    InvalidJson {
+        /// Manifest path
+        path: PathBuf,
+        /// Underlying error
+        source: serde_json::Error,
+    },
+}
+
+/// `compilerOptions.paths` of a `tsconfig.json`
+#[derive(Debug, Clone)]
+struct PathAliases {
+    /// Directory the targets are relative to (`baseUrl`, else the tsconfig's)
+    base: PathBuf,
+    /// Patterns such as `@/*` and their target patterns such as `src/*`
+    paths: Vec<(String, Vec<String>)>,
+}
+
+impl PathAliases {
+    /// Paths `specifier` maps to, in the order they should be tried
+    fn candidates(&self, specifier: &str) -> Vec<PathBuf> {
+        for (pattern, targets) in &self.paths {
+            let captured = match pattern.split_once('*') {
+                Some((prefix, suffix)) => specifier
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix)),
+                None => (pattern == specifier).then_some(""),
+            };
+            if let Some(captured) = captured {
+                return targets
+                    .iter()
+                    .map(|target| self.base.join(target.replacen('*', captured, 1)))
+                    .collect();
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// `package.json` and `tsconfig.json` files, read once per directory
+#[derive(Default)]
+pub(super) struct Manifests {
+    entries: Mutex<HashMap<PathBuf, Option<PathBuf>>>,
+    aliases: Mutex<HashMap<PathBuf, Option<PathAliases>>>,
+    errors: Mutex<Vec<ManifestError>>,
+}
+
+impl Manifests {
+    /// Entry declared by `dir/package.json` ("main", then "module"), relative to `dir`
+    fn entry(&self, dir: &Path) -> Option<PathBuf> {
+        if let Some(entry) = self.entries.lock().unwrap().get(dir) {
+            return entry.clone();
+        }
+
+        let entry = self
+            .read(&dir.join("package.json"), false)
+            .and_then(|manifest| {
+                ["main", "module"]
+                    .iter()
+                    .find_map(|field| manifest[field].as_str().map(PathBuf::from))
+            });
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), entry.clone());
+        entry
+    }
+
+    /// Path aliases of the nearest `tsconfig.json` in `dir` or its ancestors
+    fn aliases(&self, dir: &Path) -> Option<PathAliases> {
+        if let Some(aliases) = self.aliases.lock().unwrap().get(dir) {
+            return aliases.clone();
+        }
+
+        let path = dir.join("tsconfig.json");
+        let aliases = if path.is_file() {
+            self.read(&path, true).and_then(|config| {
+                let options = &config["compilerOptions"];
+                let paths = options["paths"].as_object()?;
+                let base = match options["baseUrl"].as_str() {
+                    Some(base_url) => dir.join(base_url),
+                    None => dir.to_path_buf(),
+                };
+                let mut paths: Vec<_> = paths
+                    .iter()
+                    .map(|(pattern, targets)| {
+                        let targets = targets
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|t| t.as_str().map(str::to_string))
+                            .collect();
+                        (pattern.clone(), targets)
+                    })
+                    .collect();
+                // The longest matching prefix wins, as in `tsc`
+                paths.sort_by_key(|(pattern, _)| {
+                    std::cmp::Reverse(pattern.split('*').next().unwrap_or_default().len())
+                });
+                Some(PathAliases { base, paths })
+            })
+        } else {
+            dir.parent().and_then(|parent| self.aliases(parent))
+        };
+        self.aliases
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), aliases.clone());
+        aliases
+    }
+
+    /// Parse the manifest at `path`, recording why it is unusable if it exists
+    fn read(&self, path: &Path, jsonc: bool) -> Option<serde_json::Value> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(source) => {
+                self.record(ManifestError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                });
+                return None;
+            }
+        };
+        let content = if jsonc {
+            strip_json_comments(&content)
+        } else {
+            content
+        };
+        match serde_json::from_str(&content) {
+            Ok(value) => Some(value),
+            Err(source) => {
+                self.record(ManifestError::InvalidJson {
+                    path: path.to_path_buf(),
+                    source,
+                });
+                None
+            }
+        }
+    }
+
+    fn record(&self, error: ManifestError) {
+        tracing::warn!(error = %error, "Ignoring unusable manifest");
+        self.errors.lock().unwrap().push(error);
+    }
+
+    /// Problems met so far, sorted by path
+    pub(super) fn take_errors(&self) -> Vec<ManifestError> {
+        let mut errors = std::mem::take(&mut *self.errors.lock().unwrap());
+        errors.sort_by(|a, b| a.path().cmp(b.path()));
+        errors
+    }
+}
+
+impl ManifestError {
+    /// Path of the unusable manifest
+    pub fn path(&self) -> &Path {
+        match self {
+            ManifestError::Read { path, .. } | ManifestError::InvalidJson { path, .. } => path,
+        }
+    }
+}
+
+/// `content` with `//` and `/* */` comments and trailing commas removed
+///
+/// `tsconfig.json` is JSONC, which `serde_json` does not accept as is.
+fn strip_json_comments(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => while chars.next_if(|&c| c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ('}' | ']', _) => {
+                // Drop a trailing comma before the closing bracket
+                let end = output.trim_end().len();
+                if output[..end].ends_with(',') {
+                    output.truncate(end - 1);
+                }
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
 
 /// Resolve an import path relative to the importing file
 ///
 /// Handles:
 /// - Relative imports: "./utils" -> "../src/utils.ts"
-/// - Package imports: "@/utils" or "~/utils" (TypeScript path aliases)
+/// - TypeScript path aliases: `compilerOptions.paths` of the nearest
+///   `tsconfig.json`, else "@/utils" or "~/utils" as project-relative paths
 /// - Extension-less imports: "./utils" could be "./utils.ts" or "./utils/index.ts"
+/// - Local packages: "./pkg" resolves to the "main" or "module" entry of
+///   "./pkg/package.json" before falling back to "./pkg/index.ts"
+///
+/// A `tsconfig.json` or `package.json` that cannot be parsed is skipped and
+/// recorded in `manifests`.
 pub(super) fn resolve_import_path(
     importer_path: &Path,
     import_source: &str,
     available_files: &HashMap<PathBuf, bool>,
+    manifests: &Manifests,
 ) -> Option<PathBuf> {
     // Remove quotes from import source
     let import_source = import_source.trim_matches(|c| c == '"' || c == '\'');
 
-    // Skip node_modules and package imports (e.g., "react", "lodash")
-    if !import_source.starts_with('.')
-        && !import_source.starts_with('/')
-        && !import_source.starts_with("@/")
-        && !import_source.starts_with("~/")
-    {
-        return None;
-    }
-
     // Get the directory of the importing file
     let importer_dir = importer_path.parent()?;
 
-    // Handle TypeScript path aliases (@/ and ~/ typically map to src/)
-    let import_path_str = if import_source.starts_with("@/") || import_source.starts_with("~/") {
-        import_source[2..].to_string()
-    } else {
-        import_source.to_string()
-    };
+    if !import_source.starts_with('.') && !import_source.starts_with('/') {
+        // Aliases declared by the project take precedence over the heuristic
+        if let Some(aliases) = manifests.aliases(importer_dir) {
+            let resolved = aliases
+                .candidates(import_source)
+                .iter()
+                .find_map(|base_path| resolve_module(base_path, available_files, manifests));
+            if resolved.is_some() {
+                return resolved;
+            }
+        }
+
+        // Handle TypeScript path aliases (@/ and ~/ typically map to src/)
+        // Assume path alias points to project root (simplified)
+        return match import_source
+            .strip_prefix("@/")
+            .or_else(|| import_source.strip_prefix("~/"))
+        {
+            Some(path) => resolve_module(Path::new(path), available_files, manifests),
+            // Skip node_modules and package imports (e.g., "react", "lodash")
+            None => None,
+        };
+    }
 
     // Resolve the path relative to the importer
-    let base_path = if import_path_str.starts_with("./") || import_path_str.starts_with("../") {
-        importer_dir.join(&import_path_str)
+    let base_path = if import_source.starts_with("./") || import_source.starts_with("../") {
+        importer_dir.join(import_source)
     } else {
-        // Assume path alias points to project root (simplified)
-        PathBuf::from(&import_path_str)
+        PathBuf::from(import_source)
     };
 
-    // Try to resolve with common extensions
-    let extensions = ["", ".ts", ".tsx", ".js", ".jsx"];
-    for ext in &extensions {
-        let candidate = if ext.is_empty() {
-            base_path.clone()
-        } else {
-            base_path.with_extension(&ext[1..]) // Remove the leading dot
-        };
+    // Log warning for unresolved import but don't fail
+    resolve_module(&base_path, available_files, manifests)
+}
 
-        if available_files.contains_key(&candidate) {
-            return Some(candidate);
+/// Whether an import that did not resolve should have: a relative import of
+/// a source module rather than of a stylesheet, JSON file or other asset
+pub(super) fn is_unresolved_source_import(import_source: &str) -> bool {
+    let import_source = import_source.trim_matches(|c| c == '"' || c == '\'');
+    if !import_source.starts_with("./") && !import_source.starts_with("../") {
+        return false;
+    }
+    match Path::new(import_source)
+        .extension()
+        .and_then(|e| e.to_str())
+    {
+        None => true,
+        Some(ext) => matches!(
+            ext,
+            "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" | "vue" | "svelte"
+        ),
+    }
+}
+
+/// Resolve `base_path` as a file, a local package or a directory index
+fn resolve_module(
+    base_path: &Path,
+    available_files: &HashMap<PathBuf, bool>,
+    manifests: &Manifests,
+) -> Option<PathBuf> {
+    // Analyzed paths never contain "..", so "src/../lib/utils" must become "lib/utils"
+    let base_path = &normalize_path(base_path);
+    if let Some(file) = resolve_file(base_path, available_files) {
+        return Some(file);
+    }
+
+    // Try the entry declared by a local package ("./pkg" -> "./pkg/lib/entry.js")
+    if let Some(entry) = manifests.entry(base_path) {
+        if let Some(file) = resolve_file(&base_path.join(entry), available_files) {
+            return Some(file);
         }
     }
 
@@ -66,6 +339,42 @@ pub(super) fn resolve_import_path(
         }
     }
 
-    // Log warning for unresolved import but don't fail
+    None
+}
+
+/// Resolve `path` as written or with one of the common extensions
+/// `path` with `..` components folded into their parent, without touching
+/// the file system; leading ones are kept, like a leading `.`
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn resolve_file(path: &Path, available_files: &HashMap<PathBuf, bool>) -> Option<PathBuf> {
+    let extensions = ["", ".ts", ".tsx", ".js", ".jsx"];
+    for ext in &extensions {
+        let candidate = if ext.is_empty() {
+            path.to_path_buf()
+        } else {
+            path.with_extension(&ext[1..]) // Remove the leading dot
+        };
+
+        if available_files.contains_key(&candidate) {
+            return Some(candidate);
+        }
+    }
     None
 }
@@ -1,11 +1,13 @@
 //! Tests for symbol graph construction.
 
 use super::builder::SymbolGraphBuilder;
+use super::references::{exclude_comment_references, find_name_occurrences};
 use super::extractors::is_test_file;
-use super::resolver::resolve_import_path;
+use super::resolver::{resolve_import_path, ManifestError, Manifests};
+use super::{GraphError, SymbolGraph};
 use ahash::AHashMap as HashMap;
-use crate::models::SymbolKind;
-use code_viz_core::parser::TypeScriptParser;
+use crate::models::{Symbol, SymbolKind};
+use code_viz_core::parser::{JavaScriptParser, LanguageParser, TypeScriptParser};
 use std::path::{Path, PathBuf};
 
 #[test]
@@ -125,6 +127,28 @@ const second = () => {
     assert_eq!(second.line_start, 6);
 }
 
+#[test]
+fn test_symbol_byte_ranges() {
+    // The multi-byte characters make byte and char offsets differ
+    let source = "// café ☕\nexport class Cup {\n    fill() {}\n}\nexport { brew as make } from './brew';\n";
+
+    let parser = TypeScriptParser;
+    let path = Path::new("test.ts");
+    let mut builder = SymbolGraphBuilder::new();
+
+    let symbols = builder.extract_symbols(path, source, &parser).unwrap();
+    let text = |name: &str| {
+        let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+        &source[symbol.byte_start..symbol.byte_end]
+    };
+    assert_eq!(text("Cup"), "class Cup {\n    fill() {}\n}");
+    assert_eq!(text("fill"), "fill() {}");
+
+    let reexports = builder.extract_reexports(path, source, &parser).unwrap();
+    let make = &reexports[0].symbol;
+    assert_eq!(&source[make.byte_start..make.byte_end], "brew as make");
+}
+
 #[test]
 fn test_skip_anonymous_functions() {
     let source = r#"
@@ -143,6 +167,37 @@ fn test_skip_anonymous_functions() {
     assert_eq!(symbols.len(), 0);
 }
 
+#[test]
+fn test_anonymous_default_exports_are_named_default() {
+    let source = r#"
+        export default () => {
+            return 42;
+        };
+    "#;
+
+    let parser = TypeScriptParser;
+    let mut builder = SymbolGraphBuilder::new();
+
+    let symbols = builder
+        .extract_symbols(Path::new("test.ts"), source, &parser)
+        .unwrap();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name, "default");
+    assert_eq!(symbols[0].kind, SymbolKind::ArrowFunction);
+    assert!(symbols[0].is_exported);
+
+    let symbols = builder
+        .extract_symbols(
+            Path::new("test.js"),
+            "export default class {}\n",
+            &JavaScriptParser,
+        )
+        .unwrap();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name, "default");
+    assert_eq!(symbols[0].kind, SymbolKind::Class);
+}
+
 #[test]
 fn test_symbol_id_generation() {
     let source = r#"
@@ -302,6 +357,30 @@ fn test_extract_imports() {
     assert!(imports.iter().any(|i| i.contains("@/types")));
 }
 
+#[test]
+fn test_extract_type_only_sources() {
+    let source = r#"
+        import type { Config } from "./config";
+        import { type Options, type Mode } from "./options";
+        import { type Shape, area } from "./shapes";
+        import type { Logger } from "./logger";
+        import { createLogger } from "./logger";
+        import Default, { type Extra } from "./default";
+    "#;
+
+    let parser = TypeScriptParser;
+    let path = Path::new("test.ts");
+    let builder = SymbolGraphBuilder::new();
+
+    let sources = builder
+        .extract_type_only_sources(path, source, &parser)
+        .unwrap();
+
+    assert_eq!(sources.len(), 2);
+    assert!(sources.contains("\"./config\""));
+    assert!(sources.contains("\"./options\""));
+}
+
 #[test]
 fn test_resolve_relative_imports() {
     let mut available = HashMap::new();
@@ -309,12 +388,490 @@ fn test_resolve_relative_imports() {
     available.insert(PathBuf::from("src/components/Button.tsx"), true);
 
     let importer = Path::new("src/main.ts");
+    let manifests = Manifests::default();
 
     // Resolve "./utils" to "src/utils.ts"
-    let resolved = resolve_import_path(importer, "\"./utils\"", &available);
+    let resolved = resolve_import_path(importer, "\"./utils\"", &available, &manifests);
     assert_eq!(resolved, Some(PathBuf::from("src/utils.ts")));
 
     // Resolve "./components/Button" to "src/components/Button.tsx"
-    let resolved = resolve_import_path(importer, "\"./components/Button\"", &available);
+    let resolved = resolve_import_path(importer, "\"./components/Button\"", &available, &manifests);
     assert_eq!(resolved, Some(PathBuf::from("src/components/Button.tsx")));
+
+    // Resolve "../utils" from a subdirectory to "src/utils.ts"
+    let button = Path::new("src/components/Button.tsx");
+    let resolved = resolve_import_path(button, "\"../utils\"", &available, &manifests);
+    assert_eq!(resolved, Some(PathBuf::from("src/utils.ts")));
+}
+
+#[test]
+fn test_resolve_package_main() {
+    let temp = tempfile::tempdir().unwrap();
+    let pkg = temp.path().join("pkg");
+    std::fs::create_dir_all(pkg.join("lib")).unwrap();
+    std::fs::write(
+        pkg.join("package.json"),
+        r#"{ "name": "pkg", "main": "lib/entry.js" }"#,
+    )
+    .unwrap();
+
+    // package.json takes precedence over the index file
+    let mut available = HashMap::new();
+    available.insert(pkg.join("lib/entry.js"), true);
+    available.insert(pkg.join("index.ts"), true);
+
+    let importer = temp.path().join("main.ts");
+    let manifests = Manifests::default();
+    let resolved = resolve_import_path(&importer, "\"./pkg\"", &available, &manifests);
+    assert_eq!(resolved, Some(pkg.join("lib/entry.js")));
+
+    // The cached entry is reused once package.json is gone
+    std::fs::remove_file(pkg.join("package.json")).unwrap();
+    let resolved = resolve_import_path(&importer, "\"./pkg\"", &available, &manifests);
+    assert_eq!(resolved, Some(pkg.join("lib/entry.js")));
+}
+
+#[test]
+fn test_resolve_tsconfig_paths() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp.path().join("tsconfig.json"),
+        r#"{
+            // JSONC, as written by `tsc --init`
+            "compilerOptions": {
+                "baseUrl": "./src",
+                "paths": { "@lib/*": ["lib/*"], "app/*": ["*"], },
+            },
+        }"#,
+    )
+    .unwrap();
+
+    let mut available = HashMap::new();
+    available.insert(temp.path().join("src/lib/math.ts"), true);
+    available.insert(temp.path().join("src/app/index.ts"), true);
+
+    let importer = temp.path().join("src/app/main.ts");
+    let manifests = Manifests::default();
+    let resolved = resolve_import_path(&importer, "\"@lib/math\"", &available, &manifests);
+    assert_eq!(resolved, Some(temp.path().join("src/lib/math.ts")));
+    let resolved = resolve_import_path(&importer, "\"app/app\"", &available, &manifests);
+    assert_eq!(resolved, Some(temp.path().join("src/app/index.ts")));
+    assert_eq!(
+        resolve_import_path(&importer, "\"react\"", &available, &manifests),
+        None
+    );
+    assert!(manifests.take_errors().is_empty());
+}
+
+#[test]
+fn test_resolve_with_invalid_manifests() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp.path().join("tsconfig.json"),
+        r#"{ "compilerOptions": "#,
+    )
+    .unwrap();
+    let pkg = temp.path().join("pkg");
+    std::fs::create_dir_all(&pkg).unwrap();
+    std::fs::write(pkg.join("package.json"), "{ main: lib }").unwrap();
+
+    // Both fall back to the resolution used without them
+    let mut available = HashMap::new();
+    available.insert(pkg.join("index.ts"), true);
+    let importer = temp.path().join("main.ts");
+    let manifests = Manifests::default();
+    let resolved = resolve_import_path(&importer, "\"./pkg\"", &available, &manifests);
+    assert_eq!(resolved, Some(pkg.join("index.ts")));
+    assert_eq!(
+        resolve_import_path(&importer, "\"@/pkg\"", &available, &manifests),
+        None
+    );
+
+    let errors = manifests.take_errors();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(errors[0], ManifestError::InvalidJson { .. }));
+    assert_eq!(errors[0].path(), pkg.join("package.json"));
+    assert_eq!(errors[1].path(), temp.path().join("tsconfig.json"));
+}
+
+#[test]
+fn test_extract_require_imports() {
+    let source = r#"
+        const fs = require("fs");
+        const utils = require("./utils");
+        const { foo, bar: renamed } = require('./named');
+        load(require("./nested"));
+    "#;
+
+    let parser = JavaScriptParser;
+    let path = Path::new("test.js");
+    let builder = SymbolGraphBuilder::new();
+
+    let imports = builder.extract_imports(path, source, &parser).unwrap();
+    assert_eq!(imports.len(), 4);
+    assert!(!imports.iter().any(|i| i == "require"));
+    assert!(imports.iter().any(|i| i.contains("./utils")));
+    assert!(imports.iter().any(|i| i.contains("./named")));
+    assert!(imports.iter().any(|i| i.contains("./nested")));
+
+    let bindings = builder.extract_require_bindings(path, source, &parser).unwrap();
+    assert_eq!(bindings.len(), 1);
+    assert!(bindings[0].0.contains("./named"));
+    assert_eq!(bindings[0].1, vec!["foo".to_string(), "bar".to_string()]);
+}
+
+#[test]
+fn test_build_graph_require_only_reference() {
+    let mut builder = SymbolGraphBuilder::new();
+
+    let files = vec![
+        (
+            PathBuf::from("src/x.js"),
+            r#"
+            export function fromEsm() {
+                return 1;
+            }
+            "#
+            .to_string(),
+        ),
+        (
+            PathBuf::from("src/cjs.js"),
+            r#"
+            function used() {
+                return 2;
+            }
+
+            function unused() {
+                return 3;
+            }
+
+            module.exports = { used, unused };
+            "#
+            .to_string(),
+        ),
+        (
+            PathBuf::from("src/main.js"),
+            r#"
+            const x = require("./x");
+            const { used } = require("./cjs");
+
+            function main() {
+                x.fromEsm();
+                used();
+            }
+            "#
+            .to_string(),
+        ),
+    ];
+
+    let graph = builder.build_graph(files).unwrap();
+    let id_of = |name: &str| {
+        graph
+            .symbols
+            .values()
+            .find(|s| s.name == name)
+            .map(|s| s.id.clone())
+            .unwrap()
+    };
+
+    let main_deps = graph.imports.get(&id_of("main")).expect("main should have edges");
+    assert!(main_deps.contains(&id_of("fromEsm")));
+    assert!(main_deps.contains(&id_of("used")));
+    assert!(!main_deps.contains(&id_of("unused")));
+}
+
+#[test]
+fn test_build_graph_export_aliases() {
+    let mut builder = SymbolGraphBuilder::new();
+
+    let files = vec![
+        (
+            PathBuf::from("src/format.js"),
+            r#"
+            function formatValue() {
+                return 1;
+            }
+
+            function parseValue() {
+                return 2;
+            }
+
+            exports.format = formatValue;
+            module.exports.parse = parseValue;
+            "#
+            .to_string(),
+        ),
+        (
+            PathBuf::from("src/util.ts"),
+            r#"
+            const helper = () => 1;
+            const other = () => 2;
+
+            export { helper as assist, other };
+            "#
+            .to_string(),
+        ),
+        (
+            PathBuf::from("src/index.ts"),
+            "export { assist } from './util';\n".to_string(),
+        ),
+        (
+            PathBuf::from("src/main.js"),
+            r#"
+            const { format } = require("./format");
+
+            function main() {
+                format();
+            }
+            "#
+            .to_string(),
+        ),
+    ];
+
+    let graph = builder.build_graph(files).unwrap();
+    let symbol = |name: &str, kind: SymbolKind| {
+        graph
+            .symbols
+            .values()
+            .find(|s| s.name == name && s.kind == kind)
+            .unwrap()
+    };
+
+    // `format` is the exported alias of `formatValue` only
+    let main_deps = &graph.imports[&symbol("main", SymbolKind::Function).id];
+    assert!(main_deps.contains(&symbol("formatValue", SymbolKind::Function).id));
+    assert!(!main_deps.contains(&symbol("parseValue", SymbolKind::Function).id));
+
+    // A local export clause exports its bindings under their aliases
+    let helper = symbol("helper", SymbolKind::ArrowFunction);
+    assert!(helper.is_exported);
+    assert!(symbol("other", SymbolKind::ArrowFunction).is_exported);
+    let assist = symbol("assist", SymbolKind::ReExport);
+    assert_eq!(graph.imports[&assist.id], vec![helper.id.clone()]);
+}
+
+#[test]
+fn test_comment_references_are_excluded() {
+    let source = r#"/**
+ * Legacy entry point.
+ * @see orphan
+ */
+export function orphan() {
+    return 1;
+}
+
+// TODO: call orphan() from main once the API settles
+function main() {
+    const orphanage = 2;
+    return orphanage;
+}
+"#;
+
+    let parser = TypeScriptParser;
+    let tree = parser.parse(source).unwrap();
+    let comment_ranges = parser.find_comment_ranges(&tree);
+
+    let candidates = find_name_occurrences(source, "orphan");
+    // JSDoc tag, declaration, line comment ("orphanage" is not a match)
+    assert_eq!(candidates.len(), 3);
+
+    let references = exclude_comment_references(candidates, &comment_ranges);
+    assert_eq!(references.len(), 1);
+    let declaration_start = source.find("function orphan").unwrap() + "function ".len();
+    assert_eq!(references[0].start, declaration_start);
+
+    // With no code references, orphan stays unreachable from main
+    let mut builder = SymbolGraphBuilder::new();
+    let graph = builder
+        .build_graph(vec![(PathBuf::from("src/main.ts"), source.to_string())])
+        .unwrap();
+    let main_id = graph.symbols.values().find(|s| s.name == "main").unwrap().id.clone();
+    let orphan_id = graph.symbols.values().find(|s| s.name == "orphan").unwrap().id.clone();
+    let mut analyzer = crate::reachability::ReachabilityAnalyzer::new(graph);
+    let reachable = analyzer.analyze(vec![main_id]).unwrap();
+    assert!(!reachable.contains(&orphan_id));
+}
+
+#[test]
+fn test_build_graph_reexport_nodes() {
+    let mut builder = SymbolGraphBuilder::new();
+
+    let files = vec![
+        (
+            PathBuf::from("src/lib/a.ts"),
+            "export function alpha() {}\nexport function beta() {}\n".to_string(),
+        ),
+        (
+            PathBuf::from("src/lib/index.ts"),
+            "export { alpha, beta as renamedBeta } from './a';\n".to_string(),
+        ),
+        (
+            PathBuf::from("src/main.ts"),
+            "import { alpha } from './lib';\n\nfunction main() {\n    alpha();\n}\n".to_string(),
+        ),
+    ];
+
+    let graph = builder.build_graph(files).unwrap();
+    let reexport = |name: &str| {
+        graph
+            .symbols
+            .values()
+            .find(|s| s.name == name && s.kind == SymbolKind::ReExport)
+            .unwrap_or_else(|| panic!("missing re-export {}", name))
+    };
+    let origin_id = |name: &str| {
+        graph
+            .symbols
+            .values()
+            .find(|s| s.name == name && s.kind == SymbolKind::Function)
+            .map(|s| s.id.clone())
+            .unwrap()
+    };
+
+    let alpha = reexport("alpha");
+    let renamed = reexport("renamedBeta");
+    assert_eq!(alpha.path, PathBuf::from("src/lib/index.ts"));
+    assert_eq!(alpha.line_start, 1);
+    assert!(alpha.is_exported);
+
+    // Re-exports forward to their origin symbols
+    assert_eq!(graph.imports.get(&alpha.id), Some(&vec![origin_id("alpha")]));
+    assert_eq!(graph.imports.get(&renamed.id), Some(&vec![origin_id("beta")]));
+
+    // The importer only reaches the re-export it names
+    let main_deps = graph.imports.get(&origin_id("main")).unwrap();
+    assert!(main_deps.contains(&alpha.id));
+    assert!(!main_deps.contains(&renamed.id));
+}
+
+#[test]
+fn test_build_graph_wildcard_reexport() {
+    let mut builder = SymbolGraphBuilder::new();
+
+    let files = vec![
+        (
+            PathBuf::from("src/lib/utils.ts"),
+            "export function used() {}\nexport function unused() {}\n".to_string(),
+        ),
+        (
+            PathBuf::from("src/lib/index.ts"),
+            "export * from './utils';\n".to_string(),
+        ),
+        (
+            PathBuf::from("src/main.ts"),
+            "import { used } from './lib';\n\nfunction main() {\n    used();\n}\n".to_string(),
+        ),
+    ];
+
+    let graph = builder.build_graph(files).unwrap();
+    let id_of = |name: &str, kind: SymbolKind| {
+        graph
+            .symbols
+            .values()
+            .find(|s| s.name == name && s.kind == kind)
+            .map(|s| s.id.clone())
+            .unwrap_or_else(|| panic!("missing {:?} {}", kind, name))
+    };
+
+    // The wildcard expands to one re-export per name in the barrel
+    let used_reexport = id_of("used", SymbolKind::ReExport);
+    let unused_reexport = id_of("unused", SymbolKind::ReExport);
+    assert_eq!(
+        graph.symbols[&used_reexport].path,
+        PathBuf::from("src/lib/index.ts")
+    );
+    assert!(graph.exports[&PathBuf::from("src/lib/index.ts")].contains(&used_reexport));
+    assert_eq!(
+        graph.wildcard_reexports,
+        vec![unused_reexport.clone(), used_reexport.clone()]
+    );
+
+    let main_id = id_of("main", SymbolKind::Function);
+    let used_id = id_of("used", SymbolKind::Function);
+    let unused_id = id_of("unused", SymbolKind::Function);
+    let mut analyzer = crate::reachability::ReachabilityAnalyzer::new(graph);
+    let reachable = analyzer.analyze(vec![main_id]).unwrap();
+
+    // Only the symbol main imports through the barrel stays alive
+    assert!(reachable.contains(&used_reexport));
+    assert!(reachable.contains(&used_id));
+    assert!(!reachable.contains(&unused_reexport));
+    assert!(!reachable.contains(&unused_id));
+}
+
+fn external_symbol(id: &str, path: &str, is_exported: bool) -> Symbol {
+    Symbol {
+        id: id.to_string(),
+        name: id.to_string(),
+        kind: SymbolKind::Function,
+        path: PathBuf::from(path),
+        line_start: 1,
+        line_end: 5,
+        byte_start: 0,
+        byte_end: 0,
+        is_exported,
+        is_test: false,
+        complexity: 1,
+        fingerprint: String::new(),
+        body_fingerprint: String::new(),
+        accessibility: None,
+    }
+}
+
+#[test]
+fn test_build_graph_programmatically() {
+    let mut builder = SymbolGraphBuilder::new();
+    builder
+        .add_symbol(external_symbol("main", "src/main.py", true))
+        .add_symbol(external_symbol("helper", "src/util.py", true))
+        .add_symbol(external_symbol("orphan", "src/util.py", false))
+        .add_edge("main", "helper");
+    let graph = builder.build().unwrap();
+
+    assert_eq!(graph.symbols.len(), 3);
+    assert_eq!(
+        graph.exports[&PathBuf::from("src/util.py")],
+        vec!["helper".to_string()]
+    );
+
+    let entry_points = crate::entry_points::detect_entry_points(&graph);
+    assert_eq!(entry_points, vec!["main".to_string()]);
+    let mut analyzer = crate::reachability::ReachabilityAnalyzer::new(graph);
+    let reachable = analyzer.analyze(entry_points).unwrap();
+    assert!(reachable.contains("main"));
+    assert!(reachable.contains("helper"));
+    assert!(!reachable.contains("orphan"));
+
+    // build() drains the builder
+    assert!(builder.build().unwrap().symbols.is_empty());
+}
+
+#[test]
+fn test_from_symbols_rejects_dangling_edges() {
+    let symbols = || vec![external_symbol("a", "a.py", false)];
+
+    let mut imports = HashMap::new();
+    imports.insert("a".to_string(), vec!["missing".to_string()]);
+    match SymbolGraph::from_symbols(symbols(), imports, HashMap::new()) {
+        Err(GraphError::DanglingEdge { from, to }) => {
+            assert_eq!((from.as_str(), to.as_str()), ("a", "missing"));
+        }
+        other => panic!("expected dangling edge, got {:?}", other),
+    }
+
+    let mut exports = HashMap::new();
+    exports.insert(PathBuf::from("a.py"), vec!["gone".to_string()]);
+    assert!(matches!(
+        SymbolGraph::from_symbols(symbols(), HashMap::new(), exports),
+        Err(GraphError::UnknownExport { .. })
+    ));
+
+    let mut builder = SymbolGraphBuilder::new();
+    builder
+        .add_symbol(symbols().remove(0))
+        .add_edge("ghost", "a");
+    assert!(matches!(
+        builder.build(),
+        Err(GraphError::DanglingEdge { .. })
+    ));
 }
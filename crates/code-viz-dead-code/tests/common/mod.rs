@@ -0,0 +1,41 @@
+//! Synthetic TypeScript codebases for benchmarks and performance tests.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Generate `file_count` TypeScript files, each importing `fan_out` others
+///
+/// Every file exports three functions: `run` calling its imports, `helper`
+/// used by importers and `unused`, which nothing calls. `src/mod0.ts` is the
+/// natural entry point. Imports follow a fixed stride, so the output is
+/// deterministic.
+pub fn synthetic_files(file_count: usize, fan_out: usize) -> Vec<(PathBuf, String)> {
+    (0..file_count)
+        .map(|i| {
+            let imports: Vec<usize> = (1..=fan_out)
+                .map(|k| (i + k * 7 + 1) % file_count)
+                .filter(|&j| j != i)
+                .collect();
+
+            let mut source = String::new();
+            for j in &imports {
+                writeln!(source, "import {{ helper{} }} from './mod{}';", j, j).unwrap();
+            }
+
+            writeln!(source, "\nexport function run{}(input: number) {{", i).unwrap();
+            for j in &imports {
+                writeln!(source, "    if (input > {}) {{", j).unwrap();
+                writeln!(source, "        input += helper{}(input);", j).unwrap();
+                writeln!(source, "    }}").unwrap();
+            }
+            writeln!(source, "    return input;\n}}").unwrap();
+
+            writeln!(source, "\nexport function helper{}(value: number) {{", i).unwrap();
+            writeln!(source, "    return value * 2;\n}}").unwrap();
+            writeln!(source, "\nexport function unused{}() {{", i).unwrap();
+            writeln!(source, "    return {};\n}}", i).unwrap();
+
+            (PathBuf::from(format!("src/mod{}.ts", i)), source)
+        })
+        .collect()
+}
@@ -11,7 +11,9 @@
 //!
 //! See `fixtures/EXPECTED.md` for the complete ground truth.
 
-use code_viz_dead_code::{analyze_dead_code, AnalysisConfig};
+use code_viz_dead_code::{
+    analyze_dead_code, analyze_dead_code_raw, analyze_public_api, AnalysisConfig, CacheMode,
+};
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
@@ -27,7 +29,7 @@ fn get_sample_repo_path() -> PathBuf {
 fn create_test_config() -> (AnalysisConfig, TempDir) {
     let cache_dir = TempDir::new().expect("Failed to create temp cache dir");
     let config = AnalysisConfig {
-        enable_cache: true,
+        cache_mode: CacheMode::ReadWrite,
         cache_dir: Some(cache_dir.path().to_path_buf()),
         ..Default::default()
     };
@@ -340,6 +342,114 @@ fn test_entry_point_detection() {
     }
 }
 
+/// Test the raw reachable and dead symbol IDs
+///
+/// According to EXPECTED.md, everything imported from main.ts and the tests
+/// is reachable, while dead.ts and the circular pair are not.
+#[test]
+fn test_raw_analysis_symbol_ids() {
+    let sample_repo = get_sample_repo_path();
+    let (config, _cache_dir) = create_test_config();
+    let raw = analyze_dead_code_raw(&sample_repo, Some(config)).unwrap();
+    let mut ids = raw.symbol_ids();
+    ids.strip_root(&sample_repo);
+
+    let names = |ids: &[String]| -> Vec<String> {
+        ids.iter()
+            .map(|id| id.rsplit(':').next().unwrap().to_string())
+            .collect()
+    };
+    let reachable = names(&ids.reachable);
+    let dead = names(&ids.dead);
+
+    for live in [
+        "main",
+        "activeFunction",
+        "testableFunction",
+        "processData",
+        "publicApi",
+        "testTestableFunction",
+    ] {
+        assert!(
+            reachable.contains(&live.to_string()),
+            "{} should be reachable",
+            live
+        );
+    }
+    for unused in ["unusedExportedFunction", "UnusedClass", "deadAsyncFunction"] {
+        assert!(
+            dead.contains(&unused.to_string()),
+            "{} should be dead",
+            unused
+        );
+    }
+
+    // The two sets partition the graph, and every entry point is reachable
+    assert_eq!(ids.reachable.len(), raw.reachable.len());
+    assert_eq!(
+        ids.reachable.len() + ids.dead.len(),
+        raw.graph.symbols.len()
+    );
+    assert!(ids.entry_points.iter().all(|id| ids.reachable.contains(id)));
+    assert!(ids
+        .reachable
+        .iter()
+        .all(|id| id.starts_with("src/") || id.starts_with("tests/")));
+}
+
+/// Test the public API report against the sample repository
+///
+/// Exports imported by other files are used internally, even when that code
+/// is itself dead, while exports only reached through an unused barrel are
+/// purely public.
+#[test]
+fn test_public_api_report() {
+    let sample_repo = get_sample_repo_path();
+    let (mut config, _cache_dir) = create_test_config();
+    config.entry_point_patterns = vec!["src/dead.ts".to_string()];
+    let mut report = analyze_public_api(&sample_repo, Some(config)).unwrap();
+    report.strip_root(&sample_repo);
+
+    let export = |path: &str, name: &str| {
+        report
+            .exports
+            .iter()
+            .find(|e| e.path == Path::new(path) && e.symbol == name)
+            .unwrap_or_else(|| panic!("{} should be exported from {}", name, path))
+    };
+
+    for (path, name) in [
+        ("src/used.ts", "activeFunction"),
+        ("src/internal.ts", "publicApi"),
+        ("src/circular-a.ts", "functionA"),
+    ] {
+        assert!(export(path, name).used_internally, "{} is imported", name);
+    }
+    for (path, name) in [
+        ("src/dead.ts", "unusedExportedFunction"),
+        ("src/index.ts", "indexFunction"),
+        ("src/index.ts", "publicApi"),
+        ("src/utils/helper.ts", "unusedUtility"),
+    ] {
+        assert!(
+            !export(path, name).used_internally,
+            "{} in {} is only public",
+            name,
+            path
+        );
+    }
+
+    // Unexported symbols are not part of the surface
+    assert!(report.exports.iter().all(|e| e.symbol != "internalHelper"));
+    // Entry points are independent of internal use
+    assert!(export("src/dead.ts", "unusedExportedFunction").is_entry);
+    assert!(!export("src/internal.ts", "publicApi").is_entry);
+    assert!(report
+        .exports
+        .windows(2)
+        .all(|w| (&w[0].path, w[0].line_start) <= (&w[1].path, w[1].line_start)));
+}
+
 /// Test circular import handling
 ///
 /// According to EXPECTED.md:
@@ -385,6 +495,34 @@ fn test_circular_imports() {
     }
 }
 
+/// Test that the unused circular import is reported as one dead cluster
+#[test]
+fn test_circular_imports_form_dead_cluster() {
+    let sample_repo = get_sample_repo_path();
+
+    let (config, _cache_dir) = create_test_config();
+    let result = analyze_dead_code(&sample_repo, Some(config)).expect("Analysis should succeed");
+
+    let in_circular_file =
+        |id: &String| id.contains("circular-a.ts:") || id.contains("circular-b.ts:");
+    let clusters: Vec<&Vec<String>> = result
+        .dead_clusters
+        .iter()
+        .filter(|cluster| cluster.iter().any(in_circular_file))
+        .collect();
+
+    assert_eq!(
+        clusters.len(),
+        1,
+        "circular-a.ts and circular-b.ts should form a single cluster: {:?}",
+        result.dead_clusters
+    );
+    let cluster = clusters[0];
+    assert!(cluster.iter().all(in_circular_file), "{:?}", cluster);
+    assert!(cluster.iter().any(|id| id.ends_with(":functionA")));
+    assert!(cluster.iter().any(|id| id.ends_with(":functionB")));
+}
+
 /// Test incremental analysis with caching
 ///
 /// This test verifies that:
@@ -396,7 +534,7 @@ fn test_incremental_analysis() {
     let sample_repo = get_sample_repo_path();
 
     let config = AnalysisConfig {
-        enable_cache: true,
+        cache_mode: CacheMode::ReadWrite,
         cache_dir: Some(sample_repo.join(".test-cache")),
         ..Default::default()
     };
@@ -0,0 +1,39 @@
+//! Performance regression guard for symbol graph construction
+//!
+//! The limit is loose enough for unoptimized builds on CI machines; it only
+//! trips on regressions such as accidentally quadratic import resolution.
+//! Use the `graph_building` benchmark for actual measurements.
+
+use code_viz_dead_code::{ReachabilityAnalyzer, SymbolGraphBuilder};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+mod common;
+
+const FILE_COUNT: usize = 2000;
+const FAN_OUT: usize = 3;
+const BUILD_LIMIT: Duration = Duration::from_secs(20);
+
+#[test]
+fn test_build_graph_2000_files_within_limit() {
+    let files = common::synthetic_files(FILE_COUNT, FAN_OUT);
+
+    let start = Instant::now();
+    let graph = SymbolGraphBuilder::new().build_graph(files).unwrap();
+    let elapsed = start.elapsed();
+
+    // run, helper and unused in every file
+    assert_eq!(graph.symbols.len(), FILE_COUNT * 3);
+    assert!(
+        elapsed < BUILD_LIMIT,
+        "building a {}-file graph took {:?} (limit {:?})",
+        FILE_COUNT,
+        elapsed,
+        BUILD_LIMIT
+    );
+
+    // Unused functions stay unreachable from the entry file
+    let entry_points = graph.exports[Path::new("src/mod0.ts")].clone();
+    let reachable = ReachabilityAnalyzer::new(graph).analyze(entry_points).unwrap();
+    assert!(!reachable.contains("src/mod1.ts:15:unused1"));
+}
@@ -70,10 +70,15 @@ mod integration_tests {
             path: PathBuf::from("test.rs"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: None,
+            warnings: vec![],
         };
 
         let tauri_node: TreeNode = api_node.clone().into();
@@ -82,9 +87,11 @@ mod integration_tests {
         let api_json = serde_json::to_value(&api_node).unwrap();
         let tauri_json = serde_json::to_value(&tauri_node).unwrap();
 
-        assert_eq!(api_json["id"], tauri_json["id"]);
-        assert_eq!(api_json["loc"], tauri_json["loc"]);
-        assert_eq!(api_json["lastModified"], tauri_json["lastModified"]);
+        assert_eq!(
+            serde_json::to_string(&api_node).unwrap(),
+            serde_json::to_string(&tauri_node).unwrap()
+        );
+        assert!(code_viz_api::contracts::non_camel_case_keys(&tauri_json).is_empty());
 
         // CRITICAL: Both must serialize lastModified as ISO 8601 string
         assert!(api_json["lastModified"].is_string());
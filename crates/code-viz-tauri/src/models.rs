@@ -3,6 +3,7 @@
 //! This module re-exports types from code-viz-api and adds Tauri-specific
 //! features like TypeScript type generation via specta.
 
+use code_viz_core::models::AnalysisWarning;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::path::PathBuf;
@@ -22,6 +23,14 @@ pub struct TreeNode {
     pub path: PathBuf,
     pub loc: usize,
     pub complexity: u32,
+    #[serde(default)]
+    pub max_nesting_depth: usize,
+    #[serde(default)]
+    pub comment_lines: usize,
+    #[serde(default)]
+    pub comment_ratio: f64,
+    #[serde(default)]
+    pub export_count: usize,
     #[serde(rename = "type")]
     pub node_type: String,
     #[serde(default)]
@@ -31,6 +40,8 @@ pub struct TreeNode {
     pub last_modified: SystemTime,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dead_code_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AnalysisWarning>,
 }
 
 /// Convert from code_viz_api::TreeNode to Tauri TreeNode
@@ -42,10 +53,15 @@ impl From<code_viz_api::TreeNode> for TreeNode {
             path: api_node.path,
             loc: api_node.loc,
             complexity: api_node.complexity,
+            max_nesting_depth: api_node.max_nesting_depth,
+            comment_lines: api_node.comment_lines,
+            comment_ratio: api_node.comment_ratio,
+            export_count: api_node.export_count,
             node_type: api_node.node_type,
             children: api_node.children.into_iter().map(Into::into).collect(),
             last_modified: api_node.last_modified,
             dead_code_ratio: api_node.dead_code_ratio,
+            warnings: api_node.warnings,
         }
     }
 }
@@ -63,10 +79,15 @@ mod tests {
             path: PathBuf::from("test.rs"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: None,
+            warnings: vec![],
         };
 
         let tauri_node: TreeNode = api_node.into();
@@ -84,10 +105,15 @@ mod tests {
             path: PathBuf::from("test.rs"),
             loc: 100,
             complexity: 10,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
             dead_code_ratio: None,
+            warnings: vec![],
         };
 
         let json = serde_json::to_value(&node).expect("Failed to serialize");
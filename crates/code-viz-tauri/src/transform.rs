@@ -3,7 +3,7 @@
 //! This module provides functions to transform the flat Vec<FileMetrics> output
 //! from code-viz-core into hierarchical TreeNode structures for visualization.
 
-use code_viz_core::models::FileMetrics;
+use code_viz_core::models::{comment_ratio, FileMetrics};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -114,6 +114,11 @@ fn strip_prefix(path: &Path, prefix: &Path) -> PathBuf {
 ///         loc: 100,
 ///         size_bytes: 2048,
 ///         function_count: 5,
+///         max_nesting_depth: 0,
+///         comment_lines: 0,
+///         comment_ratio: 0.0,
+///         export_count: 0,
+///         functions: vec![],
 ///         last_modified: SystemTime::now(),
 ///         dead_function_count: None,
 ///         dead_code_loc: None,
@@ -134,10 +139,15 @@ pub fn flat_to_hierarchy(files: Vec<FileMetrics>) -> TreeNode {
             path: PathBuf::from("/"),
             loc: 0,
             complexity: 0,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
             node_type: "directory".to_string(),
             children: vec![],
             last_modified: std::time::SystemTime::now(),
             dead_code_ratio: None,
+            warnings: vec![],
         };
     }
 
@@ -174,10 +184,15 @@ pub fn flat_to_hierarchy(files: Vec<FileMetrics>) -> TreeNode {
         path: root_node_path.clone(),
         loc: 0,
         complexity: 0,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "directory".to_string(),
         children: vec![],
         last_modified: std::time::SystemTime::now(),
         dead_code_ratio: None,
+        warnings: vec![],
     };
     dir_map.insert(root_node_path.clone(), root_node);
 
@@ -206,10 +221,15 @@ pub fn flat_to_hierarchy(files: Vec<FileMetrics>) -> TreeNode {
             path: file_path.clone(),
             loc: file_loc,
             complexity: file_complexity,
+            max_nesting_depth: file.max_nesting_depth,
+            comment_lines: file.comment_lines,
+            comment_ratio: file.comment_ratio,
+            export_count: file.export_count,
             node_type: "file".to_string(),
             children: vec![],
             last_modified: file.last_modified,
-            dead_code_ratio: None,
+            dead_code_ratio: file.dead_code_ratio,
+            warnings: vec![],
         };
         file_nodes.push((file_path.clone(), file_node));
 
@@ -260,10 +280,15 @@ fn ensure_parent_directories(
                 path: parent_buf.clone(),
                 loc: 0,
                 complexity: 0,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
                 node_type: "directory".to_string(),
                 children: vec![],
                 last_modified: std::time::SystemTime::now(),
                 dead_code_ratio: None,
+                warnings: vec![],
             };
             dir_map.insert(parent_buf.clone(), dir_node);
 
@@ -301,7 +326,7 @@ fn get_parent_path(path: &Path, root_path: &Path) -> PathBuf {
         .unwrap_or_else(|| root_path.to_path_buf())
 }
 
-/// Aggregates metrics (LOC, complexity) from children up to parent directories
+/// Aggregates metrics (LOC, complexity, nesting depth, comment ratio, export count) from children up to parent directories
 fn aggregate_directory_metrics(
     dir_map: &mut HashMap<PathBuf, TreeNode>,
     root_path: &Path,
@@ -323,6 +348,14 @@ fn aggregate_directory_metrics(
         // Calculate this directory's metrics from its children
         if let Some(dir_node) = dir_map.get(&path) {
             let total_loc: usize = dir_node.children.iter().map(|c| c.loc).sum();
+            let export_count: usize = dir_node.children.iter().map(|c| c.export_count).sum();
+            let max_nesting_depth = dir_node
+                .children
+                .iter()
+                .map(|c| c.max_nesting_depth)
+                .max()
+                .unwrap_or(0);
+            let comment_lines: usize = dir_node.children.iter().map(|c| c.comment_lines).sum();
             let max_modified = dir_node
                 .children
                 .iter()
@@ -337,6 +370,10 @@ fn aggregate_directory_metrics(
             if let Some(dir_node_mut) = dir_map.get_mut(&path) {
                 dir_node_mut.loc = total_loc;
                 dir_node_mut.complexity = complexity;
+                dir_node_mut.max_nesting_depth = max_nesting_depth;
+                dir_node_mut.comment_lines = comment_lines;
+                dir_node_mut.comment_ratio = comment_ratio(comment_lines, total_loc);
+                dir_node_mut.export_count = export_count;
                 dir_node_mut.last_modified = max_modified;
             }
 
@@ -368,6 +405,15 @@ fn aggregate_directory_metrics(
 
         root.loc = total_loc;
         root.complexity = calculate_complexity(total_loc);
+        root.max_nesting_depth = root
+            .children
+            .iter()
+            .map(|c| c.max_nesting_depth)
+            .max()
+            .unwrap_or(0);
+        root.comment_lines = root.children.iter().map(|c| c.comment_lines).sum();
+        root.comment_ratio = comment_ratio(root.comment_lines, total_loc);
+        root.export_count = root.children.iter().map(|c| c.export_count).sum();
         root.last_modified = max_modified;
     }
 }
@@ -389,6 +435,11 @@ mod tests {
             loc,
             size_bytes: 2048,
             function_count: 5,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
+            functions: vec![],
             last_modified: SystemTime::now(),
             dead_function_count: None,
             dead_code_loc: None,
@@ -450,6 +501,61 @@ mod tests {
         assert_eq!(tests_dir.children.len(), 1);
     }
 
+    #[test]
+    fn test_max_nesting_depth_aggregation() {
+        let mut shallow = create_test_file("src/a.rs", 10);
+        shallow.max_nesting_depth = 2;
+        let mut deep = create_test_file("src/util/b.rs", 10);
+        deep.max_nesting_depth = 5;
+        let mut other = create_test_file("tests/c.rs", 10);
+        other.max_nesting_depth = 3;
+        let tree = flat_to_hierarchy(vec![shallow, deep, other]);
+
+        assert_eq!(tree.max_nesting_depth, 5);
+        let src_dir = tree.children.iter().find(|c| c.name == "src").unwrap();
+        assert_eq!(src_dir.max_nesting_depth, 5);
+        let tests_dir = tree.children.iter().find(|c| c.name == "tests").unwrap();
+        assert_eq!(tests_dir.max_nesting_depth, 3);
+        let a = src_dir.children.iter().find(|c| c.name == "a.rs").unwrap();
+        assert_eq!(a.max_nesting_depth, 2);
+    }
+
+    #[test]
+    fn test_export_count_aggregation() {
+        let mut a = create_test_file("src/a.ts", 10);
+        a.export_count = 2;
+        let mut b = create_test_file("src/util/b.ts", 10);
+        b.export_count = 3;
+        let mut c = create_test_file("tests/c.ts", 10);
+        c.export_count = 1;
+        let tree = flat_to_hierarchy(vec![a, b, c]);
+
+        assert_eq!(tree.export_count, 6);
+        let src_dir = tree.children.iter().find(|c| c.name == "src").unwrap();
+        assert_eq!(src_dir.export_count, 5);
+        let tests_dir = tree.children.iter().find(|c| c.name == "tests").unwrap();
+        assert_eq!(tests_dir.export_count, 1);
+    }
+
+    #[test]
+    fn test_comment_ratio_sums_comment_and_code_lines() {
+        let mut documented = create_test_file("src/a.rs", 10);
+        documented.comment_lines = 90;
+        documented.comment_ratio = 0.9;
+        let mut bare = create_test_file("src/b.rs", 90);
+        bare.comment_lines = 10;
+        bare.comment_ratio = 0.1;
+        let empty = create_test_file("tests/c.rs", 0);
+        let tree = flat_to_hierarchy(vec![documented, bare, empty]);
+
+        let src_dir = tree.children.iter().find(|c| c.name == "src").unwrap();
+        assert_eq!(src_dir.comment_lines, 100);
+        assert!((src_dir.comment_ratio - 0.5).abs() < 1e-9);
+        let tests_dir = tree.children.iter().find(|c| c.name == "tests").unwrap();
+        assert_eq!(tests_dir.comment_ratio, 0.0);
+        assert!((tree.comment_ratio - 0.5).abs() < 1e-9);
+    }
+
     #[test]
     fn test_deep_nesting() {
         let files = vec![
@@ -642,6 +748,11 @@ mod tests {
                 loc: 100,
                 size_bytes: 2048,
                 function_count: 5,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
+                functions: vec![],
                 last_modified: old,
                 dead_function_count: None,
                 dead_code_loc: None,
@@ -653,6 +764,11 @@ mod tests {
                 loc: 100,
                 size_bytes: 2048,
                 function_count: 5,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
+                functions: vec![],
                 last_modified: older,
                 dead_function_count: None,
                 dead_code_loc: None,
@@ -664,6 +780,11 @@ mod tests {
                 loc: 100,
                 size_bytes: 2048,
                 function_count: 5,
+                max_nesting_depth: 0,
+                comment_lines: 0,
+                comment_ratio: 0.0,
+                export_count: 0,
+                functions: vec![],
                 last_modified: now,
                 dead_function_count: None,
                 dead_code_loc: None,
@@ -17,6 +17,11 @@ mod path_handling_tests {
             loc,
             size_bytes: 2048,
             function_count: 5,
+            max_nesting_depth: 0,
+            comment_lines: 0,
+            comment_ratio: 0.0,
+            export_count: 0,
+            functions: vec![],
             last_modified: SystemTime::now(),
             dead_function_count: None,
             dead_code_loc: None,
@@ -87,10 +87,15 @@ async fn test_ssot_contract_consistency() {
         path: PathBuf::from("test.rs"),
         loc: 100,
         complexity: 10,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "file".to_string(),
         children: vec![],
         last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1234567890),
         dead_code_ratio: Some(0.25),
+        warnings: vec![],
     };
 
     // Convert to Tauri TreeNode
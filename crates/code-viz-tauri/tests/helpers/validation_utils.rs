@@ -12,10 +12,15 @@ pub fn create_test_tree() -> TreeNode {
         path: PathBuf::from("src/main.rs"),
         loc: 100,
         complexity: 10,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "file".to_string(),
         children: vec![],
         last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1600000000),
         dead_code_ratio: Some(0.1),
+        warnings: vec![],
     };
 
     let utils_rs = TreeNode {
@@ -24,10 +29,15 @@ pub fn create_test_tree() -> TreeNode {
         path: PathBuf::from("src/utils.rs"),
         loc: 50,
         complexity: 5,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "file".to_string(),
         children: vec![],
         last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1600000000),
         dead_code_ratio: None,
+        warnings: vec![],
     };
 
     let src_dir = TreeNode {
@@ -36,10 +46,15 @@ pub fn create_test_tree() -> TreeNode {
         path: PathBuf::from("src"),
         loc: 150,
         complexity: 15,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "directory".to_string(),
         children: vec![main_rs, utils_rs],
         last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1600000000),
         dead_code_ratio: Some(0.05),
+        warnings: vec![],
     };
 
     TreeNode {
@@ -48,10 +63,15 @@ pub fn create_test_tree() -> TreeNode {
         path: PathBuf::from("."),
         loc: 150,
         complexity: 15,
+        max_nesting_depth: 0,
+        comment_lines: 0,
+        comment_ratio: 0.0,
+        export_count: 0,
         node_type: "directory".to_string(),
         children: vec![src_dir],
         last_modified: UNIX_EPOCH + std::time::Duration::from_secs(1600000000),
         dead_code_ratio: None,
+        warnings: vec![],
     }
 }
 
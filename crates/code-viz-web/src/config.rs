@@ -0,0 +1,189 @@
+//! Server configuration from command-line arguments and environment variables
+
+use clap::Parser;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Environment variable overriding the bind host
+pub const HOST_ENV: &str = "CODE_VIZ_HOST";
+
+/// Environment variable overriding the bind port
+pub const PORT_ENV: &str = "CODE_VIZ_PORT";
+
+/// Environment variable overriding the allowed analysis root
+pub const ROOT_ENV: &str = "CODE_VIZ_ROOT";
+
+/// Environment variable overriding the number of analyses run at once
+pub const MAX_ANALYSES_ENV: &str = "CODE_VIZ_MAX_ANALYSES";
+
+/// Loopback only, so the API is not exposed to the network by default
+const DEFAULT_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const DEFAULT_PORT: u16 = 8080;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ConfigError {
+    #[error("Invalid {HOST_ENV} '{0}', expected an IP address")]
+    InvalidHost(String),
+
+    #[error("Invalid {PORT_ENV} '{0}', expected a port number")]
+    InvalidPort(String),
+
+    #[error("Allowed root '{0}' is not an existing directory")]
+    RootNotFound(PathBuf),
+
+    #[error("Invalid {MAX_ANALYSES_ENV} '{0}', expected a positive number")]
+    InvalidMaxAnalyses(String),
+}
+
+/// Command-line arguments of the web server
+#[derive(Parser, Debug, Default)]
+#[command(name = "code-viz-web", about = "HTTP API and frontend for code-viz")]
+pub struct ServerArgs {
+    /// Address to bind (overrides CODE_VIZ_HOST) [default: 127.0.0.1]
+    #[arg(long)]
+    pub host: Option<IpAddr>,
+
+    /// Port to listen on (overrides CODE_VIZ_PORT) [default: 8080]
+    #[arg(long)]
+    pub port: Option<u16>,
+
+    /// Only paths under this directory can be analyzed (overrides CODE_VIZ_ROOT)
+    /// [default: current directory]
+    #[arg(long)]
+    pub root: Option<PathBuf>,
+
+    /// Analyses run at once; further requests wait their turn (overrides
+    /// CODE_VIZ_MAX_ANALYSES) [default: 2]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub max_analyses: Option<u16>,
+}
+
+impl ServerArgs {
+    /// Address to bind: CLI arguments, then `env` lookups, then 127.0.0.1:8080
+    ///
+    /// `env` is `std::env::var(..).ok()` in production and a map in tests.
+    pub fn bind_address(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<SocketAddr, ConfigError> {
+        let host = match (self.host, env(HOST_ENV)) {
+            (Some(host), _) => host,
+            (None, Some(value)) => value.parse().map_err(|_| ConfigError::InvalidHost(value))?,
+            (None, None) => DEFAULT_HOST,
+        };
+        let port = match (self.port, env(PORT_ENV)) {
+            (Some(port), _) => port,
+            (None, Some(value)) => value.parse().map_err(|_| ConfigError::InvalidPort(value))?,
+            (None, None) => DEFAULT_PORT,
+        };
+        Ok(SocketAddr::new(host, port))
+    }
+
+    /// Canonical directory requests are confined to: CLI argument, then
+    /// `env`, then the current directory
+    pub fn allowed_root(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<PathBuf, ConfigError> {
+        let root = match (&self.root, env(ROOT_ENV)) {
+            (Some(root), _) => root.clone(),
+            (None, Some(value)) => PathBuf::from(value),
+            (None, None) => PathBuf::from("."),
+        };
+        root.canonicalize()
+            .ok()
+            .filter(|path| path.is_dir())
+            .ok_or(ConfigError::RootNotFound(root))
+    }
+
+    /// Analyses run at once: CLI argument, then `env`, then
+    /// `DEFAULT_MAX_ANALYSES`
+    pub fn max_analyses(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<usize, ConfigError> {
+        match (self.max_analyses, env(MAX_ANALYSES_ENV)) {
+            (Some(limit), _) => Ok(limit.into()),
+            (None, Some(value)) => value
+                .parse()
+                .ok()
+                .filter(|&limit| limit > 0)
+                .ok_or(ConfigError::InvalidMaxAnalyses(value)),
+            (None, None) => Ok(crate::context::DEFAULT_MAX_ANALYSES),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn test_default_binds_loopback() {
+        let addr = ServerArgs::default().bind_address(env(&[])).unwrap();
+        assert_eq!(addr, "127.0.0.1:8080".parse().unwrap());
+    }
+
+    #[test]
+    fn test_env_overrides_default() {
+        let vars = [(HOST_ENV, "0.0.0.0"), (PORT_ENV, "3000")];
+        let addr = ServerArgs::default().bind_address(env(&vars)).unwrap();
+        assert_eq!(addr, "0.0.0.0:3000".parse().unwrap());
+    }
+
+    #[test]
+    fn test_args_override_env() {
+        let args = ServerArgs::parse_from(["code-viz-web", "--port", "9000"]);
+        let vars = [(HOST_ENV, "::1"), (PORT_ENV, "3000")];
+        let addr = args.bind_address(env(&vars)).unwrap();
+        assert_eq!(addr, "[::1]:9000".parse().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_env_port() {
+        let vars = [(PORT_ENV, "http")];
+        let err = ServerArgs::default().bind_address(env(&vars)).unwrap_err();
+        assert_eq!(err, ConfigError::InvalidPort("http".to_string()));
+    }
+
+    #[test]
+    fn test_max_analyses_from_env() {
+        let vars = [(MAX_ANALYSES_ENV, "4")];
+        assert_eq!(ServerArgs::default().max_analyses(env(&vars)).unwrap(), 4);
+
+        let vars = [(MAX_ANALYSES_ENV, "0")];
+        let err = ServerArgs::default().max_analyses(env(&vars)).unwrap_err();
+        assert_eq!(err, ConfigError::InvalidMaxAnalyses("0".to_string()));
+    }
+
+    #[test]
+    fn test_allowed_root_is_canonical() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let nested = temp_dir.path().join("repo");
+        std::fs::create_dir(&nested).unwrap();
+
+        let value = format!("{}/repo/../repo", temp_dir.path().display());
+        let vars = [(ROOT_ENV, value.as_str())];
+        let root = ServerArgs::default().allowed_root(env(&vars)).unwrap();
+        assert_eq!(root, nested.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_allowed_root_must_exist() {
+        let args = ServerArgs::parse_from(["code-viz-web", "--root", "/nonexistent/code-viz"]);
+        let err = args.allowed_root(env(&[])).unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::RootNotFound(PathBuf::from("/nonexistent/code-viz"))
+        );
+    }
+}
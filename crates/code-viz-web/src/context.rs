@@ -3,24 +3,123 @@
 //! Implements the trait-based dependency injection for the web server.
 
 use async_trait::async_trait;
+use code_viz_api::AnalysisSession;
 use code_viz_core::traits::AppContext;
+use code_viz_dead_code::AnalysisConfig;
 use anyhow::Result;
 use serde_json::Value;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 // Re-export shared implementations from code-viz-core
-pub use code_viz_core::context::{RealFileSystem, RealGit};
+pub use code_viz_core::context::RealFileSystem;
+
+/// Analyses run at once unless configured otherwise; each one already
+/// spreads its parsing over every core
+pub const DEFAULT_MAX_ANALYSES: usize = 2;
 
 /// Web application context
+///
+/// Holds the canonical directory that requests may analyze, so a client
+/// cannot read arbitrary directories of a shared server, the analysis
+/// session shared by all requests, and the permits bounding how many
+/// analyses run at once.
 #[derive(Clone)]
-pub struct WebContext;
+pub struct WebContext {
+    root: PathBuf,
+    session: AnalysisSession,
+    analyses: Arc<Semaphore>,
+}
+
+/// Why a requested path was refused
+#[derive(Debug, PartialEq)]
+pub enum PathRejection {
+    /// The path does not exist or cannot be read
+    NotFound,
+    /// The path resolves outside the allowed root
+    OutsideRoot,
+}
 
 impl WebContext {
-    pub fn new() -> Self {
-        Self
+    /// `root` must already be canonical (see `ServerArgs::allowed_root`)
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            session: AnalysisSession::new(AnalysisConfig::default()),
+            analyses: Arc::new(Semaphore::new(DEFAULT_MAX_ANALYSES)),
+        }
+    }
+
+    /// Analyze dead code with `config` (e.g. from the root's
+    /// `.code-viz.toml`) instead of the defaults
+    pub fn with_analysis_config(mut self, config: AnalysisConfig) -> Self {
+        self.session = AnalysisSession::new(config);
+        self
+    }
+
+    /// Run at most `limit` analyses at once (at least one); further
+    /// requests wait for a running one to finish
+    pub fn with_max_analyses(mut self, limit: usize) -> Self {
+        self.analyses = Arc::new(Semaphore::new(limit.max(1)));
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Session reused by every dead code request
+    pub fn session(&self) -> &AnalysisSession {
+        &self.session
+    }
+
+    /// Wait until another analysis may start; it runs while the permit is held
+    pub async fn analysis_permit(&self) -> OwnedSemaphorePermit {
+        self.analyses
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("analysis semaphore is never closed")
+    }
+
+    /// Canonicalize a requested path, resolving relative paths against the root
+    ///
+    /// The path is first checked lexically, with `..` components folded, so
+    /// anything spelled outside the root is refused the same way whether or
+    /// not it exists and a client cannot probe for files elsewhere.
+    /// Canonicalizing then follows symlinks, so a link out of the root is
+    /// rejected too.
+    pub fn resolve(&self, requested: &str) -> Result<PathBuf, PathRejection> {
+        let joined = normalize_lexically(&self.root.join(requested));
+        if !joined.starts_with(&self.root) {
+            return Err(PathRejection::OutsideRoot);
+        }
+
+        let path = joined.canonicalize().map_err(|_| PathRejection::NotFound)?;
+        if path.starts_with(&self.root) {
+            Ok(path)
+        } else {
+            Err(PathRejection::OutsideRoot)
+        }
     }
 }
 
+/// Fold `.` and `..` components without touching the filesystem
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
 #[async_trait]
 impl AppContext for WebContext {
     async fn emit_event(&self, event: &str, payload: Value) -> Result<()> {
@@ -51,15 +150,44 @@ mod tests {
 
     #[tokio::test]
     async fn test_web_context() {
-        let ctx = WebContext::new();
+        let ctx = WebContext::new(std::env::temp_dir());
         let result = ctx.emit_event("test", serde_json::json!({"key": "value"})).await;
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_session_uses_analysis_config() {
+        let config = AnalysisConfig {
+            min_loc: 5,
+            ..Default::default()
+        };
+        let ctx = WebContext::new(std::env::temp_dir()).with_analysis_config(config);
+        assert_eq!(ctx.session().config().min_loc, 5);
+    }
+
     #[test]
     fn test_real_filesystem() {
         let fs = RealFileSystem::new();
         let temp_dir = std::env::temp_dir();
         assert!(fs.exists(&temp_dir));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_rejects_symlink_escape() {
+        let outside = tempfile::TempDir::new().unwrap();
+        let root = tempfile::TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+        std::fs::create_dir(root.path().join("src")).unwrap();
+
+        let ctx = WebContext::new(root.path().canonicalize().unwrap());
+        assert_eq!(ctx.resolve("link"), Err(PathRejection::OutsideRoot));
+        assert_eq!(ctx.resolve("missing"), Err(PathRejection::NotFound));
+        assert_eq!(ctx.resolve("../missing"), Err(PathRejection::OutsideRoot));
+        assert_eq!(ctx.resolve("/no/such/file"), Err(PathRejection::OutsideRoot));
+        assert_eq!(
+            ctx.resolve("src/../src").unwrap(),
+            ctx.root().join("src")
+        );
+    }
 }
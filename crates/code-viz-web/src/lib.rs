@@ -2,7 +2,9 @@
 //!
 //! Provides HTTP/REST API access to code-viz functionality.
 
+pub mod config;
 pub mod context;
 pub mod routes;
 
-pub use context::{WebContext, RealFileSystem, RealGit};
+pub use code_viz_core::context::RealGit;
+pub use context::{WebContext, RealFileSystem};
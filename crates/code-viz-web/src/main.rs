@@ -10,14 +10,12 @@
 //!                           (Same handler as Tauri)
 //! ```
 
+mod config;
 mod context;
 mod routes;
 
-use axum::{
-    routing::{get, post},
-    Router,
-};
-use std::net::SocketAddr;
+use axum::Router;
+use clap::Parser;
 use tower_http::{
     cors::{Any, CorsLayer},
     services::ServeDir,
@@ -27,6 +25,11 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let args = config::ServerArgs::parse();
+    let addr = args.bind_address(|name| std::env::var(name).ok())?;
+    let root = args.allowed_root(|name| std::env::var(name).ok())?;
+    let max_analyses = args.max_analyses(|name| std::env::var(name).ok())?;
+
     // Initialize tracing
     tracing_subscriber::registry()
         .with(
@@ -36,11 +39,16 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Build API routes
-    let api_routes = Router::new()
-        .route("/analyze", post(routes::post_analyze))
-        .route("/dead-code", post(routes::post_dead_code))
-        .route("/health", get(routes::health_check));
+    // Dead code settings come from the root's .code-viz.toml, read once at startup
+    let analysis_config = code_viz_commands::config::load_config(&root)?.dead_code_config(&[]);
+
+    // Build API routes, confined to the allowed root
+    let ctx = context::WebContext::new(root)
+        .with_analysis_config(analysis_config)
+        .with_max_analyses(max_analyses);
+    tracing::info!("Analyzable paths restricted to {}", ctx.root().display());
+    tracing::info!("At most {} analyses run at once", max_analyses);
+    let api_routes = routes::api_router(ctx);
 
     // Serve frontend static files from dist/
     let frontend_service = ServeDir::new("dist")
@@ -58,12 +66,17 @@ async fn main() -> anyhow::Result<()> {
         )
         .layer(TraceLayer::new_for_http());
 
-    // Start server - bind to 0.0.0.0 to allow external access
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+    // Start server on 127.0.0.1:8080 unless --host/--port or CODE_VIZ_HOST/CODE_VIZ_PORT say otherwise
     tracing::info!("🚀 Code-viz web server starting on http://{}", addr);
-    tracing::info!("   Accessible from other machines at http://<your-ip>:3000");
+    if addr.ip().is_unspecified() {
+        tracing::info!(
+            "   Accessible from other machines at http://<your-ip>:{}",
+            addr.port()
+        );
+    }
     tracing::info!("   API endpoints:");
     tracing::info!("   - POST http://{}/api/analyze", addr);
+    tracing::info!("   - GET/POST http://{}/api/analyze/stream", addr);
     tracing::info!("   - POST http://{}/api/dead-code", addr);
     tracing::info!("   - GET  http://{}/api/health", addr);
     tracing::info!("   Frontend: http://{}", addr);
@@ -4,34 +4,27 @@
 //! identical in function to the Tauri commands but using HTTP transport.
 
 use axum::{
-    extract::Json,
+    extract::{Json, Query, State},
     http::StatusCode,
-    response::{IntoResponse, Response},
+    response::{
+        sse::{Event, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
+    Router,
 };
-use code_viz_api::{analyze_repository_handler, analyze_dead_code_handler, TreeNode};
-use code_viz_dead_code::DeadCodeResult;
-use serde::{Deserialize, Serialize};
-
-use crate::context::{WebContext, RealFileSystem, RealGit};
-
-/// Request body for repository analysis
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct AnalyzeRequest {
-    pub path: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub request_id: Option<String>,
-}
+use code_viz_api::{
+    analyze_repository_handler, analyze_repository_stream_handler,
+    analyze_dead_code_session_handler, TreeNode,
+};
+use futures_util::stream::{self, Stream};
+use serde::Serialize;
+use tokio::sync::mpsc;
 
-/// Request body for dead code analysis
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DeadCodeRequest {
-    pub path: String,
-    pub min_confidence: u8,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub request_id: Option<String>,
-}
+use crate::context::{PathRejection, WebContext, RealFileSystem};
+
+// Request bodies are the shared contract types, so they cannot drift from Tauri's
+pub use code_viz_api::contracts::{AnalyzeRequest, DeadCodePage, DeadCodePageQuery, DeadCodeRequest};
 
 /// API error response
 #[derive(Debug, Serialize)]
@@ -40,23 +33,48 @@ pub struct ErrorResponse {
 }
 
 /// Web-specific error wrapper (needed to avoid orphan rule)
-pub struct WebError(code_viz_api::ApiError);
+pub enum WebError {
+    Api(code_viz_api::ApiError),
+    /// Requested path escapes the allowed root
+    Forbidden,
+}
 
 impl From<code_viz_api::ApiError> for WebError {
     fn from(err: code_viz_api::ApiError) -> Self {
-        WebError(err)
+        WebError::Api(err)
+    }
+}
+
+impl From<PathRejection> for WebError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::NotFound => WebError::Api(code_viz_api::ApiError::InvalidPath(
+                "path does not exist".to_string(),
+            )),
+            PathRejection::OutsideRoot => WebError::Forbidden,
+        }
     }
 }
 
 /// Convert WebError to HTTP response
 impl IntoResponse for WebError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match &self.0 {
-            code_viz_api::ApiError::InvalidPath(_) => (StatusCode::BAD_REQUEST, self.0.to_user_message()),
-            code_viz_api::ApiError::AnalysisFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_user_message()),
-            code_viz_api::ApiError::DeadCodeFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_user_message()),
-            code_viz_api::ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_user_message()),
-            code_viz_api::ApiError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_user_message()),
+        let err = match self {
+            WebError::Api(err) => err,
+            // Do not echo the root or the resolved path back to the client
+            WebError::Forbidden => {
+                let body = Json(ErrorResponse {
+                    error: "Path is outside the allowed root".to_string(),
+                });
+                return (StatusCode::FORBIDDEN, body).into_response();
+            }
+        };
+        let (status, error_message) = match &err {
+            code_viz_api::ApiError::InvalidPath(_) => (StatusCode::BAD_REQUEST, err.to_user_message()),
+            code_viz_api::ApiError::AnalysisFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_user_message()),
+            code_viz_api::ApiError::DeadCodeFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_user_message()),
+            code_viz_api::ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_user_message()),
+            code_viz_api::ApiError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_user_message()),
         };
 
         let body = Json(ErrorResponse {
@@ -67,46 +85,130 @@ impl IntoResponse for WebError {
     }
 }
 
+/// API routes, mounted under `/api` by the server
+pub fn api_router(ctx: WebContext) -> Router {
+    Router::new()
+        .route("/analyze", post(post_analyze))
+        .route("/analyze/stream", get(get_analyze_stream).post(post_analyze_stream))
+        .route("/dead-code", post(post_dead_code))
+        .route("/health", get(health_check))
+        .with_state(ctx)
+}
+
 /// POST /api/analyze - Analyze a repository
 ///
 /// This route is the HTTP equivalent of the Tauri `analyze_repository` command.
 /// It uses the EXACT SAME handler from code-viz-api (SSOT).
+/// Relative paths resolve against the allowed root; paths escaping it are 403.
+/// Waits while the server already runs its maximum number of analyses.
 pub async fn post_analyze(
+    State(ctx): State<WebContext>,
     Json(req): Json<AnalyzeRequest>,
 ) -> Result<Json<TreeNode>, WebError> {
     tracing::info!(path = %req.path, request_id = ?req.request_id, "POST /api/analyze");
 
-    let ctx = WebContext::new();
+    let path = ctx.resolve(&req.path)?;
     let fs = RealFileSystem::new();
+    let _permit = ctx.analysis_permit().await;
 
     // Call the shared SSOT handler (same as Tauri uses)
-    let tree = analyze_repository_handler(ctx, fs, req.path, req.request_id).await?;
+    let tree = analyze_repository_handler(ctx, fs, path.to_string_lossy().to_string(), req.request_id).await?;
 
     Ok(Json(tree))
 }
 
+/// GET /api/analyze/stream?path=... - Stream an analysis as server-sent events
+///
+/// GET is what the browser `EventSource` API sends; see `post_analyze_stream`.
+pub async fn get_analyze_stream(
+    State(ctx): State<WebContext>,
+    Query(req): Query<AnalyzeRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, WebError> {
+    analyze_stream(ctx, req)
+}
+
+/// POST /api/analyze/stream - Stream an analysis as server-sent events
+///
+/// Sends a `file` event (`FileMetricsEvent`) as each file is analyzed, then
+/// a final `summary` event (`AnalysisSummaryEvent`), or an `error` event if
+/// the analysis fails midway. Paths are confined like `post_analyze`.
+pub async fn post_analyze_stream(
+    State(ctx): State<WebContext>,
+    Json(req): Json<AnalyzeRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, WebError> {
+    analyze_stream(ctx, req)
+}
+
+fn analyze_stream(
+    ctx: WebContext,
+    req: AnalyzeRequest,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, WebError> {
+    tracing::info!(path = %req.path, request_id = ?req.request_id, "Streaming analysis");
+
+    // Rejected paths fail the request itself, before any event is sent
+    let path = ctx.resolve(&req.path)?.to_string_lossy().to_string();
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let _permit = ctx.analysis_permit().await;
+        let files = tx.clone();
+        let result = analyze_repository_stream_handler(ctx, RealFileSystem::new(), path, move |file| {
+            // A closed channel means the client went away; the analysis just finishes
+            let _ = files.send(Event::default().event("file").json_data(file));
+        })
+        .await;
+
+        let last = match result {
+            Ok(summary) => Event::default().event("summary").json_data(summary),
+            Err(err) => Event::default().event("error").json_data(ErrorResponse {
+                error: err.to_user_message(),
+            }),
+        };
+        let _ = tx.send(last);
+    });
+
+    // The stream ends once the analysis task drops the last sender
+    let events = stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (event, rx))
+    });
+    Ok(Sse::new(events))
+}
+
 /// POST /api/dead-code - Analyze dead code
 ///
 /// This route is the HTTP equivalent of the Tauri `analyze_dead_code_command` command.
-/// It uses the EXACT SAME handler from code-viz-api (SSOT).
+/// It uses the EXACT SAME analysis from code-viz-api (SSOT), through the
+/// server's session so unchanged repositories reuse their symbol graph.
+/// Paths are confined to the allowed root like `post_analyze`.
+///
+/// With `?offset=` or `?limit=` only that slice of `files` is sent, as a
+/// `DeadCodePage` whose summary still covers the whole analysis.
 pub async fn post_dead_code(
+    State(ctx): State<WebContext>,
+    Query(page): Query<DeadCodePageQuery>,
     Json(req): Json<DeadCodeRequest>,
-) -> Result<Json<DeadCodeResult>, WebError> {
+) -> Result<Response, WebError> {
     tracing::info!(
         path = %req.path,
         min_confidence = %req.min_confidence,
         request_id = ?req.request_id,
+        offset = ?page.offset,
+        limit = ?page.limit,
         "POST /api/dead-code"
     );
 
-    let ctx = WebContext::new();
-    let fs = RealFileSystem::new();
-    let git = RealGit::new();
+    let path = ctx.resolve(&req.path)?.to_string_lossy().to_string();
+    let session = ctx.session().clone();
+    let _permit = ctx.analysis_permit().await;
 
-    // Call the shared SSOT handler (same as Tauri uses)
-    let result = analyze_dead_code_handler(ctx, fs, git, req.path, req.min_confidence, req.request_id).await?;
+    // Call the shared SSOT handler
+    let result =
+        analyze_dead_code_session_handler(&session, ctx, path, req.min_confidence, req.request_id).await?;
 
-    Ok(Json(result))
+    if page.offset.is_none() && page.limit.is_none() {
+        return Ok(Json(result).into_response());
+    }
+    Ok(Json(DeadCodePage::new(result, &page)).into_response())
 }
 
 /// GET /health - Health check endpoint
@@ -0,0 +1,43 @@
+//! Route tests for the limit on analyses running at once
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use code_viz_web::{routes::api_router, WebContext};
+use serde_json::json;
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn test_analysis_waits_for_running_one() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("main.ts"),
+        "export function main() {\n    return 1;\n}\n",
+    )
+    .unwrap();
+    let ctx = WebContext::new(temp_dir.path().canonicalize().unwrap()).with_max_analyses(1);
+    let router = api_router(ctx.clone());
+
+    // Stands in for an analysis already running
+    let running = ctx.analysis_permit().await;
+
+    let request = Request::post("/analyze")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "path": "." }).to_string()))
+        .unwrap();
+    let mut response = tokio::spawn(router.oneshot(request));
+
+    let waited = tokio::time::timeout(Duration::from_millis(300), &mut response).await;
+    assert!(waited.is_err(), "second analysis ran in parallel");
+
+    drop(running);
+    let response = response.await.unwrap().unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let tree: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert!(tree["children"].is_array());
+}
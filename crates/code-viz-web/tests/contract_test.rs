@@ -0,0 +1,120 @@
+//! Contract tests between the web routes and the Tauri commands
+//!
+//! Tauri commands return the shared handler results, which Tauri IPC
+//! serializes with serde_json. The routes wrap the same results in axum's
+//! `Json` response. Both transports must send byte-identical camelCase JSON.
+
+use axum::{
+    body::to_bytes,
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+use code_viz_api::contracts::{
+    non_camel_case_keys, AnalyzeRequest, DeadCodePageQuery, DeadCodeRequest,
+};
+use code_viz_api::TreeNode;
+use code_viz_web::routes::{post_analyze, post_dead_code};
+use code_viz_web::{RealFileSystem, RealGit, WebContext};
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+fn fixture_repo() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("main.ts"),
+        "import { used } from './lib';\n\nfunction main() {\n    used();\n}\n\nmain();\n",
+    )
+    .unwrap();
+    fs::write(
+        src.join("lib.ts"),
+        "export function used() {\n    return 1;\n}\n\nfunction unused() {\n    return 2;\n}\n",
+    )
+    .unwrap();
+    temp_dir
+}
+
+fn context(repo: &TempDir) -> WebContext {
+    WebContext::new(repo.path().canonicalize().unwrap())
+}
+
+async fn body_text(response: impl IntoResponse) -> String {
+    let body = response.into_response().into_body();
+    let bytes = to_bytes(body, usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_analyze_json_matches_tauri() {
+    let repo = fixture_repo();
+    let path = repo.path().to_string_lossy().to_string();
+
+    let http = body_text(
+        post_analyze(
+            State(context(&repo)),
+            Json(AnalyzeRequest {
+                path,
+                request_id: None,
+            }),
+        )
+        .await,
+    )
+    .await;
+
+    // Timestamps default to the scan time, so a second handler run would
+    // differ; serialize the tree the route sent the way Tauri IPC would
+    let tree: TreeNode = serde_json::from_str(&http).unwrap();
+    let ipc = serde_json::to_string(&tree).unwrap();
+
+    assert_eq!(http, ipc);
+    let json: Value = serde_json::from_str(&http).unwrap();
+    assert_eq!(non_camel_case_keys(&json), Vec::<String>::new());
+    assert!(json["lastModified"].is_string());
+}
+
+#[tokio::test]
+async fn test_dead_code_json_matches_tauri() {
+    let repo = fixture_repo();
+    // The route hands the handler the canonical path
+    let path = repo
+        .path()
+        .canonicalize()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+
+    // The Tauri command returns this same handler result (its context only differs in events)
+    let result = code_viz_api::analyze_dead_code_handler(
+        context(&repo),
+        RealFileSystem::new(),
+        RealGit::new(),
+        path.clone(),
+        0,
+        None,
+    )
+    .await
+    .unwrap();
+    let ipc = serde_json::to_string(&result).unwrap();
+
+    let http = body_text(
+        post_dead_code(
+            State(context(&repo)),
+            Query(DeadCodePageQuery::default()),
+            Json(DeadCodeRequest {
+                path,
+                min_confidence: 0,
+                request_id: None,
+            }),
+        )
+        .await,
+    )
+    .await;
+
+    assert_eq!(http, ipc);
+    let json: Value = serde_json::from_str(&http).unwrap();
+    assert_eq!(non_camel_case_keys(&json), Vec::<String>::new());
+    assert!(json["summary"]["deadFunctions"].as_u64().unwrap() >= 1);
+}
@@ -0,0 +1,70 @@
+//! Route tests for paging `/api/dead-code` results
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use code_viz_web::{routes::api_router, WebContext};
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+/// A repository with an entry point and one unused function in each of three other files
+fn repo_with_dead_files() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("main.ts"), "function main() {}\n\nmain();\n").unwrap();
+    for name in ["a", "b", "c"] {
+        fs::write(
+            src.join(format!("{}.ts", name)),
+            format!("function unused_{}() {{\n    return 1;\n}}\n", name),
+        )
+        .unwrap();
+    }
+    temp_dir
+}
+
+async fn post_dead_code(repo: &TempDir, query: &str) -> (StatusCode, Value) {
+    let router = api_router(WebContext::new(repo.path().canonicalize().unwrap()));
+    let request = Request::post(format!("/dead-code{}", query))
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "path": "src", "minConfidence": 0 }).to_string()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_dead_code_page_slices_files() {
+    let repo = repo_with_dead_files();
+
+    let (status, body) = post_dead_code(&repo, "?offset=1&limit=1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["files"].as_array().unwrap().len(), 1);
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["offset"], 1);
+    assert_eq!(body["limit"], 1);
+    // The summary covers every page
+    assert_eq!(body["summary"]["deadFunctions"], 3);
+
+    let (_, last) = post_dead_code(&repo, "?offset=2&limit=5").await;
+    assert_eq!(last["files"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_dead_code_without_paging_returns_full_result() {
+    let repo = repo_with_dead_files();
+
+    let (status, body) = post_dead_code(&repo, "").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["files"].as_array().unwrap().len(), 3);
+    assert!(body.get("total").is_none());
+    assert!(body["byDirectory"].is_array());
+}
@@ -0,0 +1,95 @@
+//! Route tests for the allowed-root restriction
+//!
+//! Requests go through the full API router, so these cover path extraction,
+//! state wiring and the status codes clients see.
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use code_viz_web::{routes::api_router, WebContext};
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+/// An allowed root containing `repo/src/main.ts`
+fn allowed_root() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("repo").join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("main.ts"),
+        "export function main() {\n    return 1;\n}\n",
+    )
+    .unwrap();
+    temp_dir
+}
+
+async fn post(root: &TempDir, uri: &str, body: Value) -> (StatusCode, Value) {
+    let router = api_router(WebContext::new(root.path().canonicalize().unwrap()));
+    let request = Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_analyze_rejects_parent_traversal() {
+    let root = allowed_root();
+
+    let (status, body) = post(&root, "/analyze", json!({ "path": "../../etc" })).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "Path is outside the allowed root");
+}
+
+#[tokio::test]
+async fn test_dead_code_rejects_absolute_path_outside_root() {
+    let root = allowed_root();
+    let outside = TempDir::new().unwrap();
+    let path = outside.path().to_string_lossy();
+
+    let (status, _) = post(
+        &root,
+        "/dead-code",
+        json!({ "path": path, "minConfidence": 0 }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_analyze_accepts_subpath() {
+    let root = allowed_root();
+
+    let (status, body) = post(&root, "/analyze", json!({ "path": "repo" })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["loc"], 3);
+}
+
+#[tokio::test]
+async fn test_analyze_missing_path_is_bad_request() {
+    let root = allowed_root();
+
+    let (status, _) = post(&root, "/analyze", json!({ "path": "repo/missing" })).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_missing_path_outside_root_is_forbidden() {
+    let root = allowed_root();
+
+    // Refused like an existing path, so clients cannot probe the server
+    let (status, _) = post(&root, "/analyze", json!({ "path": "../no-such-dir" })).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,115 @@
+//! Route tests for the server-sent event analysis stream
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use code_viz_api::contracts::non_camel_case_keys;
+use code_viz_web::{routes::api_router, WebContext};
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+fn fixture_repo() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("main.ts"),
+        "export function main() {\n    return 1;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        src.join("util.ts"),
+        "export const double = (x: number) => x * 2;\n",
+    )
+    .unwrap();
+    fs::write(
+        src.join("lib.rs"),
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )
+    .unwrap();
+    fs::write(src.join("README.md"), "# Not analyzed\n").unwrap();
+    temp_dir
+}
+
+/// Split an SSE body into (event name, JSON data) pairs
+fn parse_events(body: &str) -> Vec<(String, Value)> {
+    body.split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let field = |name: &str| {
+                block
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name))
+                    .map(str::trim)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            (
+                field("event:"),
+                serde_json::from_str(&field("data:")).unwrap(),
+            )
+        })
+        .collect()
+}
+
+async fn stream(repo: &TempDir, request: Request<Body>) -> (StatusCode, String, String) {
+    let router = api_router(WebContext::new(repo.path().canonicalize().unwrap()));
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_post_analyze_stream_emits_file_events_then_summary() {
+    let repo = fixture_repo();
+    let request = Request::post("/analyze/stream")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "path": "src" }).to_string()))
+        .unwrap();
+
+    let (status, content_type, body) = stream(&repo, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/event-stream");
+
+    let events = parse_events(&body);
+    let files: Vec<&Value> = events
+        .iter()
+        .filter(|(name, _)| name == "file")
+        .map(|(_, data)| data)
+        .collect();
+    assert_eq!(files.len(), 3);
+    assert!(files
+        .iter()
+        .all(|file| non_camel_case_keys(file).is_empty()));
+
+    // The summary comes last and agrees with the streamed files
+    let (name, summary) = events.last().unwrap();
+    assert_eq!(name, "summary");
+    assert_eq!(summary["totalFiles"], 3);
+    let streamed_loc: u64 = files.iter().map(|file| file["loc"].as_u64().unwrap()).sum();
+    assert_eq!(summary["totalLoc"], streamed_loc);
+}
+
+#[tokio::test]
+async fn test_get_analyze_stream_rejects_path_outside_root() {
+    let repo = fixture_repo();
+    let request = Request::get("/analyze/stream?path=../..")
+        .body(Body::empty())
+        .unwrap();
+
+    let (status, _, _) = stream(&repo, request).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
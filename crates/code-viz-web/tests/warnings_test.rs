@@ -0,0 +1,94 @@
+//! Route tests for analysis warnings in `/api/analyze` and `/api/dead-code` responses
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use code_viz_web::{routes::api_router, WebContext};
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+async fn post_dead_code(repo: &TempDir) -> (StatusCode, Value) {
+    let router = api_router(WebContext::new(repo.path().canonicalize().unwrap()));
+    let request = Request::post("/dead-code")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "path": "src", "minConfidence": 0 }).to_string()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn post_analyze(repo: &TempDir) -> (StatusCode, Value) {
+    let router = api_router(WebContext::new(repo.path().canonicalize().unwrap()));
+    let request = Request::post("/analyze")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "path": "src" }).to_string()))
+        .unwrap();
+
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_analyze_reports_skipped_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("main.ts"), "function main() {}\n\nmain();\n").unwrap();
+    fs::write(src.join("bundle.ts"), b"\0\x01\x02binary\0").unwrap();
+
+    let (status, body) = post_analyze(&temp_dir).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let warnings = body["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0]["kind"], "SkippedFiles");
+    let message = warnings[0]["message"].as_str().unwrap();
+    assert!(message.contains("1 binary"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_dead_code_reports_unresolved_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("main.ts"),
+        "import { helper } from './missing';\nimport './styles.css';\n\nfunction main() {\n    helper();\n}\n\nmain();\n",
+    )
+    .unwrap();
+
+    let (status, body) = post_dead_code(&temp_dir).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let warnings = body["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert_eq!(warnings[0]["kind"], "UnresolvedImport");
+    let message = warnings[0]["message"].as_str().unwrap();
+    assert!(message.contains("'./missing' in main.ts"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_dead_code_without_problems_has_no_warnings() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("main.ts"),
+        "import { helper } from './util';\n\nfunction main() {\n    helper();\n}\n\nmain();\n",
+    )
+    .unwrap();
+    fs::write(src.join("util.ts"), "export function helper() {}\n").unwrap();
+
+    let (status, body) = post_dead_code(&temp_dir).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["warnings"], json!([]));
+}
@@ -31,6 +31,18 @@ export interface TreeNode {
    */
   complexity: number;
 
+  /** Deepest control-flow nesting of any function (max of children for directories) */
+  maxNestingDepth?: number;
+
+  /** Lines that are only comments (sum of children for directories) */
+  commentLines?: number;
+
+  /** Share of lines that are only comments (0-1), from the summed comment and code lines for directories */
+  commentRatio?: number;
+
+  /** Number of exported symbols (sum of children for directories) */
+  exportCount?: number;
+
   /** Node type: "file" or "directory" */
   type: "file" | "directory";
 
@@ -42,6 +54,94 @@ export interface TreeNode {
 
   /** Dead code ratio (0.0 to 1.0), only present when dead code analysis is enabled */
   deadCodeRatio?: number;
+
+  /** Warnings about the whole analysis, only present on the root node */
+  warnings?: AnalysisWarning[];
+}
+
+/**
+ * How a node changed between two trees
+ *
+ * Corresponds to Rust enum: code_viz_api::diff::DiffStatus
+ */
+export type DiffStatus = "added" | "removed" | "changed" | "unchanged";
+
+/**
+ * Node of a merged before/after tree annotated with metric deltas
+ *
+ * Corresponds to Rust struct: code_viz_api::diff::TreeNodeDiff
+ */
+export interface TreeNodeDiff {
+  /** Identifier shared by the aligned nodes */
+  id: string;
+
+  /** Display name */
+  name: string;
+
+  /** Full path from repository root */
+  path: string;
+
+  /** Node type: "file" or "directory" */
+  type: "file" | "directory";
+
+  status: DiffStatus;
+
+  /** Lines of code in the new tree (in the old tree for removed nodes) */
+  loc: number;
+
+  /** Change in lines of code, negative when the node shrank or was removed */
+  locDelta: number;
+
+  /** Change in complexity score */
+  complexityDelta: number;
+
+  /** Merged children: those of the new tree in order, then removed ones */
+  children: TreeNodeDiff[];
+}
+
+/**
+ * `file` event of the web server's `/api/analyze/stream`, sent per analyzed file
+ *
+ * Corresponds to Rust struct: code_viz_api::contracts::FileMetricsEvent
+ */
+export interface FileMetricsEvent {
+  /** Path of the analyzed file */
+  path: string;
+
+  /** Programming language ("rust", "typescript", "python", etc.) */
+  language: string;
+
+  /** Lines of code (excluding comments and blank lines) */
+  loc: number;
+
+  /** File size in bytes */
+  sizeBytes: number;
+
+  /** Number of functions/methods */
+  functionCount: number;
+
+  /** Deepest control-flow nesting of any function in the file */
+  maxNestingDepth: number;
+
+  /** Number of exported symbols */
+  exportCount: number;
+}
+
+/**
+ * Final `summary` event of the web server's `/api/analyze/stream`
+ *
+ * Corresponds to Rust struct: code_viz_api::contracts::AnalysisSummaryEvent
+ */
+export interface AnalysisSummaryEvent {
+  totalFiles: number;
+  totalLoc: number;
+  totalFunctions: number;
+
+  /** Files skipped as too large or unreadable */
+  skippedFiles: number;
+
+  /** Warnings about the whole analysis */
+  warnings: AnalysisWarning[];
 }
 
 /**
@@ -54,7 +154,10 @@ export type SymbolKind =
   | "ArrowFunction"
   | "Class"
   | "Method"
-  | "Variable";
+  | "Variable"
+  | "ReExport"
+  | "Member"
+  | "Namespace";
 
 /**
  * A dead (unreachable) symbol with metadata
@@ -74,9 +177,18 @@ export interface DeadSymbol {
   /** Ending line number */
   lineEnd: number;
 
+  /** Byte offset of the symbol's first byte in the file */
+  byteStart: number;
+
+  /** Byte offset just past the symbol's last byte */
+  byteEnd: number;
+
   /** Lines of code in this symbol */
   loc: number;
 
+  /** Summed cyclomatic complexity of the functions in this symbol */
+  complexity: number;
+
   /** Deletion confidence score (0-100) */
   confidence: number;
 
@@ -85,6 +197,28 @@ export interface DeadSymbol {
 
   /** Last modification time (ISO 8601 format, if available) */
   lastModified?: string;
+
+  /** Whole days since the last commit changing the symbol's lines (if inside a git repository) */
+  ageDays?: number;
+
+  /** Penalties that lowered the confidence score (if requested) */
+  confidencePenalties?: ConfidencePenalty[];
+
+  /** Hash of the symbol's source, matching it across runs after it moved */
+  fingerprint: string;
+}
+
+/**
+ * A single penalty applied to a confidence score
+ *
+ * Corresponds to Rust struct: code_viz_dead_code::models::ConfidencePenalty
+ */
+export interface ConfidencePenalty {
+  /** Short name of the heuristic (e.g., "exported", "handler pattern") */
+  name: string;
+
+  /** Points subtracted from the score (negative for a bonus) */
+  amount: number;
 }
 
 /**
@@ -100,6 +234,44 @@ export interface FileDeadCode {
   deadCode: DeadSymbol[];
 }
 
+/**
+ * Size of one analyzed file, counted like the summary totals
+ *
+ * Corresponds to Rust struct: code_viz_dead_code::models::FileTotals
+ */
+export interface FileTotals {
+  /** File path */
+  path: string;
+
+  /** Lines covered by at least one symbol */
+  loc: number;
+
+  /** Summed complexity of the file's symbols */
+  complexity: number;
+
+  /** Symbols the file exports, from the graph's `is_exported` */
+  exportCount: number;
+}
+
+/**
+ * Dead code aggregated for a single directory
+ *
+ * Corresponds to Rust struct: code_viz_dead_code::models::DirectoryDeadCode
+ */
+export interface DirectoryDeadCode {
+  /** Directory path (parent of the files it aggregates) */
+  dir: string;
+
+  /** Number of dead symbols in files directly inside this directory */
+  deadSymbols: number;
+
+  /** Lines of dead code in files directly inside this directory */
+  deadLoc: number;
+
+  /** Ratio of dead code to total code in this directory (0.0 to 1.0) */
+  deadRatio: number;
+}
+
 /**
  * Summary statistics for dead code analysis
  *
@@ -112,17 +284,44 @@ export interface DeadCodeSummary {
   /** Number of files containing dead code */
   filesWithDeadCode: number;
 
-  /** Total number of dead functions */
+  /** Dead functions, arrow functions and methods */
   deadFunctions: number;
 
-  /** Total number of dead classes */
+  /** Dead classes */
   deadClasses: number;
 
-  /** Total lines of dead code */
+  /**
+   * Every other dead symbol: variables, namespaces, enum or object members
+   * and re-exports. The three kind counters add up to the dead symbols listed.
+   */
+  deadOther: number;
+
+  /** Total lines of dead code, each line counted once like `totalLoc` */
   totalDeadLoc: number;
 
+  /** Lines covered by any symbol, the total deadCodeRatio is relative to */
+  totalLoc: number;
+
+  /** Summed complexity of every symbol, the total deadComplexityRatio is relative to */
+  totalComplexity: number;
+
   /** Ratio of dead code to total code (0.0 to 1.0) */
   deadCodeRatio: number;
+
+  /** Ratio of dead complexity to total complexity (0.0 to 1.0) */
+  deadComplexityRatio: number;
+
+  /** Unreachable symbols left out because they match a keep pattern */
+  keptByPattern: number;
+
+  /** Dead symbols left out because they are shorter than the minimum LOC */
+  filteredSmall: number;
+
+  /** Dead symbols left out because their file is marked as generated */
+  skippedGenerated: number;
+
+  /** Source files left out of the scan (too large or unreadable) */
+  skippedFiles: number;
 }
 
 /**
@@ -131,11 +330,84 @@ export interface DeadCodeSummary {
  * Corresponds to Rust struct: code_viz_dead_code::models::DeadCodeResult
  */
 export interface DeadCodeResult {
+  /** Version of this JSON layout, serialized first */
+  schemaVersion: number;
   /** Aggregated summary statistics */
   summary: DeadCodeSummary;
 
   /** Dead code grouped by file */
   files: FileDeadCode[];
+
+  /** Dead code aggregated per parent directory, sorted by dead LOC (descending) */
+  byDirectory: DirectoryDeadCode[];
+
+  /** Size of every analyzed file, dead code or not, sorted by path */
+  fileTotals: FileTotals[];
+
+  /**
+   * Groups of dead symbols that only reference each other (e.g. an unused
+   * circular import), as symbol IDs, so they can be deleted together
+   */
+  deadClusters: string[][];
+
+  /** Signs that the result itself is unreliable, e.g. missed entry points */
+  warnings: AnalysisWarning[];
+
+  /**
+   * Slowest files to parse with their wall time, slowest first; only present
+   * when profiling was enabled
+   */
+  profile?: { path: string; ms: number }[];
+
+  /**
+   * Whether the symbol graph was loaded from a cache instead of built; only
+   * set in read-only cache mode
+   */
+  cacheHit?: boolean;
+}
+
+/**
+ * One page of a dead code result, returned by the web server's
+ * `/api/dead-code?offset=&limit=`
+ *
+ * Corresponds to Rust struct: code_viz_api::contracts::DeadCodePage
+ */
+export interface DeadCodePage {
+  /** Summary of the whole analysis, not only this page */
+  summary: DeadCodeSummary;
+
+  /** Files with dead code, starting at `offset` */
+  files: FileDeadCode[];
+
+  /** Files with dead code across all pages */
+  total: number;
+
+  offset: number;
+  limit: number;
+}
+
+/**
+ * Type of analysis warning
+ *
+ * Corresponds to Rust enum: code_viz_core::models::AnalysisWarningKind
+ */
+export type AnalysisWarningKind =
+  | "SuspiciousDeadCodeRatio"
+  | "InvalidManifest"
+  | "UnresolvedImport"
+  | "SkippedFiles";
+
+/**
+ * Warning that an analysis result is probably wrong as a whole
+ *
+ * Corresponds to Rust struct: code_viz_core::models::AnalysisWarning
+ */
+export interface AnalysisWarning {
+  /** What looks wrong */
+  kind: AnalysisWarningKind;
+
+  /** Human-readable explanation and suggested fix */
+  message: string;
 }
 
 /**
@@ -12,10 +12,10 @@ echo
 echo
 
 # Test /api/analyze with small directory
-echo "2. Testing /api/analyze with /tmp"
+echo "2. Testing /api/analyze with the allowed root (CODE_VIZ_ROOT)"
 RESPONSE=$(curl -s -X POST http://localhost:8080/api/analyze \
   -H "Content-Type: application/json" \
-  -d '{"path": "/tmp"}')
+  -d '{"path": "."}')
 
 echo "Root node structure:"
 echo "$RESPONSE" | jq '{